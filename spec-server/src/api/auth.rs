@@ -0,0 +1,136 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::domain::{
+    errors::DomainError,
+    value_objects::{TenantId, ValidationError},
+};
+
+/// Who a request was authenticated as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    /// Recorded as the author of every change the request makes
+    pub user: String,
+    /// The only tenant the principal's requests act for
+    pub tenant: TenantId,
+    /// Administrators can also create, list and suspend tenants
+    pub admin: bool,
+}
+
+impl Principal {
+    /// Who every request acts as while authentication is disabled
+    pub fn anonymous() -> Self {
+        Self {
+            user: "anonymous".to_string(),
+            tenant: TenantId::default(),
+            admin: false,
+        }
+    }
+
+    /// Refuse principals that cannot administer tenants
+    pub fn require_admin(&self) -> Result<(), DomainError> {
+        if self.admin {
+            return Ok(());
+        }
+        Err(DomainError::PermissionDenied(format!(
+            "{} cannot administer tenants",
+            self.user
+        )))
+    }
+}
+
+/// Bearer tokens the server accepts, each standing for one principal.
+///
+/// With no tokens configured authentication is disabled and every request
+/// acts as `Principal::anonymous`.
+#[derive(Clone, Default)]
+pub struct Authenticator {
+    /// Principals by the SHA-256 digest of their token, so the tokens
+    /// themselves are not kept
+    principals: Arc<HashMap<String, Principal>>,
+}
+
+impl Authenticator {
+    /// Number of tokens accepted; zero when authentication is disabled
+    pub fn len(&self) -> usize {
+        self.principals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.principals.is_empty()
+    }
+
+    /// Principal an `Authorization` header value of the form
+    /// `Bearer <token>` stands for
+    pub fn authenticate(&self, authorization: Option<&str>) -> Result<Principal, DomainError> {
+        if self.principals.is_empty() {
+            return Ok(Principal::anonymous());
+        }
+
+        let token = authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| DomainError::Unauthenticated("Missing bearer token".to_string()))?;
+
+        self.principals
+            .get(&digest(token.trim()))
+            .cloned()
+            .ok_or_else(|| DomainError::Unauthenticated("Unknown bearer token".to_string()))
+    }
+}
+
+impl FromStr for Authenticator {
+    type Err = ValidationError;
+
+    /// Parses comma-separated `<token>:<user>:<tenant>` entries, with
+    /// `:admin` appended for administrators, such as
+    /// `s3cret:alice@example.com:payments,t0ken:ops@example.com:default:admin`;
+    /// an empty string disables authentication
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut principals = HashMap::new();
+
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let invalid = || {
+                ValidationError::InvalidConfig(format!(
+                    "Invalid API token entry for {}",
+                    entry.split(':').nth(1).unwrap_or("unknown user")
+                ))
+            };
+
+            let mut parts = entry.split(':');
+            let (Some(token), Some(user), Some(tenant)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(invalid());
+            };
+            let admin = match parts.next() {
+                None => false,
+                Some("admin") => true,
+                Some(_) => return Err(invalid()),
+            };
+            if token.is_empty() || user.is_empty() || parts.next().is_some() {
+                return Err(invalid());
+            }
+
+            let principal = Principal {
+                user: user.to_string(),
+                tenant: TenantId::new(tenant)?,
+                admin,
+            };
+            if principals.insert(digest(token), principal).is_some() {
+                return Err(ValidationError::InvalidConfig(format!(
+                    "The token for {user} is listed more than once"
+                )));
+            }
+        }
+
+        Ok(Self {
+            principals: Arc::new(principals),
+        })
+    }
+}
+
+fn digest(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -1,14 +1,42 @@
+use prost::Message;
 use std::sync::Arc;
-use tonic::{Request, Response, Status};
+use tonic::{metadata::MetadataMap, Code, Request, Response, Status};
 use uuid::Uuid;
 
+use super::auth::{Authenticator, Principal};
 use crate::domain::{
-    aggregates::Spec,
-    commands::{CreateSpec, DeprecateSpec, PublishSpec, UpdateSpec},
+    commands::{
+        AddComment, AddLabels, ApproveSpec, BatchCommand, BatchTarget, CancelSchedule,
+        CreateFromTemplate, CreateRelease, CreateSpec, CreateTenant, DeleteSpec, DemoteSpec,
+        DeprecateSpec, EditComment, MoveFolder, PromoteSpec, PublishRelease, PublishSpec,
+        RegisterSchema, RegisterTemplate, RejectSpec, RemoveLabels, RenameSpec, RequestReview,
+        ResolveComment, RestoreSpec, RevertSpec, RollbackRelease, ScheduleTransition,
+        SetDependencies, SpecCommand, SuspendTenant, UpdateSchema, UpdateSpec, UpdateTemplate,
+        WithdrawReview,
+    },
     errors::DomainError,
-    events::{EventMetadata, SpecEvent, SpecState},
+    events::{
+        EventEnvelope, EventMetadata, ReleaseState, ReviewStatus, ScheduledTransition, SpecEvent,
+        SpecState, TenantState,
+    },
+    validation::{Severity, ValidationIssue},
+    value_objects::{
+        BumpLevel, CommentAnchor, LabelSelector, LineRange, ParameterType, ReleaseEntry, SchemaRef,
+        SpecDependency, TemplateParameter, TemplateRef, TenantId, ValidationError,
+    },
+};
+use crate::infrastructure::{
+    blob_store::DOWNLOAD_CHUNK_SIZE,
+    event_store::SqliteEventStore,
+    projections::{
+        ChannelSpecProjection, CommentProjection, CommentThreadProjection, DependencyProjection,
+        ProjectionStore, ReleaseProjection, ScheduleProjection, ScheduleStatus, SpecDeprecation,
+        SpecReview, SpecSummaryProjection, TenantProjection,
+    },
+    release_registry::ReleaseRegistry,
+    repositories::{BatchItemResult, SpecRepository},
+    tenant_registry::TenantRegistry,
 };
-use crate::infrastructure::{event_store::SqliteEventStore, projections::ProjectionStore};
 
 // Import generated protobuf types
 #[allow(clippy::pedantic, clippy::nursery, clippy::all)]
@@ -17,30 +45,286 @@ pub mod spec_proto {
 }
 
 use spec_proto::{
+    batch_item::Command as ProtoBatchCommand,
     spec_service_server::{SpecService, SpecServiceServer},
-    CreateSpecRequest, CreateSpecResponse, DeprecateSpecRequest, DeprecateSpecResponse, EventType,
-    GetSpecHistoryRequest, GetSpecHistoryResponse, GetSpecRequest, GetSpecResponse,
-    ListSpecsRequest, ListSpecsResponse, PublishSpecRequest, PublishSpecResponse,
-    SpecEvent as ProtoSpecEvent, SpecState as ProtoSpecState, SpecSummary, UpdateSpecRequest,
-    UpdateSpecResponse,
+    AddCommentRequest, AddLabelsRequest, ApproveSpecRequest, BatchExecuteRequest,
+    BatchExecuteResponse, BatchItemResult as ProtoBatchItemResult, BumpLevel as ProtoBumpLevel,
+    CancelScheduleRequest, CancelScheduleResponse, ChannelResponse, CommentResponse, ContentChunk,
+    CreateReleaseRequest, CreateSpecFromTemplateRequest, CreateSpecRequest, CreateSpecResponse,
+    CreateTenantRequest, DeleteSpecRequest, DeleteSpecResponse, DemoteSpecRequest,
+    DependenciesResponse, DeprecateSpecRequest, DeprecateSpecResponse, DownloadContentRequest,
+    EditCommentRequest, EventType, GetDependenciesRequest, GetReleaseRequest, GetSchemaRequest,
+    GetSpecByNameRequest, GetSpecChannelsRequest, GetSpecHistoryRequest, GetSpecHistoryResponse,
+    GetSpecRequest, GetSpecResponse, GetTemplateRequest, GetTreeRequest, GetTreeResponse,
+    LabelsResponse, ListChannelSpecsRequest, ListChannelSpecsResponse, ListCommentsRequest,
+    ListCommentsResponse, ListReleasesRequest, ListReleasesResponse, ListSchedulesRequest,
+    ListSchedulesResponse, ListSchemasRequest, ListSchemasResponse, ListSpecsRequest,
+    ListSpecsResponse, ListTemplateSpecsRequest, ListTemplateSpecsResponse, ListTemplatesRequest,
+    ListTemplatesResponse, ListTenantsRequest, ListTenantsResponse, MoveFolderRequest,
+    MoveFolderResponse, ParameterType as ProtoParameterType, PreviewTemplateRequest,
+    PreviewTemplateResponse, PromoteSpecRequest, PublishReleaseRequest, PublishSpecRequest,
+    PublishSpecResponse, RegisterSchemaRequest, RegisterTemplateRequest, RejectSpecRequest,
+    Release as ProtoRelease, ReleaseSpecsResponse, ReleaseState as ProtoReleaseState,
+    ReleaseStateResponse, RemoveLabelsRequest, RenameSpecRequest, RenameSpecResponse,
+    RequestReviewRequest, ResolveCommentRequest, RestoreSpecRequest, RestoreSpecResponse,
+    RevertSpecRequest, RevertSpecResponse, ReviewResponse, ReviewStatus as ProtoReviewStatus,
+    RollbackReleaseRequest, ScheduleStatus as ProtoScheduleStatus, ScheduleTransitionRequest,
+    ScheduleTransitionResponse, Schema as ProtoSchema, SchemaVersionResponse,
+    SetDependenciesRequest, SetDependenciesResponse, SpecEvent as ProtoSpecEvent,
+    SpecState as ProtoSpecState, SpecSummary, SuspendTenantRequest, Template as ProtoTemplate,
+    TemplateVersionResponse, Tenant as ProtoTenant, TenantState as ProtoTenantState,
+    TenantStateResponse, UpdateSchemaRequest, UpdateSpecRequest, UpdateSpecResponse,
+    UpdateTemplateRequest, UploadContentResponse, WithdrawReviewRequest,
 };
 
+/// Request metadata key that makes a mutating request safe to retry
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// Request metadata key carrying the bearer token a request is authenticated with
+const AUTHORIZATION: &str = "authorization";
+
 pub struct SpecServiceImpl {
     event_store: Arc<SqliteEventStore>,
     projection_store: Arc<ProjectionStore>,
+    repository: SpecRepository,
+    releases: ReleaseRegistry,
+    tenants: TenantRegistry,
+    authenticator: Authenticator,
 }
 
 impl SpecServiceImpl {
-    pub fn new(event_store: Arc<SqliteEventStore>, projection_store: Arc<ProjectionStore>) -> Self {
+    pub const fn new(
+        event_store: Arc<SqliteEventStore>,
+        projection_store: Arc<ProjectionStore>,
+        repository: SpecRepository,
+        releases: ReleaseRegistry,
+        tenants: TenantRegistry,
+        authenticator: Authenticator,
+    ) -> Self {
         Self {
             event_store,
             projection_store,
+            repository,
+            releases,
+            tenants,
+            authenticator,
         }
     }
 
     pub fn into_service(self) -> SpecServiceServer<Self> {
         SpecServiceServer::new(self)
     }
+
+    /// Who a request was made by, from its `authorization` metadata of the
+    /// form `Bearer <token>`; principals of unknown or suspended tenants are
+    /// rejected
+    async fn principal(&self, metadata: &MetadataMap) -> Result<Principal, Status> {
+        let authorization = metadata
+            .get(AUTHORIZATION)
+            .map(|value| value.to_str().unwrap_or_default());
+        let principal = self
+            .authenticator
+            .authenticate(authorization)
+            .map_err(|e| handle_domain_error(&e))?;
+
+        self.tenants
+            .authorize(&principal.tenant)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(principal)
+    }
+
+    /// Tenant a request acts for: that of the principal it was made by
+    async fn tenant<T: Sync>(&self, request: &Request<T>) -> Result<TenantId, Status> {
+        Ok(self.principal(request.metadata()).await?.tenant)
+    }
+
+    /// Principal of a request that administers tenants
+    async fn admin(&self, metadata: &MetadataMap) -> Result<Principal, Status> {
+        let principal = self.principal(metadata).await?;
+        principal
+            .require_admin()
+            .map_err(|e| handle_domain_error(&e))?;
+        Ok(principal)
+    }
+
+    /// Parse a spec id, checking that the spec exists for `tenant`
+    async fn existing_spec_id(&self, tenant: &TenantId, id: &str) -> Result<Uuid, Status> {
+        let spec_id =
+            Uuid::parse_str(id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        self.projection_store
+            .get_by_id(tenant, spec_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map(|_| spec_id)
+            .ok_or_else(|| Status::not_found("Spec not found"))
+    }
+
+    /// Id of a release given by id or by name
+    async fn resolve_release(&self, tenant: &TenantId, release: &str) -> Result<Uuid, Status> {
+        if let Ok(id) = Uuid::parse_str(release) {
+            return Ok(id);
+        }
+
+        self.event_store
+            .find_release(tenant, release)
+            .await
+            .map_err(|e| handle_domain_error(&e))?
+            .ok_or_else(|| Status::not_found("Release not found"))
+    }
+
+    /// A release by id or name, or the current release when none is given
+    async fn release_projection(
+        &self,
+        tenant: &TenantId,
+        release: &str,
+    ) -> Result<ReleaseProjection, Status> {
+        if release.is_empty() {
+            return self
+                .projection_store
+                .get_current_release(tenant)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+                .ok_or_else(|| Status::not_found("No release is published"));
+        }
+
+        let id = self.resolve_release(tenant, release).await?;
+
+        self.projection_store
+            .get_release(tenant, id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| Status::not_found("Release not found"))
+    }
+
+    /// Edit or resolve a comment, reporting the version the comment is on
+    async fn execute_comment_command(
+        &self,
+        spec_id: Uuid,
+        comment_id: Uuid,
+        command: SpecCommand,
+        metadata: EventMetadata,
+    ) -> Result<Response<CommentResponse>, Status> {
+        let tenant = metadata.tenant_id.clone();
+        let envelopes = self
+            .repository
+            .execute(spec_id, command, None, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let (spec, _) = self
+            .repository
+            .load(&tenant, spec_id)
+            .await
+            .map_err(|e| handle_domain_error(&e))?
+            .ok_or_else(|| Status::not_found("Spec not found"))?;
+
+        Ok(Response::new(CommentResponse {
+            comment_id: comment_id.to_string(),
+            version: spec
+                .comments
+                .get(&comment_id)
+                .map_or(0, |comment| comment.version.as_u32()),
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    /// Convert a batch item to the command it carries
+    async fn batch_command(
+        &self,
+        command: ProtoBatchCommand,
+        user: &str,
+    ) -> Result<BatchCommand, Status> {
+        Ok(match command {
+            ProtoBatchCommand::Create(req) => BatchCommand::Create(CreateSpec {
+                name: req.name,
+                content: self.resolve_content(req.content, req.content_hash).await?,
+                description: Some(req.description).filter(|d| !d.is_empty()),
+                schema: req
+                    .schema
+                    .as_ref()
+                    .map(proto_schema_ref_to_domain)
+                    .transpose()?,
+                labels: req.labels.into_iter().collect(),
+                template: None,
+                created_by: user.to_string(),
+            }),
+            ProtoBatchCommand::Update(req) => BatchCommand::Update(
+                BatchTarget {
+                    spec: req.id.as_str().into(),
+                    expected_version: req.expected_version,
+                },
+                UpdateSpec {
+                    spec_id: Uuid::nil(),
+                    content: self.resolve_content(req.content, req.content_hash).await?,
+                    description: req.description,
+                    bump: req.bump.map(proto_bump_to_domain).transpose()?,
+                    schema: req
+                        .schema
+                        .as_ref()
+                        .map(proto_schema_ref_to_domain)
+                        .transpose()?,
+                    updated_by: user.to_string(),
+                },
+            ),
+            ProtoBatchCommand::Publish(req) => BatchCommand::Publish(
+                BatchTarget {
+                    spec: req.id.as_str().into(),
+                    expected_version: req.expected_version,
+                },
+                PublishSpec {
+                    spec_id: Uuid::nil(),
+                    version: req.version,
+                    published_by: user.to_string(),
+                },
+            ),
+            ProtoBatchCommand::Deprecate(req) => {
+                let sunset_at = match req.sunset_at {
+                    Some(ts) => Some(
+                        proto_timestamp_to_chrono(&ts)
+                            .ok_or_else(|| Status::invalid_argument("Invalid sunset timestamp"))?,
+                    ),
+                    None => None,
+                };
+
+                BatchCommand::Deprecate(
+                    BatchTarget {
+                        spec: req.id.as_str().into(),
+                        expected_version: req.expected_version,
+                    },
+                    DeprecateSpec {
+                        spec_id: Uuid::nil(),
+                        reason: req.reason,
+                        successor_id: req
+                            .successor_id
+                            .map(|id| Uuid::parse_str(&id))
+                            .transpose()
+                            .map_err(|_| Status::invalid_argument("Invalid successor ID"))?,
+                        sunset_at,
+                        deprecated_by: user.to_string(),
+                    },
+                )
+            }
+        })
+    }
+
+    /// Content for a create or update, read from an earlier upload when a hash is given
+    async fn resolve_content(
+        &self,
+        content: String,
+        content_hash: Option<String>,
+    ) -> Result<String, Status> {
+        match content_hash {
+            Some(hash) => self
+                .event_store
+                .load_content(&hash)
+                .await
+                .map_err(|e| handle_domain_error(&e)),
+            None => Ok(content),
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -49,41 +333,50 @@ impl SpecService for SpecServiceImpl {
         &self,
         request: Request<CreateSpecRequest>,
     ) -> Result<Response<CreateSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
         let req = request.into_inner();
 
         // TODO: Extract user from request metadata
-        let user = "grpc-user@example.com";
+        let user = principal.user.as_str();
 
         let command = CreateSpec {
             name: req.name,
-            content: req.content,
+            content: self.resolve_content(req.content, req.content_hash).await?,
             description: if req.description.is_empty() {
                 None
             } else {
                 Some(req.description)
             },
+            schema: req
+                .schema
+                .as_ref()
+                .map(proto_schema_ref_to_domain)
+                .transpose()?,
+            labels: req.labels.into_iter().collect(),
+            template: None,
             created_by: user.to_string(),
         };
 
-        let events = Spec::create(command)
-            .map_err(|e| Status::invalid_argument(format!("Validation failed: {e}")))?;
-
-        let spec_id = match &events[0] {
-            SpecEvent::Created(e) => e.spec_id,
-            _ => unreachable!(),
-        };
-
         let metadata = EventMetadata {
             correlation_id: Some(Uuid::new_v4()),
             causation_id: None,
             user_agent: None,
             ip_address: None,
+            ..metadata
         };
 
-        self.event_store
-            .append_events(spec_id, events, metadata)
+        let envelopes = self
+            .repository
+            .create(command, metadata)
             .await
-            .map_err(|e| Status::internal(format!("Failed to store events: {e}")))?;
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let (spec_id, warnings) = match &envelopes[0].event {
+            SpecEvent::Created(e) => (e.spec_id, e.warnings.iter().map(issue_to_proto).collect()),
+            _ => unreachable!(),
+        };
 
         // Wait briefly for projections
         tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
@@ -91,6 +384,8 @@ impl SpecService for SpecServiceImpl {
         Ok(Response::new(CreateSpecResponse {
             id: spec_id.to_string(),
             version: 1,
+            stream_version: stream_version(&envelopes),
+            warnings,
         }))
     }
 
@@ -98,49 +393,44 @@ impl SpecService for SpecServiceImpl {
         &self,
         request: Request<UpdateSpecRequest>,
     ) -> Result<Response<UpdateSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
         let req = request.into_inner();
         let spec_id =
             Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
 
-        // Load current state
-        let events = self
-            .event_store
-            .get_events(spec_id, None)
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
-
-        if events.is_empty() {
-            return Err(Status::not_found("Spec not found"));
-        }
-
-        let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())
-            .map_err(|e| Status::internal(e.to_string()))?;
-
-        let user = "grpc-user@example.com";
+        let user = principal.user.as_str();
 
         let command = UpdateSpec {
             spec_id,
-            content: req.content,
+            content: self.resolve_content(req.content, req.content_hash).await?,
             description: req.description,
+            bump: req.bump.map(proto_bump_to_domain).transpose()?,
+            schema: req
+                .schema
+                .as_ref()
+                .map(proto_schema_ref_to_domain)
+                .transpose()?,
             updated_by: user.to_string(),
         };
 
-        let new_events = spec
-            .handle_command(command.into())
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
             .map_err(|e| handle_domain_error(&e))?;
 
-        let new_version = match &new_events[0] {
-            SpecEvent::Updated(e) => e.version,
+        let (new_version, semver, warnings) = match &envelopes[0].event {
+            SpecEvent::Updated(e) => (e.version, e.semver.clone(), e.warnings.clone()),
             _ => unreachable!(),
         };
 
-        self.event_store
-            .append_events(spec_id, new_events, EventMetadata::default())
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
-
         Ok(Response::new(UpdateSpecResponse {
             version: new_version,
+            stream_version: stream_version(&envelopes),
+            semver: semver.to_string(),
+            warnings: warnings.iter().map(issue_to_proto).collect(),
         }))
     }
 
@@ -148,120 +438,265 @@ impl SpecService for SpecServiceImpl {
         &self,
         request: Request<GetSpecRequest>,
     ) -> Result<Response<GetSpecResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
         let req = request.into_inner();
         let spec_id =
             Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
 
-        let spec = if let Some(version) = req.version {
-            // Get specific version from history
-            let (content, description) = self
-                .projection_store
-                .get_version(spec_id, version)
-                .await
-                .map_err(|e| Status::internal(e.to_string()))?
-                .ok_or_else(|| Status::not_found("Version not found"))?;
+        let include_deleted = req.include_deleted.unwrap_or(false);
 
-            // Also need current spec for metadata
-            let current = self
-                .projection_store
-                .get_by_id(spec_id)
-                .await
-                .map_err(|e| Status::internal(e.to_string()))?
-                .ok_or_else(|| Status::not_found("Spec not found"))?;
+        let current = self
+            .projection_store
+            .get_by_id(&tenant, spec_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .filter(|spec| include_deleted || spec.state != SpecState::Deleted)
+            .ok_or_else(|| Status::not_found("Spec not found"))?;
+
+        let requested_version = if req.published.unwrap_or(false) {
+            Some(
+                current
+                    .published_version
+                    .ok_or_else(|| Status::not_found("Spec has no published version"))?,
+            )
+        } else if let Some(channel) = &req.channel {
+            Some(
+                self.projection_store
+                    .get_channel_spec(&tenant, channel, spec_id)
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?
+                    .ok_or_else(|| Status::not_found("Spec is not in this channel"))?
+                    .version,
+            )
+        } else if req.version.is_some() || req.version_range.is_some() || req.head.unwrap_or(false)
+        {
+            req.version
+        } else {
+            // Consumers read the published version unless they ask for the head
+            current
+                .published_version
+                .filter(|version| *version != current.head_version)
+        };
+
+        let revision = if let Some(version) = requested_version {
+            // Get specific version from history
+            Some(
+                self.projection_store
+                    .get_version(&tenant, spec_id, version)
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?
+                    .ok_or_else(|| Status::not_found("Version not found"))?,
+            )
+        } else if let Some(range) = req.version_range {
+            let req = semver::VersionReq::parse(&range)
+                .map_err(|e| Status::invalid_argument(format!("Invalid version range: {e}")))?;
+            Some(
+                self.projection_store
+                    .resolve_version(&tenant, spec_id, &req)
+                    .await
+                    .map_err(|e| Status::internal(e.to_string()))?
+                    .ok_or_else(|| Status::not_found("Version not found"))?,
+            )
+        } else {
+            None
+        };
 
+        let spec = if let Some(revision) = revision {
             GetSpecResponse {
                 id: spec_id.to_string(),
                 name: current.name,
-                content,
-                description: description.unwrap_or_default(),
-                version,
+                content: revision.content,
+                content_hash: revision.content_hash,
+                description: revision.description.unwrap_or_default(),
+                version: revision.version,
+                semver: revision.semver.to_string(),
                 state: domain_state_to_proto(current.state) as i32,
                 created_at: Some(chrono_to_proto_timestamp(current.created_at)),
                 updated_at: Some(chrono_to_proto_timestamp(current.updated_at)),
+                stream_version: current.stream_version,
+                published_version: current.published_version,
+                deprecation: current.deprecation.map(deprecation_to_proto),
+                review: current.review.map(review_to_proto),
+                labels: current.labels.into_iter().collect(),
+                schema: current.schema.map(domain_schema_ref_to_proto),
+                template: current.template.map(domain_template_ref_to_proto),
+                head_version: current.head_version,
             }
         } else {
-            // Get current version
-            let spec = self
-                .projection_store
-                .get_by_id(spec_id)
-                .await
-                .map_err(|e| Status::internal(e.to_string()))?
-                .ok_or_else(|| Status::not_found("Spec not found"))?;
-
             GetSpecResponse {
-                id: spec.id.to_string(),
-                name: spec.name,
-                content: spec.content,
-                description: spec.description.unwrap_or_default(),
-                version: spec.version,
-                state: domain_state_to_proto(spec.state) as i32,
-                created_at: Some(chrono_to_proto_timestamp(spec.created_at)),
-                updated_at: Some(chrono_to_proto_timestamp(spec.updated_at)),
+                id: current.id.to_string(),
+                name: current.name,
+                content: current.content,
+                content_hash: current.content_hash,
+                description: current.description.unwrap_or_default(),
+                version: current.head_version,
+                semver: current.semver.to_string(),
+                state: domain_state_to_proto(current.state) as i32,
+                created_at: Some(chrono_to_proto_timestamp(current.created_at)),
+                updated_at: Some(chrono_to_proto_timestamp(current.updated_at)),
+                stream_version: current.stream_version,
+                published_version: current.published_version,
+                deprecation: current.deprecation.map(deprecation_to_proto),
+                review: current.review.map(review_to_proto),
+                labels: current.labels.into_iter().collect(),
+                schema: current.schema.map(domain_schema_ref_to_proto),
+                template: current.template.map(domain_template_ref_to_proto),
+                head_version: current.head_version,
             }
         };
 
         Ok(Response::new(spec))
     }
 
+    async fn get_spec_by_name(
+        &self,
+        request: Request<GetSpecByNameRequest>,
+    ) -> Result<Response<GetSpecResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+
+        let spec_id = self
+            .event_store
+            .resolve_name(&tenant, &req.name)
+            .await
+            .map_err(|e| handle_domain_error(&e))?
+            .ok_or_else(|| Status::not_found("Spec not found"))?;
+
+        self.get_spec(Request::new(GetSpecRequest {
+            id: spec_id.to_string(),
+            version: None,
+            include_deleted: req.include_deleted,
+            published: None,
+            version_range: None,
+            channel: req.channel,
+            head: req.head,
+        }))
+        .await
+    }
+
     async fn list_specs(
         &self,
         request: Request<ListSpecsRequest>,
     ) -> Result<Response<ListSpecsResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
         let req = request.into_inner();
 
         let state_filter = req
             .state
             .and_then(|s| ProtoSpecState::try_from(s).ok().map(proto_state_to_domain));
 
+        let selector = req
+            .label_selector
+            .as_deref()
+            .map(str::parse::<LabelSelector>)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?
+            .unwrap_or_default();
+
+        let include_deleted = req.include_deleted.unwrap_or(false);
         let page_size = i64::from(req.page_size);
         let offset = 0; // TODO: Implement page token parsing
 
         let specs = self
             .projection_store
-            .list_by_state(state_filter, page_size, offset)
+            .list_by_state(
+                &tenant,
+                state_filter,
+                include_deleted,
+                &selector,
+                page_size,
+                offset,
+            )
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
 
-        let summaries: Vec<SpecSummary> = specs
+        Ok(Response::new(ListSpecsResponse {
+            specs: specs.into_iter().map(summary_to_proto).collect(),
+            next_page_token: String::new(), // TODO: Implement pagination
+        }))
+    }
+
+    async fn get_tree(
+        &self,
+        request: Request<GetTreeRequest>,
+    ) -> Result<Response<GetTreeResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+
+        let folder = self
+            .projection_store
+            .list_folder(&tenant, req.path.trim_end_matches('/'), req.recursive)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| Status::not_found("Folder not found"))?;
+
+        Ok(Response::new(GetTreeResponse {
+            path: folder.path,
+            folders: folder
+                .folders
+                .into_iter()
+                .map(|(path, spec_count)| spec_proto::Folder { path, spec_count })
+                .collect(),
+            specs: folder.specs.into_iter().map(summary_to_proto).collect(),
+        }))
+    }
+
+    async fn move_folder(
+        &self,
+        request: Request<MoveFolderRequest>,
+    ) -> Result<Response<MoveFolderResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+
+        let user = principal.user.as_str();
+
+        let command = MoveFolder {
+            from: req.from.trim_end_matches('/').to_string(),
+            to: req.to.trim_end_matches('/').to_string(),
+            moved_by: user.to_string(),
+        };
+
+        let metadata = EventMetadata {
+            correlation_id: Some(Uuid::new_v4()),
+            ..metadata
+        };
+
+        let envelopes = self
+            .repository
+            .move_folder(command, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let moved = envelopes
             .into_iter()
-            .map(|s| SpecSummary {
-                id: s.id.to_string(),
-                name: s.name,
-                description: s.description.unwrap_or_default(),
-                latest_version: s.latest_version,
-                state: domain_state_to_proto(s.state) as i32,
-                updated_at: Some(chrono_to_proto_timestamp(s.updated_at)),
+            .filter_map(|envelope| match envelope.event {
+                SpecEvent::Renamed(e) => Some(spec_proto::MovedSpec {
+                    id: e.spec_id.to_string(),
+                    old_name: e.old_name,
+                    new_name: e.new_name,
+                    stream_version: envelope.sequence_number,
+                }),
+                _ => None,
             })
             .collect();
 
-        Ok(Response::new(ListSpecsResponse {
-            specs: summaries,
-            next_page_token: String::new(), // TODO: Implement pagination
-        }))
+        Ok(Response::new(MoveFolderResponse { moved }))
     }
 
     async fn publish_spec(
         &self,
         request: Request<PublishSpecRequest>,
     ) -> Result<Response<PublishSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
         let req = request.into_inner();
         let spec_id =
             Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
 
-        let events = self
-            .event_store
-            .get_events(spec_id, None)
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
-
-        if events.is_empty() {
-            return Err(Status::not_found("Spec not found"));
-        }
-
-        let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())
-            .map_err(|e| Status::internal(e.to_string()))?;
-
-        let user = "grpc-admin@example.com";
+        let user = principal.user.as_str();
 
         let command = PublishSpec {
             spec_id,
@@ -269,17 +704,20 @@ impl SpecService for SpecServiceImpl {
             published_by: user.to_string(),
         };
 
-        let new_events = spec
-            .handle_command(command.into())
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
             .map_err(|e| handle_domain_error(&e))?;
 
-        self.event_store
-            .append_events(spec_id, new_events, EventMetadata::default())
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+        let published_version = match &envelopes[0].event {
+            SpecEvent::StateChanged(e) => e.version,
+            _ => unreachable!(),
+        };
 
         Ok(Response::new(PublishSpecResponse {
-            published_version: spec.version.as_u32(),
+            published_version,
+            stream_version: stream_version(&envelopes),
         }))
     }
 
@@ -287,152 +725,2433 @@ impl SpecService for SpecServiceImpl {
         &self,
         request: Request<DeprecateSpecRequest>,
     ) -> Result<Response<DeprecateSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
         let req = request.into_inner();
         let spec_id =
             Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
 
-        let events = self
-            .event_store
-            .get_events(spec_id, None)
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
-
-        if events.is_empty() {
-            return Err(Status::not_found("Spec not found"));
-        }
+        let user = principal.user.as_str();
 
-        let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())
-            .map_err(|e| Status::internal(e.to_string()))?;
+        let successor_id = req
+            .successor_id
+            .map(|id| Uuid::parse_str(&id))
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Invalid successor ID"))?;
 
-        let user = "grpc-admin@example.com";
+        let sunset_at = match req.sunset_at {
+            Some(ts) => Some(
+                proto_timestamp_to_chrono(&ts)
+                    .ok_or_else(|| Status::invalid_argument("Invalid sunset timestamp"))?,
+            ),
+            None => None,
+        };
 
         let command = DeprecateSpec {
             spec_id,
             reason: req.reason,
+            successor_id,
+            sunset_at,
             deprecated_by: user.to_string(),
         };
 
-        let new_events = spec
-            .handle_command(command.into())
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
             .map_err(|e| handle_domain_error(&e))?;
 
-        self.event_store
-            .append_events(spec_id, new_events, EventMetadata::default())
+        Ok(Response::new(DeprecateSpecResponse {
+            success: true,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn delete_spec(
+        &self,
+        request: Request<DeleteSpecRequest>,
+    ) -> Result<Response<DeleteSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = DeleteSpec {
+            spec_id,
+            deleted_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
             .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+            .map_err(|e| handle_domain_error(&e))?;
 
-        Ok(Response::new(DeprecateSpecResponse { success: true }))
+        Ok(Response::new(DeleteSpecResponse {
+            success: true,
+            stream_version: stream_version(&envelopes),
+        }))
     }
 
-    async fn get_spec_history(
+    async fn restore_spec(
         &self,
-        request: Request<GetSpecHistoryRequest>,
-    ) -> Result<Response<GetSpecHistoryResponse>, Status> {
+        request: Request<RestoreSpecRequest>,
+    ) -> Result<Response<RestoreSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
         let req = request.into_inner();
         let spec_id =
             Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
 
-        let event_envelopes = self
-            .event_store
-            .get_events(spec_id, None)
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+        let user = principal.user.as_str();
 
-        let proto_events: Vec<ProtoSpecEvent> = event_envelopes
-            .into_iter()
-            .map(|envelope| {
-                let (event_type, payload) = match &envelope.event {
-                    SpecEvent::Created(e) => (
-                        EventType::Created,
-                        spec_proto::spec_event::Payload::Create(spec_proto::CreatePayload {
-                            name: e.name.clone(),
-                            content: e.content.clone(),
-                            description: e.description.clone().unwrap_or_default(),
-                        }),
-                    ),
-                    SpecEvent::Updated(e) => (
-                        EventType::Updated,
-                        spec_proto::spec_event::Payload::Update(spec_proto::UpdatePayload {
-                            content: e.content.clone(),
-                            description: e.description.clone(),
-                        }),
-                    ),
-                    SpecEvent::StateChanged(e) => (
-                        EventType::StateChanged,
-                        spec_proto::spec_event::Payload::StateChange(
-                            spec_proto::StateChangePayload {
-                                from_state: domain_state_to_proto(e.from_state) as i32,
-                                to_state: domain_state_to_proto(e.to_state) as i32,
-                                reason: e.reason.clone(),
-                            },
-                        ),
-                    ),
-                };
+        let command = RestoreSpec {
+            spec_id,
+            restored_by: user.to_string(),
+        };
 
-                ProtoSpecEvent {
-                    event_id: envelope.event_id.to_string(),
-                    event_type: event_type as i32,
-                    occurred_at: Some(chrono_to_proto_timestamp(get_event_timestamp(
-                        &envelope.event,
-                    ))),
-                    user_id: get_event_user(&envelope.event),
-                    payload: Some(payload),
-                }
-            })
-            .collect();
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
 
-        Ok(Response::new(GetSpecHistoryResponse {
-            events: proto_events,
+        let restored_state = match &envelopes[0].event {
+            SpecEvent::Restored(e) => e.to_state,
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(RestoreSpecResponse {
+            state: domain_state_to_proto(restored_state) as i32,
+            stream_version: stream_version(&envelopes),
         }))
     }
-}
 
-// Helper functions
+    async fn revert_spec(
+        &self,
+        request: Request<RevertSpecRequest>,
+    ) -> Result<Response<RevertSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
 
-fn handle_domain_error(error: &DomainError) -> Status {
-    match error {
-        DomainError::SpecNotFound(_) => Status::not_found("Spec not found"),
-        DomainError::InvalidStateTransition { .. } => {
-            Status::failed_precondition(error.to_string())
-        }
-        DomainError::VersionMismatch { .. } => Status::aborted(error.to_string()),
-        DomainError::DuplicateSpecName(_) => Status::already_exists(error.to_string()),
-        DomainError::InvalidStateForOperation(_) => Status::failed_precondition(error.to_string()),
-        DomainError::ValidationError(_) => Status::invalid_argument(error.to_string()),
-        _ => Status::internal(error.to_string()),
-    }
-}
+        let user = principal.user.as_str();
 
-fn domain_state_to_proto(state: SpecState) -> ProtoSpecState {
-    match state {
-        SpecState::Draft => ProtoSpecState::Draft,
-        SpecState::Published => ProtoSpecState::Published,
-        SpecState::Deprecated => ProtoSpecState::Deprecated,
-        SpecState::Deleted => ProtoSpecState::Deleted,
-    }
-}
+        let command = RevertSpec {
+            spec_id,
+            to_version: req.to_version,
+            reason: req.reason,
+            reverted_by: user.to_string(),
+        };
 
-fn proto_state_to_domain(state: ProtoSpecState) -> SpecState {
-    match state {
-        ProtoSpecState::Draft => SpecState::Draft,
-        ProtoSpecState::Published => SpecState::Published,
-        ProtoSpecState::Deprecated => SpecState::Deprecated,
-        ProtoSpecState::Deleted => SpecState::Deleted,
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let (version, reverted_from) = match &envelopes[0].event {
+            SpecEvent::Reverted(e) => (e.version, e.reverted_from),
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(RevertSpecResponse {
+            version,
+            reverted_from,
+            stream_version: stream_version(&envelopes),
+        }))
     }
-}
 
-fn chrono_to_proto_timestamp(dt: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
-    prost_types::Timestamp {
+    async fn rename_spec(
+        &self,
+        request: Request<RenameSpecRequest>,
+    ) -> Result<Response<RenameSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = RenameSpec {
+            spec_id,
+            new_name: req.name,
+            renamed_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let name = match &envelopes[0].event {
+            SpecEvent::Renamed(e) => e.new_name.clone(),
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(RenameSpecResponse {
+            name,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn add_labels(
+        &self,
+        request: Request<AddLabelsRequest>,
+    ) -> Result<Response<LabelsResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = AddLabels {
+            spec_id,
+            labels: req.labels.into_iter().collect(),
+            labeled_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(LabelsResponse {
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn add_comment(
+        &self,
+        request: Request<AddCommentRequest>,
+    ) -> Result<Response<CommentResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = AddComment {
+            spec_id,
+            version: req.version,
+            anchor: req
+                .anchor
+                .as_ref()
+                .map(proto_anchor_to_domain)
+                .transpose()?
+                .flatten(),
+            parent_id: req
+                .parent_id
+                .as_deref()
+                .map(Uuid::parse_str)
+                .transpose()
+                .map_err(|_| Status::invalid_argument("Invalid parent comment ID"))?,
+            body: req.body,
+            author: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), None, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let (comment_id, version) = match &envelopes[0].event {
+            SpecEvent::CommentAdded(e) => (e.comment_id, e.version),
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(CommentResponse {
+            comment_id: comment_id.to_string(),
+            version,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn edit_comment(
+        &self,
+        request: Request<EditCommentRequest>,
+    ) -> Result<Response<CommentResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+        let comment_id = Uuid::parse_str(&req.comment_id)
+            .map_err(|_| Status::invalid_argument("Invalid comment ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = EditComment {
+            spec_id,
+            comment_id,
+            body: req.body,
+            edited_by: user.to_string(),
+        };
+
+        self.execute_comment_command(spec_id, comment_id, command.into(), metadata)
+            .await
+    }
+
+    async fn resolve_comment(
+        &self,
+        request: Request<ResolveCommentRequest>,
+    ) -> Result<Response<CommentResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+        let comment_id = Uuid::parse_str(&req.comment_id)
+            .map_err(|_| Status::invalid_argument("Invalid comment ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = ResolveComment {
+            spec_id,
+            comment_id,
+            resolved_by: user.to_string(),
+        };
+
+        self.execute_comment_command(spec_id, comment_id, command.into(), metadata)
+            .await
+    }
+
+    async fn list_comments(
+        &self,
+        request: Request<ListCommentsRequest>,
+    ) -> Result<Response<ListCommentsResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+        let spec_id = self.existing_spec_id(&tenant, &req.id).await?;
+
+        let threads = self
+            .projection_store
+            .list_comment_threads(&tenant, spec_id, req.version, req.resolved)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListCommentsResponse {
+            threads: threads.into_iter().map(thread_to_proto).collect(),
+        }))
+    }
+
+    async fn set_dependencies(
+        &self,
+        request: Request<SetDependenciesRequest>,
+    ) -> Result<Response<SetDependenciesResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = SetDependencies {
+            spec_id,
+            dependencies: req
+                .dependencies
+                .iter()
+                .map(proto_dependency_to_domain)
+                .collect::<Result<_, _>>()?,
+            set_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(SetDependenciesResponse {
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn get_dependencies(
+        &self,
+        request: Request<GetDependenciesRequest>,
+    ) -> Result<Response<DependenciesResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let spec_id = self
+            .existing_spec_id(&tenant, &request.into_inner().id)
+            .await?;
+
+        let dependencies = self
+            .projection_store
+            .get_dependencies(&tenant, spec_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(DependenciesResponse {
+            specs: dependencies.into_iter().map(dependency_to_proto).collect(),
+        }))
+    }
+
+    async fn get_dependents(
+        &self,
+        request: Request<GetDependenciesRequest>,
+    ) -> Result<Response<DependenciesResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let spec_id = self
+            .existing_spec_id(&tenant, &request.into_inner().id)
+            .await?;
+
+        let dependents = self
+            .projection_store
+            .get_dependents(&tenant, spec_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(DependenciesResponse {
+            specs: dependents.into_iter().map(dependency_to_proto).collect(),
+        }))
+    }
+
+    async fn promote_spec(
+        &self,
+        request: Request<PromoteSpecRequest>,
+    ) -> Result<Response<ChannelResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = PromoteSpec {
+            spec_id,
+            version: req.version,
+            channel: req.channel.clone(),
+            promoted_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(ChannelResponse {
+            channel: req.channel,
+            version: Some(req.version),
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn demote_spec(
+        &self,
+        request: Request<DemoteSpecRequest>,
+    ) -> Result<Response<ChannelResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = DemoteSpec {
+            spec_id,
+            channel: req.channel.clone(),
+            demoted_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let version = match &envelopes[0].event {
+            SpecEvent::Demoted(e) => e.restored_version,
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(ChannelResponse {
+            channel: req.channel,
+            version,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn get_spec_channels(
+        &self,
+        request: Request<GetSpecChannelsRequest>,
+    ) -> Result<Response<ListChannelSpecsResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let spec_id = self
+            .existing_spec_id(&tenant, &request.into_inner().id)
+            .await?;
+
+        let channels = self
+            .projection_store
+            .list_spec_channels(&tenant, spec_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListChannelSpecsResponse {
+            specs: channels.into_iter().map(channel_spec_to_proto).collect(),
+        }))
+    }
+
+    async fn list_channel_specs(
+        &self,
+        request: Request<ListChannelSpecsRequest>,
+    ) -> Result<Response<ListChannelSpecsResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let specs = self
+            .projection_store
+            .list_channel_specs(&tenant, &request.into_inner().channel)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListChannelSpecsResponse {
+            specs: specs.into_iter().map(channel_spec_to_proto).collect(),
+        }))
+    }
+
+    async fn create_release(
+        &self,
+        request: Request<CreateReleaseRequest>,
+    ) -> Result<Response<ReleaseStateResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+
+        let user = principal.user.as_str();
+
+        let command = CreateRelease {
+            name: req.name,
+            description: req.description,
+            entries: req
+                .entries
+                .iter()
+                .map(proto_release_entry_to_domain)
+                .collect::<Result<_, _>>()?,
+            created_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .releases
+            .create(command, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(ReleaseStateResponse {
+            id: envelopes[0].aggregate_id.to_string(),
+            state: ProtoReleaseState::Draft as i32,
+            restored_release_id: None,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn publish_release(
+        &self,
+        request: Request<PublishReleaseRequest>,
+    ) -> Result<Response<ReleaseStateResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let release_id = self.resolve_release(&tenant, &req.release).await?;
+
+        let user = principal.user.as_str();
+
+        let command = PublishRelease {
+            release_id,
+            published_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .releases
+            .publish(command, req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(ReleaseStateResponse {
+            id: release_id.to_string(),
+            state: ProtoReleaseState::Published as i32,
+            restored_release_id: None,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn rollback_release(
+        &self,
+        request: Request<RollbackReleaseRequest>,
+    ) -> Result<Response<ReleaseStateResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let release_id = self.resolve_release(&tenant, &req.release).await?;
+
+        let user = principal.user.as_str();
+
+        let command = RollbackRelease {
+            release_id,
+            rolled_back_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .releases
+            .rollback(command, req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let restored_release_id = match &envelopes[0].event {
+            SpecEvent::ReleaseRolledBack(e) => e.restored_release_id,
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(ReleaseStateResponse {
+            id: release_id.to_string(),
+            state: ProtoReleaseState::RolledBack as i32,
+            restored_release_id: restored_release_id.map(|id| id.to_string()),
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn get_release(
+        &self,
+        request: Request<GetReleaseRequest>,
+    ) -> Result<Response<ProtoRelease>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let release = self
+            .release_projection(&tenant, &request.into_inner().release)
+            .await?;
+
+        Ok(Response::new(release_to_proto(release)))
+    }
+
+    async fn list_releases(
+        &self,
+        request: Request<ListReleasesRequest>,
+    ) -> Result<Response<ListReleasesResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let releases = self
+            .projection_store
+            .list_releases(&tenant)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListReleasesResponse {
+            releases: releases.into_iter().map(release_to_proto).collect(),
+        }))
+    }
+
+    async fn get_release_specs(
+        &self,
+        request: Request<GetReleaseRequest>,
+    ) -> Result<Response<ReleaseSpecsResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let release = self
+            .release_projection(&tenant, &request.into_inner().release)
+            .await?;
+
+        let specs = self
+            .projection_store
+            .list_release_specs(&tenant, release.id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ReleaseSpecsResponse {
+            release_id: release.id.to_string(),
+            name: release.name,
+            state: release_state_to_proto(release.state) as i32,
+            specs: specs
+                .into_iter()
+                .map(|(entry, revision)| spec_proto::ReleaseSpec {
+                    spec_id: entry.spec_id.to_string(),
+                    name: entry.name,
+                    version: revision.version,
+                    semver: revision.semver.to_string(),
+                    content: revision.content,
+                    content_hash: revision.content_hash,
+                    description: revision.description,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn remove_labels(
+        &self,
+        request: Request<RemoveLabelsRequest>,
+    ) -> Result<Response<LabelsResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = RemoveLabels {
+            spec_id,
+            keys: req.keys,
+            unlabeled_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(LabelsResponse {
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn schedule_transition(
+        &self,
+        request: Request<ScheduleTransitionRequest>,
+    ) -> Result<Response<ScheduleTransitionResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let transition = req
+            .transition
+            .ok_or_else(|| Status::invalid_argument("Transition is required"))
+            .and_then(proto_transition_to_domain)?;
+
+        let execute_at = req
+            .execute_at
+            .as_ref()
+            .and_then(proto_timestamp_to_chrono)
+            .ok_or_else(|| Status::invalid_argument("Invalid execution timestamp"))?;
+
+        let command = ScheduleTransition {
+            spec_id,
+            transition,
+            execute_at,
+            scheduled_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let schedule_id = match &envelopes[0].event {
+            SpecEvent::TransitionScheduled(e) => e.schedule_id,
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(ScheduleTransitionResponse {
+            schedule_id: schedule_id.to_string(),
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn list_schedules(
+        &self,
+        request: Request<ListSchedulesRequest>,
+    ) -> Result<Response<ListSchedulesResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+
+        let spec_id = req
+            .spec_id
+            .map(|id| Uuid::parse_str(&id))
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let status = if req.all_statuses.unwrap_or(false) {
+            None
+        } else {
+            Some(
+                req.status
+                    .and_then(|s| ProtoScheduleStatus::try_from(s).ok())
+                    .map_or(ScheduleStatus::Pending, proto_schedule_status_to_domain),
+            )
+        };
+
+        let schedules = self
+            .projection_store
+            .list_schedules(&tenant, spec_id, status)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListSchedulesResponse {
+            schedules: schedules.into_iter().map(schedule_to_proto).collect(),
+        }))
+    }
+
+    async fn cancel_schedule(
+        &self,
+        request: Request<CancelScheduleRequest>,
+    ) -> Result<Response<CancelScheduleResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+        let schedule_id = Uuid::parse_str(&req.schedule_id)
+            .map_err(|_| Status::invalid_argument("Invalid schedule ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = CancelSchedule {
+            spec_id,
+            schedule_id,
+            cancelled_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(CancelScheduleResponse {
+            success: true,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn request_review(
+        &self,
+        request: Request<RequestReviewRequest>,
+    ) -> Result<Response<ReviewResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = RequestReview {
+            spec_id,
+            requested_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(ReviewResponse {
+            success: true,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn approve_spec(
+        &self,
+        request: Request<ApproveSpecRequest>,
+    ) -> Result<Response<ReviewResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = ApproveSpec {
+            spec_id,
+            comment: req.comment,
+            approved_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(ReviewResponse {
+            success: true,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn reject_spec(
+        &self,
+        request: Request<RejectSpecRequest>,
+    ) -> Result<Response<ReviewResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = RejectSpec {
+            spec_id,
+            reason: req.reason,
+            rejected_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(ReviewResponse {
+            success: true,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn withdraw_review(
+        &self,
+        request: Request<WithdrawReviewRequest>,
+    ) -> Result<Response<ReviewResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = WithdrawReview {
+            spec_id,
+            withdrawn_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .execute(spec_id, command.into(), req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(ReviewResponse {
+            success: true,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn get_spec_history(
+        &self,
+        request: Request<GetSpecHistoryRequest>,
+    ) -> Result<Response<GetSpecHistoryResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+        let spec_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid spec ID"))?;
+
+        let event_envelopes = self
+            .event_store
+            .get_events(&tenant, spec_id, None)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        let proto_events: Vec<ProtoSpecEvent> = event_envelopes
+            .into_iter()
+            .map(|envelope| {
+                let (event_type, payload) = event_to_proto_payload(&envelope.event);
+
+                ProtoSpecEvent {
+                    event_id: envelope.event_id.to_string(),
+                    event_type: event_type as i32,
+                    occurred_at: Some(chrono_to_proto_timestamp(get_event_timestamp(
+                        &envelope.event,
+                    ))),
+                    user_id: get_event_user(&envelope.event),
+                    payload: Some(payload),
+                }
+            })
+            .collect();
+
+        Ok(Response::new(GetSpecHistoryResponse {
+            events: proto_events,
+        }))
+    }
+
+    async fn register_schema(
+        &self,
+        request: Request<RegisterSchemaRequest>,
+    ) -> Result<Response<SchemaVersionResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+
+        let user = principal.user.as_str();
+
+        let command = RegisterSchema {
+            name: req.name,
+            schema: parse_schema_json(&req.schema)?,
+            description: req.description,
+            registered_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .schemas()
+            .register(command, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(SchemaVersionResponse {
+            id: envelopes[0].aggregate_id.to_string(),
+            version: 1,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn update_schema(
+        &self,
+        request: Request<UpdateSchemaRequest>,
+    ) -> Result<Response<SchemaVersionResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let schema_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid schema ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = UpdateSchema {
+            schema_id,
+            schema: parse_schema_json(&req.schema)?,
+            description: req.description,
+            updated_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .schemas()
+            .update(command, req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let version = match &envelopes[0].event {
+            SpecEvent::SchemaUpdated(e) => e.version,
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(SchemaVersionResponse {
+            id: schema_id.to_string(),
+            version,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<GetSchemaRequest>,
+    ) -> Result<Response<ProtoSchema>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+        let schema_id =
+            Uuid::parse_str(&req.id).map_err(|_| Status::invalid_argument("Invalid schema ID"))?;
+
+        let schema = self
+            .projection_store
+            .get_schema(&tenant, schema_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| handle_domain_error(&DomainError::SchemaNotFound(schema_id)))?;
+
+        let version = req.version.unwrap_or(schema.head_version);
+        let revision = self
+            .projection_store
+            .get_schema_version(&tenant, schema_id, version)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                handle_domain_error(&DomainError::SchemaVersionNotFound { schema_id, version })
+            })?;
+
+        Ok(Response::new(ProtoSchema {
+            id: schema_id.to_string(),
+            name: schema.name,
+            description: revision.description,
+            version: revision.version,
+            schema: revision.schema.to_string(),
+            created_at: Some(chrono_to_proto_timestamp(revision.created_at)),
+            created_by: revision.created_by,
+        }))
+    }
+
+    async fn list_schemas(
+        &self,
+        request: Request<ListSchemasRequest>,
+    ) -> Result<Response<ListSchemasResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let schemas = self
+            .projection_store
+            .list_schemas(&tenant)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListSchemasResponse {
+            schemas: schemas
+                .into_iter()
+                .map(|schema| ProtoSchema {
+                    id: schema.id.to_string(),
+                    name: schema.name,
+                    description: schema.description,
+                    version: schema.head_version,
+                    schema: schema.schema.to_string(),
+                    created_at: Some(chrono_to_proto_timestamp(schema.updated_at)),
+                    created_by: schema.updated_by,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn register_template(
+        &self,
+        request: Request<RegisterTemplateRequest>,
+    ) -> Result<Response<TemplateVersionResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+
+        let user = principal.user.as_str();
+
+        let command = RegisterTemplate {
+            name: req.name,
+            body: req.body,
+            parameters: req
+                .parameters
+                .iter()
+                .map(proto_template_parameter_to_domain)
+                .collect::<Result<_, _>>()?,
+            description: req.description,
+            registered_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .templates()
+            .register(command, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(TemplateVersionResponse {
+            id: envelopes[0].aggregate_id.to_string(),
+            version: 1,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn update_template(
+        &self,
+        request: Request<UpdateTemplateRequest>,
+    ) -> Result<Response<TemplateVersionResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+        let template_id = Uuid::parse_str(&req.id)
+            .map_err(|_| Status::invalid_argument("Invalid template ID"))?;
+
+        let user = principal.user.as_str();
+
+        let command = UpdateTemplate {
+            template_id,
+            body: req.body,
+            parameters: req
+                .parameters
+                .iter()
+                .map(proto_template_parameter_to_domain)
+                .collect::<Result<_, _>>()?,
+            description: req.description,
+            updated_by: user.to_string(),
+        };
+
+        let envelopes = self
+            .repository
+            .templates()
+            .update(command, req.expected_version, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let version = match &envelopes[0].event {
+            SpecEvent::TemplateUpdated(e) => e.version,
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(TemplateVersionResponse {
+            id: template_id.to_string(),
+            version,
+            stream_version: stream_version(&envelopes),
+        }))
+    }
+
+    async fn get_template(
+        &self,
+        request: Request<GetTemplateRequest>,
+    ) -> Result<Response<ProtoTemplate>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+        let template_id = Uuid::parse_str(&req.id)
+            .map_err(|_| Status::invalid_argument("Invalid template ID"))?;
+
+        let template = self
+            .projection_store
+            .get_template(&tenant, template_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| handle_domain_error(&DomainError::TemplateNotFound(template_id)))?;
+
+        let version = req.version.unwrap_or(template.head_version);
+        let revision = self
+            .projection_store
+            .get_template_version(&tenant, template_id, version)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| {
+                handle_domain_error(&DomainError::TemplateVersionNotFound {
+                    template_id,
+                    version,
+                })
+            })?;
+
+        Ok(Response::new(ProtoTemplate {
+            id: template_id.to_string(),
+            name: template.name,
+            description: revision.description,
+            version: revision.version,
+            body: revision.body,
+            parameters: revision
+                .parameters
+                .iter()
+                .map(template_parameter_to_proto)
+                .collect(),
+            created_at: Some(chrono_to_proto_timestamp(revision.created_at)),
+            created_by: revision.created_by,
+        }))
+    }
+
+    async fn list_templates(
+        &self,
+        request: Request<ListTemplatesRequest>,
+    ) -> Result<Response<ListTemplatesResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let templates = self
+            .projection_store
+            .list_templates(&tenant)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListTemplatesResponse {
+            templates: templates
+                .into_iter()
+                .map(|template| ProtoTemplate {
+                    id: template.id.to_string(),
+                    name: template.name,
+                    description: template.description,
+                    version: template.head_version,
+                    body: template.body,
+                    parameters: template
+                        .parameters
+                        .iter()
+                        .map(template_parameter_to_proto)
+                        .collect(),
+                    created_at: Some(chrono_to_proto_timestamp(template.updated_at)),
+                    created_by: template.updated_by,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn preview_template(
+        &self,
+        request: Request<PreviewTemplateRequest>,
+    ) -> Result<Response<PreviewTemplateResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+        let template_id = Uuid::parse_str(&req.id)
+            .map_err(|_| Status::invalid_argument("Invalid template ID"))?;
+
+        let (template, content) = self
+            .repository
+            .templates()
+            .render(
+                &tenant,
+                template_id,
+                req.version,
+                &parse_parameters_json(&req.parameters)?,
+            )
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(PreviewTemplateResponse {
+            template: Some(domain_template_ref_to_proto(template)),
+            content,
+        }))
+    }
+
+    async fn create_spec_from_template(
+        &self,
+        request: Request<CreateSpecFromTemplateRequest>,
+    ) -> Result<Response<CreateSpecResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+
+        let user = principal.user.as_str();
+
+        let command = CreateFromTemplate {
+            name: req.name,
+            template_id: Uuid::parse_str(&req.template_id)
+                .map_err(|_| Status::invalid_argument("Invalid template ID"))?,
+            version: req.version,
+            parameters: parse_parameters_json(&req.parameters)?,
+            description: if req.description.is_empty() {
+                None
+            } else {
+                Some(req.description)
+            },
+            schema: req
+                .schema
+                .as_ref()
+                .map(proto_schema_ref_to_domain)
+                .transpose()?,
+            labels: req.labels.into_iter().collect(),
+            created_by: user.to_string(),
+        };
+
+        let metadata = EventMetadata {
+            correlation_id: Some(Uuid::new_v4()),
+            causation_id: None,
+            user_agent: None,
+            ip_address: None,
+            ..metadata
+        };
+
+        let envelopes = self
+            .repository
+            .create_from_template(command, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        let (spec_id, warnings) = match &envelopes[0].event {
+            SpecEvent::Created(e) => (e.spec_id, e.warnings.iter().map(issue_to_proto).collect()),
+            _ => unreachable!(),
+        };
+
+        Ok(Response::new(CreateSpecResponse {
+            id: spec_id.to_string(),
+            version: 1,
+            stream_version: stream_version(&envelopes),
+            warnings,
+        }))
+    }
+
+    async fn list_template_specs(
+        &self,
+        request: Request<ListTemplateSpecsRequest>,
+    ) -> Result<Response<ListTemplateSpecsResponse>, Status> {
+        let tenant = self.tenant(&request).await?;
+        let req = request.into_inner();
+        let template_id = Uuid::parse_str(&req.id)
+            .map_err(|_| Status::invalid_argument("Invalid template ID"))?;
+
+        let template = self
+            .projection_store
+            .get_template(&tenant, template_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .ok_or_else(|| handle_domain_error(&DomainError::TemplateNotFound(template_id)))?;
+
+        let specs = self
+            .projection_store
+            .list_template_specs(&tenant, template_id)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListTemplateSpecsResponse {
+            head_version: template.head_version,
+            specs: specs
+                .into_iter()
+                .map(|spec| spec_proto::DerivedSpec {
+                    id: spec.id.to_string(),
+                    name: spec.name,
+                    state: domain_state_to_proto(spec.state) as i32,
+                    template_version: spec.template_version,
+                    outdated: spec.template_version < template.head_version,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn upload_content(
+        &self,
+        request: Request<tonic::Streaming<ContentChunk>>,
+    ) -> Result<Response<UploadContentResponse>, Status> {
+        self.principal(request.metadata()).await?;
+        let mut chunks = request.into_inner();
+        let mut upload = self
+            .event_store
+            .start_upload()
+            .map_err(|e| handle_domain_error(&e))?;
+
+        while let Some(chunk) = chunks.message().await? {
+            upload
+                .write(&chunk.data)
+                .map_err(|e| handle_domain_error(&e))?;
+        }
+
+        let content_ref = self
+            .event_store
+            .put_content(upload)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(UploadContentResponse {
+            hash: content_ref.hash,
+            size: content_ref.size,
+        }))
+    }
+
+    type DownloadContentStream =
+        tokio_stream::Iter<std::vec::IntoIter<Result<ContentChunk, Status>>>;
+
+    async fn batch_execute(
+        &self,
+        request: Request<BatchExecuteRequest>,
+    ) -> Result<Response<BatchExecuteResponse>, Status> {
+        let principal = self.principal(request.metadata()).await?;
+        let tenant = principal.tenant.clone();
+        let metadata = request_metadata(&request, &tenant)?;
+        let req = request.into_inner();
+
+        let mut commands = Vec::with_capacity(req.items.len());
+        for item in req.items {
+            let command = item
+                .command
+                .ok_or_else(|| Status::invalid_argument("Batch item has no command"))?;
+            commands.push(self.batch_command(command, &principal.user).await?);
+        }
+
+        let metadata = EventMetadata {
+            correlation_id: Some(Uuid::new_v4()),
+            ..metadata
+        };
+
+        let outcome = self
+            .repository
+            .execute_batch(commands, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(BatchExecuteResponse {
+            committed: outcome.committed,
+            results: outcome.items.iter().map(batch_item_to_proto).collect(),
+        }))
+    }
+
+    async fn create_tenant(
+        &self,
+        request: Request<CreateTenantRequest>,
+    ) -> Result<Response<TenantStateResponse>, Status> {
+        let admin = self.admin(request.metadata()).await?;
+        let metadata = request_metadata(&request, &TenantId::default())?;
+        let req = request.into_inner();
+
+        let command = CreateTenant {
+            tenant_id: TenantId::new(&req.id).map_err(|e| handle_domain_error(&e.into()))?,
+            display_name: req.display_name,
+            created_by: admin.user,
+        };
+
+        self.tenants
+            .create(command, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(TenantStateResponse {
+            id: req.id,
+            state: ProtoTenantState::Active as i32,
+        }))
+    }
+
+    async fn suspend_tenant(
+        &self,
+        request: Request<SuspendTenantRequest>,
+    ) -> Result<Response<TenantStateResponse>, Status> {
+        let admin = self.admin(request.metadata()).await?;
+        let metadata = request_metadata(&request, &TenantId::default())?;
+        let req = request.into_inner();
+
+        let command = SuspendTenant {
+            tenant_id: TenantId::new(&req.id).map_err(|e| handle_domain_error(&e.into()))?,
+            reason: req.reason,
+            suspended_by: admin.user,
+        };
+
+        self.tenants
+            .suspend(command, metadata)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        Ok(Response::new(TenantStateResponse {
+            id: req.id,
+            state: ProtoTenantState::Suspended as i32,
+        }))
+    }
+
+    async fn list_tenants(
+        &self,
+        request: Request<ListTenantsRequest>,
+    ) -> Result<Response<ListTenantsResponse>, Status> {
+        self.admin(request.metadata()).await?;
+
+        let tenants = self
+            .projection_store
+            .list_tenants()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(ListTenantsResponse {
+            tenants: tenants.into_iter().map(tenant_to_proto).collect(),
+        }))
+    }
+
+    async fn download_content(
+        &self,
+        request: Request<DownloadContentRequest>,
+    ) -> Result<Response<Self::DownloadContentStream>, Status> {
+        self.principal(request.metadata()).await?;
+        let req = request.into_inner();
+
+        let content = self
+            .event_store
+            .get_content(&req.hash)
+            .await
+            .map_err(|e| handle_domain_error(&e))?
+            .ok_or_else(|| Status::not_found("Content not found"))?;
+
+        let chunks: Vec<_> = content
+            .chunks(DOWNLOAD_CHUNK_SIZE)
+            .map(|data| ContentChunk {
+                data: data.to_vec(),
+            })
+            .map(Ok)
+            .collect();
+
+        Ok(Response::new(tokio_stream::iter(chunks)))
+    }
+}
+
+// Helper functions
+
+/// Event metadata for a mutating request, carrying its `idempotency-key` metadata
+#[allow(clippy::result_large_err)]
+fn request_metadata<T>(request: &Request<T>, tenant: &TenantId) -> Result<EventMetadata, Status> {
+    let metadata = EventMetadata::default().with_tenant(tenant.clone());
+    let Some(value) = request.metadata().get(IDEMPOTENCY_KEY) else {
+        return Ok(metadata);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| Status::invalid_argument("Invalid idempotency-key metadata"))?;
+
+    metadata
+        .with_idempotency_key(key)
+        .map_err(|e| handle_domain_error(&e.into()))
+}
+
+fn handle_domain_error(error: &DomainError) -> Status {
+    match error {
+        DomainError::SpecNotFound(_) => Status::not_found("Spec not found"),
+        DomainError::InvalidStateTransition { .. } => {
+            Status::failed_precondition(error.to_string())
+        }
+        DomainError::VersionMismatch { .. }
+        | DomainError::ConcurrencyConflict { .. }
+        | DomainError::BatchAborted => Status::aborted(error.to_string()),
+        DomainError::VersionNotFound(_)
+        | DomainError::SpecNameNotFound(_)
+        | DomainError::ScheduleNotFound(_)
+        | DomainError::SchemaNotFound(_)
+        | DomainError::SchemaVersionNotFound { .. }
+        | DomainError::TemplateNotFound(_)
+        | DomainError::TemplateVersionNotFound { .. }
+        | DomainError::CommentNotFound(_)
+        | DomainError::ReleaseNotFound(_)
+        | DomainError::TenantNotFound(_)
+        | DomainError::ContentNotFound(_) => Status::not_found(error.to_string()),
+        DomainError::DuplicateSpecName(_)
+        | DomainError::DuplicateReleaseName(_)
+        | DomainError::DuplicateTenant(_) => Status::already_exists(error.to_string()),
+        DomainError::InvalidStateForOperation(_)
+        | DomainError::ReviewRequired(_)
+        | DomainError::InvalidReview(_)
+        | DomainError::UnpublishedDependency { .. }
+        | DomainError::HasDependents(_)
+        | DomainError::InvalidPromotion(_)
+        | DomainError::InvalidRelease(_)
+        | DomainError::InvalidTenant(_)
+        | DomainError::IdempotencyKeyReused(_) => Status::failed_precondition(error.to_string()),
+        DomainError::SelfApproval(_)
+        | DomainError::NotCommentAuthor { .. }
+        | DomainError::TenantSuspended(_)
+        | DomainError::PermissionDenied(_) => Status::permission_denied(error.to_string()),
+        DomainError::Unauthenticated(_) => Status::unauthenticated(error.to_string()),
+        DomainError::ValidationError(ValidationError::ContentRejected(issues)) => {
+            let details = spec_proto::ValidationIssues {
+                issues: issues.iter().map(issue_to_proto).collect(),
+            };
+            Status::with_details(
+                Code::InvalidArgument,
+                error.to_string(),
+                details.encode_to_vec().into(),
+            )
+        }
+        DomainError::ValidationError(_)
+        | DomainError::InvalidRevertTarget(_)
+        | DomainError::InvalidDeprecation(_)
+        | DomainError::InvalidSchedule(_)
+        | DomainError::InvalidLabels(_)
+        | DomainError::InvalidComment(_)
+        | DomainError::InvalidDependencies(_)
+        | DomainError::InvalidBatch(_)
+        | DomainError::InvalidMove(_) => Status::invalid_argument(error.to_string()),
+        DomainError::SchemaViolation(violations) => {
+            let details = spec_proto::SchemaViolations {
+                violations: violations
+                    .iter()
+                    .map(|v| spec_proto::SchemaViolation {
+                        path: v.path.clone(),
+                        message: v.message.clone(),
+                    })
+                    .collect(),
+            };
+            Status::with_details(
+                Code::InvalidArgument,
+                error.to_string(),
+                details.encode_to_vec().into(),
+            )
+        }
+        _ => Status::internal(error.to_string()),
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn event_to_proto_payload(event: &SpecEvent) -> (EventType, spec_proto::spec_event::Payload) {
+    match event {
+        SpecEvent::Created(e) => (
+            EventType::Created,
+            spec_proto::spec_event::Payload::Create(spec_proto::CreatePayload {
+                name: e.name.clone(),
+                content: e.content.clone(),
+                description: e.description.clone().unwrap_or_default(),
+                template: e.template.map(domain_template_ref_to_proto),
+            }),
+        ),
+        SpecEvent::Updated(e) => (
+            EventType::Updated,
+            spec_proto::spec_event::Payload::Update(spec_proto::UpdatePayload {
+                content: e.content.clone(),
+                description: e.description.clone(),
+                semver: e.semver.clone().to_string(),
+            }),
+        ),
+        SpecEvent::StateChanged(e) => (
+            EventType::StateChanged,
+            spec_proto::spec_event::Payload::StateChange(spec_proto::StateChangePayload {
+                from_state: domain_state_to_proto(e.from_state) as i32,
+                to_state: domain_state_to_proto(e.to_state) as i32,
+                reason: e.reason.clone(),
+                successor_id: e.successor_id.map(|id| id.to_string()),
+                sunset_at: e.sunset_at.map(chrono_to_proto_timestamp),
+            }),
+        ),
+        SpecEvent::Reverted(e) => (
+            EventType::Reverted,
+            spec_proto::spec_event::Payload::Revert(spec_proto::RevertPayload {
+                content: e.content.clone(),
+                description: e.description.clone(),
+                reverted_from: e.reverted_from,
+                reason: e.reason.clone(),
+                semver: e.semver.clone().to_string(),
+            }),
+        ),
+        SpecEvent::Renamed(e) => (
+            EventType::Renamed,
+            spec_proto::spec_event::Payload::Rename(spec_proto::RenamePayload {
+                old_name: e.old_name.clone(),
+                new_name: e.new_name.clone(),
+            }),
+        ),
+        SpecEvent::Restored(e) => (
+            EventType::Restored,
+            spec_proto::spec_event::Payload::Restore(spec_proto::RestorePayload {
+                to_state: domain_state_to_proto(e.to_state) as i32,
+            }),
+        ),
+        SpecEvent::TransitionScheduled(e) => (
+            EventType::TransitionScheduled,
+            spec_proto::spec_event::Payload::Schedule(spec_proto::SchedulePayload {
+                schedule_id: e.schedule_id.to_string(),
+                transition: Some(domain_transition_to_proto(&e.transition)),
+                execute_at: Some(chrono_to_proto_timestamp(e.execute_at)),
+            }),
+        ),
+        SpecEvent::ScheduleCancelled(e) => (
+            EventType::ScheduleCancelled,
+            spec_proto::spec_event::Payload::ScheduleCancel(spec_proto::ScheduleCancelPayload {
+                schedule_id: e.schedule_id.to_string(),
+            }),
+        ),
+        SpecEvent::ReviewRequested(e) => (
+            EventType::ReviewRequested,
+            spec_proto::spec_event::Payload::Review(spec_proto::ReviewPayload {
+                version: e.version,
+                required_approvals: Some(e.required_approvals),
+                comment: None,
+            }),
+        ),
+        SpecEvent::ReviewApproved(e) => (
+            EventType::ReviewApproved,
+            spec_proto::spec_event::Payload::Review(spec_proto::ReviewPayload {
+                version: e.version,
+                required_approvals: None,
+                comment: e.comment.clone(),
+            }),
+        ),
+        SpecEvent::ReviewRejected(e) => (
+            EventType::ReviewRejected,
+            spec_proto::spec_event::Payload::Review(spec_proto::ReviewPayload {
+                version: e.version,
+                required_approvals: None,
+                comment: Some(e.reason.clone()),
+            }),
+        ),
+        SpecEvent::ReviewWithdrawn(e) => (
+            EventType::ReviewWithdrawn,
+            spec_proto::spec_event::Payload::Review(spec_proto::ReviewPayload {
+                version: e.version,
+                required_approvals: None,
+                comment: None,
+            }),
+        ),
+        SpecEvent::LabelsAdded(e) => (
+            EventType::LabelsAdded,
+            spec_proto::spec_event::Payload::Labels(spec_proto::LabelsPayload {
+                added: e.labels.clone().into_iter().collect(),
+                removed: Vec::new(),
+            }),
+        ),
+        SpecEvent::LabelsRemoved(e) => (
+            EventType::LabelsRemoved,
+            spec_proto::spec_event::Payload::Labels(spec_proto::LabelsPayload {
+                added: std::collections::HashMap::new(),
+                removed: e.keys.clone(),
+            }),
+        ),
+        SpecEvent::DependenciesSet(e) => (
+            EventType::DependenciesSet,
+            spec_proto::spec_event::Payload::Dependencies(spec_proto::DependenciesPayload {
+                dependencies: e
+                    .dependencies
+                    .iter()
+                    .map(|dependency| spec_proto::Dependency {
+                        spec_id: dependency.spec_id.to_string(),
+                        version: dependency.version.to_string(),
+                    })
+                    .collect(),
+            }),
+        ),
+        SpecEvent::ScheduleExecuted(e) => (
+            EventType::ScheduleExecuted,
+            spec_proto::spec_event::Payload::ScheduleExecute(spec_proto::ScheduleExecutePayload {
+                schedule_id: e.schedule_id.to_string(),
+                failure: e.failure.clone(),
+            }),
+        ),
+        SpecEvent::SchemaRegistered(e) => (
+            EventType::SchemaRegistered,
+            spec_proto::spec_event::Payload::Schema(spec_proto::SchemaPayload {
+                schema_id: e.schema_id.to_string(),
+                version: 1,
+                schema: e.schema.to_string(),
+                description: e.description.clone(),
+            }),
+        ),
+        SpecEvent::SchemaUpdated(e) => (
+            EventType::SchemaUpdated,
+            spec_proto::spec_event::Payload::Schema(spec_proto::SchemaPayload {
+                schema_id: e.schema_id.to_string(),
+                version: e.version,
+                schema: e.schema.to_string(),
+                description: e.description.clone(),
+            }),
+        ),
+        SpecEvent::CommentAdded(e) => (
+            EventType::CommentAdded,
+            spec_proto::spec_event::Payload::Comment(spec_proto::CommentPayload {
+                comment_id: e.comment_id.to_string(),
+                version: Some(e.version),
+                parent_id: e.parent_id.map(|id| id.to_string()),
+                anchor: e.anchor.as_ref().map(anchor_to_proto),
+                body: Some(e.body.clone()),
+                resolved: false,
+            }),
+        ),
+        SpecEvent::CommentEdited(e) => (
+            EventType::CommentEdited,
+            spec_proto::spec_event::Payload::Comment(spec_proto::CommentPayload {
+                comment_id: e.comment_id.to_string(),
+                version: None,
+                parent_id: None,
+                anchor: None,
+                body: Some(e.body.clone()),
+                resolved: false,
+            }),
+        ),
+        SpecEvent::CommentResolved(e) => (
+            EventType::CommentResolved,
+            spec_proto::spec_event::Payload::Comment(spec_proto::CommentPayload {
+                comment_id: e.comment_id.to_string(),
+                version: None,
+                parent_id: None,
+                anchor: None,
+                body: None,
+                resolved: true,
+            }),
+        ),
+        SpecEvent::Promoted(e) => (
+            EventType::Promoted,
+            spec_proto::spec_event::Payload::Channel(spec_proto::ChannelPayload {
+                channel: e.channel.clone(),
+                version: Some(e.version),
+                previous_version: e.previous_version,
+            }),
+        ),
+        SpecEvent::Demoted(e) => (
+            EventType::Demoted,
+            spec_proto::spec_event::Payload::Channel(spec_proto::ChannelPayload {
+                channel: e.channel.clone(),
+                version: e.restored_version,
+                previous_version: Some(e.version),
+            }),
+        ),
+        SpecEvent::SunsetFailed(e) => (
+            EventType::SunsetFailed,
+            spec_proto::spec_event::Payload::SunsetFailed(spec_proto::SunsetFailedPayload {
+                failure: e.failure.clone(),
+            }),
+        ),
+        SpecEvent::TemplateRegistered(e) => (
+            EventType::TemplateRegistered,
+            spec_proto::spec_event::Payload::Template(spec_proto::TemplatePayload {
+                template_id: e.template_id.to_string(),
+                version: 1,
+                body: e.body.clone(),
+                parameters: e
+                    .parameters
+                    .iter()
+                    .map(template_parameter_to_proto)
+                    .collect(),
+                description: e.description.clone(),
+            }),
+        ),
+        SpecEvent::TemplateUpdated(e) => (
+            EventType::TemplateUpdated,
+            spec_proto::spec_event::Payload::Template(spec_proto::TemplatePayload {
+                template_id: e.template_id.to_string(),
+                version: e.version,
+                body: e.body.clone(),
+                parameters: e
+                    .parameters
+                    .iter()
+                    .map(template_parameter_to_proto)
+                    .collect(),
+                description: e.description.clone(),
+            }),
+        ),
+        SpecEvent::ReleaseCreated(e) => (
+            EventType::ReleaseCreated,
+            spec_proto::spec_event::Payload::Release(spec_proto::ReleasePayload {
+                release_id: e.release_id.to_string(),
+                name: Some(e.name.clone()),
+                entries: e.entries.iter().map(release_entry_to_proto).collect(),
+                restored_release_id: None,
+            }),
+        ),
+        SpecEvent::ReleasePublished(e) => (
+            EventType::ReleasePublished,
+            spec_proto::spec_event::Payload::Release(spec_proto::ReleasePayload {
+                release_id: e.release_id.to_string(),
+                name: None,
+                entries: Vec::new(),
+                restored_release_id: None,
+            }),
+        ),
+        SpecEvent::ReleaseRolledBack(e) => (
+            EventType::ReleaseRolledBack,
+            spec_proto::spec_event::Payload::Release(spec_proto::ReleasePayload {
+                release_id: e.release_id.to_string(),
+                name: None,
+                entries: Vec::new(),
+                restored_release_id: e.restored_release_id.map(|id| id.to_string()),
+            }),
+        ),
+        SpecEvent::TenantCreated(e) => (
+            EventType::TenantCreated,
+            spec_proto::spec_event::Payload::Tenant(spec_proto::TenantPayload {
+                tenant_id: e.tenant_id.to_string(),
+                display_name: e.display_name.clone(),
+                reason: None,
+            }),
+        ),
+        SpecEvent::TenantSuspended(e) => (
+            EventType::TenantSuspended,
+            spec_proto::spec_event::Payload::Tenant(spec_proto::TenantPayload {
+                tenant_id: e.tenant_id.to_string(),
+                display_name: None,
+                reason: e.reason.clone(),
+            }),
+        ),
+    }
+}
+
+fn stream_version(envelopes: &[EventEnvelope]) -> i64 {
+    envelopes.last().map_or(0, |e| e.sequence_number)
+}
+
+fn deprecation_to_proto(deprecation: SpecDeprecation) -> spec_proto::Deprecation {
+    spec_proto::Deprecation {
+        reason: deprecation.reason.unwrap_or_default(),
+        deprecated_at: Some(chrono_to_proto_timestamp(deprecation.deprecated_at)),
+        successor_id: deprecation.successor_id.map(|id| id.to_string()),
+        sunset_at: deprecation.sunset_at.map(chrono_to_proto_timestamp),
+    }
+}
+
+fn review_to_proto(review: SpecReview) -> spec_proto::Review {
+    let status = match review.status {
+        ReviewStatus::Pending => ProtoReviewStatus::AwaitingApproval,
+        ReviewStatus::Approved => ProtoReviewStatus::Approved,
+        ReviewStatus::Rejected => ProtoReviewStatus::Rejected,
+    };
+
+    spec_proto::Review {
+        version: review.version,
+        status: status as i32,
+        required_approvals: review.required_approvals,
+        approvals: review
+            .approvals
+            .into_iter()
+            .map(|approval| spec_proto::Approval {
+                approved_by: approval.approved_by,
+                approved_at: Some(chrono_to_proto_timestamp(approval.approved_at)),
+                comment: approval.comment,
+            })
+            .collect(),
+        requested_by: review.requested_by,
+        requested_at: Some(chrono_to_proto_timestamp(review.requested_at)),
+        rejected_by: review.rejected_by,
+        rejection_reason: review.rejection_reason,
+    }
+}
+
+fn domain_transition_to_proto(transition: &ScheduledTransition) -> spec_proto::Transition {
+    let action = match transition {
+        ScheduledTransition::Publish { version } => {
+            spec_proto::transition::Action::Publish(spec_proto::PublishTransition {
+                version: *version,
+            })
+        }
+        ScheduledTransition::Deprecate {
+            reason,
+            successor_id,
+            sunset_at,
+        } => spec_proto::transition::Action::Deprecate(spec_proto::DeprecateTransition {
+            reason: reason.clone(),
+            successor_id: successor_id.map(|id| id.to_string()),
+            sunset_at: sunset_at.map(chrono_to_proto_timestamp),
+        }),
+    };
+
+    spec_proto::Transition {
+        action: Some(action),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn proto_transition_to_domain(
+    transition: spec_proto::Transition,
+) -> Result<ScheduledTransition, Status> {
+    match transition.action {
+        Some(spec_proto::transition::Action::Publish(publish)) => {
+            Ok(ScheduledTransition::Publish {
+                version: publish.version,
+            })
+        }
+        Some(spec_proto::transition::Action::Deprecate(deprecate)) => {
+            let successor_id = deprecate
+                .successor_id
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|_| Status::invalid_argument("Invalid successor ID"))?;
+
+            let sunset_at = match deprecate.sunset_at {
+                Some(ts) => Some(
+                    proto_timestamp_to_chrono(&ts)
+                        .ok_or_else(|| Status::invalid_argument("Invalid sunset timestamp"))?,
+                ),
+                None => None,
+            };
+
+            Ok(ScheduledTransition::Deprecate {
+                reason: deprecate.reason,
+                successor_id,
+                sunset_at,
+            })
+        }
+        None => Err(Status::invalid_argument("Transition action is required")),
+    }
+}
+
+fn schedule_to_proto(schedule: ScheduleProjection) -> spec_proto::Schedule {
+    spec_proto::Schedule {
+        schedule_id: schedule.schedule_id.to_string(),
+        spec_id: schedule.spec_id.to_string(),
+        transition: Some(domain_transition_to_proto(&schedule.transition)),
+        execute_at: Some(chrono_to_proto_timestamp(schedule.execute_at)),
+        scheduled_by: schedule.scheduled_by,
+        scheduled_at: Some(chrono_to_proto_timestamp(schedule.scheduled_at)),
+        status: domain_schedule_status_to_proto(schedule.status) as i32,
+        completed_at: schedule.completed_at.map(chrono_to_proto_timestamp),
+        failure: schedule.failure,
+    }
+}
+
+const fn domain_schedule_status_to_proto(status: ScheduleStatus) -> ProtoScheduleStatus {
+    match status {
+        ScheduleStatus::Pending => ProtoScheduleStatus::Pending,
+        ScheduleStatus::Executed => ProtoScheduleStatus::Executed,
+        ScheduleStatus::Failed => ProtoScheduleStatus::Failed,
+        ScheduleStatus::Cancelled => ProtoScheduleStatus::Cancelled,
+    }
+}
+
+const fn proto_schedule_status_to_domain(status: ProtoScheduleStatus) -> ScheduleStatus {
+    match status {
+        ProtoScheduleStatus::Pending => ScheduleStatus::Pending,
+        ProtoScheduleStatus::Executed => ScheduleStatus::Executed,
+        ProtoScheduleStatus::Failed => ScheduleStatus::Failed,
+        ProtoScheduleStatus::Cancelled => ScheduleStatus::Cancelled,
+    }
+}
+
+fn batch_item_to_proto(item: &Result<BatchItemResult, DomainError>) -> ProtoBatchItemResult {
+    match item {
+        Ok(result) => ProtoBatchItemResult {
+            code: Code::Ok as i32,
+            message: String::new(),
+            id: result.spec_id.to_string(),
+            version: result.version,
+            semver: result.semver.to_string(),
+            state: domain_state_to_proto(result.state) as i32,
+            stream_version: result.stream_version,
+        },
+        Err(e) => {
+            let status = handle_domain_error(e);
+            ProtoBatchItemResult {
+                code: status.code() as i32,
+                message: status.message().to_string(),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+fn domain_state_to_proto(state: SpecState) -> ProtoSpecState {
+    match state {
+        SpecState::Draft => ProtoSpecState::Draft,
+        SpecState::Published => ProtoSpecState::Published,
+        SpecState::Deprecated => ProtoSpecState::Deprecated,
+        SpecState::Deleted => ProtoSpecState::Deleted,
+    }
+}
+
+fn proto_state_to_domain(state: ProtoSpecState) -> SpecState {
+    match state {
+        ProtoSpecState::Draft => SpecState::Draft,
+        ProtoSpecState::Published => SpecState::Published,
+        ProtoSpecState::Deprecated => SpecState::Deprecated,
+        ProtoSpecState::Deleted => SpecState::Deleted,
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn proto_bump_to_domain(bump: i32) -> Result<BumpLevel, Status> {
+    match ProtoBumpLevel::try_from(bump) {
+        Ok(ProtoBumpLevel::Patch) => Ok(BumpLevel::Patch),
+        Ok(ProtoBumpLevel::Minor) => Ok(BumpLevel::Minor),
+        Ok(ProtoBumpLevel::Major) => Ok(BumpLevel::Major),
+        Err(_) => Err(Status::invalid_argument("Invalid bump level")),
+    }
+}
+
+fn issue_to_proto(issue: &ValidationIssue) -> spec_proto::ValidationIssue {
+    let severity = match issue.severity {
+        Severity::Warning => spec_proto::Severity::Warning,
+        Severity::Error => spec_proto::Severity::Error,
+    };
+
+    spec_proto::ValidationIssue {
+        validator: issue.validator.clone(),
+        severity: severity as i32,
+        path: issue.path.clone(),
+        line: issue.line.and_then(|line| u32::try_from(line).ok()),
+        message: issue.message.clone(),
+    }
+}
+
+fn dependency_to_proto(dependency: DependencyProjection) -> spec_proto::DependencyEdge {
+    spec_proto::DependencyEdge {
+        spec_id: dependency.spec_id.to_string(),
+        name: dependency.name,
+        state: domain_state_to_proto(dependency.state) as i32,
+        published_version: dependency.published_version,
+        version: dependency.version.to_string(),
+    }
+}
+
+fn channel_spec_to_proto(entry: ChannelSpecProjection) -> spec_proto::ChannelSpec {
+    spec_proto::ChannelSpec {
+        channel: entry.channel,
+        spec_id: entry.spec_id.to_string(),
+        name: entry.name,
+        state: domain_state_to_proto(entry.state) as i32,
+        version: entry.version,
+        semver: entry.semver.to_string(),
+        changed_by: entry.changed_by,
+        changed_at: Some(chrono_to_proto_timestamp(entry.changed_at)),
+    }
+}
+
+fn release_to_proto(release: ReleaseProjection) -> ProtoRelease {
+    ProtoRelease {
+        id: release.id.to_string(),
+        name: release.name,
+        description: release.description,
+        state: release_state_to_proto(release.state) as i32,
+        entries: release
+            .entries
+            .into_iter()
+            .map(|entry| spec_proto::ReleaseManifestEntry {
+                spec_id: entry.spec_id.to_string(),
+                name: entry.name,
+                version: entry.version,
+                semver: entry.semver.to_string(),
+                content_hash: entry.content_hash,
+            })
+            .collect(),
+        created_at: Some(chrono_to_proto_timestamp(release.created_at)),
+        created_by: release.created_by,
+        updated_at: Some(chrono_to_proto_timestamp(release.updated_at)),
+        updated_by: release.updated_by,
+        published_at: release.published_at.map(chrono_to_proto_timestamp),
+        stream_version: release.stream_version,
+    }
+}
+
+fn summary_to_proto(summary: SpecSummaryProjection) -> SpecSummary {
+    SpecSummary {
+        id: summary.id.to_string(),
+        name: summary.name,
+        description: summary.description.unwrap_or_default(),
+        latest_version: summary.latest_version,
+        semver: summary.semver.to_string(),
+        published_version: summary.published_version,
+        state: domain_state_to_proto(summary.state) as i32,
+        updated_at: Some(chrono_to_proto_timestamp(summary.updated_at)),
+        labels: summary.labels.into_iter().collect(),
+    }
+}
+
+fn tenant_to_proto(tenant: TenantProjection) -> ProtoTenant {
+    ProtoTenant {
+        id: tenant.id.to_string(),
+        display_name: tenant.display_name,
+        state: tenant_state_to_proto(tenant.state) as i32,
+        suspension_reason: tenant.suspension_reason,
+        created_at: Some(chrono_to_proto_timestamp(tenant.created_at)),
+        created_by: tenant.created_by,
+        updated_at: Some(chrono_to_proto_timestamp(tenant.updated_at)),
+        updated_by: tenant.updated_by,
+    }
+}
+
+const fn tenant_state_to_proto(state: TenantState) -> ProtoTenantState {
+    match state {
+        TenantState::Active => ProtoTenantState::Active,
+        TenantState::Suspended => ProtoTenantState::Suspended,
+    }
+}
+
+const fn release_state_to_proto(state: ReleaseState) -> ProtoReleaseState {
+    match state {
+        ReleaseState::Draft => ProtoReleaseState::Draft,
+        ReleaseState::Published => ProtoReleaseState::Published,
+        ReleaseState::RolledBack => ProtoReleaseState::RolledBack,
+    }
+}
+
+fn release_entry_to_proto(entry: &ReleaseEntry) -> spec_proto::ReleaseEntry {
+    spec_proto::ReleaseEntry {
+        spec_id: entry.spec_id.to_string(),
+        version: entry.version,
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn proto_release_entry_to_domain(entry: &spec_proto::ReleaseEntry) -> Result<ReleaseEntry, Status> {
+    Ok(ReleaseEntry {
+        spec_id: Uuid::parse_str(&entry.spec_id)
+            .map_err(|_| Status::invalid_argument("Invalid spec ID"))?,
+        version: entry.version,
+    })
+}
+
+#[allow(clippy::result_large_err)]
+fn proto_dependency_to_domain(
+    dependency: &spec_proto::Dependency,
+) -> Result<SpecDependency, Status> {
+    Ok(SpecDependency {
+        spec_id: Uuid::parse_str(&dependency.spec_id)
+            .map_err(|_| Status::invalid_argument("Invalid dependency spec ID"))?,
+        version: semver::VersionReq::parse(&dependency.version)
+            .map_err(|e| Status::invalid_argument(format!("Invalid version range: {e}")))?,
+    })
+}
+
+fn domain_schema_ref_to_proto(schema: SchemaRef) -> spec_proto::SchemaRef {
+    spec_proto::SchemaRef {
+        schema_id: schema.schema_id.to_string(),
+        version: schema.version,
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn proto_schema_ref_to_domain(schema: &spec_proto::SchemaRef) -> Result<SchemaRef, Status> {
+    Ok(SchemaRef {
+        schema_id: Uuid::parse_str(&schema.schema_id)
+            .map_err(|_| Status::invalid_argument("Invalid schema ID"))?,
+        version: schema.version,
+    })
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_schema_json(schema: &str) -> Result<serde_json::Value, Status> {
+    serde_json::from_str(schema)
+        .map_err(|e| Status::invalid_argument(format!("Invalid schema JSON: {e}")))
+}
+
+fn anchor_to_proto(anchor: &CommentAnchor) -> spec_proto::CommentAnchor {
+    spec_proto::CommentAnchor {
+        path: anchor.path.clone(),
+        start_line: anchor.lines.map(|lines| lines.start),
+        end_line: anchor.lines.map(|lines| lines.end),
+    }
+}
+
+/// An anchor with nothing set means the comment is about the whole version
+#[allow(clippy::result_large_err)]
+fn proto_anchor_to_domain(
+    anchor: &spec_proto::CommentAnchor,
+) -> Result<Option<CommentAnchor>, Status> {
+    let lines = match (anchor.start_line, anchor.end_line) {
+        (Some(start), Some(end)) => Some(LineRange { start, end }),
+        (None, None) => None,
+        _ => {
+            return Err(Status::invalid_argument(
+                "A line range needs both start_line and end_line",
+            ))
+        }
+    };
+
+    if anchor.path.is_none() && lines.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(CommentAnchor {
+        path: anchor.path.clone(),
+        lines,
+    }))
+}
+
+fn comment_to_proto(comment: CommentProjection) -> spec_proto::Comment {
+    spec_proto::Comment {
+        id: comment.id.to_string(),
+        version: comment.version,
+        anchor: comment.anchor.as_ref().map(anchor_to_proto),
+        body: comment.body,
+        author: comment.author,
+        created_at: Some(chrono_to_proto_timestamp(comment.created_at)),
+        edited_at: comment.edited_at.map(chrono_to_proto_timestamp),
+    }
+}
+
+fn thread_to_proto(thread: CommentThreadProjection) -> spec_proto::CommentThread {
+    let CommentThreadProjection { comment, replies } = thread;
+
+    spec_proto::CommentThread {
+        resolved_by: comment.resolved_by.clone(),
+        resolved_at: comment.resolved_at.map(chrono_to_proto_timestamp),
+        comment: Some(comment_to_proto(comment)),
+        replies: replies.into_iter().map(comment_to_proto).collect(),
+    }
+}
+
+fn domain_template_ref_to_proto(template: TemplateRef) -> spec_proto::TemplateRef {
+    spec_proto::TemplateRef {
+        template_id: template.template_id.to_string(),
+        version: template.version,
+    }
+}
+
+fn template_parameter_to_proto(parameter: &TemplateParameter) -> spec_proto::TemplateParameter {
+    let kind = match parameter.kind {
+        ParameterType::String => ProtoParameterType::String,
+        ParameterType::Integer => ProtoParameterType::Integer,
+        ParameterType::Number => ProtoParameterType::Number,
+        ParameterType::Boolean => ProtoParameterType::Boolean,
+    };
+
+    spec_proto::TemplateParameter {
+        name: parameter.name.clone(),
+        r#type: kind as i32,
+        description: parameter.description.clone(),
+        default_value: parameter.default.as_ref().map(ToString::to_string),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn proto_template_parameter_to_domain(
+    parameter: &spec_proto::TemplateParameter,
+) -> Result<TemplateParameter, Status> {
+    let kind = match ProtoParameterType::try_from(parameter.r#type) {
+        Ok(ProtoParameterType::String) => ParameterType::String,
+        Ok(ProtoParameterType::Integer) => ParameterType::Integer,
+        Ok(ProtoParameterType::Number) => ParameterType::Number,
+        Ok(ProtoParameterType::Boolean) => ParameterType::Boolean,
+        Err(_) => return Err(Status::invalid_argument("Invalid parameter type")),
+    };
+
+    Ok(TemplateParameter {
+        name: parameter.name.clone(),
+        kind,
+        description: parameter.description.clone(),
+        default: parameter
+            .default_value
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(format!("Invalid default value JSON: {e}")))?,
+    })
+}
+
+/// Parameter values sent as a JSON object; empty text means no values
+#[allow(clippy::result_large_err)]
+fn parse_parameters_json(
+    parameters: &str,
+) -> Result<std::collections::BTreeMap<String, serde_json::Value>, Status> {
+    if parameters.trim().is_empty() {
+        return Ok(std::collections::BTreeMap::new());
+    }
+
+    serde_json::from_str(parameters)
+        .map_err(|e| Status::invalid_argument(format!("Invalid parameters JSON: {e}")))
+}
+
+fn chrono_to_proto_timestamp(dt: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
         seconds: dt.timestamp(),
         nanos: i32::try_from(dt.timestamp_subsec_nanos()).unwrap_or(0),
     }
 }
 
+fn proto_timestamp_to_chrono(ts: &prost_types::Timestamp) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::from_timestamp(ts.seconds, u32::try_from(ts.nanos).ok()?)
+}
+
 fn get_event_timestamp(event: &SpecEvent) -> chrono::DateTime<chrono::Utc> {
     match event {
         SpecEvent::Created(e) => e.created_at,
         SpecEvent::Updated(e) => e.updated_at,
         SpecEvent::StateChanged(e) => e.changed_at,
+        SpecEvent::Restored(e) => e.restored_at,
+        SpecEvent::Reverted(e) => e.reverted_at,
+        SpecEvent::Renamed(e) => e.renamed_at,
+        SpecEvent::TransitionScheduled(e) => e.scheduled_at,
+        SpecEvent::ScheduleCancelled(e) => e.cancelled_at,
+        SpecEvent::ScheduleExecuted(e) => e.executed_at,
+        SpecEvent::ReviewRequested(e) => e.requested_at,
+        SpecEvent::ReviewApproved(e) => e.approved_at,
+        SpecEvent::ReviewRejected(e) => e.rejected_at,
+        SpecEvent::ReviewWithdrawn(e) => e.withdrawn_at,
+        SpecEvent::LabelsAdded(e) => e.labeled_at,
+        SpecEvent::LabelsRemoved(e) => e.unlabeled_at,
+        SpecEvent::DependenciesSet(e) => e.set_at,
+        SpecEvent::CommentAdded(e) => e.added_at,
+        SpecEvent::CommentEdited(e) => e.edited_at,
+        SpecEvent::CommentResolved(e) => e.resolved_at,
+        SpecEvent::Promoted(e) => e.promoted_at,
+        SpecEvent::Demoted(e) => e.demoted_at,
+        SpecEvent::SunsetFailed(e) => e.failed_at,
+        SpecEvent::SchemaRegistered(e) => e.registered_at,
+        SpecEvent::SchemaUpdated(e) => e.updated_at,
+        SpecEvent::TemplateRegistered(e) => e.registered_at,
+        SpecEvent::TemplateUpdated(e) => e.updated_at,
+        SpecEvent::ReleaseCreated(e) => e.created_at,
+        SpecEvent::ReleasePublished(e) => e.published_at,
+        SpecEvent::ReleaseRolledBack(e) => e.rolled_back_at,
+        SpecEvent::TenantCreated(e) => e.created_at,
+        SpecEvent::TenantSuspended(e) => e.suspended_at,
     }
 }
 
@@ -441,6 +3160,34 @@ fn get_event_user(event: &SpecEvent) -> String {
         SpecEvent::Created(e) => e.created_by.clone(),
         SpecEvent::Updated(e) => e.updated_by.clone(),
         SpecEvent::StateChanged(e) => e.changed_by.clone(),
+        SpecEvent::Restored(e) => e.restored_by.clone(),
+        SpecEvent::Reverted(e) => e.reverted_by.clone(),
+        SpecEvent::Renamed(e) => e.renamed_by.clone(),
+        SpecEvent::TransitionScheduled(e) => e.scheduled_by.clone(),
+        SpecEvent::ScheduleCancelled(e) => e.cancelled_by.clone(),
+        SpecEvent::ScheduleExecuted(_) => "system:scheduler".to_string(),
+        SpecEvent::ReviewRequested(e) => e.requested_by.clone(),
+        SpecEvent::ReviewApproved(e) => e.approved_by.clone(),
+        SpecEvent::ReviewRejected(e) => e.rejected_by.clone(),
+        SpecEvent::ReviewWithdrawn(e) => e.withdrawn_by.clone(),
+        SpecEvent::LabelsAdded(e) => e.labeled_by.clone(),
+        SpecEvent::LabelsRemoved(e) => e.unlabeled_by.clone(),
+        SpecEvent::DependenciesSet(e) => e.set_by.clone(),
+        SpecEvent::CommentAdded(e) => e.author.clone(),
+        SpecEvent::CommentEdited(e) => e.edited_by.clone(),
+        SpecEvent::CommentResolved(e) => e.resolved_by.clone(),
+        SpecEvent::Promoted(e) => e.promoted_by.clone(),
+        SpecEvent::Demoted(e) => e.demoted_by.clone(),
+        SpecEvent::SunsetFailed(_) => "system:sunset".to_string(),
+        SpecEvent::SchemaRegistered(e) => e.registered_by.clone(),
+        SpecEvent::SchemaUpdated(e) => e.updated_by.clone(),
+        SpecEvent::TemplateRegistered(e) => e.registered_by.clone(),
+        SpecEvent::TemplateUpdated(e) => e.updated_by.clone(),
+        SpecEvent::ReleaseCreated(e) => e.created_by.clone(),
+        SpecEvent::ReleasePublished(e) => e.published_by.clone(),
+        SpecEvent::ReleaseRolledBack(e) => e.rolled_back_by.clone(),
+        SpecEvent::TenantCreated(e) => e.created_by.clone(),
+        SpecEvent::TenantSuspended(e) => e.suspended_by.clone(),
     }
 }
 
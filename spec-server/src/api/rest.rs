@@ -1,23 +1,51 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
-    routing::{get, post},
+    body::{Body, Bytes},
+    extract::{FromRequestParts, Path, Query, State},
+    http::{header, request::Parts, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Json},
+    routing::{delete, get, post, put},
     Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::Infallible;
 use std::sync::Arc;
+use tokio_stream::StreamExt;
 use uuid::Uuid;
 
+use super::auth::{Authenticator, Principal};
 use crate::domain::{
-    aggregates::Spec,
-    commands::{CreateSpec, DeprecateSpec, PublishSpec, UpdateSpec},
+    commands::{
+        AddComment, AddLabels, ApproveSpec, BatchCommand, BatchTarget, CancelSchedule,
+        CreateFromTemplate, CreateRelease, CreateSpec, CreateTenant, DeleteSpec, DemoteSpec,
+        DeprecateSpec, EditComment, MoveFolder, PromoteSpec, PublishRelease, PublishSpec,
+        RegisterSchema, RegisterTemplate, RejectSpec, RemoveLabels, RenameSpec, RequestReview,
+        ResolveComment, RestoreSpec, RevertSpec, RollbackRelease, ScheduleTransition,
+        SetDependencies, SuspendTenant, UpdateSchema, UpdateSpec, UpdateTemplate, WithdrawReview,
+    },
     errors::DomainError,
-    events::{EventMetadata, SpecEvent, SpecState},
+    events::{
+        EventEnvelope, EventMetadata, ReleaseState, ScheduledTransition, SpecEvent, SpecState,
+        TenantState,
+    },
+    validation::ValidationIssue,
+    value_objects::{
+        BumpLevel, CommentAnchor, LabelSelector, ReleaseEntry, SchemaRef, SchemaViolation,
+        SpecDependency, TemplateParameter, TemplateRef, TenantId, ValidationError,
+    },
 };
 use crate::infrastructure::{
+    blob_store::DOWNLOAD_CHUNK_SIZE,
     event_store::SqliteEventStore,
-    projections::{ProjectionStore, SpecProjection, SpecSummaryProjection},
+    projections::{
+        ChannelSpecProjection, CommentProjection, CommentThreadProjection, DependencyProjection,
+        ProjectionStore, ReleaseProjection, ScheduleProjection, ScheduleStatus, SchemaProjection,
+        SpecProjection, SpecReview, SpecSummaryProjection, TemplateProjection, TenantProjection,
+    },
+    release_registry::ReleaseRegistry,
+    repositories::{BatchItemResult, SpecRepository},
+    tenant_registry::TenantRegistry,
 };
 
 /// Shared application state
@@ -25,6 +53,89 @@ use crate::infrastructure::{
 pub struct AppState {
     pub event_store: Arc<SqliteEventStore>,
     pub projection_store: Arc<ProjectionStore>,
+    pub repository: SpecRepository,
+    pub releases: ReleaseRegistry,
+    pub tenants: TenantRegistry,
+    pub authenticator: Authenticator,
+}
+
+/// `ETag` response header carrying a spec's stream version
+type ETagHeader = [(HeaderName, String); 1];
+
+/// Request header that makes a mutating request safe to retry
+const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Who a request was made by, from its `Authorization: Bearer` header.
+///
+/// Requests with a missing or unknown token, and requests of principals
+/// whose tenant is unknown or suspended, are rejected before reaching their
+/// handler.
+#[axum::async_trait]
+impl FromRequestParts<AppState> for Principal {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        // Handlers may extract both the principal and its tenant
+        if let Some(principal) = parts.extensions.get::<Self>() {
+            return Ok(principal.clone());
+        }
+
+        let authorization = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .map(|value| value.to_str().unwrap_or_default());
+        let principal = state
+            .authenticator
+            .authenticate(authorization)
+            .map_err(|e| handle_domain_error(&e))?;
+
+        state
+            .tenants
+            .authorize(&principal.tenant)
+            .await
+            .map_err(|e| handle_domain_error(&e))?;
+
+        parts.extensions.insert(principal.clone());
+        Ok(principal)
+    }
+}
+
+/// Tenant a request acts for: that of the principal it was authenticated as
+pub struct Tenant(pub TenantId);
+
+#[axum::async_trait]
+impl FromRequestParts<AppState> for Tenant {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let principal = Principal::from_request_parts(parts, state).await?;
+        Ok(Self(principal.tenant))
+    }
+}
+
+/// Principal of a request that administers tenants
+pub struct Admin(pub Principal);
+
+#[axum::async_trait]
+impl FromRequestParts<AppState> for Admin {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let principal = Principal::from_request_parts(parts, state).await?;
+        principal
+            .require_admin()
+            .map_err(|e| handle_domain_error(&e))?;
+        Ok(Self(principal))
+    }
 }
 
 /// Request/Response DTOs
@@ -32,25 +143,58 @@ pub struct AppState {
 #[derive(Debug, Deserialize)]
 pub struct CreateSpecRequest {
     pub name: String,
+    #[serde(default)]
     pub content: String,
+    /// Hash of content uploaded to `/content`, used in place of `content`
+    pub content_hash: Option<String>,
+    pub description: Option<String>,
+    /// Schema version the content must conform to
+    pub schema: Option<SchemaRef>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateFromTemplateRequest {
+    pub name: String,
+    pub template_id: Uuid,
+    /// Template version to render; the latest if omitted
+    pub version: Option<u32>,
+    #[serde(default)]
+    pub parameters: BTreeMap<String, serde_json::Value>,
     pub description: Option<String>,
+    pub schema: Option<SchemaRef>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct CreateSpecResponse {
     pub id: Uuid,
     pub version: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ValidationIssue>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateSpecRequest {
+    #[serde(default)]
     pub content: String,
+    /// Hash of content uploaded to `/content`, used in place of `content`
+    pub content_hash: Option<String>,
     pub description: Option<String>,
+    /// `major`, `minor` or `patch`; inferred from the content change if omitted
+    pub bump: Option<BumpLevel>,
+    /// Schema version to validate against; the current one is kept if omitted
+    pub schema: Option<SchemaRef>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct UpdateSpecResponse {
     pub version: u32,
+    pub semver: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ValidationIssue>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -61,11 +205,284 @@ pub struct PublishSpecRequest {
 #[derive(Debug, Deserialize)]
 pub struct DeprecateSpecRequest {
     pub reason: String,
+    pub successor_id: Option<Uuid>,
+    pub sunset_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchRequest {
+    pub items: Vec<BatchItemRequest>,
+}
+
+/// One command of a batch, tagged by `op`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchItemRequest {
+    Create(CreateSpecRequest),
+    Update {
+        #[serde(flatten)]
+        target: BatchTargetRequest,
+        #[serde(flatten)]
+        request: UpdateSpecRequest,
+    },
+    Publish {
+        #[serde(flatten)]
+        target: BatchTargetRequest,
+        #[serde(flatten)]
+        request: PublishSpecRequest,
+    },
+    Deprecate {
+        #[serde(flatten)]
+        target: BatchTargetRequest,
+        #[serde(flatten)]
+        request: DeprecateSpecRequest,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchTargetRequest {
+    /// Id or name of the spec, which may be created earlier in the batch
+    pub spec: String,
+    /// Stream version the spec must be at, as its `ETag` reports
+    pub expected_version: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchResponse {
+    pub committed: bool,
+    pub items: Vec<BatchItemResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchItemResponse {
+    pub status: u16,
+    #[serde(flatten)]
+    pub result: BatchItemBody,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum BatchItemBody {
+    Applied {
+        id: Uuid,
+        version: u32,
+        semver: String,
+        state: SpecState,
+        stream_version: i64,
+    },
+    Failed(ErrorResponse),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RevertSpecRequest {
+    pub to_version: u32,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevertSpecResponse {
+    pub version: u32,
+    pub semver: String,
+    pub reverted_from: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RenameSpecRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddLabelsRequest {
+    pub labels: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemoveLabelsRequest {
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetDependenciesRequest {
+    /// Each as `{"spec_id": ..., "version": "^1.2"}`; replaces the current set
+    pub dependencies: Vec<SpecDependency>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyResponse {
+    pub spec_id: Uuid,
+    pub name: String,
+    pub state: String,
+    pub published_version: Option<u32>,
+    /// Accepted versions of the depended-on spec
+    pub version: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListDependenciesResponse {
+    pub dependencies: Vec<DependencyResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromoteSpecRequest {
+    pub channel: String,
+    pub version: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DemoteSpecRequest {
+    pub channel: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChannelResponse {
+    pub channel: String,
+    /// Version the channel now serves; `None` once the spec has left it
+    pub version: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChannelSpecResponse {
+    pub channel: String,
+    pub spec_id: Uuid,
+    pub name: String,
+    pub state: String,
+    pub version: u32,
+    pub semver: String,
+    pub changed_by: String,
+    pub changed_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListChannelSpecsResponse {
+    pub specs: Vec<ChannelSpecResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListSpecChannelsResponse {
+    pub channels: Vec<ChannelSpecResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListDependentsResponse {
+    pub dependents: Vec<DependencyResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApproveSpecRequest {
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RejectSpecRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScheduleTransitionRequest {
+    pub execute_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub transition: ScheduledTransition,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListSchedulesQuery {
+    /// `pending` (default), `executed`, `failed`, `cancelled` or `all`
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleResponse {
+    pub schedule_id: Uuid,
+    pub spec_id: Uuid,
+    #[serde(flatten)]
+    pub transition: ScheduledTransition,
+    pub execute_at: String,
+    pub scheduled_by: String,
+    pub scheduled_at: String,
+    pub status: String,
+    pub completed_at: Option<String>,
+    pub failure: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScheduleTransitionResponse {
+    pub schedule_id: Uuid,
+    pub execute_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListSchedulesResponse {
+    pub schedules: Vec<ScheduleResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddCommentRequest {
+    /// Version to comment on; the head if omitted, the thread's for replies
+    pub version: Option<u32>,
+    pub anchor: Option<CommentAnchor>,
+    /// Comment to reply to
+    pub parent_id: Option<Uuid>,
+    pub body: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddCommentResponse {
+    pub id: Uuid,
+    pub version: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EditCommentRequest {
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListCommentsQuery {
+    pub version: Option<u32>,
+    /// Only resolved or only open threads
+    pub resolved: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentResponse {
+    pub id: Uuid,
+    pub version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<CommentAnchor>,
+    pub body: String,
+    pub author: String,
+    pub created_at: String,
+    pub edited_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentThreadResponse {
+    #[serde(flatten)]
+    pub comment: CommentResponse,
+    pub resolved: bool,
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<String>,
+    pub replies: Vec<CommentResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListCommentsResponse {
+    pub threads: Vec<CommentThreadResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetSpecQuery {
+    pub include_deleted: Option<bool>,
+    /// `head` reads the latest revision instead of the published one
+    pub revision: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ListSpecsQuery {
     pub state: Option<String>,
+    pub include_deleted: Option<bool>,
+    /// Label selector such as `team=payments,env!=test`
+    pub selector: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
@@ -75,13 +492,23 @@ pub struct SpecResponse {
     pub id: Uuid,
     pub name: String,
     pub content: String,
+    pub content_hash: String,
     pub description: Option<String>,
+    /// Version of the returned content
     pub version: u32,
+    pub semver: String,
+    pub published_version: Option<u32>,
+    /// Latest revision, ahead of the published version while edits are pending
+    pub head_version: u32,
     pub state: String,
     pub created_at: String,
     pub updated_at: String,
     pub created_by: String,
     pub updated_by: String,
+    pub review: Option<SpecReview>,
+    pub labels: BTreeMap<String, String>,
+    pub schema: Option<SchemaRef>,
+    pub template: Option<TemplateRef>,
 }
 
 #[derive(Debug, Serialize)]
@@ -90,8 +517,11 @@ pub struct SpecSummaryResponse {
     pub name: String,
     pub description: Option<String>,
     pub latest_version: u32,
+    pub semver: String,
+    pub published_version: Option<u32>,
     pub state: String,
     pub updated_at: String,
+    pub labels: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -102,291 +532,2578 @@ pub struct ListSpecsResponse {
     pub offset: i64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TreeQuery {
+    /// List every spec and folder below the path, not just its children
+    pub recursive: Option<bool>,
+}
+
 #[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub details: Option<String>,
+pub struct FolderResponse {
+    pub path: String,
+    /// Specs at any depth below the folder
+    pub spec_count: u64,
 }
 
-/// Create the REST API router
-pub fn create_router(state: AppState) -> Router {
-    Router::new()
-        .route("/specs", post(create_spec).get(list_specs))
-        .route("/specs/:id", get(get_spec).put(update_spec))
-        .route("/specs/:id/publish", post(publish_spec))
-        .route("/specs/:id/deprecate", post(deprecate_spec))
-        .route("/specs/:id/versions/:version", get(get_spec_version))
-        .route("/health", get(health_check))
-        .with_state(state)
+#[derive(Debug, Serialize)]
+pub struct TreeResponse {
+    pub path: String,
+    pub folders: Vec<FolderResponse>,
+    pub specs: Vec<SpecSummaryResponse>,
 }
 
-// Handler functions
+#[derive(Debug, Deserialize)]
+pub struct MoveFolderRequest {
+    pub from: String,
+    pub to: String,
+}
 
-async fn create_spec(
-    State(state): State<AppState>,
-    Json(req): Json<CreateSpecRequest>,
-) -> Result<(StatusCode, Json<CreateSpecResponse>), (StatusCode, Json<ErrorResponse>)> {
-    // TODO: Extract user from auth context
-    let user = "user@example.com";
+#[derive(Debug, Serialize)]
+pub struct MovedSpecResponse {
+    pub id: Uuid,
+    pub old_name: String,
+    pub new_name: String,
+    pub stream_version: i64,
+}
 
-    let command = CreateSpec {
-        name: req.name,
-        content: req.content,
-        description: req.description,
-        created_by: user.to_string(),
-    };
+#[derive(Debug, Serialize)]
+pub struct MoveFolderResponse {
+    pub moved: Vec<MovedSpecResponse>,
+}
 
-    let events = Spec::create(command).map_err(|e| handle_domain_error(&e))?;
+#[derive(Debug, Serialize)]
+pub struct UploadContentResponse {
+    pub hash: String,
+    pub size: u64,
+}
 
-    let spec_id = match &events[0] {
-        SpecEvent::Created(e) => e.spec_id,
-        _ => unreachable!(),
-    };
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub details: Option<String>,
+    /// Schema violations, each annotated with the JSON pointer it occurred at
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<SchemaViolation>,
+    /// Errors reported by content validators
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<ValidationIssue>,
+}
 
-    let metadata = EventMetadata {
-        correlation_id: Some(Uuid::new_v4()),
-        causation_id: None,
-        user_agent: None, // TODO: Extract from headers
-        ip_address: None, // TODO: Extract from connection
-    };
+#[derive(Debug, Deserialize)]
+pub struct RegisterSchemaRequest {
+    pub name: String,
+    pub schema: serde_json::Value,
+    pub description: Option<String>,
+}
 
-    state
-        .event_store
-        .append_events(spec_id, events, metadata)
-        .await
-        .map_err(|e| handle_domain_error(&e))?;
+#[derive(Debug, Deserialize)]
+pub struct UpdateSchemaRequest {
+    pub schema: serde_json::Value,
+    pub description: Option<String>,
+}
 
-    Ok((
-        StatusCode::CREATED,
-        Json(CreateSpecResponse {
-            id: spec_id,
-            version: 1,
-        }),
-    ))
+#[derive(Debug, Serialize)]
+pub struct SchemaVersionResponse {
+    pub id: Uuid,
+    pub version: u32,
 }
 
-async fn get_spec(
-    State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-) -> Result<Json<SpecResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let spec = state
-        .projection_store
-        .get_by_id(id)
-        .await
-        .map_err(|e| handle_domain_error(&e))?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: "Spec not found".to_string(),
-                    details: None,
-                }),
-            )
-        })?;
+#[derive(Debug, Serialize)]
+pub struct SchemaResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub version: u32,
+    pub schema: serde_json::Value,
+    pub created_at: String,
+    pub updated_at: String,
+    pub created_by: String,
+    pub updated_by: String,
+}
 
-    Ok(Json(projection_to_response(spec)))
+#[derive(Debug, Serialize)]
+pub struct ListSchemasResponse {
+    pub schemas: Vec<SchemaResponse>,
 }
 
-async fn update_spec(
-    State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Json(req): Json<UpdateSpecRequest>,
-) -> Result<Json<UpdateSpecResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Load current state from events
-    let events = state
-        .event_store
-        .get_events(id, None)
-        .await
-        .map_err(|e| handle_domain_error(&e))?;
+#[derive(Debug, Deserialize)]
+pub struct RegisterTemplateRequest {
+    pub name: String,
+    pub body: String,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+    pub description: Option<String>,
+}
 
-    if events.is_empty() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: "Spec not found".to_string(),
-                details: None,
-            }),
-        ));
-    }
+#[derive(Debug, Deserialize)]
+pub struct UpdateTemplateRequest {
+    pub body: String,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateVersionResponse {
+    pub id: Uuid,
+    pub version: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub version: u32,
+    pub body: String,
+    pub parameters: Vec<TemplateParameter>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub created_by: String,
+    pub updated_by: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListTemplatesResponse {
+    pub templates: Vec<TemplateResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewTemplateRequest {
+    /// Template version to render; the latest if omitted
+    pub version: Option<u32>,
+    #[serde(default)]
+    pub parameters: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreviewTemplateResponse {
+    pub template_id: Uuid,
+    pub version: u32,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DerivedSpecResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub state: String,
+    pub template_version: u32,
+    /// Whether the template has versions newer than the one the spec used
+    pub outdated: bool,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReleaseRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// Spec versions to pin, published in this order
+    pub entries: Vec<ReleaseEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseStateResponse {
+    pub id: Uuid,
+    pub state: ReleaseState,
+    /// Set on rollback: the release that is current again, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restored_release_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseEntryResponse {
+    pub spec_id: Uuid,
+    pub name: String,
+    pub version: u32,
+    pub semver: String,
+    pub content_hash: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub state: ReleaseState,
+    pub entries: Vec<ReleaseEntryResponse>,
+    pub created_at: String,
+    pub created_by: String,
+    pub updated_at: String,
+    pub updated_by: String,
+    pub published_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListReleasesResponse {
+    pub releases: Vec<ReleaseResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseSpecResponse {
+    pub spec_id: Uuid,
+    pub name: String,
+    pub version: u32,
+    pub semver: String,
+    pub content: String,
+    pub content_hash: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseSpecsResponse {
+    pub release_id: Uuid,
+    pub name: String,
+    pub state: ReleaseState,
+    pub specs: Vec<ReleaseSpecResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTenantRequest {
+    pub id: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuspendTenantRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TenantResponse {
+    pub id: TenantId,
+    pub display_name: Option<String>,
+    pub state: TenantState,
+    pub suspension_reason: Option<String>,
+    pub created_at: String,
+    pub created_by: String,
+    pub updated_at: String,
+    pub updated_by: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListTenantsResponse {
+    pub tenants: Vec<TenantResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListTemplateSpecsResponse {
+    pub template_id: Uuid,
+    pub head_version: u32,
+    pub specs: Vec<DerivedSpecResponse>,
+}
+
+/// Create the REST API router
+pub fn create_router(state: AppState) -> Router {
+    Router::new()
+        .route("/specs", post(create_spec).get(list_specs))
+        .route("/specs/from-template", post(create_spec_from_template))
+        .route("/specs/batch", post(batch_specs))
+        .route("/specs/move", post(move_folder))
+        .route(
+            "/specs/:id",
+            get(get_spec).put(update_spec).delete(delete_spec),
+        )
+        .route("/specs/:id/publish", post(publish_spec))
+        .route("/specs/:id/deprecate", post(deprecate_spec))
+        .route("/specs/:id/restore", post(restore_spec))
+        .route("/specs/:id/revert", post(revert_spec))
+        .route("/specs/:id/rename", post(rename_spec))
+        .route("/specs/:id/labels", post(add_labels))
+        .route("/specs/:id/labels/remove", post(remove_labels))
+        .route(
+            "/specs/:id/dependencies",
+            get(get_dependencies).put(set_dependencies),
+        )
+        .route("/specs/:id/dependents", get(get_dependents))
+        .route("/specs/:id/promote", post(promote_spec))
+        .route("/specs/:id/demote", post(demote_spec))
+        .route("/specs/:id/channels", get(get_spec_channels))
+        .route("/channels/:channel/specs", get(list_channel_specs))
+        .route("/channels/:channel/specs/*name", get(get_channel_spec))
+        .route("/specs/:id/comments", post(add_comment).get(list_comments))
+        .route("/specs/:id/comments/:comment_id", put(edit_comment))
+        .route(
+            "/specs/:id/comments/:comment_id/resolve",
+            post(resolve_comment),
+        )
+        .route("/specs/:id/review", post(request_review))
+        .route("/specs/:id/review/approve", post(approve_spec))
+        .route("/specs/:id/review/reject", post(reject_spec))
+        .route("/specs/:id/review/withdraw", post(withdraw_review))
+        .route(
+            "/specs/:id/schedules",
+            post(schedule_transition).get(list_spec_schedules),
+        )
+        .route("/specs/:id/schedules/:schedule_id", delete(cancel_schedule))
+        .route("/schedules", get(list_schedules))
+        .route("/specs/by-name/*name", get(get_spec_by_name))
+        .route("/tree", get(get_root_folder))
+        .route("/tree/*path", get(get_folder))
+        .route("/specs/:id/published", get(get_published_spec))
+        .route("/specs/:id/versions/:version", get(get_spec_version))
+        .route("/schemas", post(register_schema).get(list_schemas))
+        .route("/schemas/:id", get(get_schema).put(update_schema))
+        .route("/schemas/:id/versions/:version", get(get_schema_version))
+        .route("/templates", post(register_template).get(list_templates))
+        .route("/templates/:id", get(get_template).put(update_template))
+        .route(
+            "/templates/:id/versions/:version",
+            get(get_template_version),
+        )
+        .route("/templates/:id/preview", post(preview_template))
+        .route("/templates/:id/specs", get(list_template_specs))
+        .route("/releases", post(create_release).get(list_releases))
+        .route("/releases/current", get(get_current_release))
+        .route("/releases/current/specs", get(list_current_release_specs))
+        .route("/releases/:release", get(get_release))
+        .route("/releases/:release/specs", get(list_release_specs))
+        .route("/releases/:release/publish", post(publish_release))
+        .route("/releases/:release/rollback", post(rollback_release))
+        .route("/tenants", post(create_tenant).get(list_tenants))
+        .route("/tenants/:tenant/suspend", post(suspend_tenant))
+        .route("/content", post(upload_content))
+        .route("/content/:hash", get(download_content))
+        .route("/health", get(health_check))
+        .with_state(state)
+}
+
+// Handler functions
+
+async fn create_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    headers: HeaderMap,
+    Json(req): Json<CreateSpecRequest>,
+) -> Result<(StatusCode, ETagHeader, Json<CreateSpecResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let command = CreateSpec {
+        name: req.name,
+        content: resolve_content(&state, req.content, req.content_hash).await?,
+        description: req.description,
+        schema: req.schema,
+        labels: req.labels,
+        template: None,
+        created_by: user.to_string(),
+    };
+
+    let metadata = EventMetadata {
+        correlation_id: Some(Uuid::new_v4()),
+        causation_id: None,
+        user_agent: None, // TODO: Extract from headers
+        ip_address: None, // TODO: Extract from connection
+        ..request_metadata(&headers, &tenant)?
+    };
+
+    let envelopes = state
+        .repository
+        .create(command, metadata)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
 
-    let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())
+    let (spec_id, warnings) = match &envelopes[0].event {
+        SpecEvent::Created(e) => (e.spec_id, e.warnings.clone()),
+        _ => unreachable!(),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        etag(&envelopes),
+        Json(CreateSpecResponse {
+            id: spec_id,
+            version: 1,
+            warnings,
+        }),
+    ))
+}
+
+/// Create a spec by rendering a template with the given parameter values
+async fn create_spec_from_template(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    headers: HeaderMap,
+    Json(req): Json<CreateFromTemplateRequest>,
+) -> Result<(StatusCode, ETagHeader, Json<CreateSpecResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let command = CreateFromTemplate {
+        name: req.name,
+        template_id: req.template_id,
+        version: req.version,
+        parameters: req.parameters,
+        description: req.description,
+        schema: req.schema,
+        labels: req.labels,
+        created_by: user.to_string(),
+    };
+
+    let metadata = EventMetadata {
+        correlation_id: Some(Uuid::new_v4()),
+        causation_id: None,
+        user_agent: None,
+        ip_address: None,
+        ..request_metadata(&headers, &tenant)?
+    };
+
+    let envelopes = state
+        .repository
+        .create_from_template(command, metadata)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let (spec_id, warnings) = match &envelopes[0].event {
+        SpecEvent::Created(e) => (e.spec_id, e.warnings.clone()),
+        _ => unreachable!(),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        etag(&envelopes),
+        Json(CreateSpecResponse {
+            id: spec_id,
+            version: 1,
+            warnings,
+        }),
+    ))
+}
+
+/// Apply creates, updates, publishes and deprecations atomically, reporting
+/// the outcome of each
+async fn batch_specs(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    headers: HeaderMap,
+    Json(req): Json<BatchRequest>,
+) -> Result<(StatusCode, Json<BatchResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let mut commands = Vec::with_capacity(req.items.len());
+    for item in req.items {
+        commands.push(batch_command(&state, item, user).await?);
+    }
+    let creates: Vec<bool> = commands
+        .iter()
+        .map(|command| matches!(command, BatchCommand::Create(_)))
+        .collect();
+
+    let metadata = EventMetadata {
+        correlation_id: Some(Uuid::new_v4()),
+        ..request_metadata(&headers, &tenant)?
+    };
+
+    let outcome = state
+        .repository
+        .execute_batch(commands, metadata)
+        .await
         .map_err(|e| handle_domain_error(&e))?;
 
-    let user = "user@example.com"; // TODO: From auth
+    let items: Vec<BatchItemResponse> = outcome
+        .items
+        .into_iter()
+        .zip(creates)
+        .map(|(item, create)| batch_item_to_response(item, create))
+        .collect();
+
+    let status = if outcome.committed {
+        StatusCode::OK
+    } else {
+        // The first item that failed in its own right, not just by aborting
+        items
+            .iter()
+            .map(|item| StatusCode::from_u16(item.status).unwrap_or(StatusCode::BAD_REQUEST))
+            .find(|status| *status != StatusCode::FAILED_DEPENDENCY)
+            .unwrap_or(StatusCode::BAD_REQUEST)
+    };
+
+    Ok((
+        status,
+        Json(BatchResponse {
+            committed: outcome.committed,
+            items,
+        }),
+    ))
+}
+
+async fn batch_command(
+    state: &AppState,
+    item: BatchItemRequest,
+    user: &str,
+) -> Result<BatchCommand, (StatusCode, Json<ErrorResponse>)> {
+    let target = |target: BatchTargetRequest| BatchTarget {
+        spec: target.spec.as_str().into(),
+        expected_version: target.expected_version,
+    };
+
+    Ok(match item {
+        BatchItemRequest::Create(req) => BatchCommand::Create(CreateSpec {
+            name: req.name,
+            content: resolve_content(state, req.content, req.content_hash).await?,
+            description: req.description,
+            schema: req.schema,
+            labels: req.labels,
+            template: None,
+            created_by: user.to_string(),
+        }),
+        BatchItemRequest::Update {
+            target: spec,
+            request: req,
+        } => BatchCommand::Update(
+            target(spec),
+            UpdateSpec {
+                spec_id: Uuid::nil(),
+                content: resolve_content(state, req.content, req.content_hash).await?,
+                description: req.description,
+                bump: req.bump,
+                schema: req.schema,
+                updated_by: user.to_string(),
+            },
+        ),
+        BatchItemRequest::Publish {
+            target: spec,
+            request: req,
+        } => BatchCommand::Publish(
+            target(spec),
+            PublishSpec {
+                spec_id: Uuid::nil(),
+                version: req.version,
+                published_by: user.to_string(),
+            },
+        ),
+        BatchItemRequest::Deprecate {
+            target: spec,
+            request: req,
+        } => BatchCommand::Deprecate(
+            target(spec),
+            DeprecateSpec {
+                spec_id: Uuid::nil(),
+                reason: req.reason,
+                successor_id: req.successor_id,
+                sunset_at: req.sunset_at,
+                deprecated_by: user.to_string(),
+            },
+        ),
+    })
+}
+
+async fn get_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+    Query(query): Query<GetSpecQuery>,
+) -> Result<(HeaderMap, Json<SpecResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let include_deleted = query.include_deleted.unwrap_or(false);
+
+    let head = match query.revision.as_deref() {
+        None => false,
+        Some("head") => true,
+        Some(revision) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid revision".to_string(),
+                    details: Some(format!("Unknown revision {revision:?}, expected \"head\"")),
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            ));
+        }
+    };
+
+    let spec = if head {
+        state.projection_store.get_by_id(&tenant, id).await
+    } else {
+        state.projection_store.get_current(&tenant, id).await
+    };
+
+    let spec = spec
+        .map_err(|e| handle_domain_error(&e))?
+        .filter(|spec| include_deleted || spec.state != SpecState::Deleted)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Spec not found".to_string(),
+                    details: None,
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            )
+        })?;
+
+    let mut headers = HeaderMap::new();
+    insert_header(&mut headers, header::ETAG, format_etag(spec.stream_version));
+
+    // Machine-readable migration guidance (RFC 9745, RFC 8594)
+    if let Some(deprecation) = &spec.deprecation {
+        insert_header(
+            &mut headers,
+            HeaderName::from_static("deprecation"),
+            format!("@{}", deprecation.deprecated_at.timestamp()),
+        );
+        if let Some(sunset_at) = deprecation.sunset_at {
+            insert_header(
+                &mut headers,
+                HeaderName::from_static("sunset"),
+                sunset_at.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            );
+        }
+        if let Some(successor_id) = deprecation.successor_id {
+            insert_header(
+                &mut headers,
+                header::LINK,
+                format!("</specs/{successor_id}>; rel=\"successor-version\""),
+            );
+        }
+    }
+
+    let served_version = if head {
+        spec.head_version
+    } else {
+        spec.published_version.unwrap_or(spec.head_version)
+    };
+
+    Ok((
+        headers,
+        Json(SpecResponse {
+            version: served_version,
+            ..projection_to_response(spec)
+        }),
+    ))
+}
+
+/// Look a spec up by its name, or by a former name during the alias period
+async fn get_spec_by_name(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(name): Path<String>,
+    query: Query<GetSpecQuery>,
+) -> Result<(HeaderMap, Json<SpecResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let id = state
+        .event_store
+        .resolve_name(&tenant, &name)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Spec not found".to_string(),
+                    details: None,
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            )
+        })?;
+
+    get_spec(State(state), Tenant(tenant), Path(id), query).await
+}
+
+async fn update_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<UpdateSpecRequest>,
+) -> Result<(ETagHeader, Json<UpdateSpecResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
 
     let command = UpdateSpec {
         spec_id: id,
-        content: req.content,
+        content: resolve_content(&state, req.content, req.content_hash).await?,
         description: req.description,
+        bump: req.bump,
+        schema: req.schema,
         updated_by: user.to_string(),
     };
 
-    let new_events = spec
-        .handle_command(command.into())
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
         .map_err(|e| handle_domain_error(&e))?;
 
-    let new_version = match &new_events[0] {
-        SpecEvent::Updated(e) => e.version,
+    let (new_version, semver, warnings) = match &envelopes[0].event {
+        SpecEvent::Updated(e) => (e.version, e.semver.clone(), e.warnings.clone()),
         _ => unreachable!(),
     };
 
-    state
-        .event_store
-        .append_events(id, new_events, EventMetadata::default())
+    Ok((
+        etag(&envelopes),
+        Json(UpdateSpecResponse {
+            version: new_version,
+            semver: semver.to_string(),
+            warnings,
+        }),
+    ))
+}
+
+async fn publish_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<PublishSpecRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = PublishSpec {
+        spec_id: id,
+        version: req.version,
+        published_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn deprecate_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<DeprecateSpecRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = DeprecateSpec {
+        spec_id: id,
+        reason: req.reason,
+        successor_id: req.successor_id,
+        sunset_at: req.sunset_at,
+        deprecated_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn delete_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = DeleteSpec {
+        spec_id: id,
+        deleted_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::NO_CONTENT, etag(&envelopes)))
+}
+
+async fn restore_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = RestoreSpec {
+        spec_id: id,
+        restored_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn revert_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<RevertSpecRequest>,
+) -> Result<(ETagHeader, Json<RevertSpecResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = RevertSpec {
+        spec_id: id,
+        to_version: req.to_version,
+        reason: req.reason,
+        reverted_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let (version, semver, reverted_from) = match &envelopes[0].event {
+        SpecEvent::Reverted(e) => (e.version, e.semver.clone(), e.reverted_from),
+        _ => unreachable!(),
+    };
+
+    Ok((
+        etag(&envelopes),
+        Json(RevertSpecResponse {
+            version,
+            semver: semver.to_string(),
+            reverted_from,
+        }),
+    ))
+}
+
+async fn rename_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<RenameSpecRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = RenameSpec {
+        spec_id: id,
+        new_name: req.name,
+        renamed_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn add_labels(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<AddLabelsRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = AddLabels {
+        spec_id: id,
+        labels: req.labels,
+        labeled_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn set_dependencies(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<SetDependenciesRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = SetDependencies {
+        spec_id: id,
+        dependencies: req.dependencies,
+        set_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+/// Point a channel at a version of a spec
+async fn promote_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<PromoteSpecRequest>,
+) -> Result<(ETagHeader, Json<ChannelResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = PromoteSpec {
+        spec_id: id,
+        version: req.version,
+        channel: req.channel.clone(),
+        promoted_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((
+        etag(&envelopes),
+        Json(ChannelResponse {
+            channel: req.channel,
+            version: Some(req.version),
+        }),
+    ))
+}
+
+/// Take the version a channel serves out of it, falling back to the one before
+async fn demote_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<DemoteSpecRequest>,
+) -> Result<(ETagHeader, Json<ChannelResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = DemoteSpec {
+        spec_id: id,
+        channel: req.channel.clone(),
+        demoted_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let version = match &envelopes[0].event {
+        SpecEvent::Demoted(e) => e.restored_version,
+        _ => unreachable!(),
+    };
+
+    Ok((
+        etag(&envelopes),
+        Json(ChannelResponse {
+            channel: req.channel,
+            version,
+        }),
+    ))
+}
+
+/// Channels serving a spec, with the version each serves
+async fn get_spec_channels(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListSpecChannelsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    ensure_spec_exists(&state, &tenant, id).await?;
+
+    let channels = state
+        .projection_store
+        .list_spec_channels(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListSpecChannelsResponse {
+        channels: channels.into_iter().map(channel_spec_to_response).collect(),
+    }))
+}
+
+async fn list_channel_specs(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(channel): Path<String>,
+) -> Result<Json<ListChannelSpecsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let specs = state
+        .projection_store
+        .list_channel_specs(&tenant, &channel)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListChannelSpecsResponse {
+        specs: specs.into_iter().map(channel_spec_to_response).collect(),
+    }))
+}
+
+/// The version of a spec a channel serves, for consumers in that environment
+async fn get_channel_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path((channel, name)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let not_found = |error: &str| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: error.to_string(),
+                details: None,
+                violations: Vec::new(),
+                issues: Vec::new(),
+            }),
+        )
+    };
+
+    let id = state
+        .event_store
+        .resolve_name(&tenant, &name)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| not_found("Spec not found"))?;
+
+    let entry = state
+        .projection_store
+        .get_channel_spec(&tenant, &channel, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| not_found("Spec is not in this channel"))?;
+
+    let revision = state
+        .projection_store
+        .get_version(&tenant, id, entry.version)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| not_found("Version not found"))?;
+
+    Ok(Json(serde_json::json!({
+        "id": id,
+        "name": entry.name,
+        "channel": entry.channel,
+        "version": entry.version,
+        "semver": revision.semver.to_string(),
+        "content": revision.content,
+        "content_hash": revision.content_hash,
+        "description": revision.description,
+        "state": format!("{:?}", entry.state).to_lowercase(),
+    })))
+}
+
+/// Comment on a spec version; the spec's version is left unchanged
+async fn add_comment(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<AddCommentRequest>,
+) -> Result<(StatusCode, ETagHeader, Json<AddCommentResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let command = AddComment {
+        spec_id: id,
+        version: req.version,
+        anchor: req.anchor,
+        parent_id: req.parent_id,
+        body: req.body,
+        author: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            None,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let (comment_id, version) = match &envelopes[0].event {
+        SpecEvent::CommentAdded(e) => (e.comment_id, e.version),
+        _ => unreachable!(),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        etag(&envelopes),
+        Json(AddCommentResponse {
+            id: comment_id,
+            version,
+        }),
+    ))
+}
+
+async fn edit_comment(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(req): Json<EditCommentRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let command = EditComment {
+        spec_id: id,
+        comment_id,
+        body: req.body,
+        edited_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            None,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn resolve_comment(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let command = ResolveComment {
+        spec_id: id,
+        comment_id,
+        resolved_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            None,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn list_comments(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ListCommentsQuery>,
+) -> Result<Json<ListCommentsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    ensure_spec_exists(&state, &tenant, id).await?;
+
+    let threads = state
+        .projection_store
+        .list_comment_threads(&tenant, id, query.version, query.resolved)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListCommentsResponse {
+        threads: threads.into_iter().map(thread_to_response).collect(),
+    }))
+}
+
+async fn get_dependencies(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListDependenciesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    ensure_spec_exists(&state, &tenant, id).await?;
+
+    let dependencies = state
+        .projection_store
+        .get_dependencies(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListDependenciesResponse {
+        dependencies: dependencies
+            .into_iter()
+            .map(dependency_to_response)
+            .collect(),
+    }))
+}
+
+async fn get_dependents(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListDependentsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    ensure_spec_exists(&state, &tenant, id).await?;
+
+    let dependents = state
+        .projection_store
+        .get_dependents(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListDependentsResponse {
+        dependents: dependents.into_iter().map(dependency_to_response).collect(),
+    }))
+}
+
+async fn remove_labels(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<RemoveLabelsRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = RemoveLabels {
+        spec_id: id,
+        keys: req.keys,
+        unlabeled_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn request_review(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = RequestReview {
+        spec_id: id,
+        requested_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn approve_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<ApproveSpecRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = ApproveSpec {
+        spec_id: id,
+        comment: req.comment,
+        approved_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn reject_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<RejectSpecRequest>,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = RejectSpec {
+        spec_id: id,
+        reason: req.reason,
+        rejected_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn withdraw_review(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = WithdrawReview {
+        spec_id: id,
+        withdrawn_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::OK, etag(&envelopes)))
+}
+
+async fn schedule_transition(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<ScheduleTransitionRequest>,
+) -> Result<
+    (StatusCode, ETagHeader, Json<ScheduleTransitionResponse>),
+    (StatusCode, Json<ErrorResponse>),
+> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = ScheduleTransition {
+        spec_id: id,
+        transition: req.transition,
+        execute_at: req.execute_at,
+        scheduled_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let (schedule_id, execute_at) = match &envelopes[0].event {
+        SpecEvent::TransitionScheduled(e) => (e.schedule_id, e.execute_at),
+        _ => unreachable!(),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        etag(&envelopes),
+        Json(ScheduleTransitionResponse {
+            schedule_id,
+            execute_at: execute_at.to_rfc3339(),
+        }),
+    ))
+}
+
+async fn cancel_schedule(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path((id, schedule_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, ETagHeader), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = CancelSchedule {
+        spec_id: id,
+        schedule_id,
+        cancelled_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .execute(
+            id,
+            command.into(),
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((StatusCode::NO_CONTENT, etag(&envelopes)))
+}
+
+async fn list_spec_schedules(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ListSchedulesQuery>,
+) -> Result<Json<ListSchedulesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    fetch_schedules(&state, &tenant, Some(id), query).await
+}
+
+async fn list_schedules(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Query(query): Query<ListSchedulesQuery>,
+) -> Result<Json<ListSchedulesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    fetch_schedules(&state, &tenant, None, query).await
+}
+
+async fn fetch_schedules(
+    state: &AppState,
+    tenant: &TenantId,
+    spec_id: Option<Uuid>,
+    query: ListSchedulesQuery,
+) -> Result<Json<ListSchedulesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let status = match query.status.as_deref() {
+        None => Some(ScheduleStatus::Pending),
+        Some("all") => None,
+        Some(s) => Some(ScheduleStatus::parse(s).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid schedule status".to_string(),
+                    details: Some(s.to_string()),
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            )
+        })?),
+    };
+
+    let schedules = state
+        .projection_store
+        .list_schedules(tenant, spec_id, status)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListSchedulesResponse {
+        schedules: schedules.into_iter().map(schedule_to_response).collect(),
+    }))
+}
+
+async fn list_specs(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Query(query): Query<ListSpecsQuery>,
+) -> Result<Json<ListSpecsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let state_filter = match query.state.as_deref() {
+        Some("draft") => Some(SpecState::Draft),
+        Some("published") => Some(SpecState::Published),
+        Some("deprecated") => Some(SpecState::Deprecated),
+        Some("deleted") => Some(SpecState::Deleted),
+        _ => None,
+    };
+
+    let selector = query
+        .selector
+        .as_deref()
+        .map(str::parse::<LabelSelector>)
+        .transpose()
+        .map_err(|e| handle_domain_error(&e.into()))?
+        .unwrap_or_default();
+
+    let include_deleted = query.include_deleted.unwrap_or(false);
+    let limit = query.limit.unwrap_or(20).min(100);
+    let offset = query.offset.unwrap_or(0);
+
+    let specs = state
+        .projection_store
+        .list_by_state(
+            &tenant,
+            state_filter,
+            include_deleted,
+            &selector,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let total = specs.len();
+
+    Ok(Json(ListSpecsResponse {
+        specs: specs.into_iter().map(summary_to_response).collect(),
+        total,
+        limit,
+        offset,
+    }))
+}
+
+async fn get_root_folder(
+    state: State<AppState>,
+    tenant: Tenant,
+    query: Query<TreeQuery>,
+) -> Result<Json<TreeResponse>, (StatusCode, Json<ErrorResponse>)> {
+    get_folder(state, tenant, Path(String::new()), query).await
+}
+
+/// List a folder of path-style spec names, such as `GET /tree/payments/auth`
+async fn get_folder(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(path): Path<String>,
+    Query(query): Query<TreeQuery>,
+) -> Result<Json<TreeResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let folder = state
+        .projection_store
+        .list_folder(
+            &tenant,
+            path.trim_end_matches('/'),
+            query.recursive.unwrap_or(false),
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Folder not found".to_string(),
+                    details: None,
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            )
+        })?;
+
+    Ok(Json(TreeResponse {
+        path: folder.path,
+        folders: folder
+            .folders
+            .into_iter()
+            .map(|(path, spec_count)| FolderResponse { path, spec_count })
+            .collect(),
+        specs: folder.specs.into_iter().map(summary_to_response).collect(),
+    }))
+}
+
+/// Move a spec or a whole folder of specs to another path in one transaction
+async fn move_folder(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    headers: HeaderMap,
+    Json(req): Json<MoveFolderRequest>,
+) -> Result<Json<MoveFolderResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let command = MoveFolder {
+        from: req.from.trim_end_matches('/').to_string(),
+        to: req.to.trim_end_matches('/').to_string(),
+        moved_by: user.to_string(),
+    };
+
+    let metadata = EventMetadata {
+        correlation_id: Some(Uuid::new_v4()),
+        ..request_metadata(&headers, &tenant)?
+    };
+
+    let envelopes = state
+        .repository
+        .move_folder(command, metadata)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let moved = envelopes
+        .into_iter()
+        .filter_map(|envelope| match envelope.event {
+            SpecEvent::Renamed(e) => Some(MovedSpecResponse {
+                id: e.spec_id,
+                old_name: e.old_name,
+                new_name: e.new_name,
+                stream_version: envelope.sequence_number,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    Ok(Json(MoveFolderResponse { moved }))
+}
+
+/// Fetch a revision by number, or the highest published revision whose
+/// semantic version matches a range such as `^2.1`
+async fn get_spec_version(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path((id, version)): Path<(Uuid, String)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let found = if let Ok(version) = version.parse::<u32>() {
+        state
+            .projection_store
+            .get_version(&tenant, id, version)
+            .await
+    } else {
+        let req = semver::VersionReq::parse(&version).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Invalid version range".to_string(),
+                    details: Some(e.to_string()),
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            )
+        })?;
+        state
+            .projection_store
+            .resolve_version(&tenant, id, &req)
+            .await
+    };
+
+    let revision = found.map_err(|e| handle_domain_error(&e))?.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "Version not found".to_string(),
+                details: None,
+                violations: Vec::new(),
+                issues: Vec::new(),
+            }),
+        )
+    })?;
+
+    Ok(Json(serde_json::json!({
+        "id": id,
+        "version": revision.version,
+        "semver": revision.semver.to_string(),
+        "content": revision.content,
+        "content_hash": revision.content_hash,
+        "description": revision.description,
+        "published_at": revision.published_at.map(|at| at.to_rfc3339()),
+    })))
+}
+
+async fn get_published_spec(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let not_found = |error: &str| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: error.to_string(),
+                details: None,
+                violations: Vec::new(),
+                issues: Vec::new(),
+            }),
+        )
+    };
+
+    let spec = state
+        .projection_store
+        .get_by_id(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .filter(|spec| spec.state != SpecState::Deleted)
+        .ok_or_else(|| not_found("Spec not found"))?;
+
+    let version = spec
+        .published_version
+        .ok_or_else(|| not_found("Spec has no published version"))?;
+
+    let revision = state
+        .projection_store
+        .get_version(&tenant, id, version)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| not_found("Version not found"))?;
+
+    Ok(Json(serde_json::json!({
+        "id": id,
+        "name": spec.name,
+        "version": version,
+        "semver": revision.semver.to_string(),
+        "content": revision.content,
+        "content_hash": revision.content_hash,
+        "description": revision.description,
+        "state": format!("{:?}", spec.state).to_lowercase(),
+    })))
+}
+
+async fn register_schema(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    headers: HeaderMap,
+    Json(req): Json<RegisterSchemaRequest>,
+) -> Result<(StatusCode, ETagHeader, Json<SchemaVersionResponse>), (StatusCode, Json<ErrorResponse>)>
+{
+    let user = principal.user.as_str();
+
+    let command = RegisterSchema {
+        name: req.name,
+        schema: req.schema,
+        description: req.description,
+        registered_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .schemas()
+        .register(command, request_metadata(&headers, &tenant)?)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((
+        StatusCode::CREATED,
+        etag(&envelopes),
+        Json(SchemaVersionResponse {
+            id: envelopes[0].aggregate_id,
+            version: 1,
+        }),
+    ))
+}
+
+/// Add a new version of a schema; specs keep the version they declared
+async fn update_schema(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<UpdateSchemaRequest>,
+) -> Result<(ETagHeader, Json<SchemaVersionResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = UpdateSchema {
+        schema_id: id,
+        schema: req.schema,
+        description: req.description,
+        updated_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .schemas()
+        .update(
+            command,
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let version = match &envelopes[0].event {
+        SpecEvent::SchemaUpdated(e) => e.version,
+        _ => unreachable!(),
+    };
+
+    Ok((
+        etag(&envelopes),
+        Json(SchemaVersionResponse { id, version }),
+    ))
+}
+
+async fn get_schema(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+) -> Result<(ETagHeader, Json<SchemaResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let schema = state
+        .projection_store
+        .get_schema(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| handle_domain_error(&DomainError::SchemaNotFound(id)))?;
+
+    Ok((
+        [(header::ETAG, format_etag(schema.stream_version))],
+        Json(schema_to_response(schema)),
+    ))
+}
+
+async fn list_schemas(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+) -> Result<Json<ListSchemasResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let schemas = state
+        .projection_store
+        .list_schemas(&tenant)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListSchemasResponse {
+        schemas: schemas.into_iter().map(schema_to_response).collect(),
+    }))
+}
+
+async fn get_schema_version(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path((id, version)): Path<(Uuid, u32)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let revision = state
+        .projection_store
+        .get_schema_version(&tenant, id, version)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| {
+            handle_domain_error(&DomainError::SchemaVersionNotFound {
+                schema_id: id,
+                version,
+            })
+        })?;
+
+    Ok(Json(serde_json::json!({
+        "id": id,
+        "version": revision.version,
+        "schema": revision.schema,
+        "description": revision.description,
+        "created_at": revision.created_at.to_rfc3339(),
+        "created_by": revision.created_by,
+    })))
+}
+
+async fn register_template(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    headers: HeaderMap,
+    Json(req): Json<RegisterTemplateRequest>,
+) -> Result<
+    (StatusCode, ETagHeader, Json<TemplateVersionResponse>),
+    (StatusCode, Json<ErrorResponse>),
+> {
+    let user = principal.user.as_str();
+
+    let command = RegisterTemplate {
+        name: req.name,
+        body: req.body,
+        parameters: req.parameters,
+        description: req.description,
+        registered_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .templates()
+        .register(command, request_metadata(&headers, &tenant)?)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((
+        StatusCode::CREATED,
+        etag(&envelopes),
+        Json(TemplateVersionResponse {
+            id: envelopes[0].aggregate_id,
+            version: 1,
+        }),
+    ))
+}
+
+/// Add a new version of a template; derived specs keep the version they used
+async fn update_template(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(req): Json<UpdateTemplateRequest>,
+) -> Result<(ETagHeader, Json<TemplateVersionResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+
+    let command = UpdateTemplate {
+        template_id: id,
+        body: req.body,
+        parameters: req.parameters,
+        description: req.description,
+        updated_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .repository
+        .templates()
+        .update(
+            command,
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let version = match &envelopes[0].event {
+        SpecEvent::TemplateUpdated(e) => e.version,
+        _ => unreachable!(),
+    };
+
+    Ok((
+        etag(&envelopes),
+        Json(TemplateVersionResponse { id, version }),
+    ))
+}
+
+async fn get_template(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+) -> Result<(ETagHeader, Json<TemplateResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let template = state
+        .projection_store
+        .get_template(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| handle_domain_error(&DomainError::TemplateNotFound(id)))?;
+
+    Ok((
+        [(header::ETAG, format_etag(template.stream_version))],
+        Json(template_to_response(template)),
+    ))
+}
+
+async fn list_templates(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+) -> Result<Json<ListTemplatesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let templates = state
+        .projection_store
+        .list_templates(&tenant)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListTemplatesResponse {
+        templates: templates.into_iter().map(template_to_response).collect(),
+    }))
+}
+
+async fn get_template_version(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path((id, version)): Path<(Uuid, u32)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    let revision = state
+        .projection_store
+        .get_template_version(&tenant, id, version)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| {
+            handle_domain_error(&DomainError::TemplateVersionNotFound {
+                template_id: id,
+                version,
+            })
+        })?;
+
+    Ok(Json(serde_json::json!({
+        "id": id,
+        "version": revision.version,
+        "body": revision.body,
+        "parameters": revision.parameters,
+        "description": revision.description,
+        "created_at": revision.created_at.to_rfc3339(),
+        "created_by": revision.created_by,
+    })))
+}
+
+/// Render a template without creating a spec
+async fn preview_template(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+    Json(req): Json<PreviewTemplateRequest>,
+) -> Result<Json<PreviewTemplateResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (template, content) = state
+        .repository
+        .templates()
+        .render(&tenant, id, req.version, &req.parameters)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(PreviewTemplateResponse {
+        template_id: template.template_id,
+        version: template.version,
+        content,
+    }))
+}
+
+/// Specs rendered from a template, flagging those made from an older version
+async fn list_template_specs(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListTemplateSpecsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let template = state
+        .projection_store
+        .get_template(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| handle_domain_error(&DomainError::TemplateNotFound(id)))?;
+
+    let specs = state
+        .projection_store
+        .list_template_specs(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListTemplateSpecsResponse {
+        template_id: id,
+        head_version: template.head_version,
+        specs: specs
+            .into_iter()
+            .map(|spec| DerivedSpecResponse {
+                id: spec.id,
+                name: spec.name,
+                state: format!("{:?}", spec.state).to_lowercase(),
+                template_version: spec.template_version,
+                outdated: spec.template_version < template.head_version,
+                updated_at: spec.updated_at.to_rfc3339(),
+            })
+            .collect(),
+    }))
+}
+
+/// Pin a set of spec versions as a draft release
+async fn create_release(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    headers: HeaderMap,
+    Json(req): Json<CreateReleaseRequest>,
+) -> Result<(StatusCode, ETagHeader, Json<ReleaseStateResponse>), (StatusCode, Json<ErrorResponse>)>
+{
+    let user = principal.user.as_str();
+
+    let command = CreateRelease {
+        name: req.name,
+        description: req.description,
+        entries: req.entries,
+        created_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .releases
+        .create(command, request_metadata(&headers, &tenant)?)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((
+        StatusCode::CREATED,
+        etag(&envelopes),
+        Json(ReleaseStateResponse {
+            id: envelopes[0].aggregate_id,
+            state: ReleaseState::Draft,
+            restored_release_id: None,
+        }),
+    ))
+}
+
+/// Make a release current, publishing every pinned version in one transaction
+async fn publish_release(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(release): Path<String>,
+    headers: HeaderMap,
+) -> Result<(ETagHeader, Json<ReleaseStateResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+    let id = resolve_release(&state, &tenant, &release).await?;
+
+    let command = PublishRelease {
+        release_id: id,
+        published_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .releases
+        .publish(
+            command,
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok((
+        etag(&envelopes),
+        Json(ReleaseStateResponse {
+            id,
+            state: ReleaseState::Published,
+            restored_release_id: None,
+        }),
+    ))
+}
+
+/// Roll back the current release; the one published before it is current again
+async fn rollback_release(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    principal: Principal,
+    Path(release): Path<String>,
+    headers: HeaderMap,
+) -> Result<(ETagHeader, Json<ReleaseStateResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = principal.user.as_str();
+
+    let expected_version = parse_if_match(&headers)?;
+    let id = resolve_release(&state, &tenant, &release).await?;
+
+    let command = RollbackRelease {
+        release_id: id,
+        rolled_back_by: user.to_string(),
+    };
+
+    let envelopes = state
+        .releases
+        .rollback(
+            command,
+            expected_version,
+            request_metadata(&headers, &tenant)?,
+        )
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    let restored_release_id = match &envelopes[0].event {
+        SpecEvent::ReleaseRolledBack(e) => e.restored_release_id,
+        _ => unreachable!(),
+    };
+
+    Ok((
+        etag(&envelopes),
+        Json(ReleaseStateResponse {
+            id,
+            state: ReleaseState::RolledBack,
+            restored_release_id,
+        }),
+    ))
+}
+
+async fn list_releases(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+) -> Result<Json<ListReleasesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let releases = state
+        .projection_store
+        .list_releases(&tenant)
+        .await
+        .map_err(|e| handle_domain_error(&e))?;
+
+    Ok(Json(ListReleasesResponse {
+        releases: releases.into_iter().map(release_to_response).collect(),
+    }))
+}
+
+/// A release's manifest: the spec versions it pins
+async fn get_release(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(release): Path<String>,
+) -> Result<(ETagHeader, Json<ReleaseResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let id = resolve_release(&state, &tenant, &release).await?;
+
+    let release = state
+        .projection_store
+        .get_release(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| handle_domain_error(&DomainError::ReleaseNotFound(id)))?;
+
+    Ok((
+        [(header::ETAG, format_etag(release.stream_version))],
+        Json(release_to_response(release)),
+    ))
+}
+
+async fn get_current_release(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+) -> Result<(ETagHeader, Json<ReleaseResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let release = current_release(&state, &tenant).await?;
+
+    Ok((
+        [(header::ETAG, format_etag(release.stream_version))],
+        Json(release_to_response(release)),
+    ))
+}
+
+/// Content of every spec version a release pins
+async fn list_release_specs(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+    Path(release): Path<String>,
+) -> Result<Json<ReleaseSpecsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let id = resolve_release(&state, &tenant, &release).await?;
+
+    let release = state
+        .projection_store
+        .get_release(&tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| handle_domain_error(&DomainError::ReleaseNotFound(id)))?;
+
+    release_specs(&state, &tenant, release).await.map(Json)
+}
+
+async fn list_current_release_specs(
+    State(state): State<AppState>,
+    Tenant(tenant): Tenant,
+) -> Result<Json<ReleaseSpecsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let release = current_release(&state, &tenant).await?;
+
+    release_specs(&state, &tenant, release).await.map(Json)
+}
+
+async fn current_release(
+    state: &AppState,
+    tenant: &TenantId,
+) -> Result<ReleaseProjection, (StatusCode, Json<ErrorResponse>)> {
+    state
+        .projection_store
+        .get_current_release(tenant)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "No release is published".to_string(),
+                    details: None,
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            )
+        })
+}
+
+async fn release_specs(
+    state: &AppState,
+    tenant: &TenantId,
+    release: ReleaseProjection,
+) -> Result<ReleaseSpecsResponse, (StatusCode, Json<ErrorResponse>)> {
+    let specs = state
+        .projection_store
+        .list_release_specs(tenant, release.id)
         .await
         .map_err(|e| handle_domain_error(&e))?;
 
-    Ok(Json(UpdateSpecResponse {
-        version: new_version,
-    }))
+    Ok(ReleaseSpecsResponse {
+        release_id: release.id,
+        name: release.name,
+        state: release.state,
+        specs: specs
+            .into_iter()
+            .map(|(entry, revision)| ReleaseSpecResponse {
+                spec_id: entry.spec_id,
+                name: entry.name,
+                version: revision.version,
+                semver: revision.semver.to_string(),
+                content: revision.content,
+                content_hash: revision.content_hash,
+                description: revision.description,
+            })
+            .collect(),
+    })
 }
 
-async fn publish_spec(
+/// Register a tenant principals can then be given tokens for
+async fn create_tenant(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Json(req): Json<PublishSpecRequest>,
-) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-    let events = state
-        .event_store
-        .get_events(id, None)
-        .await
-        .map_err(|e| handle_domain_error(&e))?;
-
-    if events.is_empty() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: "Spec not found".to_string(),
-                details: None,
-            }),
-        ));
-    }
+    Admin(admin): Admin,
+    headers: HeaderMap,
+    Json(req): Json<CreateTenantRequest>,
+) -> Result<(StatusCode, Json<TenantResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let user = admin.user;
+
+    let tenant_id = TenantId::new(&req.id).map_err(|e| handle_domain_error(&e.into()))?;
+
+    let command = CreateTenant {
+        tenant_id,
+        display_name: req.display_name,
+        created_by: user,
+    };
 
-    let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())
+    let envelopes = state
+        .tenants
+        .create(command, request_metadata(&headers, &TenantId::default())?)
+        .await
         .map_err(|e| handle_domain_error(&e))?;
 
-    let user = "admin@example.com"; // TODO: From auth, check permissions
-
-    let command = PublishSpec {
-        spec_id: id,
-        version: req.version,
-        published_by: user.to_string(),
+    let SpecEvent::TenantCreated(created) = &envelopes[0].event else {
+        unreachable!()
     };
 
-    let new_events = spec
-        .handle_command(command.into())
-        .map_err(|e| handle_domain_error(&e))?;
+    Ok((
+        StatusCode::CREATED,
+        Json(TenantResponse {
+            id: created.tenant_id.clone(),
+            display_name: created.display_name.clone(),
+            state: TenantState::Active,
+            suspension_reason: None,
+            created_at: created.created_at.to_rfc3339(),
+            created_by: created.created_by.clone(),
+            updated_at: created.created_at.to_rfc3339(),
+            updated_by: created.created_by.clone(),
+        }),
+    ))
+}
 
-    state
-        .event_store
-        .append_events(id, new_events, EventMetadata::default())
+async fn list_tenants(
+    State(state): State<AppState>,
+    _admin: Admin,
+) -> Result<Json<ListTenantsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let tenants = state
+        .projection_store
+        .list_tenants()
         .await
         .map_err(|e| handle_domain_error(&e))?;
 
-    Ok(StatusCode::OK)
+    Ok(Json(ListTenantsResponse {
+        tenants: tenants.into_iter().map(tenant_to_response).collect(),
+    }))
 }
 
-async fn deprecate_spec(
+/// Refuse every further request for a tenant; its specs are kept
+async fn suspend_tenant(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Json(req): Json<DeprecateSpecRequest>,
+    Admin(admin): Admin,
+    Path(tenant): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<SuspendTenantRequest>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-    let events = state
-        .event_store
-        .get_events(id, None)
-        .await
-        .map_err(|e| handle_domain_error(&e))?;
-
-    if events.is_empty() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                error: "Spec not found".to_string(),
-                details: None,
-            }),
-        ));
-    }
-
-    let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())
-        .map_err(|e| handle_domain_error(&e))?;
+    let user = admin.user;
 
-    let user = "admin@example.com"; // TODO: From auth, check permissions
+    let tenant_id = TenantId::new(&tenant).map_err(|e| handle_domain_error(&e.into()))?;
 
-    let command = DeprecateSpec {
-        spec_id: id,
+    let command = SuspendTenant {
+        tenant_id,
         reason: req.reason,
-        deprecated_by: user.to_string(),
+        suspended_by: user,
     };
 
-    let new_events = spec
-        .handle_command(command.into())
-        .map_err(|e| handle_domain_error(&e))?;
-
     state
-        .event_store
-        .append_events(id, new_events, EventMetadata::default())
+        .tenants
+        .suspend(command, request_metadata(&headers, &TenantId::default())?)
         .await
         .map_err(|e| handle_domain_error(&e))?;
 
-    Ok(StatusCode::OK)
+    Ok(StatusCode::NO_CONTENT)
 }
 
-async fn list_specs(
+/// Store a request body of any size, read as it streams in
+async fn upload_content(
     State(state): State<AppState>,
-    Query(query): Query<ListSpecsQuery>,
-) -> Result<Json<ListSpecsResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let state_filter = match query.state.as_deref() {
-        Some("draft") => Some(SpecState::Draft),
-        Some("published") => Some(SpecState::Published),
-        Some("deprecated") => Some(SpecState::Deprecated),
-        Some("deleted") => Some(SpecState::Deleted),
-        _ => None,
-    };
+    _principal: Principal,
+    body: Body,
+) -> Result<(StatusCode, Json<UploadContentResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let mut upload = state
+        .event_store
+        .start_upload()
+        .map_err(|e| handle_domain_error(&e))?;
 
-    let limit = query.limit.unwrap_or(20).min(100);
-    let offset = query.offset.unwrap_or(0);
+    let mut chunks = body.into_data_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "Failed to read request body".to_string(),
+                    details: Some(e.to_string()),
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            )
+        })?;
+        upload.write(&chunk).map_err(|e| handle_domain_error(&e))?;
+    }
 
-    let specs = state
-        .projection_store
-        .list_by_state(state_filter, limit, offset)
+    let content_ref = state
+        .event_store
+        .put_content(upload)
         .await
         .map_err(|e| handle_domain_error(&e))?;
 
-    let total = specs.len();
-
-    Ok(Json(ListSpecsResponse {
-        specs: specs.into_iter().map(summary_to_response).collect(),
-        total,
-        limit,
-        offset,
-    }))
+    Ok((
+        StatusCode::CREATED,
+        Json(UploadContentResponse {
+            hash: content_ref.hash,
+            size: content_ref.size,
+        }),
+    ))
 }
 
-async fn get_spec_version(
+/// Stream stored content back with chunked transfer encoding
+async fn download_content(
     State(state): State<AppState>,
-    Path((id, version)): Path<(Uuid, u32)>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
-    let (content, description) = state
-        .projection_store
-        .get_version(id, version)
+    _principal: Principal,
+    Path(hash): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let content = state
+        .event_store
+        .get_content(&hash)
         .await
         .map_err(|e| handle_domain_error(&e))?
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: "Version not found".to_string(),
-                    details: None,
-                }),
-            )
-        })?;
+        .ok_or_else(|| handle_domain_error(&DomainError::ContentNotFound(hash.clone())))?;
 
-    Ok(Json(serde_json::json!({
-        "id": id,
-        "version": version,
-        "content": content,
-        "description": description,
-    })))
+    let content = Bytes::from(content);
+    let chunks: Vec<Result<Bytes, Infallible>> = (0..content.len())
+        .step_by(DOWNLOAD_CHUNK_SIZE)
+        .map(|start| Ok(content.slice(start..content.len().min(start + DOWNLOAD_CHUNK_SIZE))))
+        .collect();
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/yaml".to_string()),
+            (header::ETAG, format!("\"{hash}\"")),
+        ],
+        Body::from_stream(tokio_stream::iter(chunks)),
+    ))
 }
 
 async fn health_check() -> StatusCode {
@@ -397,17 +3114,74 @@ async fn health_check() -> StatusCode {
 
 fn handle_domain_error(error: &DomainError) -> (StatusCode, Json<ErrorResponse>) {
     let (status, message) = match error {
-        DomainError::SpecNotFound(_) => (StatusCode::NOT_FOUND, "Spec not found"),
+        DomainError::SpecNotFound(_) | DomainError::SpecNameNotFound(_) => {
+            (StatusCode::NOT_FOUND, "Spec not found")
+        }
         DomainError::InvalidStateTransition { .. } => {
             (StatusCode::BAD_REQUEST, "Invalid state transition")
         }
         DomainError::VersionMismatch { .. } => (StatusCode::CONFLICT, "Version mismatch"),
+        DomainError::VersionNotFound(_) => (StatusCode::NOT_FOUND, "Version not found"),
+        DomainError::InvalidRevertTarget(_) => (StatusCode::BAD_REQUEST, "Invalid revert target"),
+        DomainError::ConcurrencyConflict { .. } => (
+            StatusCode::PRECONDITION_FAILED,
+            "Spec was modified concurrently",
+        ),
         DomainError::DuplicateSpecName(_) => (StatusCode::CONFLICT, "Spec name already exists"),
         DomainError::InvalidStateForOperation(_) => (
             StatusCode::BAD_REQUEST,
             "Invalid operation for current state",
         ),
+        DomainError::InvalidDeprecation(_) => (StatusCode::BAD_REQUEST, "Invalid deprecation"),
+        DomainError::ScheduleNotFound(_) => (StatusCode::NOT_FOUND, "Schedule not found"),
+        DomainError::InvalidSchedule(_) => (StatusCode::BAD_REQUEST, "Invalid schedule"),
+        DomainError::ReviewRequired(_) => (StatusCode::CONFLICT, "Approval required"),
+        DomainError::InvalidReview(_) => (StatusCode::CONFLICT, "Invalid review"),
+        DomainError::SelfApproval(_) => (StatusCode::FORBIDDEN, "Self-approval not allowed"),
+        DomainError::InvalidLabels(_) => (StatusCode::BAD_REQUEST, "Invalid labels"),
         DomainError::ValidationError(_) => (StatusCode::BAD_REQUEST, "Validation failed"),
+        DomainError::SchemaNotFound(_) => (StatusCode::NOT_FOUND, "Schema not found"),
+        DomainError::SchemaVersionNotFound { .. } => {
+            (StatusCode::NOT_FOUND, "Schema version not found")
+        }
+        DomainError::TemplateNotFound(_) => (StatusCode::NOT_FOUND, "Template not found"),
+        DomainError::TemplateVersionNotFound { .. } => {
+            (StatusCode::NOT_FOUND, "Template version not found")
+        }
+        DomainError::ContentNotFound(_) => (StatusCode::NOT_FOUND, "Content not found"),
+        DomainError::IdempotencyKeyReused(_) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Idempotency key already used",
+        ),
+        DomainError::CommentNotFound(_) => (StatusCode::NOT_FOUND, "Comment not found"),
+        DomainError::InvalidComment(_) => (StatusCode::BAD_REQUEST, "Invalid comment"),
+        DomainError::NotCommentAuthor { .. } => {
+            (StatusCode::FORBIDDEN, "Only the author can edit a comment")
+        }
+        DomainError::InvalidDependencies(_) => (StatusCode::BAD_REQUEST, "Invalid dependencies"),
+        DomainError::UnpublishedDependency { .. } => {
+            (StatusCode::CONFLICT, "Dependency is not published")
+        }
+        DomainError::HasDependents(_) => (StatusCode::CONFLICT, "Spec has published dependents"),
+        DomainError::InvalidPromotion(_) => (StatusCode::CONFLICT, "Invalid promotion"),
+        DomainError::SchemaViolation(_) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Content does not match schema",
+        ),
+        DomainError::ReleaseNotFound(_) => (StatusCode::NOT_FOUND, "Release not found"),
+        DomainError::DuplicateReleaseName(_) => {
+            (StatusCode::CONFLICT, "Release name already exists")
+        }
+        DomainError::InvalidRelease(_) => (StatusCode::CONFLICT, "Invalid release"),
+        DomainError::InvalidBatch(_) => (StatusCode::BAD_REQUEST, "Invalid batch"),
+        DomainError::InvalidMove(_) => (StatusCode::BAD_REQUEST, "Invalid move"),
+        DomainError::BatchAborted => (StatusCode::FAILED_DEPENDENCY, "Not applied"),
+        DomainError::TenantNotFound(_) => (StatusCode::NOT_FOUND, "Tenant not found"),
+        DomainError::DuplicateTenant(_) => (StatusCode::CONFLICT, "Tenant already exists"),
+        DomainError::TenantSuspended(_) => (StatusCode::FORBIDDEN, "Tenant is suspended"),
+        DomainError::InvalidTenant(_) => (StatusCode::CONFLICT, "Invalid tenant"),
+        DomainError::Unauthenticated(_) => (StatusCode::UNAUTHORIZED, "Not authenticated"),
+        DomainError::PermissionDenied(_) => (StatusCode::FORBIDDEN, "Permission denied"),
         _ => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
     };
 
@@ -416,22 +3190,315 @@ fn handle_domain_error(error: &DomainError) -> (StatusCode, Json<ErrorResponse>)
         Json(ErrorResponse {
             error: message.to_string(),
             details: Some(error.to_string()),
+            violations: match error {
+                DomainError::SchemaViolation(violations) => violations.clone(),
+                _ => Vec::new(),
+            },
+            issues: match error {
+                DomainError::ValidationError(ValidationError::ContentRejected(issues)) => {
+                    issues.clone()
+                }
+                _ => Vec::new(),
+            },
         }),
     )
 }
 
+/// Id of a release given by id or by name
+async fn resolve_release(
+    state: &AppState,
+    tenant: &TenantId,
+    release: &str,
+) -> Result<Uuid, (StatusCode, Json<ErrorResponse>)> {
+    if let Ok(id) = Uuid::parse_str(release) {
+        return Ok(id);
+    }
+
+    state
+        .event_store
+        .find_release(tenant, release)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Release not found".to_string(),
+                    details: Some(format!("Release not found with name: {release}")),
+                    violations: Vec::new(),
+                    issues: Vec::new(),
+                }),
+            )
+        })
+}
+
+async fn ensure_spec_exists(
+    state: &AppState,
+    tenant: &TenantId,
+    id: Uuid,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    state
+        .projection_store
+        .get_by_id(tenant, id)
+        .await
+        .map_err(|e| handle_domain_error(&e))?
+        .map(|_| ())
+        .ok_or_else(|| handle_domain_error(&DomainError::SpecNotFound(id)))
+}
+
+/// Content for a create or update, read from an earlier upload when a hash is given
+async fn resolve_content(
+    state: &AppState,
+    content: String,
+    content_hash: Option<String>,
+) -> Result<String, (StatusCode, Json<ErrorResponse>)> {
+    match content_hash {
+        Some(hash) => state
+            .event_store
+            .load_content(&hash)
+            .await
+            .map_err(|e| handle_domain_error(&e)),
+        None => Ok(content),
+    }
+}
+
+/// Read the expected stream version from an `If-Match` header, if any
+fn parse_if_match(headers: &HeaderMap) -> Result<Option<i64>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+
+    let invalid = || {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid If-Match header".to_string(),
+                details: None,
+                violations: Vec::new(),
+                issues: Vec::new(),
+            }),
+        )
+    };
+
+    let value = value.to_str().map_err(|_| invalid())?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse()
+        .map(Some)
+        .map_err(|_| invalid())
+}
+
+/// Event metadata for a mutating request made for `tenant`, carrying its
+/// `Idempotency-Key` header
+fn request_metadata(
+    headers: &HeaderMap,
+    tenant: &TenantId,
+) -> Result<EventMetadata, (StatusCode, Json<ErrorResponse>)> {
+    let metadata = EventMetadata::default().with_tenant(tenant.clone());
+    let Some(value) = headers.get(IDEMPOTENCY_KEY) else {
+        return Ok(metadata);
+    };
+
+    let key = value.to_str().map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Invalid Idempotency-Key header".to_string(),
+                details: None,
+                violations: Vec::new(),
+                issues: Vec::new(),
+            }),
+        )
+    })?;
+
+    metadata
+        .with_idempotency_key(key)
+        .map_err(|e| handle_domain_error(&e.into()))
+}
+
+fn insert_header(headers: &mut HeaderMap, name: HeaderName, value: String) {
+    if let Ok(value) = HeaderValue::try_from(value) {
+        headers.insert(name, value);
+    }
+}
+
+fn format_etag(stream_version: i64) -> String {
+    format!("\"{stream_version}\"")
+}
+
+fn etag(envelopes: &[EventEnvelope]) -> ETagHeader {
+    let stream_version = envelopes.last().map_or(0, |e| e.sequence_number);
+    [(header::ETAG, format_etag(stream_version))]
+}
+
 fn projection_to_response(proj: SpecProjection) -> SpecResponse {
     SpecResponse {
         id: proj.id,
         name: proj.name,
         content: proj.content,
+        content_hash: proj.content_hash,
         description: proj.description,
-        version: proj.version,
+        version: proj.head_version,
+        semver: proj.semver.to_string(),
+        published_version: proj.published_version,
+        head_version: proj.head_version,
         state: format!("{:?}", proj.state).to_lowercase(),
         created_at: proj.created_at.to_rfc3339(),
         updated_at: proj.updated_at.to_rfc3339(),
         created_by: proj.created_by,
         updated_by: proj.updated_by,
+        review: proj.review,
+        labels: proj.labels,
+        schema: proj.schema,
+        template: proj.template,
+    }
+}
+
+fn batch_item_to_response(
+    item: Result<BatchItemResult, DomainError>,
+    create: bool,
+) -> BatchItemResponse {
+    match item {
+        Ok(result) => BatchItemResponse {
+            status: if create {
+                StatusCode::CREATED.as_u16()
+            } else {
+                StatusCode::OK.as_u16()
+            },
+            result: BatchItemBody::Applied {
+                id: result.spec_id,
+                version: result.version,
+                semver: result.semver.to_string(),
+                state: result.state,
+                stream_version: result.stream_version,
+            },
+        },
+        Err(e) => {
+            let (status, Json(error)) = handle_domain_error(&e);
+            BatchItemResponse {
+                status: status.as_u16(),
+                result: BatchItemBody::Failed(error),
+            }
+        }
+    }
+}
+
+fn comment_to_response(comment: CommentProjection) -> CommentResponse {
+    CommentResponse {
+        id: comment.id,
+        version: comment.version,
+        anchor: comment.anchor,
+        body: comment.body,
+        author: comment.author,
+        created_at: comment.created_at.to_rfc3339(),
+        edited_at: comment.edited_at.map(|at| at.to_rfc3339()),
+    }
+}
+
+fn thread_to_response(thread: CommentThreadProjection) -> CommentThreadResponse {
+    let CommentThreadProjection { comment, replies } = thread;
+
+    CommentThreadResponse {
+        resolved: comment.resolved_at.is_some(),
+        resolved_by: comment.resolved_by.clone(),
+        resolved_at: comment.resolved_at.map(|at| at.to_rfc3339()),
+        comment: comment_to_response(comment),
+        replies: replies.into_iter().map(comment_to_response).collect(),
+    }
+}
+
+fn dependency_to_response(dependency: DependencyProjection) -> DependencyResponse {
+    DependencyResponse {
+        spec_id: dependency.spec_id,
+        name: dependency.name,
+        state: format!("{:?}", dependency.state).to_lowercase(),
+        published_version: dependency.published_version,
+        version: dependency.version.to_string(),
+    }
+}
+
+fn channel_spec_to_response(entry: ChannelSpecProjection) -> ChannelSpecResponse {
+    ChannelSpecResponse {
+        channel: entry.channel,
+        spec_id: entry.spec_id,
+        name: entry.name,
+        state: format!("{:?}", entry.state).to_lowercase(),
+        version: entry.version,
+        semver: entry.semver.to_string(),
+        changed_by: entry.changed_by,
+        changed_at: entry.changed_at.to_rfc3339(),
+    }
+}
+
+fn schema_to_response(schema: SchemaProjection) -> SchemaResponse {
+    SchemaResponse {
+        id: schema.id,
+        name: schema.name,
+        description: schema.description,
+        version: schema.head_version,
+        schema: schema.schema,
+        created_at: schema.created_at.to_rfc3339(),
+        updated_at: schema.updated_at.to_rfc3339(),
+        created_by: schema.created_by,
+        updated_by: schema.updated_by,
+    }
+}
+
+fn release_to_response(release: ReleaseProjection) -> ReleaseResponse {
+    ReleaseResponse {
+        id: release.id,
+        name: release.name,
+        description: release.description,
+        state: release.state,
+        entries: release
+            .entries
+            .into_iter()
+            .map(|entry| ReleaseEntryResponse {
+                spec_id: entry.spec_id,
+                name: entry.name,
+                version: entry.version,
+                semver: entry.semver.to_string(),
+                content_hash: entry.content_hash,
+            })
+            .collect(),
+        created_at: release.created_at.to_rfc3339(),
+        created_by: release.created_by,
+        updated_at: release.updated_at.to_rfc3339(),
+        updated_by: release.updated_by,
+        published_at: release.published_at.map(|at| at.to_rfc3339()),
+    }
+}
+
+fn template_to_response(template: TemplateProjection) -> TemplateResponse {
+    TemplateResponse {
+        id: template.id,
+        name: template.name,
+        description: template.description,
+        version: template.head_version,
+        body: template.body,
+        parameters: template.parameters,
+        created_at: template.created_at.to_rfc3339(),
+        updated_at: template.updated_at.to_rfc3339(),
+        created_by: template.created_by,
+        updated_by: template.updated_by,
+    }
+}
+
+fn tenant_to_response(tenant: TenantProjection) -> TenantResponse {
+    TenantResponse {
+        id: tenant.id,
+        display_name: tenant.display_name,
+        state: tenant.state,
+        suspension_reason: tenant.suspension_reason,
+        created_at: tenant.created_at.to_rfc3339(),
+        created_by: tenant.created_by,
+        updated_at: tenant.updated_at.to_rfc3339(),
+        updated_by: tenant.updated_by,
     }
 }
 
@@ -441,8 +3508,25 @@ fn summary_to_response(summary: SpecSummaryProjection) -> SpecSummaryResponse {
         name: summary.name,
         description: summary.description,
         latest_version: summary.latest_version,
+        semver: summary.semver.to_string(),
+        published_version: summary.published_version,
         state: format!("{:?}", summary.state).to_lowercase(),
         updated_at: summary.updated_at.to_rfc3339(),
+        labels: summary.labels,
+    }
+}
+
+fn schedule_to_response(schedule: ScheduleProjection) -> ScheduleResponse {
+    ScheduleResponse {
+        schedule_id: schedule.schedule_id,
+        spec_id: schedule.spec_id,
+        transition: schedule.transition,
+        execute_at: schedule.execute_at.to_rfc3339(),
+        scheduled_by: schedule.scheduled_by,
+        scheduled_at: schedule.scheduled_at.to_rfc3339(),
+        status: schedule.status.as_str().to_string(),
+        completed_at: schedule.completed_at.map(|at| at.to_rfc3339()),
+        failure: schedule.failure,
     }
 }
 
@@ -464,3 +3548,111 @@ impl From<DeprecateSpec> for crate::domain::commands::SpecCommand {
         Self::Deprecate(cmd)
     }
 }
+
+impl From<DeleteSpec> for crate::domain::commands::SpecCommand {
+    fn from(cmd: DeleteSpec) -> Self {
+        Self::Delete(cmd)
+    }
+}
+
+impl From<RestoreSpec> for crate::domain::commands::SpecCommand {
+    fn from(cmd: RestoreSpec) -> Self {
+        Self::Restore(cmd)
+    }
+}
+
+impl From<RevertSpec> for crate::domain::commands::SpecCommand {
+    fn from(cmd: RevertSpec) -> Self {
+        Self::Revert(cmd)
+    }
+}
+
+impl From<RenameSpec> for crate::domain::commands::SpecCommand {
+    fn from(cmd: RenameSpec) -> Self {
+        Self::Rename(cmd)
+    }
+}
+
+impl From<ScheduleTransition> for crate::domain::commands::SpecCommand {
+    fn from(cmd: ScheduleTransition) -> Self {
+        Self::ScheduleTransition(cmd)
+    }
+}
+
+impl From<CancelSchedule> for crate::domain::commands::SpecCommand {
+    fn from(cmd: CancelSchedule) -> Self {
+        Self::CancelSchedule(cmd)
+    }
+}
+
+impl From<RequestReview> for crate::domain::commands::SpecCommand {
+    fn from(cmd: RequestReview) -> Self {
+        Self::RequestReview(cmd)
+    }
+}
+
+impl From<ApproveSpec> for crate::domain::commands::SpecCommand {
+    fn from(cmd: ApproveSpec) -> Self {
+        Self::Approve(cmd)
+    }
+}
+
+impl From<RejectSpec> for crate::domain::commands::SpecCommand {
+    fn from(cmd: RejectSpec) -> Self {
+        Self::Reject(cmd)
+    }
+}
+
+impl From<WithdrawReview> for crate::domain::commands::SpecCommand {
+    fn from(cmd: WithdrawReview) -> Self {
+        Self::WithdrawReview(cmd)
+    }
+}
+
+impl From<AddComment> for crate::domain::commands::SpecCommand {
+    fn from(cmd: AddComment) -> Self {
+        Self::AddComment(cmd)
+    }
+}
+
+impl From<EditComment> for crate::domain::commands::SpecCommand {
+    fn from(cmd: EditComment) -> Self {
+        Self::EditComment(cmd)
+    }
+}
+
+impl From<ResolveComment> for crate::domain::commands::SpecCommand {
+    fn from(cmd: ResolveComment) -> Self {
+        Self::ResolveComment(cmd)
+    }
+}
+
+impl From<AddLabels> for crate::domain::commands::SpecCommand {
+    fn from(cmd: AddLabels) -> Self {
+        Self::AddLabels(cmd)
+    }
+}
+
+impl From<RemoveLabels> for crate::domain::commands::SpecCommand {
+    fn from(cmd: RemoveLabels) -> Self {
+        Self::RemoveLabels(cmd)
+    }
+}
+
+impl From<PromoteSpec> for crate::domain::commands::SpecCommand {
+    fn from(cmd: PromoteSpec) -> Self {
+        Self::Promote(cmd)
+    }
+}
+
+impl From<DemoteSpec> for crate::domain::commands::SpecCommand {
+    fn from(cmd: DemoteSpec) -> Self {
+        Self::Demote(cmd)
+    }
+}
+
+impl From<SetDependencies> for crate::domain::commands::SpecCommand {
+    fn from(cmd: SetDependencies) -> Self {
+        Self::SetDependencies(cmd)
+    }
+}
@@ -1,13 +1,124 @@
 use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
 use uuid::Uuid;
 
 use super::{
-    commands::{CreateSpec, DeleteSpec, DeprecateSpec, PublishSpec, SpecCommand, UpdateSpec},
+    commands::{
+        AddComment, AddLabels, ApproveSpec, CancelSchedule, CreateRelease, CreateSpec,
+        CreateTenant, DeleteSpec, DemoteSpec, DeprecateSpec, EditComment, ExecuteSchedule,
+        FailSchedule, FailSunset, PromoteSpec, PublishRelease, PublishSpec, RegisterSchema,
+        RegisterTemplate, RejectSpec, RemoveLabels, RenameSpec, RepublishSpec, RequestReview,
+        ResolveComment, RestoreSpec, RevertSpec, RollbackRelease, ScheduleTransition,
+        SetDependencies, SpecCommand, SuspendTenant, UpdateSchema, UpdateSpec, UpdateTemplate,
+        WithdrawReview,
+    },
     errors::DomainError,
-    events::{SpecCreated, SpecEvent, SpecState, SpecStateChanged, SpecUpdated},
-    value_objects::{SpecContent, SpecName, Version},
+    events::{
+        ReleaseCreated, ReleasePublished, ReleaseRolledBack, ReleaseState, ReviewStatus,
+        ScheduledTransition, SchemaRegistered, SchemaUpdated, SpecCommentAdded, SpecCommentEdited,
+        SpecCommentResolved, SpecCreated, SpecDemoted, SpecDependenciesSet, SpecEvent,
+        SpecLabelsAdded, SpecLabelsRemoved, SpecPromoted, SpecRenamed, SpecRestored, SpecReverted,
+        SpecReviewApproved, SpecReviewRejected, SpecReviewRequested, SpecReviewWithdrawn,
+        SpecScheduleCancelled, SpecScheduleExecuted, SpecState, SpecStateChanged, SpecSunsetFailed,
+        SpecTransitionScheduled, SpecUpdated, TemplateRegistered, TemplateUpdated, TenantCreated,
+        TenantState, TenantSuspended,
+    },
+    validation::{ValidationPolicy, ValidatorPipeline},
+    value_objects::{
+        validate_channel, validate_label_key, Labels, ReleaseEntry, SchemaDocument, SchemaRef,
+        SpecContent, SpecDependency, SpecName, TemplateDocument, TenantId, ValidationError,
+        Version,
+    },
 };
 
+/// Content and description as they were at one version
+#[derive(Debug, Clone)]
+pub struct SpecRevision {
+    pub content: SpecContent,
+    pub semver: semver::Version,
+    pub description: Option<String>,
+    /// Schema the content was validated against
+    pub schema: Option<SchemaRef>,
+    /// Whether the revision has ever been published
+    pub published: bool,
+}
+
+/// What the aggregate needs to know about a comment to validate later ones
+#[derive(Debug, Clone)]
+pub struct SpecComment {
+    pub version: Version,
+    /// Thread the comment replies to; `None` for the first comment of a thread
+    pub parent_id: Option<Uuid>,
+    pub author: String,
+    pub resolved: bool,
+}
+
+/// How many approvals a version needs before it can be published
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReviewPolicy {
+    /// With zero, reviews are optional, but a version under review must
+    /// still be approved before it is published
+    pub required_approvals: u32,
+}
+
+/// Channels versions are promoted through, in order
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PromotionPolicy {
+    /// With none, any channel may be used in any order; otherwise only these,
+    /// and a version must be in a channel before it can go to the next one
+    pub channels: Vec<String>,
+}
+
+impl PromotionPolicy {
+    /// Channel a version must already be in before it can be promoted to `channel`
+    pub fn preceding(&self, channel: &str) -> Option<&str> {
+        let position = self.channels.iter().position(|c| c == channel)?;
+        position
+            .checked_sub(1)
+            .map(|previous| self.channels[previous].as_str())
+    }
+
+    fn check_known(&self, channel: &str) -> Result<(), DomainError> {
+        if self.channels.is_empty() || self.channels.iter().any(|c| c == channel) {
+            return Ok(());
+        }
+        Err(DomainError::InvalidPromotion(format!(
+            "Unknown channel {channel}; channels are {}",
+            self.channels.join(", ")
+        )))
+    }
+}
+
+impl FromStr for PromotionPolicy {
+    type Err = ValidationError;
+
+    /// Parses comma-separated channels in promotion order, such as
+    /// `dev,staging,prod`; an empty string allows any channel
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut channels: Vec<String> = Vec::new();
+        for channel in s.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            validate_channel(channel)?;
+            if channels.iter().any(|c| c == channel) {
+                return Err(ValidationError::InvalidConfig(format!(
+                    "Channel {channel} is listed more than once"
+                )));
+            }
+            channels.push(channel.to_string());
+        }
+        Ok(Self { channels })
+    }
+}
+
+/// Review of the head version
+#[derive(Debug, Clone)]
+pub struct Review {
+    pub version: Version,
+    pub required_approvals: u32,
+    pub approved_by: Vec<String>,
+    pub status: ReviewStatus,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct Spec {
@@ -15,8 +126,39 @@ pub struct Spec {
     pub name: SpecName,
     pub content: SpecContent,
     pub description: Option<String>,
-    pub version: Version,
+    /// Latest revision of the content, which may not be published yet
+    pub head_version: Version,
+    /// Semantic version of the head revision
+    pub semver: semver::Version,
+    /// Revision consumers see; pinned until a newer revision is published
+    pub published_version: Option<Version>,
     pub state: SpecState,
+    /// State the spec was in before it was deleted, used by restore
+    pub state_before_deletion: Option<SpecState>,
+    /// Every version of the content, used to revert to an earlier one
+    pub revisions: BTreeMap<Version, SpecRevision>,
+    /// Transitions waiting to be run by the scheduler, by schedule id
+    pub pending_schedules: BTreeMap<Uuid, SpecTransitionScheduled>,
+    /// Review of the head version; cleared whenever the head changes
+    pub review: Option<Review>,
+    /// Approval rules applied when reviewing and publishing
+    pub review_policy: ReviewPolicy,
+    /// Validators new content runs through, chosen by the spec's labels
+    pub validators: ValidatorPipeline,
+    /// Size and naming limits, chosen by the spec's labels
+    pub validation_policy: ValidationPolicy,
+    /// Labels do not change the content, so they never bump the version
+    pub labels: BTreeMap<String, String>,
+    /// Schema the content is validated against on update and publish
+    pub schema: Option<SchemaRef>,
+    /// Specs this one relies on, which must be published before it is
+    pub dependencies: Vec<SpecDependency>,
+    /// Review comments by id; they never bump the version
+    pub comments: BTreeMap<Uuid, SpecComment>,
+    /// Versions each channel has been pointed at, the one it serves last
+    pub channels: BTreeMap<String, Vec<Version>>,
+    /// Channel names and order applied when promoting
+    pub promotion_policy: PromotionPolicy,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: String,
@@ -29,25 +171,105 @@ impl Spec {
             SpecCommand::Create(_) => Err(DomainError::DuplicateSpecName(self.name.to_string())),
             SpecCommand::Update(cmd) => self.handle_update(cmd),
             SpecCommand::Publish(cmd) => self.handle_publish(cmd),
+            SpecCommand::Republish(cmd) => self.handle_republish(cmd),
             SpecCommand::Deprecate(cmd) => self.handle_deprecate(cmd),
             SpecCommand::Delete(cmd) => self.handle_delete(cmd),
+            SpecCommand::Restore(cmd) => self.handle_restore(cmd),
+            SpecCommand::Revert(cmd) => self.handle_revert(cmd),
+            SpecCommand::Rename(cmd) => self.handle_rename(cmd),
+            SpecCommand::ScheduleTransition(cmd) => self.handle_schedule_transition(cmd),
+            SpecCommand::CancelSchedule(cmd) => self.handle_cancel_schedule(cmd),
+            SpecCommand::ExecuteSchedule(cmd) => self.handle_execute_schedule(&cmd),
+            SpecCommand::FailSchedule(cmd) => self.handle_fail_schedule(cmd),
+            SpecCommand::FailSunset(cmd) => self.handle_fail_sunset(cmd),
+            SpecCommand::RequestReview(cmd) => self.handle_request_review(cmd),
+            SpecCommand::Approve(cmd) => self.handle_approve(cmd),
+            SpecCommand::Reject(cmd) => self.handle_reject(cmd),
+            SpecCommand::WithdrawReview(cmd) => self.handle_withdraw_review(cmd),
+            SpecCommand::AddLabels(cmd) => self.handle_add_labels(cmd),
+            SpecCommand::RemoveLabels(cmd) => self.handle_remove_labels(cmd),
+            SpecCommand::SetDependencies(cmd) => self.handle_set_dependencies(cmd),
+            SpecCommand::AddComment(cmd) => self.handle_add_comment(cmd),
+            SpecCommand::EditComment(cmd) => self.handle_edit_comment(cmd),
+            SpecCommand::ResolveComment(cmd) => self.handle_resolve_comment(cmd),
+            SpecCommand::Promote(cmd) => self.handle_promote(cmd),
+            SpecCommand::Demote(cmd) => self.handle_demote(cmd),
         }
     }
 
-    pub fn create(command: CreateSpec) -> Result<Vec<SpecEvent>, DomainError> {
+    /// Create a spec whose name and content must satisfy `policy` and whose
+    /// content must pass `validators`
+    pub fn create(
+        command: CreateSpec,
+        policy: &ValidationPolicy,
+        validators: &ValidatorPipeline,
+    ) -> Result<Vec<SpecEvent>, DomainError> {
         let spec_id = Uuid::new_v4();
-        let name = SpecName::new(command.name)?;
-        let content = SpecContent::new(command.content)?;
+        let name = SpecName::new(command.name, policy)?;
+        let (content, warnings) = SpecContent::new(command.content, policy, validators)?;
+        let labels = if command.labels.is_empty() {
+            BTreeMap::new()
+        } else {
+            Labels::new(command.labels)?.into_inner()
+        };
         let now = Utc::now();
 
-        Ok(vec![SpecEvent::Created(SpecCreated {
+        let mut events = vec![SpecEvent::Created(SpecCreated {
             spec_id,
             name: name.as_str().to_string(),
             content: content.as_str().to_string(),
             description: command.description,
-            created_by: command.created_by,
+            schema: command.schema,
+            template: command.template,
+            warnings,
+            created_by: command.created_by.clone(),
             created_at: now,
-        })])
+        })];
+
+        if !labels.is_empty() {
+            events.push(SpecEvent::LabelsAdded(SpecLabelsAdded {
+                spec_id,
+                labels,
+                labeled_by: command.created_by,
+                labeled_at: now,
+            }));
+        }
+
+        Ok(events)
+    }
+
+    /// Apply `policy` when handling review and publish commands
+    #[must_use]
+    pub const fn with_review_policy(mut self, policy: ReviewPolicy) -> Self {
+        self.review_policy = policy;
+        self
+    }
+
+    /// Apply `policy` when handling promotions
+    #[must_use]
+    pub fn with_promotion_policy(mut self, policy: PromotionPolicy) -> Self {
+        self.promotion_policy = policy;
+        self
+    }
+
+    /// Run new content through `validators` when handling updates
+    #[must_use]
+    pub fn with_validators(mut self, validators: ValidatorPipeline) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    /// Check renames and new content against `policy`
+    #[must_use]
+    pub fn with_validation_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.validation_policy = policy;
+        self
+    }
+
+    /// Whether the head holds edits that have not been published yet
+    pub fn has_pending_revision(&self) -> bool {
+        self.published_version
+            .is_some_and(|published| published != self.head_version)
     }
 
     fn handle_update(&self, command: UpdateSpec) -> Result<Vec<SpecEvent>, DomainError> {
@@ -55,14 +277,23 @@ impl Spec {
             return Err(DomainError::InvalidStateForOperation(self.state));
         }
 
-        let content = SpecContent::new(command.content)?;
+        // Edits to a published spec become a new head revision; the published
+        // version stays pinned until that revision is published explicitly
+        let (content, warnings) =
+            SpecContent::new(command.content, &self.validation_policy, &self.validators)?;
+        let bump = command
+            .bump
+            .unwrap_or_else(|| self.content.infer_bump(&content));
         let now = Utc::now();
 
         Ok(vec![SpecEvent::Updated(SpecUpdated {
             spec_id: self.id,
-            version: self.version.increment().as_u32(),
+            version: self.head_version.increment().as_u32(),
+            semver: bump.apply(&self.semver),
             content: content.as_str().to_string(),
             description: command.description,
+            schema: command.schema.or(self.schema),
+            warnings,
             updated_by: command.updated_by,
             updated_at: now,
         })])
@@ -70,27 +301,72 @@ impl Spec {
 
     fn handle_publish(&self, command: PublishSpec) -> Result<Vec<SpecEvent>, DomainError> {
         if let Some(version) = command.version {
-            if version != self.version.as_u32() {
+            if version != self.head_version.as_u32() {
                 return Err(DomainError::VersionMismatch {
-                    expected: self.version.as_u32(),
+                    expected: self.head_version.as_u32(),
                     actual: version,
                 });
             }
         }
 
-        if self.state != SpecState::Draft {
+        // A published spec can only be published again to release a pending revision
+        let can_publish = match self.state {
+            SpecState::Draft => true,
+            SpecState::Published => self.has_pending_revision(),
+            SpecState::Deprecated | SpecState::Deleted => false,
+        };
+
+        if !can_publish {
+            return Err(DomainError::InvalidStateTransition {
+                from: self.state,
+                to: SpecState::Published,
+            });
+        }
+
+        let approved = match &self.review {
+            Some(review) => review.status == ReviewStatus::Approved,
+            None => self.review_policy.required_approvals == 0,
+        };
+
+        if !approved {
+            return Err(DomainError::ReviewRequired(self.head_version.as_u32()));
+        }
+
+        Ok(vec![SpecEvent::StateChanged(SpecStateChanged {
+            spec_id: self.id,
+            version: self.head_version.as_u32(),
+            from_state: self.state,
+            to_state: SpecState::Published,
+            reason: None,
+            successor_id: None,
+            sunset_at: None,
+            changed_by: command.published_by,
+            changed_at: Utc::now(),
+        })])
+    }
+
+    /// Publish an earlier version again, leaving the later ones as pending
+    /// revisions; no review is needed, as the version was released before
+    fn handle_republish(&self, command: RepublishSpec) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state != SpecState::Published {
             return Err(DomainError::InvalidStateTransition {
                 from: self.state,
                 to: SpecState::Published,
             });
         }
 
+        if command.version == 0 || command.version > self.head_version.as_u32() {
+            return Err(DomainError::VersionNotFound(command.version));
+        }
+
         Ok(vec![SpecEvent::StateChanged(SpecStateChanged {
             spec_id: self.id,
-            version: self.version.as_u32(),
+            version: command.version,
             from_state: self.state,
             to_state: SpecState::Published,
             reason: None,
+            successor_id: None,
+            sunset_at: None,
             changed_by: command.published_by,
             changed_at: Utc::now(),
         })])
@@ -104,14 +380,30 @@ impl Spec {
             });
         }
 
+        if command.successor_id == Some(self.id) {
+            return Err(DomainError::InvalidDeprecation(
+                "A spec cannot be its own successor".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+
+        if command.sunset_at.is_some_and(|sunset| sunset <= now) {
+            return Err(DomainError::InvalidDeprecation(
+                "Sunset must be in the future".to_string(),
+            ));
+        }
+
         Ok(vec![SpecEvent::StateChanged(SpecStateChanged {
             spec_id: self.id,
-            version: self.version.as_u32(),
+            version: self.head_version.as_u32(),
             from_state: self.state,
             to_state: SpecState::Deprecated,
             reason: Some(command.reason),
+            successor_id: command.successor_id,
+            sunset_at: command.sunset_at,
             changed_by: command.deprecated_by,
-            changed_at: Utc::now(),
+            changed_at: now,
         })])
     }
 
@@ -122,34 +414,793 @@ impl Spec {
 
         Ok(vec![SpecEvent::StateChanged(SpecStateChanged {
             spec_id: self.id,
-            version: self.version.as_u32(),
+            version: self.head_version.as_u32(),
             from_state: self.state,
             to_state: SpecState::Deleted,
             reason: None,
+            successor_id: None,
+            sunset_at: None,
             changed_by: command.deleted_by,
             changed_at: Utc::now(),
         })])
     }
 
+    fn handle_restore(&self, command: RestoreSpec) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state != SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        Ok(vec![SpecEvent::Restored(SpecRestored {
+            spec_id: self.id,
+            name: self.name.to_string(),
+            version: self.head_version.as_u32(),
+            to_state: self.state_before_deletion.unwrap_or_default(),
+            restored_by: command.restored_by,
+            restored_at: Utc::now(),
+        })])
+    }
+
+    fn handle_revert(&self, command: RevertSpec) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        if command.to_version == self.head_version.as_u32() {
+            return Err(DomainError::InvalidRevertTarget(command.to_version));
+        }
+
+        let revision = self
+            .revisions
+            .get(&Version::new(command.to_version))
+            .ok_or(DomainError::VersionNotFound(command.to_version))?;
+
+        // Like an update, the reverted content becomes a new head revision
+        let bump = self.content.infer_bump(&revision.content);
+
+        Ok(vec![SpecEvent::Reverted(SpecReverted {
+            spec_id: self.id,
+            version: self.head_version.increment().as_u32(),
+            semver: bump.apply(&self.semver),
+            reverted_from: command.to_version,
+            content: revision.content.as_str().to_string(),
+            description: revision.description.clone(),
+            reason: command.reason,
+            reverted_by: command.reverted_by,
+            reverted_at: Utc::now(),
+        })])
+    }
+
+    fn handle_rename(&self, command: RenameSpec) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        let new_name = SpecName::new(command.new_name, &self.validation_policy)?;
+        if new_name == self.name {
+            return Err(DomainError::DuplicateSpecName(new_name.to_string()));
+        }
+
+        Ok(vec![SpecEvent::Renamed(SpecRenamed {
+            spec_id: self.id,
+            old_name: self.name.to_string(),
+            new_name: new_name.to_string(),
+            renamed_by: command.renamed_by,
+            renamed_at: Utc::now(),
+        })])
+    }
+
+    fn handle_add_labels(&self, command: AddLabels) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        let mut labels = Labels::new(command.labels)?.into_inner();
+        labels.retain(|key, value| self.labels.get(key) != Some(value));
+        if labels.is_empty() {
+            return Err(DomainError::InvalidLabels(
+                "Labels are already set".to_string(),
+            ));
+        }
+
+        Ok(vec![SpecEvent::LabelsAdded(SpecLabelsAdded {
+            spec_id: self.id,
+            labels,
+            labeled_by: command.labeled_by,
+            labeled_at: Utc::now(),
+        })])
+    }
+
+    fn handle_remove_labels(&self, command: RemoveLabels) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        if command.keys.is_empty() {
+            return Err(DomainError::InvalidLabels(
+                "At least one key is required".to_string(),
+            ));
+        }
+
+        for key in &command.keys {
+            validate_label_key(key)?;
+            if !self.labels.contains_key(key) {
+                return Err(DomainError::InvalidLabels(format!(
+                    "Spec has no label {key:?}"
+                )));
+            }
+        }
+
+        let mut keys = command.keys;
+        keys.sort();
+        keys.dedup();
+
+        Ok(vec![SpecEvent::LabelsRemoved(SpecLabelsRemoved {
+            spec_id: self.id,
+            keys,
+            unlabeled_by: command.unlabeled_by,
+            unlabeled_at: Utc::now(),
+        })])
+    }
+
+    fn handle_set_dependencies(
+        &self,
+        command: SetDependencies,
+    ) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        let mut dependencies = command.dependencies;
+        dependencies.sort_by_key(|dependency| dependency.spec_id);
+
+        if dependencies
+            .iter()
+            .any(|dependency| dependency.spec_id == self.id)
+        {
+            return Err(DomainError::InvalidDependencies(
+                "A spec cannot depend on itself".to_string(),
+            ));
+        }
+
+        if let Some(pair) = dependencies
+            .windows(2)
+            .find(|pair| pair[0].spec_id == pair[1].spec_id)
+        {
+            return Err(DomainError::InvalidDependencies(format!(
+                "Spec {} is listed more than once",
+                pair[0].spec_id
+            )));
+        }
+
+        if dependencies == self.dependencies {
+            return Err(DomainError::InvalidDependencies(
+                "Dependencies are unchanged".to_string(),
+            ));
+        }
+
+        Ok(vec![SpecEvent::DependenciesSet(SpecDependenciesSet {
+            spec_id: self.id,
+            dependencies,
+            set_by: command.set_by,
+            set_at: Utc::now(),
+        })])
+    }
+
+    fn handle_add_comment(&self, command: AddComment) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        if command.body.trim().is_empty() {
+            return Err(DomainError::InvalidComment(
+                "Comment cannot be empty".to_string(),
+            ));
+        }
+
+        let (version, parent_id) = if let Some(parent_id) = command.parent_id {
+            let parent = self.comment(parent_id)?;
+            // Replies always belong to the thread's first comment
+            let thread_id = parent.parent_id.unwrap_or(parent_id);
+            let thread = self.comment(thread_id)?;
+
+            if thread.resolved {
+                return Err(DomainError::InvalidComment(
+                    "Thread is resolved".to_string(),
+                ));
+            }
+            if command.anchor.is_some() {
+                return Err(DomainError::InvalidComment(
+                    "Replies cannot be anchored".to_string(),
+                ));
+            }
+            if command
+                .version
+                .is_some_and(|v| Version::new(v) != thread.version)
+            {
+                return Err(DomainError::InvalidComment(format!(
+                    "Thread is on version {}",
+                    thread.version
+                )));
+            }
+
+            (thread.version, Some(thread_id))
+        } else {
+            let version = command.version.map_or(self.head_version, Version::new);
+            let revision = self
+                .revisions
+                .get(&version)
+                .ok_or_else(|| DomainError::VersionNotFound(version.as_u32()))?;
+
+            if let Some(anchor) = &command.anchor {
+                anchor.validate(&revision.content)?;
+            }
+
+            (version, None)
+        };
+
+        Ok(vec![SpecEvent::CommentAdded(SpecCommentAdded {
+            spec_id: self.id,
+            comment_id: Uuid::new_v4(),
+            version: version.as_u32(),
+            anchor: command.anchor,
+            parent_id,
+            body: command.body,
+            author: command.author,
+            added_at: Utc::now(),
+        })])
+    }
+
+    fn handle_edit_comment(&self, command: EditComment) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        let comment = self.comment(command.comment_id)?;
+        if comment.author != command.edited_by {
+            return Err(DomainError::NotCommentAuthor {
+                comment_id: command.comment_id,
+                author: comment.author.clone(),
+            });
+        }
+
+        if command.body.trim().is_empty() {
+            return Err(DomainError::InvalidComment(
+                "Comment cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(vec![SpecEvent::CommentEdited(SpecCommentEdited {
+            spec_id: self.id,
+            comment_id: command.comment_id,
+            body: command.body,
+            edited_by: command.edited_by,
+            edited_at: Utc::now(),
+        })])
+    }
+
+    fn handle_resolve_comment(
+        &self,
+        command: ResolveComment,
+    ) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        let comment = self.comment(command.comment_id)?;
+        if comment.parent_id.is_some() {
+            return Err(DomainError::InvalidComment(
+                "Only a thread's first comment can be resolved".to_string(),
+            ));
+        }
+        if comment.resolved {
+            return Err(DomainError::InvalidComment(
+                "Thread is already resolved".to_string(),
+            ));
+        }
+
+        Ok(vec![SpecEvent::CommentResolved(SpecCommentResolved {
+            spec_id: self.id,
+            comment_id: command.comment_id,
+            resolved_by: command.resolved_by,
+            resolved_at: Utc::now(),
+        })])
+    }
+
+    fn comment(&self, comment_id: Uuid) -> Result<&SpecComment, DomainError> {
+        self.comments
+            .get(&comment_id)
+            .ok_or(DomainError::CommentNotFound(comment_id))
+    }
+
+    /// Version `channel` serves, if the spec has been promoted to it
+    pub fn channel_version(&self, channel: &str) -> Option<Version> {
+        self.channels
+            .get(channel)
+            .and_then(|versions| versions.last().copied())
+    }
+
+    fn handle_promote(&self, command: PromoteSpec) -> Result<Vec<SpecEvent>, DomainError> {
+        if matches!(self.state, SpecState::Deprecated | SpecState::Deleted) {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        validate_channel(&command.channel)?;
+        self.promotion_policy.check_known(&command.channel)?;
+
+        let version = Version::new(command.version);
+        let revision = self
+            .revisions
+            .get(&version)
+            .ok_or(DomainError::VersionNotFound(command.version))?;
+
+        // Channels are read by consumers, so only versions that passed review
+        // or were released can be put in one
+        let approved = self.review.as_ref().is_some_and(|review| {
+            review.version == version && review.status == ReviewStatus::Approved
+        });
+        if !revision.published && !approved {
+            return Err(DomainError::InvalidPromotion(format!(
+                "Version {version} must be published or approved before it can be promoted"
+            )));
+        }
+
+        let previous_version = self.channel_version(&command.channel);
+        if previous_version == Some(version) {
+            return Err(DomainError::InvalidPromotion(format!(
+                "Channel {} already serves version {version}",
+                command.channel
+            )));
+        }
+
+        if let Some(preceding) = self.promotion_policy.preceding(&command.channel) {
+            if self.channel_version(preceding) != Some(version) {
+                return Err(DomainError::InvalidPromotion(format!(
+                    "Version {version} must be in {preceding} before it can be promoted to {}",
+                    command.channel
+                )));
+            }
+        }
+
+        Ok(vec![SpecEvent::Promoted(SpecPromoted {
+            spec_id: self.id,
+            channel: command.channel,
+            version: command.version,
+            previous_version: previous_version.map(Version::as_u32),
+            promoted_by: command.promoted_by,
+            promoted_at: Utc::now(),
+        })])
+    }
+
+    fn handle_demote(&self, command: DemoteSpec) -> Result<Vec<SpecEvent>, DomainError> {
+        let Some((version, earlier)) = self
+            .channels
+            .get(&command.channel)
+            .and_then(|versions| versions.split_last())
+        else {
+            return Err(DomainError::InvalidPromotion(format!(
+                "Spec is not in channel {}",
+                command.channel
+            )));
+        };
+
+        Ok(vec![SpecEvent::Demoted(SpecDemoted {
+            spec_id: self.id,
+            channel: command.channel,
+            version: version.as_u32(),
+            restored_version: earlier.last().map(|v| v.as_u32()),
+            demoted_by: command.demoted_by,
+            demoted_at: Utc::now(),
+        })])
+    }
+
+    /// Semantic version of the revision consumers see, if one is published
+    pub fn published_semver(&self) -> Option<&semver::Version> {
+        self.published_version
+            .and_then(|version| self.revisions.get(&version))
+            .map(|revision| &revision.semver)
+    }
+
+    fn handle_schedule_transition(
+        &self,
+        command: ScheduleTransition,
+    ) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == SpecState::Deleted {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        let now = Utc::now();
+
+        if command.execute_at <= now {
+            return Err(DomainError::InvalidSchedule(
+                "Execution time must be in the future".to_string(),
+            ));
+        }
+
+        // Catch mistakes now rather than when the schedule fires unattended
+        if let ScheduledTransition::Deprecate {
+            successor_id,
+            sunset_at,
+            ..
+        } = &command.transition
+        {
+            if *successor_id == Some(self.id) {
+                return Err(DomainError::InvalidDeprecation(
+                    "A spec cannot be its own successor".to_string(),
+                ));
+            }
+            if sunset_at.is_some_and(|sunset| sunset <= command.execute_at) {
+                return Err(DomainError::InvalidDeprecation(
+                    "Sunset must be after the scheduled deprecation".to_string(),
+                ));
+            }
+        }
+
+        Ok(vec![SpecEvent::TransitionScheduled(
+            SpecTransitionScheduled {
+                spec_id: self.id,
+                schedule_id: Uuid::new_v4(),
+                transition: command.transition,
+                execute_at: command.execute_at,
+                scheduled_by: command.scheduled_by,
+                scheduled_at: now,
+            },
+        )])
+    }
+
+    fn handle_cancel_schedule(
+        &self,
+        command: CancelSchedule,
+    ) -> Result<Vec<SpecEvent>, DomainError> {
+        if !self.pending_schedules.contains_key(&command.schedule_id) {
+            return Err(DomainError::ScheduleNotFound(command.schedule_id));
+        }
+
+        Ok(vec![SpecEvent::ScheduleCancelled(SpecScheduleCancelled {
+            spec_id: self.id,
+            schedule_id: command.schedule_id,
+            cancelled_by: command.cancelled_by,
+            cancelled_at: Utc::now(),
+        })])
+    }
+
+    fn handle_execute_schedule(
+        &self,
+        command: &ExecuteSchedule,
+    ) -> Result<Vec<SpecEvent>, DomainError> {
+        let schedule = self
+            .pending_schedules
+            .get(&command.schedule_id)
+            .ok_or(DomainError::ScheduleNotFound(command.schedule_id))?;
+
+        let now = Utc::now();
+
+        if schedule.execute_at > now {
+            return Err(DomainError::InvalidSchedule(format!(
+                "Schedule {} is not due until {}",
+                schedule.schedule_id, schedule.execute_at
+            )));
+        }
+
+        // Run the real command on behalf of whoever scheduled it
+        let result = match &schedule.transition {
+            ScheduledTransition::Publish { version } => self.handle_publish(PublishSpec {
+                spec_id: self.id,
+                version: *version,
+                published_by: schedule.scheduled_by.clone(),
+            }),
+            ScheduledTransition::Deprecate {
+                reason,
+                successor_id,
+                sunset_at,
+            } => self.handle_deprecate(DeprecateSpec {
+                spec_id: self.id,
+                reason: reason.clone(),
+                successor_id: *successor_id,
+                sunset_at: *sunset_at,
+                deprecated_by: schedule.scheduled_by.clone(),
+            }),
+        };
+
+        // A rejected transition still completes the schedule, recording why
+        let (mut events, failure) = match result {
+            Ok(events) => (events, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+
+        events.push(SpecEvent::ScheduleExecuted(SpecScheduleExecuted {
+            spec_id: self.id,
+            schedule_id: schedule.schedule_id,
+            failure,
+            executed_at: now,
+        }));
+
+        Ok(events)
+    }
+
+    fn handle_fail_schedule(&self, command: FailSchedule) -> Result<Vec<SpecEvent>, DomainError> {
+        if !self.pending_schedules.contains_key(&command.schedule_id) {
+            return Err(DomainError::ScheduleNotFound(command.schedule_id));
+        }
+
+        Ok(vec![SpecEvent::ScheduleExecuted(SpecScheduleExecuted {
+            spec_id: self.id,
+            schedule_id: command.schedule_id,
+            failure: Some(command.failure),
+            executed_at: Utc::now(),
+        })])
+    }
+
+    fn handle_fail_sunset(&self, command: FailSunset) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state != SpecState::Deprecated {
+            return Err(DomainError::InvalidStateForOperation(self.state));
+        }
+
+        Ok(vec![SpecEvent::SunsetFailed(SpecSunsetFailed {
+            spec_id: self.id,
+            failure: command.failure,
+            failed_at: Utc::now(),
+        })])
+    }
+
+    fn handle_request_review(&self, command: RequestReview) -> Result<Vec<SpecEvent>, DomainError> {
+        match self.state {
+            SpecState::Draft => {}
+            SpecState::Published if self.has_pending_revision() => {}
+            SpecState::Published => {
+                return Err(DomainError::InvalidReview(format!(
+                    "Version {} is already published",
+                    self.head_version
+                )))
+            }
+            SpecState::Deprecated | SpecState::Deleted => {
+                return Err(DomainError::InvalidStateForOperation(self.state))
+            }
+        }
+
+        // A rejected version may be put up for review again
+        if self
+            .review
+            .as_ref()
+            .is_some_and(|review| review.status != ReviewStatus::Rejected)
+        {
+            return Err(DomainError::InvalidReview(format!(
+                "Version {} is already under review",
+                self.head_version
+            )));
+        }
+
+        Ok(vec![SpecEvent::ReviewRequested(SpecReviewRequested {
+            spec_id: self.id,
+            version: self.head_version.as_u32(),
+            required_approvals: self.review_policy.required_approvals.max(1),
+            requested_by: command.requested_by,
+            requested_at: Utc::now(),
+        })])
+    }
+
+    fn handle_approve(&self, command: ApproveSpec) -> Result<Vec<SpecEvent>, DomainError> {
+        let review = self.pending_review()?;
+
+        // Four-eyes rule: whoever made the change cannot sign it off
+        if command.approved_by == self.updated_by {
+            return Err(DomainError::SelfApproval(command.approved_by));
+        }
+
+        if review.approved_by.contains(&command.approved_by) {
+            return Err(DomainError::InvalidReview(format!(
+                "{} has already approved version {}",
+                command.approved_by, review.version
+            )));
+        }
+
+        Ok(vec![SpecEvent::ReviewApproved(SpecReviewApproved {
+            spec_id: self.id,
+            version: review.version.as_u32(),
+            comment: command.comment,
+            approved_by: command.approved_by,
+            approved_at: Utc::now(),
+        })])
+    }
+
+    fn handle_reject(&self, command: RejectSpec) -> Result<Vec<SpecEvent>, DomainError> {
+        let review = self.pending_review()?;
+
+        Ok(vec![SpecEvent::ReviewRejected(SpecReviewRejected {
+            spec_id: self.id,
+            version: review.version.as_u32(),
+            reason: command.reason,
+            rejected_by: command.rejected_by,
+            rejected_at: Utc::now(),
+        })])
+    }
+
+    fn handle_withdraw_review(
+        &self,
+        command: WithdrawReview,
+    ) -> Result<Vec<SpecEvent>, DomainError> {
+        let review = self
+            .review
+            .as_ref()
+            .ok_or_else(|| DomainError::InvalidReview("No review to withdraw".to_string()))?;
+
+        Ok(vec![SpecEvent::ReviewWithdrawn(SpecReviewWithdrawn {
+            spec_id: self.id,
+            version: review.version.as_u32(),
+            withdrawn_by: command.withdrawn_by,
+            withdrawn_at: Utc::now(),
+        })])
+    }
+
+    fn pending_review(&self) -> Result<&Review, DomainError> {
+        self.review
+            .as_ref()
+            .filter(|review| review.status == ReviewStatus::Pending)
+            .ok_or_else(|| DomainError::InvalidReview("No review is pending".to_string()))
+    }
+
+    fn record_revision(&mut self) {
+        self.revisions.insert(
+            self.head_version,
+            SpecRevision {
+                content: self.content.clone(),
+                semver: self.semver.clone(),
+                description: self.description.clone(),
+                schema: self.schema,
+                published: false,
+            },
+        );
+    }
+
     #[must_use]
+    #[allow(clippy::too_many_lines)]
     pub fn apply_event(mut self, event: &SpecEvent) -> Self {
         match event {
             SpecEvent::Created(_e) => {
                 panic!("Cannot apply Created event to existing spec");
             }
             SpecEvent::Updated(e) => {
-                self.content = SpecContent::new(e.content.clone()).unwrap();
+                self.content = SpecContent::recorded(e.content.clone());
                 if let Some(desc) = &e.description {
                     self.description = Some(desc.clone());
                 }
-                self.version = Version::new(e.version);
+                self.head_version = Version::new(e.version);
+                self.semver = e.semver.clone();
+                if e.schema.is_some() {
+                    self.schema = e.schema;
+                }
                 self.updated_by.clone_from(&e.updated_by);
                 self.updated_at = e.updated_at;
+                self.review = None;
+                self.record_revision();
+            }
+            SpecEvent::Reverted(e) => {
+                self.content = SpecContent::recorded(e.content.clone());
+                self.description.clone_from(&e.description);
+                self.head_version = Version::new(e.version);
+                self.semver = e.semver.clone();
+                self.updated_by.clone_from(&e.reverted_by);
+                self.updated_at = e.reverted_at;
+                self.review = None;
+                self.record_revision();
             }
             SpecEvent::StateChanged(e) => {
+                if e.to_state == SpecState::Published {
+                    self.published_version = Some(Version::new(e.version));
+                    if let Some(revision) = self.revisions.get_mut(&Version::new(e.version)) {
+                        revision.published = true;
+                    }
+                }
+                if e.to_state == SpecState::Deleted {
+                    self.state_before_deletion = Some(e.from_state);
+                }
                 self.state = e.to_state;
                 self.updated_at = e.changed_at;
             }
+            SpecEvent::Renamed(e) => {
+                self.name = SpecName::recorded(e.new_name.clone());
+                self.updated_at = e.renamed_at;
+            }
+            SpecEvent::Restored(e) => {
+                self.state = e.to_state;
+                self.state_before_deletion = None;
+                self.updated_at = e.restored_at;
+            }
+            SpecEvent::TransitionScheduled(e) => {
+                self.pending_schedules.insert(e.schedule_id, e.clone());
+            }
+            SpecEvent::ScheduleCancelled(e) => {
+                self.pending_schedules.remove(&e.schedule_id);
+            }
+            SpecEvent::ScheduleExecuted(e) => {
+                self.pending_schedules.remove(&e.schedule_id);
+            }
+            SpecEvent::ReviewRequested(e) => {
+                self.review = Some(Review {
+                    version: Version::new(e.version),
+                    required_approvals: e.required_approvals,
+                    approved_by: Vec::new(),
+                    status: ReviewStatus::Pending,
+                });
+            }
+            SpecEvent::ReviewApproved(e) => {
+                if let Some(review) = self.review.as_mut() {
+                    review.approved_by.push(e.approved_by.clone());
+                    if review.approved_by.len() >= review.required_approvals as usize {
+                        review.status = ReviewStatus::Approved;
+                    }
+                }
+            }
+            SpecEvent::ReviewRejected(_) => {
+                if let Some(review) = self.review.as_mut() {
+                    review.status = ReviewStatus::Rejected;
+                }
+            }
+            SpecEvent::ReviewWithdrawn(_) => {
+                self.review = None;
+            }
+            SpecEvent::LabelsAdded(e) => {
+                self.labels
+                    .extend(e.labels.iter().map(|(k, v)| (k.clone(), v.clone())));
+                self.updated_at = e.labeled_at;
+            }
+            SpecEvent::LabelsRemoved(e) => {
+                for key in &e.keys {
+                    self.labels.remove(key);
+                }
+                self.updated_at = e.unlabeled_at;
+            }
+            SpecEvent::DependenciesSet(e) => {
+                self.dependencies.clone_from(&e.dependencies);
+                self.updated_at = e.set_at;
+            }
+            // Comments leave the content, version and modification time alone
+            SpecEvent::CommentAdded(e) => {
+                self.comments.insert(
+                    e.comment_id,
+                    SpecComment {
+                        version: Version::new(e.version),
+                        parent_id: e.parent_id,
+                        author: e.author.clone(),
+                        resolved: false,
+                    },
+                );
+            }
+            SpecEvent::CommentResolved(e) => {
+                if let Some(comment) = self.comments.get_mut(&e.comment_id) {
+                    comment.resolved = true;
+                }
+            }
+            SpecEvent::Promoted(e) => {
+                self.channels
+                    .entry(e.channel.clone())
+                    .or_default()
+                    .push(Version::new(e.version));
+                self.updated_at = e.promoted_at;
+            }
+            SpecEvent::Demoted(e) => {
+                if let Some(versions) = self.channels.get_mut(&e.channel) {
+                    versions.pop();
+                    if versions.is_empty() {
+                        self.channels.remove(&e.channel);
+                    }
+                }
+                self.updated_at = e.demoted_at;
+            }
+            SpecEvent::SunsetFailed(e) => {
+                self.updated_at = e.failed_at;
+            }
+            // Edits do not affect later comments, and schema, template,
+            // release and tenant events are only ever recorded in their own streams
+            SpecEvent::CommentEdited(_)
+            | SpecEvent::SchemaRegistered(_)
+            | SpecEvent::SchemaUpdated(_)
+            | SpecEvent::TemplateRegistered(_)
+            | SpecEvent::TemplateUpdated(_)
+            | SpecEvent::ReleaseCreated(_)
+            | SpecEvent::ReleasePublished(_)
+            | SpecEvent::ReleaseRolledBack(_)
+            | SpecEvent::TenantCreated(_)
+            | SpecEvent::TenantSuspended(_) => {}
         }
         self
     }
@@ -164,11 +1215,26 @@ impl Spec {
         let mut spec = match first_event {
             SpecEvent::Created(e) => Self {
                 id: e.spec_id,
-                name: SpecName::new(e.name)?,
-                content: SpecContent::new(e.content)?,
+                name: SpecName::recorded(e.name),
+                content: SpecContent::recorded(e.content),
                 description: e.description,
-                version: Version::initial(),
+                head_version: Version::initial(),
+                semver: Version::initial().legacy_semver(),
+                published_version: None,
                 state: SpecState::Draft,
+                state_before_deletion: None,
+                revisions: BTreeMap::new(),
+                pending_schedules: BTreeMap::new(),
+                review: None,
+                review_policy: ReviewPolicy::default(),
+                validators: ValidatorPipeline::default(),
+                validation_policy: ValidationPolicy::default(),
+                labels: BTreeMap::new(),
+                schema: e.schema,
+                dependencies: Vec::new(),
+                comments: BTreeMap::new(),
+                channels: BTreeMap::new(),
+                promotion_policy: PromotionPolicy::default(),
                 created_at: e.created_at,
                 updated_at: e.created_at,
                 created_by: e.created_by.clone(),
@@ -181,6 +1247,8 @@ impl Spec {
             }
         };
 
+        spec.record_revision();
+
         for event in events_iter {
             spec = spec.apply_event(&event);
         }
@@ -188,3 +1256,409 @@ impl Spec {
         Ok(spec)
     }
 }
+
+/// A JSON Schema in the registry; every version is kept so specs can pin one
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct Schema {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub head_version: Version,
+    pub versions: BTreeMap<Version, SchemaDocument>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub created_by: String,
+    pub updated_by: String,
+}
+
+impl Schema {
+    pub fn register(command: RegisterSchema) -> Result<Vec<SpecEvent>, DomainError> {
+        // Schema names follow the default rules for spec names, without folders
+        let name = SpecName::single_segment(command.name, &ValidationPolicy::default())?;
+        let schema = SchemaDocument::new(command.schema)?;
+
+        Ok(vec![SpecEvent::SchemaRegistered(SchemaRegistered {
+            schema_id: Uuid::new_v4(),
+            name: name.to_string(),
+            schema: schema.as_value().clone(),
+            description: command.description,
+            registered_by: command.registered_by,
+            registered_at: Utc::now(),
+        })])
+    }
+
+    pub fn handle_update(&self, command: UpdateSchema) -> Result<Vec<SpecEvent>, DomainError> {
+        let schema = SchemaDocument::new(command.schema)?;
+
+        Ok(vec![SpecEvent::SchemaUpdated(SchemaUpdated {
+            schema_id: self.id,
+            version: self.head_version.increment().as_u32(),
+            schema: schema.as_value().clone(),
+            description: command.description,
+            updated_by: command.updated_by,
+            updated_at: Utc::now(),
+        })])
+    }
+
+    /// The document of one version, failing if the schema has no such version
+    pub fn document(&self, version: u32) -> Result<&SchemaDocument, DomainError> {
+        self.versions
+            .get(&Version::new(version))
+            .ok_or(DomainError::SchemaVersionNotFound {
+                schema_id: self.id,
+                version,
+            })
+    }
+
+    #[must_use]
+    pub fn apply_event(mut self, event: &SpecEvent) -> Self {
+        if let SpecEvent::SchemaUpdated(e) = event {
+            self.versions.insert(
+                Version::new(e.version),
+                SchemaDocument::recorded(e.schema.clone()),
+            );
+            if let Some(desc) = &e.description {
+                self.description = Some(desc.clone());
+            }
+            self.head_version = Version::new(e.version);
+            self.updated_by.clone_from(&e.updated_by);
+            self.updated_at = e.updated_at;
+        }
+        self
+    }
+
+    pub fn from_events(events: Vec<SpecEvent>) -> Result<Self, DomainError> {
+        let mut events_iter = events.into_iter();
+
+        let Some(SpecEvent::SchemaRegistered(e)) = events_iter.next() else {
+            return Err(DomainError::EventStoreError(
+                "First event must be SchemaRegistered".to_string(),
+            ));
+        };
+
+        let mut schema = Self {
+            id: e.schema_id,
+            name: e.name,
+            description: e.description,
+            head_version: Version::initial(),
+            versions: BTreeMap::from([(Version::initial(), SchemaDocument::recorded(e.schema))]),
+            created_at: e.registered_at,
+            updated_at: e.registered_at,
+            created_by: e.registered_by.clone(),
+            updated_by: e.registered_by,
+        };
+
+        for event in events_iter {
+            schema = schema.apply_event(&event);
+        }
+
+        Ok(schema)
+    }
+}
+
+/// A parameterized spec body; every version is kept so derived specs can be
+/// traced back to the one they were rendered from
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct Template {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub head_version: Version,
+    pub versions: BTreeMap<Version, TemplateDocument>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub created_by: String,
+    pub updated_by: String,
+}
+
+impl Template {
+    pub fn register(command: RegisterTemplate) -> Result<Vec<SpecEvent>, DomainError> {
+        // Template names follow the default rules for spec names, without folders
+        let name = SpecName::single_segment(command.name, &ValidationPolicy::default())?;
+        let document = TemplateDocument::new(command.body, command.parameters)?;
+
+        Ok(vec![SpecEvent::TemplateRegistered(TemplateRegistered {
+            template_id: Uuid::new_v4(),
+            name: name.to_string(),
+            body: document.body().to_string(),
+            parameters: document.parameters().to_vec(),
+            description: command.description,
+            registered_by: command.registered_by,
+            registered_at: Utc::now(),
+        })])
+    }
+
+    pub fn handle_update(&self, command: UpdateTemplate) -> Result<Vec<SpecEvent>, DomainError> {
+        let document = TemplateDocument::new(command.body, command.parameters)?;
+
+        Ok(vec![SpecEvent::TemplateUpdated(TemplateUpdated {
+            template_id: self.id,
+            version: self.head_version.increment().as_u32(),
+            body: document.body().to_string(),
+            parameters: document.parameters().to_vec(),
+            description: command.description,
+            updated_by: command.updated_by,
+            updated_at: Utc::now(),
+        })])
+    }
+
+    /// The document of one version, failing if the template has no such version
+    pub fn document(&self, version: u32) -> Result<&TemplateDocument, DomainError> {
+        self.versions
+            .get(&Version::new(version))
+            .ok_or(DomainError::TemplateVersionNotFound {
+                template_id: self.id,
+                version,
+            })
+    }
+
+    #[must_use]
+    pub fn apply_event(mut self, event: &SpecEvent) -> Self {
+        if let SpecEvent::TemplateUpdated(e) = event {
+            self.versions.insert(
+                Version::new(e.version),
+                TemplateDocument::recorded(e.body.clone(), e.parameters.clone()),
+            );
+            if let Some(desc) = &e.description {
+                self.description = Some(desc.clone());
+            }
+            self.head_version = Version::new(e.version);
+            self.updated_by.clone_from(&e.updated_by);
+            self.updated_at = e.updated_at;
+        }
+        self
+    }
+
+    pub fn from_events(events: Vec<SpecEvent>) -> Result<Self, DomainError> {
+        let mut events_iter = events.into_iter();
+
+        let Some(SpecEvent::TemplateRegistered(e)) = events_iter.next() else {
+            return Err(DomainError::EventStoreError(
+                "First event must be TemplateRegistered".to_string(),
+            ));
+        };
+
+        let mut template = Self {
+            id: e.template_id,
+            name: e.name,
+            description: e.description,
+            head_version: Version::initial(),
+            versions: BTreeMap::from([(
+                Version::initial(),
+                TemplateDocument::recorded(e.body, e.parameters),
+            )]),
+            created_at: e.registered_at,
+            updated_at: e.registered_at,
+            created_by: e.registered_by.clone(),
+            updated_by: e.registered_by,
+        };
+
+        for event in events_iter {
+            template = template.apply_event(&event);
+        }
+
+        Ok(template)
+    }
+}
+
+/// A named set of spec versions that go live together
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct Release {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub entries: Vec<ReleaseEntry>,
+    pub state: ReleaseState,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub created_by: String,
+    pub updated_by: String,
+}
+
+impl Release {
+    /// Most spec versions one release can pin
+    pub const MAX_ENTRIES: usize = 100;
+
+    pub fn create(command: CreateRelease) -> Result<Vec<SpecEvent>, DomainError> {
+        // Release names follow the default rules for spec names, without folders
+        let name = SpecName::single_segment(command.name, &ValidationPolicy::default())?;
+
+        if command.entries.is_empty() || command.entries.len() > Self::MAX_ENTRIES {
+            return Err(DomainError::InvalidRelease(format!(
+                "A release must pin 1 to {} spec versions",
+                Self::MAX_ENTRIES
+            )));
+        }
+
+        let mut specs = BTreeSet::new();
+        for entry in &command.entries {
+            if !specs.insert(entry.spec_id) {
+                return Err(DomainError::InvalidRelease(format!(
+                    "Spec {} is pinned more than once",
+                    entry.spec_id
+                )));
+            }
+        }
+
+        Ok(vec![SpecEvent::ReleaseCreated(ReleaseCreated {
+            release_id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: command.description,
+            entries: command.entries,
+            created_by: command.created_by,
+            created_at: Utc::now(),
+        })])
+    }
+
+    /// A rolled back release can be published again
+    pub fn handle_publish(&self, command: PublishRelease) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == ReleaseState::Published {
+            return Err(DomainError::InvalidRelease(format!(
+                "Release {} is already published",
+                self.name
+            )));
+        }
+
+        Ok(vec![SpecEvent::ReleasePublished(ReleasePublished {
+            release_id: self.id,
+            published_by: command.published_by,
+            published_at: Utc::now(),
+        })])
+    }
+
+    /// Roll back a published release; `restored_release_id` is the release
+    /// published before it that becomes current again
+    pub fn handle_rollback(
+        &self,
+        command: RollbackRelease,
+        restored_release_id: Option<Uuid>,
+    ) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state != ReleaseState::Published {
+            return Err(DomainError::InvalidRelease(format!(
+                "Release {} is not published",
+                self.name
+            )));
+        }
+
+        Ok(vec![SpecEvent::ReleaseRolledBack(ReleaseRolledBack {
+            release_id: self.id,
+            restored_release_id,
+            rolled_back_by: command.rolled_back_by,
+            rolled_back_at: Utc::now(),
+        })])
+    }
+
+    #[must_use]
+    pub fn apply_event(mut self, event: &SpecEvent) -> Self {
+        match event {
+            SpecEvent::ReleasePublished(e) => {
+                self.state = ReleaseState::Published;
+                self.updated_by.clone_from(&e.published_by);
+                self.updated_at = e.published_at;
+            }
+            SpecEvent::ReleaseRolledBack(e) => {
+                self.state = ReleaseState::RolledBack;
+                self.updated_by.clone_from(&e.rolled_back_by);
+                self.updated_at = e.rolled_back_at;
+            }
+            _ => {}
+        }
+        self
+    }
+
+    pub fn from_events(events: Vec<SpecEvent>) -> Result<Self, DomainError> {
+        let mut events_iter = events.into_iter();
+
+        let Some(SpecEvent::ReleaseCreated(e)) = events_iter.next() else {
+            return Err(DomainError::EventStoreError(
+                "First event must be ReleaseCreated".to_string(),
+            ));
+        };
+
+        let mut release = Self {
+            id: e.release_id,
+            name: e.name,
+            description: e.description,
+            entries: e.entries,
+            state: ReleaseState::Draft,
+            created_at: e.created_at,
+            updated_at: e.created_at,
+            created_by: e.created_by.clone(),
+            updated_by: e.created_by,
+        };
+
+        for event in events_iter {
+            release = release.apply_event(&event);
+        }
+
+        Ok(release)
+    }
+}
+
+/// A tenant whose requests are served until it is suspended
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    pub id: TenantId,
+    pub state: TenantState,
+}
+
+impl Tenant {
+    /// The default tenant exists without being created
+    pub fn create(command: CreateTenant) -> Result<Vec<SpecEvent>, DomainError> {
+        if command.tenant_id.is_default() {
+            return Err(DomainError::DuplicateTenant(command.tenant_id.to_string()));
+        }
+
+        Ok(vec![SpecEvent::TenantCreated(TenantCreated {
+            tenant_id: command.tenant_id,
+            display_name: command.display_name,
+            created_by: command.created_by,
+            created_at: Utc::now(),
+        })])
+    }
+
+    pub fn handle_suspend(&self, command: SuspendTenant) -> Result<Vec<SpecEvent>, DomainError> {
+        if self.state == TenantState::Suspended {
+            return Err(DomainError::TenantSuspended(self.id.to_string()));
+        }
+
+        Ok(vec![SpecEvent::TenantSuspended(TenantSuspended {
+            tenant_id: self.id.clone(),
+            reason: command.reason,
+            suspended_by: command.suspended_by,
+            suspended_at: Utc::now(),
+        })])
+    }
+
+    #[must_use]
+    pub fn apply_event(mut self, event: &SpecEvent) -> Self {
+        if let SpecEvent::TenantSuspended(_) = event {
+            self.state = TenantState::Suspended;
+        }
+        self
+    }
+
+    pub fn from_events(events: Vec<SpecEvent>) -> Result<Self, DomainError> {
+        let mut events_iter = events.into_iter();
+
+        let Some(SpecEvent::TenantCreated(e)) = events_iter.next() else {
+            return Err(DomainError::EventStoreError(
+                "First event must be TenantCreated".to_string(),
+            ));
+        };
+
+        let mut tenant = Self {
+            id: e.tenant_id,
+            state: TenantState::Active,
+        };
+
+        for event in events_iter {
+            tenant = tenant.apply_event(&event);
+        }
+
+        Ok(tenant)
+    }
+}
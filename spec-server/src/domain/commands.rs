@@ -1,13 +1,44 @@
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+use super::{
+    events::ScheduledTransition,
+    value_objects::{
+        BumpLevel, CommentAnchor, ReleaseEntry, SchemaRef, SpecDependency, TemplateParameter,
+        TemplateRef, TenantId,
+    },
+};
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum SpecCommand {
     Create(CreateSpec),
     Update(UpdateSpec),
     Publish(PublishSpec),
+    Republish(RepublishSpec),
     Deprecate(DeprecateSpec),
     Delete(DeleteSpec),
+    Restore(RestoreSpec),
+    Revert(RevertSpec),
+    Rename(RenameSpec),
+    ScheduleTransition(ScheduleTransition),
+    CancelSchedule(CancelSchedule),
+    ExecuteSchedule(ExecuteSchedule),
+    FailSchedule(FailSchedule),
+    FailSunset(FailSunset),
+    RequestReview(RequestReview),
+    Approve(ApproveSpec),
+    Reject(RejectSpec),
+    WithdrawReview(WithdrawReview),
+    AddLabels(AddLabels),
+    RemoveLabels(RemoveLabels),
+    SetDependencies(SetDependencies),
+    AddComment(AddComment),
+    EditComment(EditComment),
+    ResolveComment(ResolveComment),
+    Promote(PromoteSpec),
+    Demote(DemoteSpec),
 }
 
 #[derive(Debug, Clone)]
@@ -15,6 +46,12 @@ pub struct CreateSpec {
     pub name: String,
     pub content: String,
     pub description: Option<String>,
+    /// Schema the content must conform to
+    pub schema: Option<SchemaRef>,
+    /// Initial labels, which also select the validators the content runs through
+    pub labels: BTreeMap<String, String>,
+    /// Template version the content was rendered from
+    pub template: Option<TemplateRef>,
     pub created_by: String,
 }
 
@@ -24,6 +61,10 @@ pub struct UpdateSpec {
     pub spec_id: Uuid,
     pub content: String,
     pub description: Option<String>,
+    /// Semantic version bump; inferred from the content change when absent
+    pub bump: Option<BumpLevel>,
+    /// Schema the content must conform to; the current one is kept when absent
+    pub schema: Option<SchemaRef>,
     pub updated_by: String,
 }
 
@@ -35,11 +76,25 @@ pub struct PublishSpec {
     pub published_by: String,
 }
 
+/// Point a published spec back at an earlier version, issued when a release
+/// is rolled back to the one before it
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RepublishSpec {
+    pub spec_id: Uuid,
+    pub version: u32,
+    pub published_by: String,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct DeprecateSpec {
     pub spec_id: Uuid,
     pub reason: String,
+    /// Spec consumers should migrate to
+    pub successor_id: Option<Uuid>,
+    /// When the spec will be deleted automatically
+    pub sunset_at: Option<DateTime<Utc>>,
     pub deprecated_by: String,
 }
 
@@ -50,6 +105,310 @@ pub struct DeleteSpec {
     pub deleted_by: String,
 }
 
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RestoreSpec {
+    pub spec_id: Uuid,
+    pub restored_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RevertSpec {
+    pub spec_id: Uuid,
+    pub to_version: u32,
+    pub reason: Option<String>,
+    pub reverted_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RenameSpec {
+    pub spec_id: Uuid,
+    pub new_name: String,
+    pub renamed_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ScheduleTransition {
+    pub spec_id: Uuid,
+    pub transition: ScheduledTransition,
+    pub execute_at: DateTime<Utc>,
+    pub scheduled_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct CancelSchedule {
+    pub spec_id: Uuid,
+    pub schedule_id: Uuid,
+    pub cancelled_by: String,
+}
+
+/// Run a due scheduled transition, issued by the scheduler
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ExecuteSchedule {
+    pub spec_id: Uuid,
+    pub schedule_id: Uuid,
+}
+
+/// Complete a scheduled transition whose events were rejected when they were
+/// stored, issued by the scheduler
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct FailSchedule {
+    pub spec_id: Uuid,
+    pub schedule_id: Uuid,
+    pub failure: String,
+}
+
+/// Drop the sunset of a deprecated spec that could not be deleted, issued by
+/// the sunset processor
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct FailSunset {
+    pub spec_id: Uuid,
+    pub failure: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RequestReview {
+    pub spec_id: Uuid,
+    pub requested_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ApproveSpec {
+    pub spec_id: Uuid,
+    pub comment: Option<String>,
+    pub approved_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RejectSpec {
+    pub spec_id: Uuid,
+    pub reason: String,
+    pub rejected_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct WithdrawReview {
+    pub spec_id: Uuid,
+    pub withdrawn_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct AddLabels {
+    pub spec_id: Uuid,
+    pub labels: BTreeMap<String, String>,
+    pub labeled_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RemoveLabels {
+    pub spec_id: Uuid,
+    pub keys: Vec<String>,
+    pub unlabeled_by: String,
+}
+
+/// Replace the specs a spec depends on; an empty list clears them
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct SetDependencies {
+    pub spec_id: Uuid,
+    pub dependencies: Vec<SpecDependency>,
+    pub set_by: String,
+}
+
+/// Comment on a version of a spec, starting a thread or replying to one
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct AddComment {
+    pub spec_id: Uuid,
+    /// Version commented on; the head when absent. Replies use their thread's.
+    pub version: Option<u32>,
+    pub anchor: Option<CommentAnchor>,
+    /// Comment being replied to
+    pub parent_id: Option<Uuid>,
+    pub body: String,
+    pub author: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct EditComment {
+    pub spec_id: Uuid,
+    pub comment_id: Uuid,
+    pub body: String,
+    pub edited_by: String,
+}
+
+/// Mark a comment thread as addressed
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ResolveComment {
+    pub spec_id: Uuid,
+    pub comment_id: Uuid,
+    pub resolved_by: String,
+}
+
+/// Point a channel, such as `staging`, at one version of a spec
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct PromoteSpec {
+    pub spec_id: Uuid,
+    pub version: u32,
+    pub channel: String,
+    pub promoted_by: String,
+}
+
+/// Take the version a channel serves out of it; the channel falls back to
+/// the version it served before, if any
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct DemoteSpec {
+    pub spec_id: Uuid,
+    pub channel: String,
+    pub demoted_by: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegisterSchema {
+    pub name: String,
+    pub schema: serde_json::Value,
+    pub description: Option<String>,
+    pub registered_by: String,
+}
+
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct UpdateSchema {
+    pub schema_id: Uuid,
+    pub schema: serde_json::Value,
+    pub description: Option<String>,
+    pub updated_by: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegisterTemplate {
+    pub name: String,
+    pub body: String,
+    pub parameters: Vec<TemplateParameter>,
+    pub description: Option<String>,
+    pub registered_by: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateTemplate {
+    pub template_id: Uuid,
+    pub body: String,
+    pub parameters: Vec<TemplateParameter>,
+    pub description: Option<String>,
+    pub updated_by: String,
+}
+
+/// Pin a set of spec versions under a release name
+#[derive(Debug, Clone)]
+pub struct CreateRelease {
+    pub name: String,
+    pub description: Option<String>,
+    pub entries: Vec<ReleaseEntry>,
+    pub created_by: String,
+}
+
+/// Make a release current, publishing every pinned version that is not yet
+#[derive(Debug, Clone)]
+pub struct PublishRelease {
+    pub release_id: Uuid,
+    pub published_by: String,
+}
+
+/// Withdraw the current release, so the one published before it is current again
+#[derive(Debug, Clone)]
+pub struct RollbackRelease {
+    pub release_id: Uuid,
+    pub rolled_back_by: String,
+}
+
+/// Register a tenant requests can act for
+#[derive(Debug, Clone)]
+pub struct CreateTenant {
+    pub tenant_id: TenantId,
+    pub display_name: Option<String>,
+    pub created_by: String,
+}
+
+/// Refuse further requests for a tenant
+#[derive(Debug, Clone)]
+pub struct SuspendTenant {
+    pub tenant_id: TenantId,
+    pub reason: Option<String>,
+    pub suspended_by: String,
+}
+
+/// Rename the spec named `from` and every spec in the folder `from`, moving
+/// them under `to`
+#[derive(Debug, Clone)]
+pub struct MoveFolder {
+    pub from: String,
+    pub to: String,
+    pub moved_by: String,
+}
+
+/// Create a spec whose content is a template rendered with `parameters`
+#[derive(Debug, Clone)]
+pub struct CreateFromTemplate {
+    pub name: String,
+    pub template_id: Uuid,
+    /// Template version to render; the latest when absent
+    pub version: Option<u32>,
+    pub parameters: BTreeMap<String, serde_json::Value>,
+    pub description: Option<String>,
+    pub schema: Option<SchemaRef>,
+    pub labels: BTreeMap<String, String>,
+    pub created_by: String,
+}
+
+/// Spec a batch command applies to, by id or by name; a name may be that of
+/// a spec created earlier in the same batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecRef {
+    Id(Uuid),
+    Name(String),
+}
+
+impl From<&str> for SpecRef {
+    fn from(value: &str) -> Self {
+        Uuid::parse_str(value).map_or_else(|_| Self::Name(value.to_string()), Self::Id)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchTarget {
+    pub spec: SpecRef,
+    /// Reject the command unless the spec's stream is at this version
+    pub expected_version: Option<i64>,
+}
+
+/// One command of an atomic batch.
+///
+/// The wrapped command's `spec_id` is filled in once its target is resolved.
+#[derive(Debug, Clone)]
+pub enum BatchCommand {
+    Create(CreateSpec),
+    Update(BatchTarget, UpdateSpec),
+    Publish(BatchTarget, PublishSpec),
+    Deprecate(BatchTarget, DeprecateSpec),
+}
+
 #[allow(dead_code)]
 pub struct CommandContext {
     pub correlation_id: Option<Uuid>,
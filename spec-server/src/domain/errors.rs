@@ -1,7 +1,7 @@
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::events::SpecState;
+use super::{events::SpecState, value_objects::SchemaViolation};
 
 #[derive(Debug, Error)]
 #[allow(dead_code)]
@@ -9,18 +9,132 @@ pub enum DomainError {
     #[error("Spec not found: {0}")]
     SpecNotFound(Uuid),
 
+    #[error("Spec not found with name: {0}")]
+    SpecNameNotFound(String),
+
     #[error("Invalid state transition from {from:?} to {to:?}")]
     InvalidStateTransition { from: SpecState, to: SpecState },
 
     #[error("Version mismatch: expected {expected}, got {actual}")]
     VersionMismatch { expected: u32, actual: u32 },
 
+    #[error("Version not found: {0}")]
+    VersionNotFound(u32),
+
+    #[error("Cannot revert to version {0}: it is already the head version")]
+    InvalidRevertTarget(u32),
+
+    #[error("Concurrent modification: expected stream version {expected}, found {actual}")]
+    ConcurrencyConflict { expected: i64, actual: i64 },
+
     #[error("Spec already exists with name: {0}")]
     DuplicateSpecName(String),
 
     #[error("Cannot modify spec in {0:?} state")]
     InvalidStateForOperation(SpecState),
 
+    #[error("Invalid deprecation: {0}")]
+    InvalidDeprecation(String),
+
+    #[error("Schedule not found: {0}")]
+    ScheduleNotFound(Uuid),
+
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+
+    #[error("Version {0} must be approved before it can be published")]
+    ReviewRequired(u32),
+
+    #[error("Invalid review: {0}")]
+    InvalidReview(String),
+
+    #[error("{0} made the last change and cannot approve it")]
+    SelfApproval(String),
+
+    #[error("Invalid labels: {0}")]
+    InvalidLabels(String),
+
+    #[error("Comment not found: {0}")]
+    CommentNotFound(Uuid),
+
+    #[error("Invalid comment: {0}")]
+    InvalidComment(String),
+
+    #[error("Only {author} can edit comment {comment_id}")]
+    NotCommentAuthor { comment_id: Uuid, author: String },
+
+    #[error("Invalid dependencies: {0}")]
+    InvalidDependencies(String),
+
+    #[error("Dependency {spec_id} has no published version matching {version}")]
+    UnpublishedDependency {
+        spec_id: Uuid,
+        version: semver::VersionReq,
+    },
+
+    #[error("Published specs still depend on this spec: {}", format_ids(.0))]
+    HasDependents(Vec<Uuid>),
+
+    #[error("Invalid promotion: {0}")]
+    InvalidPromotion(String),
+
+    #[error("Schema not found: {0}")]
+    SchemaNotFound(Uuid),
+
+    #[error("Schema {schema_id} has no version {version}")]
+    SchemaVersionNotFound { schema_id: Uuid, version: u32 },
+
+    #[error("Template not found: {0}")]
+    TemplateNotFound(Uuid),
+
+    #[error("Template {template_id} has no version {version}")]
+    TemplateVersionNotFound { template_id: Uuid, version: u32 },
+
+    #[error("Content not found: {0}")]
+    ContentNotFound(String),
+
+    #[error("Idempotency key {0:?} was already used for a different request")]
+    IdempotencyKeyReused(String),
+
+    #[error("Release not found: {0}")]
+    ReleaseNotFound(Uuid),
+
+    #[error("Release already exists with name: {0}")]
+    DuplicateReleaseName(String),
+
+    #[error("Invalid release: {0}")]
+    InvalidRelease(String),
+
+    #[error("Tenant not found: {0}")]
+    TenantNotFound(String),
+
+    #[error("Tenant already exists: {0}")]
+    DuplicateTenant(String),
+
+    #[error("Tenant {0} is suspended")]
+    TenantSuspended(String),
+
+    #[error("Invalid tenant: {0}")]
+    InvalidTenant(String),
+
+    #[error("Not authenticated: {0}")]
+    Unauthenticated(String),
+
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("Invalid batch: {0}")]
+    InvalidBatch(String),
+
+    #[error("Invalid move: {0}")]
+    InvalidMove(String),
+
+    #[error("Not applied: another command in the batch failed")]
+    BatchAborted,
+
+    #[error("Content does not match schema: {}", format_violations(.0))]
+    SchemaViolation(Vec<SchemaViolation>),
+
     #[error("Validation error: {0}")]
     ValidationError(#[from] super::value_objects::ValidationError),
 
@@ -30,3 +144,29 @@ pub enum DomainError {
     #[error("Projection error: {0}")]
     ProjectionError(String),
 }
+
+impl DomainError {
+    /// Whether the same command may succeed when retried, as opposed to being
+    /// rejected by the domain
+    pub const fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::ConcurrencyConflict { .. } | Self::EventStoreError(_) | Self::ProjectionError(_)
+        )
+    }
+}
+
+fn format_ids(ids: &[Uuid]) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_violations(violations: &[SchemaViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
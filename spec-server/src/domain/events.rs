@@ -1,13 +1,52 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+use super::validation::ValidationIssue;
+use super::value_objects::{
+    CommentAnchor, ReleaseEntry, SchemaRef, SpecDependency, TemplateParameter, TemplateRef,
+    TenantId, ValidationError,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SpecEvent {
     Created(SpecCreated),
     Updated(SpecUpdated),
     StateChanged(SpecStateChanged),
+    Restored(SpecRestored),
+    Reverted(SpecReverted),
+    Renamed(SpecRenamed),
+    TransitionScheduled(SpecTransitionScheduled),
+    ScheduleCancelled(SpecScheduleCancelled),
+    ScheduleExecuted(SpecScheduleExecuted),
+    ReviewRequested(SpecReviewRequested),
+    ReviewApproved(SpecReviewApproved),
+    ReviewRejected(SpecReviewRejected),
+    ReviewWithdrawn(SpecReviewWithdrawn),
+    LabelsAdded(SpecLabelsAdded),
+    LabelsRemoved(SpecLabelsRemoved),
+    DependenciesSet(SpecDependenciesSet),
+    CommentAdded(SpecCommentAdded),
+    CommentEdited(SpecCommentEdited),
+    CommentResolved(SpecCommentResolved),
+    Promoted(SpecPromoted),
+    Demoted(SpecDemoted),
+    SunsetFailed(SpecSunsetFailed),
+    /// Schema registry events, recorded in each schema's own stream
+    SchemaRegistered(SchemaRegistered),
+    SchemaUpdated(SchemaUpdated),
+    /// Template events, recorded in each template's own stream
+    TemplateRegistered(TemplateRegistered),
+    TemplateUpdated(TemplateUpdated),
+    /// Release events, recorded in each release's own stream
+    ReleaseCreated(ReleaseCreated),
+    ReleasePublished(ReleasePublished),
+    ReleaseRolledBack(ReleaseRolledBack),
+    /// Tenant events, recorded in a stream of the tenant they describe
+    TenantCreated(TenantCreated),
+    TenantSuspended(TenantSuspended),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +55,15 @@ pub struct SpecCreated {
     pub name: String,
     pub content: String,
     pub description: Option<String>,
+    /// Schema the content was validated against, if one was declared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaRef>,
+    /// Template version the content was rendered from, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateRef>,
+    /// Warnings validators reported when the content was accepted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ValidationIssue>,
     pub created_by: String,
     pub created_at: DateTime<Utc>,
 }
@@ -24,8 +72,17 @@ pub struct SpecCreated {
 pub struct SpecUpdated {
     pub spec_id: Uuid,
     pub version: u32,
+    /// Stored since schema version 2; earlier events are upcast to the
+    /// version their number was reported as
+    pub semver: semver::Version,
     pub content: String,
     pub description: Option<String>,
+    /// Schema the content was validated against, if one was declared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaRef>,
+    /// Warnings validators reported when the content was accepted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ValidationIssue>,
     pub updated_by: String,
     pub updated_at: DateTime<Utc>,
 }
@@ -37,25 +94,351 @@ pub struct SpecStateChanged {
     pub from_state: SpecState,
     pub to_state: SpecState,
     pub reason: Option<String>,
+    /// Set on deprecation: the spec consumers should migrate to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor_id: Option<Uuid>,
+    /// Set on deprecation: when the spec will be deleted automatically
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset_at: Option<DateTime<Utc>>,
     pub changed_by: String,
     pub changed_at: DateTime<Utc>,
 }
 
+/// Content of an earlier version restored as a new version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReverted {
+    pub spec_id: Uuid,
+    pub version: u32,
+    /// Stored since schema version 2; earlier events are upcast to the
+    /// version their number was reported as
+    pub semver: semver::Version,
+    pub reverted_from: u32,
+    pub content: String,
+    pub description: Option<String>,
+    pub reason: Option<String>,
+    pub reverted_by: String,
+    pub reverted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecRenamed {
+    pub spec_id: Uuid,
+    pub old_name: String,
+    pub new_name: String,
+    pub renamed_by: String,
+    pub renamed_at: DateTime<Utc>,
+}
+
+/// A deleted spec returned to the state it was in before deletion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecRestored {
+    pub spec_id: Uuid,
+    pub name: String,
+    pub version: u32,
+    pub to_state: SpecState,
+    pub restored_by: String,
+    pub restored_at: DateTime<Utc>,
+}
+
+/// State transition to run at a later time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ScheduledTransition {
+    Publish {
+        version: Option<u32>,
+    },
+    Deprecate {
+        reason: String,
+        successor_id: Option<Uuid>,
+        sunset_at: Option<DateTime<Utc>>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecTransitionScheduled {
+    pub spec_id: Uuid,
+    pub schedule_id: Uuid,
+    pub transition: ScheduledTransition,
+    pub execute_at: DateTime<Utc>,
+    pub scheduled_by: String,
+    pub scheduled_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecScheduleCancelled {
+    pub spec_id: Uuid,
+    pub schedule_id: Uuid,
+    pub cancelled_by: String,
+    pub cancelled_at: DateTime<Utc>,
+}
+
+/// A scheduled transition was run; `failure` is set when it was rejected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecScheduleExecuted {
+    pub spec_id: Uuid,
+    pub schedule_id: Uuid,
+    pub failure: Option<String>,
+    pub executed_at: DateTime<Utc>,
+}
+
+/// Review of one version, opened before it can be published
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReviewRequested {
+    pub spec_id: Uuid,
+    pub version: u32,
+    pub required_approvals: u32,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReviewApproved {
+    pub spec_id: Uuid,
+    pub version: u32,
+    pub comment: Option<String>,
+    pub approved_by: String,
+    pub approved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReviewRejected {
+    pub spec_id: Uuid,
+    pub version: u32,
+    pub reason: String,
+    pub rejected_by: String,
+    pub rejected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReviewWithdrawn {
+    pub spec_id: Uuid,
+    pub version: u32,
+    pub withdrawn_by: String,
+    pub withdrawn_at: DateTime<Utc>,
+}
+
+/// Labels set on a spec, replacing the values of keys it already had
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecLabelsAdded {
+    pub spec_id: Uuid,
+    pub labels: BTreeMap<String, String>,
+    pub labeled_by: String,
+    pub labeled_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecLabelsRemoved {
+    pub spec_id: Uuid,
+    pub keys: Vec<String>,
+    pub unlabeled_by: String,
+    pub unlabeled_at: DateTime<Utc>,
+}
+
+/// Dependencies declared by a spec, replacing any it had before
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecDependenciesSet {
+    pub spec_id: Uuid,
+    pub dependencies: Vec<SpecDependency>,
+    pub set_by: String,
+    pub set_at: DateTime<Utc>,
+}
+
+/// Feedback on one version of a spec; comments never change its content or version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecCommentAdded {
+    pub spec_id: Uuid,
+    pub comment_id: Uuid,
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<CommentAnchor>,
+    /// First comment of the thread this one replies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+    pub body: String,
+    pub author: String,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecCommentEdited {
+    pub spec_id: Uuid,
+    pub comment_id: Uuid,
+    pub body: String,
+    pub edited_by: String,
+    pub edited_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecCommentResolved {
+    pub spec_id: Uuid,
+    pub comment_id: Uuid,
+    pub resolved_by: String,
+    pub resolved_at: DateTime<Utc>,
+}
+
+/// A channel pointed at a version of the spec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecPromoted {
+    pub spec_id: Uuid,
+    pub channel: String,
+    pub version: u32,
+    /// Version the channel served before, which demoting returns it to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_version: Option<u32>,
+    pub promoted_by: String,
+    pub promoted_at: DateTime<Utc>,
+}
+
+/// A version taken out of a channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecDemoted {
+    pub spec_id: Uuid,
+    pub channel: String,
+    pub version: u32,
+    /// Version the channel serves again; `None` when the spec left the channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restored_version: Option<u32>,
+    pub demoted_by: String,
+    pub demoted_at: DateTime<Utc>,
+}
+
+/// A deprecated spec could not be deleted at its sunset, which is dropped so
+/// the spec stays deprecated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecSunsetFailed {
+    pub spec_id: Uuid,
+    pub failure: String,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// First version of a JSON Schema added to the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaRegistered {
+    pub schema_id: Uuid,
+    pub name: String,
+    pub schema: serde_json::Value,
+    pub description: Option<String>,
+    pub registered_by: String,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// New version of a registered schema; earlier versions stay available
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaUpdated {
+    pub schema_id: Uuid,
+    pub version: u32,
+    pub schema: serde_json::Value,
+    pub description: Option<String>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// First version of a spec template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateRegistered {
+    pub template_id: Uuid,
+    pub name: String,
+    pub body: String,
+    pub parameters: Vec<TemplateParameter>,
+    pub description: Option<String>,
+    pub registered_by: String,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// New version of a template; specs rendered from earlier versions keep theirs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateUpdated {
+    pub template_id: Uuid,
+    pub version: u32,
+    pub body: String,
+    pub parameters: Vec<TemplateParameter>,
+    pub description: Option<String>,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A named set of spec versions, not yet published
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseCreated {
+    pub release_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub entries: Vec<ReleaseEntry>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A release made current; the pinned versions it published are recorded in
+/// the streams of their specs in the same transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleasePublished {
+    pub release_id: Uuid,
+    pub published_by: String,
+    pub published_at: DateTime<Utc>,
+}
+
+/// The current release withdrawn; specs keep the versions it published
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseRolledBack {
+    pub release_id: Uuid,
+    /// Release that is current again; `None` when no earlier one is published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restored_release_id: Option<Uuid>,
+    pub rolled_back_by: String,
+    pub rolled_back_at: DateTime<Utc>,
+}
+
+/// A tenant that requests can act for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantCreated {
+    pub tenant_id: TenantId,
+    pub display_name: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A tenant whose requests are refused from now on; its data is kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantSuspended {
+    pub tenant_id: TenantId,
+    pub reason: Option<String>,
+    pub suspended_by: String,
+    pub suspended_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TenantState {
+    Active,
+    Suspended,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseState {
+    Draft,
+    Published,
+    RolledBack,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+pub enum ReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SpecState {
+    #[default]
     Draft,
     Published,
     Deprecated,
     Deleted,
 }
 
-impl Default for SpecState {
-    fn default() -> Self {
-        Self::Draft
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventEnvelope {
     pub event_id: Uuid,
@@ -71,4 +454,34 @@ pub struct EventMetadata {
     pub causation_id: Option<Uuid>,
     pub user_agent: Option<String>,
     pub ip_address: Option<String>,
+    /// Key the client sent so a retry of the request replays these events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Tenant the request acted for
+    #[serde(default)]
+    pub tenant_id: TenantId,
+}
+
+impl EventMetadata {
+    /// Longest idempotency key accepted from clients
+    pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+    /// Attach the idempotency key a client sent with its request
+    pub fn with_idempotency_key(mut self, key: &str) -> Result<Self, ValidationError> {
+        if key.is_empty() || key.len() > Self::MAX_IDEMPOTENCY_KEY_LENGTH {
+            return Err(ValidationError::InvalidIdempotencyKey {
+                max: Self::MAX_IDEMPOTENCY_KEY_LENGTH,
+            });
+        }
+
+        self.idempotency_key = Some(key.to_string());
+        Ok(self)
+    }
+
+    /// Record the tenant a request acts for
+    #[must_use]
+    pub fn with_tenant(mut self, tenant_id: TenantId) -> Self {
+        self.tenant_id = tenant_id;
+        self
+    }
 }
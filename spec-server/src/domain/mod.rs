@@ -2,4 +2,5 @@ pub mod aggregates;
 pub mod commands;
 pub mod errors;
 pub mod events;
+pub mod validation;
 pub mod value_objects;
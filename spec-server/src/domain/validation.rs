@@ -0,0 +1,687 @@
+use serde::de::{self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::value_objects::{LabelSelector, ValidationError};
+
+/// Whether an issue rejects the content or is only reported back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Something a validator found in spec content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// Name of the validator that reported the issue
+    pub validator: String,
+    pub severity: Severity,
+    /// JSON Pointer to the offending value, for issues in the parsed document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// 1-based line number, for issues in the raw text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn error(validator: &str, message: impl Into<String>) -> Self {
+        Self {
+            validator: validator.to_string(),
+            severity: Severity::Error,
+            path: None,
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(validator: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(validator, message)
+        }
+    }
+
+    #[must_use]
+    pub fn at_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.validator)?;
+        if let Some(line) = self.line {
+            write!(f, " (line {line})")?;
+        }
+        if let Some(path) = &self.path {
+            let path = if path.is_empty() { "/" } else { path };
+            write!(f, " at {path}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Limits on spec names and content, loaded from server configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    pub max_content_bytes: usize,
+    pub max_name_length: usize,
+    /// Characters allowed in names besides letters and digits
+    pub name_punctuation: String,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            max_content_bytes: 2048,
+            max_name_length: 255,
+            name_punctuation: "-_.".to_string(),
+        }
+    }
+}
+
+impl ValidationPolicy {
+    fn apply(&mut self, setting: &PolicySetting) {
+        match setting {
+            PolicySetting::MaxContentBytes(max) => self.max_content_bytes = *max,
+            PolicySetting::MaxNameLength(max) => self.max_name_length = *max,
+            PolicySetting::NamePunctuation(chars) => self.name_punctuation.clone_from(chars),
+        }
+    }
+}
+
+/// One configured value of a validation policy
+#[derive(Debug, Clone)]
+enum PolicySetting {
+    MaxContentBytes(usize),
+    MaxNameLength(usize),
+    NamePunctuation(String),
+}
+
+/// Parse comma-separated settings such as
+/// `max_content_bytes=16384,max_name_length=64,name_punctuation=-_`
+fn parse_settings(s: &str) -> Result<Vec<PolicySetting>, ValidationError> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|setting| !setting.is_empty())
+        .map(|setting| {
+            let invalid = || ValidationError::InvalidConfig(setting.to_string());
+            match setting.split_once('=').ok_or_else(invalid)? {
+                ("max_content_bytes", value) => Ok(PolicySetting::MaxContentBytes(
+                    value.parse().map_err(|_| invalid())?,
+                )),
+                ("max_name_length", value) => Ok(PolicySetting::MaxNameLength(
+                    value.parse().map_err(|_| invalid())?,
+                )),
+                ("name_punctuation", value) => {
+                    Ok(PolicySetting::NamePunctuation(value.to_string()))
+                }
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+/// The server-wide validation policy with overrides for matching specs.
+///
+/// Overrides apply to specs whose labels match a selector; a spec's kind and
+/// namespace are its `kind` and `namespace` labels, so an override for
+/// `kind=approval-workflow` covers every spec of that kind.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationPolicies {
+    base: ValidationPolicy,
+    overrides: Vec<(LabelSelector, Vec<PolicySetting>)>,
+}
+
+impl ValidationPolicies {
+    /// The policy for a spec with `labels`: the base policy with the settings
+    /// of every matching override applied in the order they were configured
+    pub fn policy_for(&self, labels: &BTreeMap<String, String>) -> ValidationPolicy {
+        let mut policy = self.base.clone();
+        let matching = self
+            .overrides
+            .iter()
+            .filter(|(selector, _)| selector.matches(labels));
+        for setting in matching.flat_map(|(_, settings)| settings) {
+            policy.apply(setting);
+        }
+        policy
+    }
+}
+
+impl FromStr for ValidationPolicies {
+    type Err = ValidationError;
+
+    /// Parses the base settings followed by `;`-separated overrides of the
+    /// form `<selector>:<settings>`, such as
+    /// `max_content_bytes=4096;kind=approval-workflow:max_content_bytes=16384`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sections = s.split(';');
+
+        let mut base = ValidationPolicy::default();
+        for setting in parse_settings(sections.next().unwrap_or_default())? {
+            base.apply(&setting);
+        }
+
+        let overrides = sections
+            .filter(|section| !section.trim().is_empty())
+            .map(|section| {
+                let (selector, settings) = section.split_once(':').ok_or_else(|| {
+                    ValidationError::InvalidConfig(format!("Override {section:?} needs a selector"))
+                })?;
+                Ok((selector.parse()?, parse_settings(settings)?))
+            })
+            .collect::<Result<_, ValidationError>>()?;
+
+        Ok(Self { base, overrides })
+    }
+}
+
+/// A check run over spec content before it is accepted.
+///
+/// `document` is the parsed YAML, or `None` when the content does not parse;
+/// validators that only look at the structure report nothing in that case.
+pub trait SpecValidator: Send + Sync {
+    /// Name reported with every issue this validator finds
+    fn name(&self) -> &'static str;
+
+    fn validate(&self, content: &str, document: Option<&serde_yaml::Value>)
+        -> Vec<ValidationIssue>;
+}
+
+/// Validators run in order over spec content. Every pipeline starts with
+/// the YAML syntax check, since spec content must always be YAML.
+#[derive(Clone)]
+pub struct ValidatorPipeline(Vec<Arc<dyn SpecValidator>>);
+
+impl ValidatorPipeline {
+    pub fn new() -> Self {
+        Self(vec![Arc::new(YamlSyntax)])
+    }
+
+    #[must_use]
+    pub fn with_all(mut self, validators: &[Arc<dyn SpecValidator>]) -> Self {
+        self.0.extend(validators.iter().cloned());
+        self
+    }
+
+    /// Run every validator, collecting errors and warnings alike
+    pub fn run(&self, content: &str) -> Vec<ValidationIssue> {
+        let document = serde_yaml::from_str::<serde_yaml::Value>(content).ok();
+
+        self.0
+            .iter()
+            .flat_map(|validator| validator.validate(content, document.as_ref()))
+            .collect()
+    }
+}
+
+impl Default for ValidatorPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ValidatorPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|validator| validator.name()))
+            .finish()
+    }
+}
+
+/// Which validators apply to a spec. The base validators apply to every
+/// spec; rules add more for specs whose labels match a selector, and a spec's
+/// kind is its `kind` label.
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorRegistry {
+    base: ValidatorPipeline,
+    rules: Vec<ValidatorRule>,
+}
+
+#[derive(Clone)]
+struct ValidatorRule {
+    selector: LabelSelector,
+    validators: Vec<Arc<dyn SpecValidator>>,
+}
+
+impl fmt::Debug for ValidatorRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidatorRule")
+            .field("selector", &self.selector)
+            .field(
+                "validators",
+                &self.validators.iter().map(|v| v.name()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ValidatorRegistry {
+    pub const fn new(base: ValidatorPipeline) -> Self {
+        Self {
+            base,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Add validators for specs whose labels match `selector`
+    #[must_use]
+    pub fn with_rule(
+        mut self,
+        selector: LabelSelector,
+        validators: Vec<Arc<dyn SpecValidator>>,
+    ) -> Self {
+        self.rules.push(ValidatorRule {
+            selector,
+            validators,
+        });
+        self
+    }
+
+    /// The pipeline for a spec with `labels`: the base validators followed
+    /// by those of every matching rule, in the order they were added
+    pub fn pipeline_for(&self, labels: &BTreeMap<String, String>) -> ValidatorPipeline {
+        self.rules
+            .iter()
+            .filter(|rule| rule.selector.matches(labels))
+            .fold(self.base.clone(), |pipeline, rule| {
+                pipeline.with_all(&rule.validators)
+            })
+    }
+}
+
+impl FromStr for ValidatorRegistry {
+    type Err = ValidationError;
+
+    /// Parses the base validators followed by `;`-separated rules of the
+    /// form `<selector>:<validators>`, such as
+    /// `duplicate_keys,max_nesting_depth=16;kind=policy:forbidden_keys=password|token`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sections = s.split(';');
+        let base = ValidatorPipeline::new()
+            .with_all(&parse_validators(sections.next().unwrap_or_default())?);
+
+        sections.filter(|rule| !rule.trim().is_empty()).try_fold(
+            Self::new(base),
+            |registry, rule| {
+                let (selector, validators) = rule.split_once(':').ok_or_else(|| {
+                    ValidationError::InvalidConfig(format!("Rule {rule:?} needs a selector"))
+                })?;
+                Ok(registry.with_rule(selector.parse()?, parse_validators(validators)?))
+            },
+        )
+    }
+}
+
+/// Parse a comma-separated list of built-in validators: `duplicate_keys`,
+/// `tab_indentation`, `max_nesting_depth=<n>` and `forbidden_keys=<a|b>`
+fn parse_validators(s: &str) -> Result<Vec<Arc<dyn SpecValidator>>, ValidationError> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            let invalid = || ValidationError::InvalidConfig(term.to_string());
+            let validator: Arc<dyn SpecValidator> = match term.split_once('=') {
+                None if term == "duplicate_keys" => Arc::new(DuplicateKeys),
+                None if term == "tab_indentation" => Arc::new(TabIndentation),
+                Some(("max_nesting_depth", depth)) => {
+                    Arc::new(MaxNestingDepth(depth.parse().map_err(|_| invalid())?))
+                }
+                Some(("forbidden_keys", keys)) => Arc::new(ForbiddenKeys(
+                    keys.split('|').map(|key| key.trim().to_string()).collect(),
+                )),
+                _ => return Err(invalid()),
+            };
+            Ok(validator)
+        })
+        .collect()
+}
+
+/// Rejects content that is not YAML
+pub struct YamlSyntax;
+
+impl SpecValidator for YamlSyntax {
+    fn name(&self) -> &'static str {
+        "yaml_syntax"
+    }
+
+    fn validate(
+        &self,
+        content: &str,
+        _document: Option<&serde_yaml::Value>,
+    ) -> Vec<ValidationIssue> {
+        // Only syntax matters here; duplicate keys are reported separately
+        match serde_yaml::from_str::<de::IgnoredAny>(content) {
+            Ok(_) => Vec::new(),
+            Err(e) => {
+                let issue = ValidationIssue::error(self.name(), e.to_string());
+                vec![match e.location() {
+                    Some(location) => issue.at_line(location.line()),
+                    None => issue,
+                }]
+            }
+        }
+    }
+}
+
+/// Rejects mappings that repeat a key
+pub struct DuplicateKeys;
+
+impl SpecValidator for DuplicateKeys {
+    fn name(&self) -> &'static str {
+        "duplicate_keys"
+    }
+
+    fn validate(
+        &self,
+        content: &str,
+        _document: Option<&serde_yaml::Value>,
+    ) -> Vec<ValidationIssue> {
+        let found = RefCell::new(Vec::new());
+        let scan = KeyScan {
+            path: String::new(),
+            found: &found,
+        };
+
+        // Syntax errors are reported by `YamlSyntax`
+        let _ = scan.deserialize(serde_yaml::Deserializer::from_str(content));
+
+        found
+            .into_inner()
+            .into_iter()
+            .map(|(path, key)| {
+                ValidationIssue::error(self.name(), format!("Duplicate key {key:?}")).at_path(path)
+            })
+            .collect()
+    }
+}
+
+/// Warns about lines indented with tabs. YAML forbids tabs as indentation,
+/// so outside block scalars the syntax check rejects them as well.
+pub struct TabIndentation;
+
+impl SpecValidator for TabIndentation {
+    fn name(&self) -> &'static str {
+        "tab_indentation"
+    }
+
+    fn validate(
+        &self,
+        content: &str,
+        _document: Option<&serde_yaml::Value>,
+    ) -> Vec<ValidationIssue> {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                line.chars()
+                    .take_while(|c| *c == ' ' || *c == '\t')
+                    .any(|c| c == '\t')
+            })
+            .map(|(index, _)| {
+                ValidationIssue::warning(self.name(), "Line is indented with a tab")
+                    .at_line(index + 1)
+            })
+            .collect()
+    }
+}
+
+/// Rejects documents whose mappings and sequences nest deeper than a limit
+pub struct MaxNestingDepth(pub usize);
+
+impl SpecValidator for MaxNestingDepth {
+    fn name(&self) -> &'static str {
+        "max_nesting_depth"
+    }
+
+    fn validate(
+        &self,
+        _content: &str,
+        document: Option<&serde_yaml::Value>,
+    ) -> Vec<ValidationIssue> {
+        document
+            .and_then(|document| too_deep(document, String::new(), 0, self.0))
+            .map(|path| {
+                vec![ValidationIssue::error(
+                    self.name(),
+                    format!("Nesting exceeds the maximum depth of {}", self.0),
+                )
+                .at_path(path)]
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Path of the first collection nested deeper than `max_depth`
+fn too_deep(
+    value: &serde_yaml::Value,
+    path: String,
+    depth: usize,
+    max_depth: usize,
+) -> Option<String> {
+    use serde_yaml::Value;
+
+    let children: Vec<(String, &Value)> = match value {
+        Value::Mapping(mapping) => mapping
+            .iter()
+            .map(|(key, value)| (key_label(key), value))
+            .collect(),
+        Value::Sequence(sequence) => sequence
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (index.to_string(), value))
+            .collect(),
+        Value::Tagged(tagged) => return too_deep(&tagged.value, path, depth, max_depth),
+        _ => return None,
+    };
+
+    if depth >= max_depth {
+        return Some(path);
+    }
+
+    children.into_iter().find_map(|(segment, child)| {
+        too_deep(child, child_path(&path, &segment), depth + 1, max_depth)
+    })
+}
+
+/// Rejects mapping keys from a deny list, at any depth
+pub struct ForbiddenKeys(pub BTreeSet<String>);
+
+impl SpecValidator for ForbiddenKeys {
+    fn name(&self) -> &'static str {
+        "forbidden_keys"
+    }
+
+    fn validate(
+        &self,
+        _content: &str,
+        document: Option<&serde_yaml::Value>,
+    ) -> Vec<ValidationIssue> {
+        let mut found = Vec::new();
+        if let Some(document) = document {
+            self.collect(document, "", &mut found);
+        }
+        found
+    }
+}
+
+impl ForbiddenKeys {
+    fn collect(&self, value: &serde_yaml::Value, path: &str, found: &mut Vec<ValidationIssue>) {
+        use serde_yaml::Value;
+
+        match value {
+            Value::Mapping(mapping) => {
+                for (key, value) in mapping {
+                    let label = key_label(key);
+                    let key_path = child_path(path, &label);
+                    if self.0.contains(&label) {
+                        found.push(
+                            ValidationIssue::error(
+                                self.name(),
+                                format!("Key {label:?} is not allowed"),
+                            )
+                            .at_path(key_path.clone()),
+                        );
+                    }
+                    self.collect(value, &key_path, found);
+                }
+            }
+            Value::Sequence(sequence) => {
+                for (index, value) in sequence.iter().enumerate() {
+                    self.collect(value, &child_path(path, &index.to_string()), found);
+                }
+            }
+            Value::Tagged(tagged) => self.collect(&tagged.value, path, found),
+            _ => {}
+        }
+    }
+}
+
+/// Walks a YAML document recording repeated mapping keys, which parsing into
+/// `serde_yaml::Value` would reject outright without saying where
+struct KeyScan<'a> {
+    path: String,
+    found: &'a RefCell<Vec<(String, String)>>,
+}
+
+impl KeyScan<'_> {
+    fn child(&self, segment: &str) -> Self {
+        Self {
+            path: child_path(&self.path, segment),
+            found: self.found,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for KeyScan<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeyScan<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a YAML document")
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i128<E: de::Error>(self, _: i128) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u128<E: de::Error>(self, _: u128) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut index = 0usize;
+        while seq
+            .next_element_seed(self.child(&index.to_string()))?
+            .is_some()
+        {
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut seen = Vec::new();
+        while let Some(key) = map.next_key::<serde_yaml::Value>()? {
+            let label = key_label(&key);
+            if seen.contains(&key) {
+                self.found
+                    .borrow_mut()
+                    .push((child_path(&self.path, &label), label.clone()));
+            } else {
+                seen.push(key);
+            }
+            map.next_value_seed(self.child(&label))?;
+        }
+        Ok(())
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<(), A::Error> {
+        // Tagged values such as `!include path`
+        let (_, variant) = data.variant::<de::IgnoredAny>()?;
+        de::VariantAccess::newtype_variant_seed(variant, self)
+    }
+}
+
+/// Mapping key as it appears in a path
+fn key_label(key: &serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Append a JSON Pointer segment, escaping `~` and `/`
+fn child_path(path: &str, segment: &str) -> String {
+    format!("{path}/{}", segment.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Used in error messages listing several issues
+pub fn format_issues(issues: &[ValidationIssue]) -> String {
+    issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
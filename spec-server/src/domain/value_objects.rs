@@ -1,29 +1,86 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use super::validation::{format_issues, ValidationIssue, ValidationPolicy, ValidatorPipeline};
+
+/// Separates the folders of a path-style name such as `payments/auth/regex-rules`
+pub const NAME_SEPARATOR: char = '/';
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SpecName(String);
 
 impl SpecName {
-    pub fn new(name: String) -> Result<Self, ValidationError> {
+    /// A name of one or more `/`-separated segments, each checked against the
+    /// policy's characters; the length limit applies to the whole path
+    pub fn new(name: String, policy: &ValidationPolicy) -> Result<Self, ValidationError> {
         if name.is_empty() {
             return Err(ValidationError::EmptyName);
         }
-        if name.len() > 255 {
-            return Err(ValidationError::NameTooLong);
+        if name.chars().count() > policy.max_name_length {
+            return Err(ValidationError::NameTooLong {
+                max: policy.max_name_length,
+            });
         }
-        if !name
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
-        {
-            return Err(ValidationError::InvalidCharacters);
+        for segment in name.split(NAME_SEPARATOR) {
+            if segment.is_empty() || segment == "." || segment == ".." {
+                return Err(ValidationError::InvalidNameSegment(segment.to_string()));
+            }
+            if !segment
+                .chars()
+                .all(|c| c.is_alphanumeric() || policy.name_punctuation.contains(c))
+            {
+                return Err(ValidationError::InvalidCharacters {
+                    allowed: policy.name_punctuation.clone(),
+                });
+            }
         }
         Ok(Self(name))
     }
 
+    /// A name of a single segment, for the schemas, templates and releases
+    /// that are addressed by name in one path segment
+    pub fn single_segment(
+        name: String,
+        policy: &ValidationPolicy,
+    ) -> Result<Self, ValidationError> {
+        if name.contains(NAME_SEPARATOR) {
+            return Err(ValidationError::InvalidCharacters {
+                allowed: policy.name_punctuation.clone(),
+            });
+        }
+        Self::new(name, policy)
+    }
+
+    /// A name read back from a stored event, which was checked against the
+    /// policy in force when it was recorded
+    pub const fn recorded(name: String) -> Self {
+        Self(name)
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Folders holding the name, from the root folder `""` down to its parent
+    pub fn folders(&self) -> Vec<&str> {
+        std::iter::once("")
+            .chain(
+                self.0
+                    .match_indices(NAME_SEPARATOR)
+                    .map(|(index, _)| &self.0[..index]),
+            )
+            .collect()
+    }
+
+    /// Whether the name is `path` itself or lies in the folder `path`
+    pub fn is_within(&self, path: &str) -> bool {
+        self.0
+            .strip_prefix(path)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(NAME_SEPARATOR))
+    }
 }
 
 impl fmt::Display for SpecName {
@@ -32,29 +89,652 @@ impl fmt::Display for SpecName {
     }
 }
 
+/// Key/value labels used to group specs by team, domain or consumer
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Labels(BTreeMap<String, String>);
+
+impl Labels {
+    pub fn new(labels: BTreeMap<String, String>) -> Result<Self, ValidationError> {
+        if labels.is_empty() {
+            return Err(ValidationError::NoLabels);
+        }
+        for (key, value) in &labels {
+            validate_label_key(key)?;
+            if value.len() > 63 || !value.chars().all(is_label_char) {
+                return Err(ValidationError::InvalidLabelValue(value.clone()));
+            }
+        }
+        Ok(Self(labels))
+    }
+
+    pub fn into_inner(self) -> BTreeMap<String, String> {
+        self.0
+    }
+}
+
+/// Keys are 1-63 characters of letters, digits, `-`, `_`, `.` and `/`
+pub fn validate_label_key(key: &str) -> Result<(), ValidationError> {
+    if key.is_empty() || key.len() > 63 || !key.chars().all(is_label_char) {
+        return Err(ValidationError::InvalidLabelKey(key.to_string()));
+    }
+    Ok(())
+}
+
+/// Channel names are 1-63 lowercase letters, digits and `-`, such as `staging`
+pub fn validate_channel(channel: &str) -> Result<(), ValidationError> {
+    let valid = !channel.is_empty()
+        && channel.len() <= 63
+        && channel
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        return Err(ValidationError::InvalidChannel(channel.to_string()));
+    }
+    Ok(())
+}
+
+/// Tenant that specs and everything built around them belong to: 1-63
+/// lowercase letters, digits and `-`, such as `payments`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Tenant of requests that name none, and of data stored before tenants existed
+    pub const DEFAULT: &'static str = "default";
+
+    pub fn new(id: &str) -> Result<Self, ValidationError> {
+        let valid = !id.is_empty()
+            && id.len() <= 63
+            && id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid {
+            return Err(ValidationError::InvalidTenantId(id.to_string()));
+        }
+        Ok(Self(id.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == Self::DEFAULT
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self(Self::DEFAULT.to_string())
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn is_label_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/')
+}
+
+/// One term of a label selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelRequirement {
+    /// `key=value` or `key==value`
+    Equals(String, String),
+    /// `key!=value`; also matches specs without the label
+    NotEquals(String, String),
+    /// `key`
+    Exists(String),
+    /// `!key`
+    NotExists(String),
+}
+
+/// Comma-separated label requirements that must all hold, such as
+/// `team=payments,env!=test`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LabelSelector(Vec<LabelRequirement>);
+
+impl LabelSelector {
+    pub fn requirements(&self) -> &[LabelRequirement] {
+        &self.0
+    }
+
+    /// Whether `labels` satisfy every requirement
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.0.iter().all(|requirement| match requirement {
+            LabelRequirement::Equals(key, value) => labels.get(key) == Some(value),
+            LabelRequirement::NotEquals(key, value) => labels.get(key) != Some(value),
+            LabelRequirement::Exists(key) => labels.contains_key(key),
+            LabelRequirement::NotExists(key) => !labels.contains_key(key),
+        })
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ValidationError::InvalidLabelSelector(s.to_string());
+
+        let requirements = s
+            .split(',')
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .map(|term| {
+                let requirement = if let Some((key, value)) = term.split_once("!=") {
+                    LabelRequirement::NotEquals(key.trim().to_string(), value.trim().to_string())
+                } else if let Some((key, value)) = term.split_once('=') {
+                    let value = value.strip_prefix('=').unwrap_or(value);
+                    LabelRequirement::Equals(key.trim().to_string(), value.trim().to_string())
+                } else if let Some(key) = term.strip_prefix('!') {
+                    LabelRequirement::NotExists(key.trim().to_string())
+                } else {
+                    LabelRequirement::Exists(term.to_string())
+                };
+
+                let key = match &requirement {
+                    LabelRequirement::Equals(key, _)
+                    | LabelRequirement::NotEquals(key, _)
+                    | LabelRequirement::Exists(key)
+                    | LabelRequirement::NotExists(key) => key,
+                };
+                validate_label_key(key).map_err(|_| invalid())?;
+
+                Ok(requirement)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self(requirements))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SpecContent(String);
 
 impl SpecContent {
-    pub fn new(content: String) -> Result<Self, ValidationError> {
+    /// Check `content` against the size limit of `policy` and run it through
+    /// a validator pipeline, returning it together with any warnings when no
+    /// validator reported an error
+    pub fn new(
+        content: String,
+        policy: &ValidationPolicy,
+        pipeline: &ValidatorPipeline,
+    ) -> Result<(Self, Vec<ValidationIssue>), ValidationError> {
         if content.is_empty() {
             return Err(ValidationError::EmptyContent);
         }
-        if content.len() > 2048 {
-            return Err(ValidationError::ContentTooLarge);
+        if content.len() > policy.max_content_bytes {
+            return Err(ValidationError::ContentTooLarge {
+                max: policy.max_content_bytes,
+            });
         }
-        // Validate YAML
-        serde_yaml::from_str::<serde_yaml::Value>(&content)
-            .map_err(|_| ValidationError::InvalidYaml)?;
-        Ok(Self(content))
+
+        let (errors, warnings): (Vec<_>, Vec<_>) = pipeline
+            .run(&content)
+            .into_iter()
+            .partition(ValidationIssue::is_error);
+        if !errors.is_empty() {
+            return Err(ValidationError::ContentRejected(errors));
+        }
+
+        Ok((Self(content), warnings))
+    }
+
+    /// Content read back from a stored event, which was validated when it
+    /// was recorded
+    pub const fn recorded(content: String) -> Self {
+        Self(content)
     }
 
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Infer how significant the change to `new` is from the YAML structure:
+    /// removed keys or changed value types break consumers, added keys extend
+    /// the spec, and anything else only changes values
+    pub fn infer_bump(&self, new: &Self) -> BumpLevel {
+        match (
+            serde_yaml::from_str::<serde_yaml::Value>(&self.0),
+            serde_yaml::from_str::<serde_yaml::Value>(&new.0),
+        ) {
+            (Ok(old), Ok(new)) => structural_change(&old, &new),
+            _ => BumpLevel::Major,
+        }
+    }
+}
+
+fn structural_change(old: &serde_yaml::Value, new: &serde_yaml::Value) -> BumpLevel {
+    use serde_yaml::Value;
+
+    match (old, new) {
+        (Value::Mapping(old), Value::Mapping(new)) => {
+            let mut level = BumpLevel::Patch;
+            for (key, old_value) in old {
+                let change = new.get(key).map_or(BumpLevel::Major, |new_value| {
+                    structural_change(old_value, new_value)
+                });
+                level = level.max(change);
+            }
+            if new.keys().any(|key| !old.contains_key(key)) {
+                level = level.max(BumpLevel::Minor);
+            }
+            level
+        }
+        (Value::Sequence(old), Value::Sequence(new)) => {
+            let mut level = match new.len().cmp(&old.len()) {
+                std::cmp::Ordering::Less => BumpLevel::Major,
+                std::cmp::Ordering::Greater => BumpLevel::Minor,
+                std::cmp::Ordering::Equal => BumpLevel::Patch,
+            };
+            for (old_item, new_item) in old.iter().zip(new) {
+                level = level.max(structural_change(old_item, new_item));
+            }
+            level
+        }
+        (Value::Tagged(old), Value::Tagged(new)) if old.tag == new.tag => {
+            structural_change(&old.value, &new.value)
+        }
+        (old, new) if std::mem::discriminant(old) == std::mem::discriminant(new) => {
+            BumpLevel::Patch
+        }
+        _ => BumpLevel::Major,
+    }
+}
+
+/// Another spec this one relies on, at any published version matching `version`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecDependency {
+    pub spec_id: Uuid,
+    pub version: semver::VersionReq,
+}
+
+/// One spec version pinned by a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseEntry {
+    pub spec_id: Uuid,
+    pub version: u32,
+}
+
+/// A registered schema version that a spec's content must conform to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaRef {
+    pub schema_id: Uuid,
+    pub version: u32,
+}
+
+/// JSON Schema document spec content can be validated against
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDocument(serde_json::Value);
+
+impl SchemaDocument {
+    pub fn new(schema: serde_json::Value) -> Result<Self, ValidationError> {
+        jsonschema::validator_for(&schema)
+            .map_err(|e| ValidationError::InvalidSchema(e.to_string()))?;
+        Ok(Self(schema))
+    }
+
+    /// A schema read back from a stored event, which was checked when it was
+    /// recorded
+    pub const fn recorded(schema: serde_json::Value) -> Self {
+        Self(schema)
+    }
+
+    pub const fn as_value(&self) -> &serde_json::Value {
+        &self.0
+    }
+
+    /// Check the parsed YAML of `content` against the schema, reporting every
+    /// place it does not conform
+    pub fn validate(&self, content: &SpecContent) -> Result<(), Vec<SchemaViolation>> {
+        let validator = jsonschema::validator_for(&self.0).map_err(|e| {
+            vec![SchemaViolation {
+                path: String::new(),
+                message: e.to_string(),
+            }]
+        })?;
+
+        // Non-string mapping keys have no JSON equivalent
+        let instance = serde_yaml::from_str::<serde_yaml::Value>(content.as_str())
+            .ok()
+            .and_then(|yaml| serde_json::to_value(yaml).ok())
+            .ok_or_else(|| {
+                vec![SchemaViolation {
+                    path: String::new(),
+                    message: "Content cannot be represented as JSON".to_string(),
+                }]
+            })?;
+
+        let violations: Vec<_> = validator
+            .iter_errors(&instance)
+            .map(|error| SchemaViolation {
+                path: error.instance_path.to_string(),
+                message: error.to_string(),
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// One place where spec content does not match its schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON Pointer to the offending value, empty for the document root
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{path}: {}", self.message)
+    }
+}
+
+/// Where in a spec version a comment points; a comment without one is about
+/// the version as a whole
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CommentAnchor {
+    /// JSON Pointer into the parsed YAML, as reported for schema violations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<LineRange>,
+}
+
+/// Inclusive, 1-based range of lines in a spec's content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl CommentAnchor {
+    /// Check the anchor points at something that exists in `content`
+    pub fn validate(&self, content: &SpecContent) -> Result<(), ValidationError> {
+        if let Some(lines) = self.lines {
+            let count = content.as_str().lines().count();
+            if lines.start == 0 || lines.start > lines.end || lines.end as usize > count {
+                return Err(ValidationError::InvalidAnchor(format!(
+                    "lines {}-{} are outside the content's {count} lines",
+                    lines.start, lines.end
+                )));
+            }
+        }
+
+        if let Some(path) = &self.path {
+            if !path.starts_with('/') {
+                return Err(ValidationError::InvalidAnchor(format!(
+                    "{path:?} is not a JSON Pointer"
+                )));
+            }
+
+            let found = serde_yaml::from_str::<serde_yaml::Value>(content.as_str())
+                .ok()
+                .and_then(|yaml| serde_json::to_value(yaml).ok())
+                .is_some_and(|instance| instance.pointer(path).is_some());
+            if !found {
+                return Err(ValidationError::InvalidAnchor(format!(
+                    "{path:?} does not exist in the content"
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
+/// A template version a spec was rendered from
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateRef {
+    pub template_id: Uuid,
+    pub version: u32,
+}
+
+/// Type a template parameter's value must have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParameterType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+}
+
+impl ParameterType {
+    fn accepts(self, value: &serde_json::Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Integer => value.is_i64() || value.is_u64(),
+            Self::Number => value.is_number(),
+            Self::Boolean => value.is_boolean(),
+        }
+    }
+}
+
+impl fmt::Display for ParameterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String => write!(f, "string"),
+            Self::Integer => write!(f, "integer"),
+            Self::Number => write!(f, "number"),
+            Self::Boolean => write!(f, "boolean"),
+        }
+    }
+}
+
+impl FromStr for ParameterType {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "string" => Ok(Self::String),
+            "integer" => Ok(Self::Integer),
+            "number" => Ok(Self::Number),
+            "boolean" => Ok(Self::Boolean),
+            _ => Err(ValidationError::InvalidTemplate(format!(
+                "unknown parameter type {s:?}"
+            ))),
+        }
+    }
+}
+
+/// A value a template expects when it is rendered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateParameter {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: ParameterType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Used when no value is given; parameters without one are required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+}
+
+/// YAML body with `{{ name }}` placeholders for its declared parameters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateDocument {
+    body: String,
+    parameters: Vec<TemplateParameter>,
+}
+
+impl TemplateDocument {
+    pub fn new(body: String, parameters: Vec<TemplateParameter>) -> Result<Self, ValidationError> {
+        if body.trim().is_empty() {
+            return Err(ValidationError::EmptyContent);
+        }
+
+        for (i, parameter) in parameters.iter().enumerate() {
+            if !is_identifier(&parameter.name) {
+                return Err(ValidationError::InvalidTemplate(format!(
+                    "invalid parameter name {:?}",
+                    parameter.name
+                )));
+            }
+            if parameters[..i].iter().any(|p| p.name == parameter.name) {
+                return Err(ValidationError::InvalidTemplate(format!(
+                    "parameter {:?} is declared more than once",
+                    parameter.name
+                )));
+            }
+            if let Some(default) = &parameter.default {
+                if !parameter.kind.accepts(default) {
+                    return Err(ValidationError::InvalidTemplate(format!(
+                        "default for parameter {:?} is not of type {}",
+                        parameter.name, parameter.kind
+                    )));
+                }
+            }
+        }
+
+        for segment in segments(&body)? {
+            if let Segment::Placeholder(name) = segment {
+                if !parameters.iter().any(|p| p.name == name) {
+                    return Err(ValidationError::InvalidTemplate(format!(
+                        "placeholder {name:?} is not a declared parameter"
+                    )));
+                }
+            }
+        }
+
+        Ok(Self { body, parameters })
+    }
+
+    /// A template read back from a stored event, which was checked when it
+    /// was recorded
+    pub const fn recorded(body: String, parameters: Vec<TemplateParameter>) -> Self {
+        Self { body, parameters }
+    }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    pub fn parameters(&self) -> &[TemplateParameter] {
+        &self.parameters
+    }
+
+    /// Substitute each placeholder with its parameter's value, or its default
+    /// when none is given. Strings are inserted as-is, so the body decides
+    /// whether they are quoted.
+    pub fn render(
+        &self,
+        values: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<String, ValidationError> {
+        if let Some(name) = values
+            .keys()
+            .find(|name| !self.parameters.iter().any(|p| &p.name == *name))
+        {
+            return Err(ValidationError::InvalidParameters(format!(
+                "{name:?} is not a parameter of this template"
+            )));
+        }
+
+        let mut resolved = BTreeMap::new();
+        for parameter in &self.parameters {
+            let value = values
+                .get(&parameter.name)
+                .or(parameter.default.as_ref())
+                .ok_or_else(|| {
+                    ValidationError::InvalidParameters(format!("{:?} is required", parameter.name))
+                })?;
+            if !parameter.kind.accepts(value) {
+                return Err(ValidationError::InvalidParameters(format!(
+                    "{:?} must be of type {}",
+                    parameter.name, parameter.kind
+                )));
+            }
+            let text = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            resolved.insert(parameter.name.as_str(), text);
+        }
+
+        let mut rendered = String::with_capacity(self.body.len());
+        for segment in segments(&self.body)? {
+            match segment {
+                Segment::Text(text) => rendered.push_str(text),
+                Segment::Placeholder(name) => rendered.push_str(&resolved[name]),
+            }
+        }
+
+        Ok(rendered)
+    }
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split a template body into literal text and `{{ name }}` placeholders
+fn segments(body: &str) -> Result<Vec<Segment<'_>>, ValidationError> {
+    let mut segments = Vec::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find("{{") {
+        segments.push(Segment::Text(&rest[..start]));
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            ValidationError::InvalidTemplate("unterminated placeholder".to_string())
+        })?;
+        let name = after[..end].trim();
+        if !is_identifier(name) {
+            return Err(ValidationError::InvalidTemplate(format!(
+                "invalid placeholder {name:?}"
+            )));
+        }
+        segments.push(Segment::Placeholder(name));
+        rest = &after[end + 2..];
+    }
+    segments.push(Segment::Text(rest));
+
+    Ok(segments)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Which part of a semantic version an update increments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl BumpLevel {
+    /// The semantic version following `version` at this level
+    pub fn apply(self, version: &semver::Version) -> semver::Version {
+        match self {
+            Self::Major => semver::Version::new(version.major + 1, 0, 0),
+            Self::Minor => semver::Version::new(version.major, version.minor + 1, 0),
+            Self::Patch => semver::Version::new(version.major, version.minor, version.patch + 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Version(u32);
 
 impl Version {
@@ -74,6 +754,12 @@ impl Version {
     pub fn as_u32(self) -> u32 {
         self.0
     }
+
+    /// Semantic version of a revision recorded before semantic versions
+    /// were tracked, or of the first revision
+    pub fn legacy_semver(self) -> semver::Version {
+        semver::Version::new(u64::from(self.0), 0, 0)
+    }
 }
 
 impl fmt::Display for Version {
@@ -86,14 +772,42 @@ impl fmt::Display for Version {
 pub enum ValidationError {
     #[error("Name cannot be empty")]
     EmptyName,
-    #[error("Name too long (max 255 characters)")]
-    NameTooLong,
-    #[error("Name contains invalid characters")]
-    InvalidCharacters,
+    #[error("Name too long (max {max} characters)")]
+    NameTooLong { max: usize },
+    #[error("Name contains invalid characters (allowed: letters, digits and {allowed:?})")]
+    InvalidCharacters { allowed: String },
+    #[error("Invalid name segment: {0:?}")]
+    InvalidNameSegment(String),
     #[error("Content cannot be empty")]
     EmptyContent,
-    #[error("Content too large (max 2048 characters)")]
-    ContentTooLarge,
-    #[error("Invalid YAML content")]
-    InvalidYaml,
+    #[error("Content too large (max {max} bytes)")]
+    ContentTooLarge { max: usize },
+    #[error("Content is not valid UTF-8")]
+    InvalidEncoding,
+    #[error("Content failed validation: {}", format_issues(.0))]
+    ContentRejected(Vec<ValidationIssue>),
+    #[error("Invalid validation configuration: {0}")]
+    InvalidConfig(String),
+    #[error("At least one label is required")]
+    NoLabels,
+    #[error("Invalid label key: {0:?}")]
+    InvalidLabelKey(String),
+    #[error("Invalid label value: {0:?}")]
+    InvalidLabelValue(String),
+    #[error("Invalid label selector: {0:?}")]
+    InvalidLabelSelector(String),
+    #[error("Invalid JSON Schema: {0}")]
+    InvalidSchema(String),
+    #[error("Invalid template: {0}")]
+    InvalidTemplate(String),
+    #[error("Invalid template parameters: {0}")]
+    InvalidParameters(String),
+    #[error("Invalid comment anchor: {0}")]
+    InvalidAnchor(String),
+    #[error("Idempotency key must be 1 to {max} characters")]
+    InvalidIdempotencyKey { max: usize },
+    #[error("Invalid channel name: {0:?}")]
+    InvalidChannel(String),
+    #[error("Invalid tenant id: {0:?}")]
+    InvalidTenantId(String),
 }
@@ -3,8 +3,11 @@ use spec_server::domain::{
     aggregates::Spec,
     commands::{CreateSpec, PublishSpec, UpdateSpec},
     events::{EventMetadata, SpecEvent},
+    validation::{ValidationPolicy, ValidatorPipeline},
+    value_objects::TenantId,
 };
 use spec_server::infrastructure::event_store::SqliteEventStore;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 /// Demonstrates the basic workflow of creating, updating, and publishing a spec
@@ -16,6 +19,9 @@ async fn main() -> Result<()> {
     let event_store = SqliteEventStore::new("sqlite::memory:").await?;
     event_store.init_schema().await?;
 
+    // Everything here acts for the default tenant
+    let tenant = TenantId::default();
+
     // Example 1: Create a new spec
     println!("=== Creating a new spec ===");
 
@@ -32,10 +38,17 @@ rules:
 "#
         .to_string(),
         description: Some("Validates capitalized words without digits".to_string()),
+        schema: None,
+        labels: BTreeMap::new(),
+        template: None,
         created_by: "alice@example.com".to_string(),
     };
 
-    let events = Spec::create(create_cmd)?;
+    let events = Spec::create(
+        create_cmd,
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )?;
     let spec_id = match &events[0] {
         SpecEvent::Created(e) => e.spec_id,
         _ => panic!("Expected Created event"),
@@ -46,17 +59,22 @@ rules:
         causation_id: None,
         user_agent: Some("example-cli/1.0".to_string()),
         ip_address: Some("127.0.0.1".to_string()),
+        idempotency_key: None,
+        tenant_id: tenant.clone(),
     };
 
     event_store
-        .append_events(spec_id, events, metadata.clone())
+        .append_events(spec_id, events, Some(0), metadata.clone(), None)
         .await?;
     println!("Created spec with ID: {}", spec_id);
 
     // Load the spec from events
-    let events = event_store.get_events(spec_id, None).await?;
+    let events = event_store.get_events(&tenant, spec_id, None).await?;
     let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())?;
-    println!("Loaded spec: {} (version: {})", spec.name, spec.version);
+    println!(
+        "Loaded spec: {} (version: {})",
+        spec.name, spec.head_version
+    );
 
     // Example 2: Update the spec
     println!("\n=== Updating the spec ===");
@@ -77,41 +95,43 @@ rules:
 "#
         .to_string(),
         description: Some("Updated: Added minimum length requirement".to_string()),
+        bump: None,
+        schema: None,
         updated_by: "bob@example.com".to_string(),
     };
 
     let update_events = spec.handle_command(IntoSpecCommand::into(update_cmd))?;
     event_store
-        .append_events(spec_id, update_events, metadata.clone())
+        .append_events(spec_id, update_events, None, metadata.clone(), None)
         .await?;
 
     // Reload spec
-    let events = event_store.get_events(spec_id, None).await?;
+    let events = event_store.get_events(&tenant, spec_id, None).await?;
     let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())?;
-    println!("Updated spec to version: {}", spec.version);
+    println!("Updated spec to version: {}", spec.head_version);
 
     // Example 3: Publish the spec
     println!("\n=== Publishing the spec ===");
 
     let publish_cmd = PublishSpec {
         spec_id,
-        version: Some(spec.version.as_u32()),
+        version: Some(spec.head_version.as_u32()),
         published_by: "admin@example.com".to_string(),
     };
 
     let publish_events = spec.handle_command(IntoSpecCommand::into(publish_cmd))?;
     event_store
-        .append_events(spec_id, publish_events, metadata)
+        .append_events(spec_id, publish_events, None, metadata, None)
         .await?;
 
     // Reload spec to see final state
-    let events = event_store.get_events(spec_id, None).await?;
+    let events = event_store.get_events(&tenant, spec_id, None).await?;
     let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())?;
     println!("Spec state: {:?}", spec.state);
 
     // Example 4: Query all events for audit trail
     println!("\n=== Event History ===");
-    let all_events = event_store.get_events(spec_id, None).await?;
+    let all_events = event_store.get_events(&tenant, spec_id, None).await?;
     for (i, envelope) in all_events.iter().enumerate() {
         println!("Event {}: {:?}", i + 1, envelope.event);
     }
@@ -3,11 +3,14 @@ use spec_server::domain::{
     aggregates::Spec,
     commands::{CreateSpec, DeprecateSpec, PublishSpec, UpdateSpec},
     events::{EventMetadata, SpecEvent, SpecState},
+    validation::{ValidationPolicy, ValidatorPipeline},
+    value_objects::{LabelSelector, TenantId},
 };
 use spec_server::infrastructure::{
     event_processor::EventProcessorManager, event_store::SqliteEventStore,
     projections::ProjectionStore,
 };
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -26,6 +29,9 @@ async fn main() -> Result<()> {
     event_store.init_schema().await?;
     projection_store.init_schema().await?;
 
+    // Everything here acts for the default tenant
+    let tenant = TenantId::default();
+
     // Start event processor in background
     let manager = EventProcessorManager::new(event_store.clone(), projection_store.clone());
     let (_handle, _shutdown) = manager.start_background();
@@ -53,12 +59,19 @@ async fn main() -> Result<()> {
     let mut spec_ids = Vec::new();
 
     for (name, desc, user) in specs {
-        let events = Spec::create(CreateSpec {
-            name: name.to_string(),
-            content: format!("# {} spec\nversion: 1.0\nrules: []", name),
-            description: Some(desc.to_string()),
-            created_by: user.to_string(),
-        })?;
+        let events = Spec::create(
+            CreateSpec {
+                name: name.to_string(),
+                content: format!("# {} spec\nversion: 1.0\nrules: []", name),
+                description: Some(desc.to_string()),
+                schema: None,
+                labels: BTreeMap::new(),
+                template: None,
+                created_by: user.to_string(),
+            },
+            &ValidationPolicy::default(),
+            &ValidatorPipeline::default(),
+        )?;
 
         let spec_id = match &events[0] {
             SpecEvent::Created(e) => e.spec_id,
@@ -71,12 +84,16 @@ async fn main() -> Result<()> {
             .append_events(
                 spec_id,
                 events,
+                Some(0),
                 EventMetadata {
                     correlation_id: Some(Uuid::new_v4()),
                     causation_id: None,
                     user_agent: None,
                     ip_address: None,
+                    idempotency_key: None,
+                    tenant_id: tenant.clone(),
                 },
+                None,
             )
             .await?;
     }
@@ -87,7 +104,14 @@ async fn main() -> Result<()> {
     println!("\n=== Querying all draft specs ===");
 
     let draft_specs = projection_store
-        .list_by_state(Some(SpecState::Draft), 10, 0)
+        .list_by_state(
+            &tenant,
+            Some(SpecState::Draft),
+            false,
+            &LabelSelector::default(),
+            10,
+            0,
+        )
         .await?;
     for spec in &draft_specs {
         println!(
@@ -104,7 +128,7 @@ async fn main() -> Result<()> {
 
     for (spec_id, name) in &spec_ids[..2] {
         // Load current state
-        let events = event_store.get_events(*spec_id, None).await?;
+        let events = event_store.get_events(&tenant, *spec_id, None).await?;
         let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())?;
 
         // Publish
@@ -115,7 +139,13 @@ async fn main() -> Result<()> {
         }))?;
 
         event_store
-            .append_events(*spec_id, publish_events, EventMetadata::default())
+            .append_events(
+                *spec_id,
+                publish_events,
+                None,
+                EventMetadata::default(),
+                None,
+            )
             .await?;
 
         println!("Published: {}", name);
@@ -127,7 +157,14 @@ async fn main() -> Result<()> {
     println!("\n=== Querying published specs ===");
 
     let published_specs = projection_store
-        .list_by_state(Some(SpecState::Published), 10, 0)
+        .list_by_state(
+            &tenant,
+            Some(SpecState::Published),
+            false,
+            &LabelSelector::default(),
+            10,
+            0,
+        )
         .await?;
     for spec in &published_specs {
         println!("- {} (v{}) - Published", spec.name, spec.latest_version);
@@ -136,10 +173,10 @@ async fn main() -> Result<()> {
     // Query by name
     println!("\n=== Query by name ===");
 
-    if let Some(spec) = projection_store.get_by_name("auth-rules").await? {
+    if let Some(spec) = projection_store.get_by_name(&tenant, "auth-rules").await? {
         println!("Found spec 'auth-rules':");
         println!("  ID: {}", spec.id);
-        println!("  Version: {}", spec.version);
+        println!("  Version: {}", spec.head_version);
         println!("  State: {:?}", spec.state);
         println!("  Updated by: {}", spec.updated_by);
     }
@@ -148,7 +185,7 @@ async fn main() -> Result<()> {
     println!("\n=== Version history example ===");
 
     let (spec_id, name) = &spec_ids[0];
-    let events = event_store.get_events(*spec_id, None).await?;
+    let events = event_store.get_events(&tenant, *spec_id, None).await?;
     let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())?;
 
     // Update content
@@ -159,52 +196,71 @@ async fn main() -> Result<()> {
             name
         ),
         description: Some("Updated with new rules".to_string()),
+        bump: None,
+        schema: None,
         updated_by: "alice@example.com".to_string(),
     }))?;
 
     event_store
-        .append_events(*spec_id, update_events, EventMetadata::default())
+        .append_events(
+            *spec_id,
+            update_events,
+            None,
+            EventMetadata::default(),
+            None,
+        )
         .await?;
 
     // Wait for projection
     tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
 
     // Query current version
-    if let Some(current) = projection_store.get_by_id(*spec_id).await? {
+    if let Some(current) = projection_store.get_by_id(&tenant, *spec_id).await? {
         println!(
             "\nCurrent version of '{}': v{}",
-            current.name, current.version
+            current.name, current.head_version
         );
     }
 
     // Query specific version
-    if let Some((content, _desc)) = projection_store.get_version(*spec_id, 1).await? {
+    if let Some(revision) = projection_store.get_version(&tenant, *spec_id, 1).await? {
         println!(
-            "\nVersion 1 content preview: {}",
-            &content[..50.min(content.len())]
+            "\nVersion {} content preview: {}",
+            revision.semver,
+            &revision.content[..50.min(revision.content.len())]
         );
     }
 
     // Demonstrate deprecation
     println!("\n=== Deprecating a spec ===");
 
-    let events = event_store.get_events(*spec_id, None).await?;
+    let events = event_store.get_events(&tenant, *spec_id, None).await?;
     let spec = Spec::from_events(events.into_iter().map(|e| e.event).collect())?;
 
     let deprecate_events = spec.handle_command(IntoSpecCommand::into(DeprecateSpec {
         spec_id: *spec_id,
         reason: "Replaced by auth-rules-v2".to_string(),
+        successor_id: None,
+        sunset_at: None,
         deprecated_by: "admin@example.com".to_string(),
     }))?;
 
     event_store
-        .append_events(*spec_id, deprecate_events, EventMetadata::default())
+        .append_events(
+            *spec_id,
+            deprecate_events,
+            None,
+            EventMetadata::default(),
+            None,
+        )
         .await?;
 
     // Wait and query
     tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
 
-    let all_non_deleted = projection_store.list_by_state(None, 10, 0).await?;
+    let all_non_deleted = projection_store
+        .list_by_state(&tenant, None, false, &LabelSelector::default(), 10, 0)
+        .await?;
     println!("\nAll non-deleted specs:");
     for spec in all_non_deleted {
         println!(
@@ -0,0 +1,137 @@
+use std::io::Write;
+
+use sha2::{Digest, Sha256};
+use sqlx::SqliteConnection;
+
+use crate::domain::{errors::DomainError, value_objects::ValidationError};
+
+/// zstd level content is compressed at
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Size of the pieces content is streamed back in
+pub const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Largest upload accepted by default, before any validation policy applies
+pub const DEFAULT_MAX_UPLOAD_BYTES: usize = 16 * 1024 * 1024;
+
+/// Content-addressed blobs, shared by every event and version that references them
+pub(super) const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS content_blobs (
+        hash TEXT PRIMARY KEY,
+        size INTEGER NOT NULL,
+        data BLOB NOT NULL,
+        created_at TEXT NOT NULL
+    );
+";
+
+/// A stored blob, identified by the hex SHA-256 of its uncompressed bytes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentRef {
+    pub hash: String,
+    pub size: u64,
+}
+
+/// Hashes and compresses content as it arrives in chunks
+pub struct ContentUpload {
+    hasher: Sha256,
+    encoder: zstd::stream::write::Encoder<'static, Vec<u8>>,
+    size: usize,
+    max_size: usize,
+}
+
+impl ContentUpload {
+    pub fn new(max_size: usize) -> Result<Self, DomainError> {
+        Ok(Self {
+            hasher: Sha256::new(),
+            encoder: zstd::stream::write::Encoder::new(Vec::new(), COMPRESSION_LEVEL)
+                .map_err(|e| DomainError::EventStoreError(e.to_string()))?,
+            size: 0,
+            max_size,
+        })
+    }
+
+    /// Add the next chunk, rejecting uploads that grow past the limit
+    pub fn write(&mut self, chunk: &[u8]) -> Result<(), DomainError> {
+        self.size += chunk.len();
+        if self.size > self.max_size {
+            return Err(ValidationError::ContentTooLarge { max: self.max_size }.into());
+        }
+
+        self.hasher.update(chunk);
+        self.encoder
+            .write_all(chunk)
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))
+    }
+
+    fn finish(self) -> Result<(ContentRef, Vec<u8>), DomainError> {
+        let data = self
+            .encoder
+            .finish()
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        let content_ref = ContentRef {
+            hash: hex::encode(self.hasher.finalize()),
+            size: self.size as u64,
+        };
+
+        Ok((content_ref, data))
+    }
+}
+
+/// Store `content` unless a blob with the same hash already exists
+pub(super) async fn store(
+    conn: &mut SqliteConnection,
+    content: &[u8],
+) -> Result<ContentRef, DomainError> {
+    let mut upload = ContentUpload::new(usize::MAX)?;
+    upload.write(content)?;
+    store_upload(conn, upload).await
+}
+
+/// Store a finished upload unless a blob with the same hash already exists
+pub(super) async fn store_upload(
+    conn: &mut SqliteConnection,
+    upload: ContentUpload,
+) -> Result<ContentRef, DomainError> {
+    let (content_ref, data) = upload.finish()?;
+
+    sqlx::query(
+        "
+        INSERT OR IGNORE INTO content_blobs (hash, size, data, created_at)
+        VALUES (?, ?, ?, ?)
+        ",
+    )
+    .bind(&content_ref.hash)
+    .bind(i64::try_from(content_ref.size).unwrap_or(i64::MAX))
+    .bind(data)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    Ok(content_ref)
+}
+
+/// Load and decompress the blob stored under `hash`
+pub(super) async fn load(
+    conn: &mut SqliteConnection,
+    hash: &str,
+) -> Result<Option<Vec<u8>>, DomainError> {
+    let data = sqlx::query_scalar::<_, Vec<u8>>("SELECT data FROM content_blobs WHERE hash = ?")
+        .bind(hash)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    data.map(|data| decompress(&data)).transpose()
+}
+
+/// Decompress blob data selected alongside the rows that reference it
+pub(super) fn decompress(data: &[u8]) -> Result<Vec<u8>, DomainError> {
+    zstd::decode_all(data).map_err(|e| DomainError::EventStoreError(e.to_string()))
+}
+
+/// Decompress blob data that holds spec content
+pub(super) fn decompress_text(data: &[u8]) -> Result<String, DomainError> {
+    String::from_utf8(decompress(data)?).map_err(|e| DomainError::EventStoreError(e.to_string()))
+}
@@ -77,12 +77,8 @@ impl EventProcessor {
 
         let mut processed_count = 0;
 
-        for (aggregate_id, envelope) in events {
-            match self
-                .projection_store
-                .apply_event(aggregate_id, &envelope.event)
-                .await
-            {
+        for (_aggregate_id, envelope) in events {
+            match self.projection_store.apply_event(&envelope).await {
                 Ok(()) => {
                     processed_count += 1;
                 }
@@ -133,16 +129,7 @@ impl EventProcessorManager {
     pub async fn rebuild_projections(&self) -> Result<(), DomainError> {
         info!("Rebuilding all projections from events");
 
-        // Clear existing projections
-        sqlx::query("DELETE FROM spec_projections")
-            .execute(&self.projection_store.pool)
-            .await
-            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
-
-        sqlx::query("DELETE FROM spec_version_history")
-            .execute(&self.projection_store.pool)
-            .await
-            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        self.projection_store.clear().await?;
 
         // Process all events from the beginning
         let mut position = 0;
@@ -158,10 +145,8 @@ impl EventProcessorManager {
                 break;
             }
 
-            for (aggregate_id, envelope) in &events {
-                self.projection_store
-                    .apply_event(*aggregate_id, &envelope.event)
-                    .await?;
+            for (_aggregate_id, envelope) in &events {
+                self.projection_store.apply_event(envelope).await?;
             }
 
             position += i64::try_from(events.len()).unwrap_or(i64::MAX);
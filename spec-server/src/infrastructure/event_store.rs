@@ -1,22 +1,143 @@
+use std::{fmt, ops::RangeInclusive};
+
 use anyhow::Result;
-use chrono::Utc;
-use sqlx::{sqlite::SqlitePool, Row};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::{sqlite::SqlitePool, Row, SqliteConnection};
 use uuid::Uuid;
 
+use super::{
+    blob_store::{self, ContentRef, ContentUpload, DEFAULT_MAX_UPLOAD_BYTES},
+    name_registry::{self, NameReleasePolicy},
+    upcasters::{self, CURRENT_SCHEMA_VERSION},
+};
 use crate::domain::{
     errors::DomainError,
-    events::{EventEnvelope, EventMetadata, SpecEvent},
+    events::{EventEnvelope, EventMetadata, SpecEvent, SpecState},
+    value_objects::{TenantId, ValidationError},
 };
 
+/// How long a renamed spec's old name keeps resolving to it by default
+const DEFAULT_RENAME_ALIAS_PERIOD_DAYS: i64 = 30;
+
+/// How long a retried request is answered with its original events by default
+const DEFAULT_IDEMPOTENCY_WINDOW_HOURS: i64 = 24;
+
+/// Name reservations, maintained alongside events to keep names unique
+/// within each tenant
+const SPEC_NAMES_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS spec_names (
+        tenant_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        spec_id TEXT NOT NULL,
+        reserved_at TEXT NOT NULL,
+        released_at TEXT,
+        PRIMARY KEY (tenant_id, name)
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_spec_names_spec_id
+    ON spec_names(spec_id);
+";
+
+/// Events appended for requests made with an idempotency key
+const IDEMPOTENCY_KEYS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS idempotency_keys (
+        tenant_id TEXT NOT NULL,
+        key TEXT NOT NULL,
+        request_digest TEXT NOT NULL,
+        aggregate_id TEXT NOT NULL,
+        first_sequence INTEGER NOT NULL,
+        last_sequence INTEGER NOT NULL,
+        created_at TEXT NOT NULL,
+        PRIMARY KEY (tenant_id, key)
+    );
+";
+
+/// Further streams a batch request appended to, after the one recorded with
+/// its key
+const IDEMPOTENCY_KEY_APPENDS_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS idempotency_key_appends (
+        tenant_id TEXT NOT NULL,
+        key TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        aggregate_id TEXT NOT NULL,
+        first_sequence INTEGER NOT NULL,
+        last_sequence INTEGER NOT NULL,
+        PRIMARY KEY (tenant_id, key, position)
+    );
+";
+
+/// A request made with an idempotency key, told apart from other requests
+/// reusing the key by a digest of its command
+#[derive(Debug, Clone)]
+pub struct IdempotentRequest {
+    tenant_id: TenantId,
+    key: String,
+    digest: String,
+}
+
+impl IdempotentRequest {
+    /// The request behind `command`, if its metadata carries an idempotency key.
+    ///
+    /// Digests are only compared within the idempotency window, so the
+    /// command's `Debug` form identifies it well enough.
+    pub fn new(metadata: &EventMetadata, command: &impl fmt::Debug) -> Option<Self> {
+        metadata.idempotency_key.as_ref().map(|key| Self {
+            tenant_id: metadata.tenant_id.clone(),
+            key: key.clone(),
+            digest: hex::encode(Sha256::digest(format!("{command:?}"))),
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct SqliteEventStore {
     pool: SqlitePool,
+    name_release_policy: NameReleasePolicy,
+    rename_alias_period: Duration,
+    max_upload_bytes: usize,
+    idempotency_window: Duration,
 }
 
 impl SqliteEventStore {
     pub async fn new(database_url: &str) -> Result<Self> {
         let pool = SqlitePool::connect(database_url).await?;
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            name_release_policy: NameReleasePolicy::default(),
+            rename_alias_period: Duration::days(DEFAULT_RENAME_ALIAS_PERIOD_DAYS),
+            max_upload_bytes: DEFAULT_MAX_UPLOAD_BYTES,
+            idempotency_window: Duration::hours(DEFAULT_IDEMPOTENCY_WINDOW_HOURS),
+        })
+    }
+
+    /// Set when names of deleted specs become available for reuse
+    #[must_use]
+    pub fn with_name_release_policy(mut self, policy: NameReleasePolicy) -> Self {
+        self.name_release_policy = policy;
+        self
+    }
+
+    /// Set how long the old name of a renamed spec stays reserved as an alias
+    #[must_use]
+    pub fn with_rename_alias_period(mut self, period: Duration) -> Self {
+        self.rename_alias_period = period;
+        self
+    }
+
+    /// Set the largest content accepted through streaming uploads
+    #[must_use]
+    pub fn with_max_upload_bytes(mut self, max: usize) -> Self {
+        self.max_upload_bytes = max;
+        self
+    }
+
+    /// Set how long a request retried with the same idempotency key is
+    /// answered with its original events
+    #[must_use]
+    pub fn with_idempotency_window(mut self, window: Duration) -> Self {
+        self.idempotency_window = window;
+        self
     }
 
     pub async fn init_schema(&self) -> Result<()> {
@@ -29,7 +150,9 @@ impl SqliteEventStore {
                 event_type TEXT NOT NULL,
                 event_data TEXT NOT NULL,
                 metadata TEXT NOT NULL,
-                created_at TEXT NOT NULL
+                created_at TEXT NOT NULL,
+                schema_version INTEGER NOT NULL DEFAULT 1,
+                tenant_id TEXT NOT NULL DEFAULT 'default'
             );
 
             CREATE INDEX IF NOT EXISTS idx_events_aggregate_id 
@@ -49,14 +172,151 @@ impl SqliteEventStore {
         .execute(&self.pool)
         .await?;
 
+        let columns = self.table_columns("events").await?;
+
+        if !columns.iter().any(|column| column == "schema_version") {
+            // Events stored before versions were recorded are version 1
+            sqlx::query("ALTER TABLE events ADD COLUMN schema_version INTEGER NOT NULL DEFAULT 1")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        if !columns.iter().any(|column| column == "tenant_id") {
+            // Events stored before tenants existed belong to the default tenant
+            sqlx::query("ALTER TABLE events ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default'")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_events_tenant_type ON events(tenant_id, event_type)",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Specs stored before names were reserved need their reservations
+        let backfill_names = self.table_columns("spec_names").await?.is_empty();
+
+        self.key_by_tenant("spec_names", SPEC_NAMES_SCHEMA).await?;
+        self.key_by_tenant("idempotency_keys", IDEMPOTENCY_KEYS_SCHEMA)
+            .await?;
+
+        sqlx::query(SPEC_NAMES_SCHEMA).execute(&self.pool).await?;
+        sqlx::query(IDEMPOTENCY_KEYS_SCHEMA)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(IDEMPOTENCY_KEY_APPENDS_SCHEMA)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(blob_store::SCHEMA).execute(&self.pool).await?;
+
+        if backfill_names {
+            self.backfill_spec_names().await?;
+        }
+
         Ok(())
     }
 
+    /// Reserve the names of stored specs by replaying the events that claim
+    /// and release names, each as of when it was stored
+    async fn backfill_spec_names(&self) -> Result<()> {
+        let rows = sqlx::query(
+            "
+            SELECT e.event_data, e.schema_version, e.tenant_id, e.created_at,
+                   b.data AS content_data
+            FROM events e
+            LEFT JOIN content_blobs b ON b.hash = json_extract(e.event_data, '$.content_hash')
+            WHERE e.event_type IN ('created', 'restored', 'renamed', 'state_changed')
+            ORDER BY e.rowid
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        for row in rows {
+            let event = decode_event(
+                row.get("event_data"),
+                row.get("schema_version"),
+                row.get::<Option<Vec<u8>>, _>("content_data").as_deref(),
+            )?;
+            let tenant = TenantId::new(row.get("tenant_id"))?;
+            let stored_at = DateTime::parse_from_rfc3339(row.get("created_at"))?.to_utc();
+
+            match self
+                .update_name_index(&mut tx, &tenant, &event, stored_at)
+                .await
+            {
+                // Names were not unique before they were reserved; the spec
+                // that took a name first keeps it
+                Err(DomainError::DuplicateSpecName(name)) => {
+                    tracing::warn!("Name {name} is used by more than one spec of {tenant}");
+                }
+                result => result?,
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Column names of `table`, empty when it does not exist
+    async fn table_columns(&self, table: &str) -> Result<Vec<String>> {
+        Ok(
+            sqlx::query_scalar::<_, String>("SELECT name FROM pragma_table_info(?)")
+                .bind(table)
+                .fetch_all(&self.pool)
+                .await?,
+        )
+    }
+
+    /// Rebuild a table created before tenants existed under `schema`, which
+    /// keys it by tenant; its rows belong to the default tenant
+    async fn key_by_tenant(&self, table: &str, schema: &str) -> Result<()> {
+        let columns = self.table_columns(table).await?;
+        if columns.is_empty() || columns.iter().any(|column| column == "tenant_id") {
+            return Ok(());
+        }
+
+        let columns = columns.join(", ");
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::raw_sql(&format!(
+            "
+            ALTER TABLE {table} RENAME TO {table}_before_tenants;
+            {schema}
+            INSERT INTO {table} (tenant_id, {columns})
+            SELECT '{default}', {columns} FROM {table}_before_tenants;
+            DROP TABLE {table}_before_tenants;
+            ",
+            default = TenantId::DEFAULT,
+        ))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Append events to an aggregate's stream.
+    ///
+    /// When `expected_version` is given the append is rejected with
+    /// `DomainError::ConcurrencyConflict` unless the stream is still at that
+    /// sequence number (0 for a new stream).
+    ///
+    /// A `request` made with an idempotency key is recorded alongside the
+    /// events; if a concurrent request with the same key got there first,
+    /// nothing is appended and that request's events are returned instead.
     pub async fn append_events(
         &self,
         aggregate_id: Uuid,
         events: Vec<SpecEvent>,
+        expected_version: Option<i64>,
         metadata: EventMetadata,
+        request: Option<&IdempotentRequest>,
     ) -> Result<Vec<EventEnvelope>, DomainError> {
         let mut tx = self
             .pool
@@ -64,39 +324,154 @@ impl SqliteEventStore {
             .await
             .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
 
-        let last_sequence = sqlx::query_scalar::<_, i64>(
-            "SELECT COALESCE(MAX(sequence_number), 0) FROM events WHERE aggregate_id = ?",
-        )
-        .bind(aggregate_id.to_string())
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+        let now = Utc::now();
+
+        // Claim the key first, so a concurrent retry replays instead of failing
+        if let Some(request) = request {
+            if !self
+                .claim_idempotency_key(&mut tx, request, aggregate_id, events.len(), now)
+                .await?
+            {
+                tx.rollback()
+                    .await
+                    .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+                return self.replay_claimed(request).await;
+            }
+        }
+
+        let envelopes = self
+            .append_to_stream(
+                &mut tx,
+                aggregate_id,
+                events,
+                expected_version,
+                &metadata,
+                now,
+            )
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        Ok(envelopes)
+    }
+
+    /// Append events to several streams in one transaction, so either all of
+    /// them are stored or none are.
+    ///
+    /// Each append is checked against its expected version like
+    /// `append_events`; a stream may appear more than once, in order. A
+    /// `request` is recorded against the events of every append, so a retry
+    /// replays all of them.
+    pub async fn append_batch(
+        &self,
+        appends: Vec<(Uuid, Vec<SpecEvent>, Option<i64>)>,
+        metadata: EventMetadata,
+        request: Option<&IdempotentRequest>,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
 
-        let mut envelopes = Vec::new();
         let now = Utc::now();
+        let mut envelopes = Vec::new();
+
+        if let (Some(request), Some((aggregate_id, events, _))) = (request, appends.first()) {
+            if !self
+                .claim_idempotency_key(&mut tx, request, *aggregate_id, events.len(), now)
+                .await?
+            {
+                tx.rollback()
+                    .await
+                    .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+                return self.replay_claimed(request).await;
+            }
+        }
+
+        for (position, (aggregate_id, events, expected_version)) in appends.into_iter().enumerate()
+        {
+            let appended = self
+                .append_to_stream(
+                    &mut tx,
+                    aggregate_id,
+                    events,
+                    expected_version,
+                    &metadata,
+                    now,
+                )
+                .await?;
+
+            // The first append is recorded when the key is claimed
+            if let (Some(request), Some(first), Some(last), true) =
+                (request, appended.first(), appended.last(), position > 0)
+            {
+                record_batch_append(
+                    &mut tx,
+                    request,
+                    position,
+                    aggregate_id,
+                    first.sequence_number..=last.sequence_number,
+                )
+                .await?;
+            }
+
+            envelopes.extend(appended);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        Ok(envelopes)
+    }
+
+    async fn append_to_stream(
+        &self,
+        conn: &mut SqliteConnection,
+        aggregate_id: Uuid,
+        events: Vec<SpecEvent>,
+        expected_version: Option<i64>,
+        metadata: &EventMetadata,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let last_sequence = last_sequence(conn, aggregate_id).await?;
+
+        if let Some(expected) = expected_version {
+            if expected != last_sequence {
+                return Err(DomainError::ConcurrencyConflict {
+                    expected,
+                    actual: last_sequence,
+                });
+            }
+        }
+
+        let metadata_json = serde_json::to_string(metadata)
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        let mut envelopes = Vec::new();
 
         for (i, event) in events.into_iter().enumerate() {
             let event_id = Uuid::new_v4();
             let sequence_number = last_sequence + i64::try_from(i).unwrap_or(0) + 1;
 
-            let event_type = match &event {
-                SpecEvent::Created(_) => "created",
-                SpecEvent::Updated(_) => "updated",
-                SpecEvent::StateChanged(_) => "state_changed",
-            };
+            let event_type = event_type(&event);
 
-            let event_data = serde_json::to_string(&event)
-                .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+            self.update_name_index(&mut *conn, &metadata.tenant_id, &event, now)
+                .await?;
 
-            let metadata_json = serde_json::to_string(&metadata)
-                .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+            let event_data = encode_event(&mut *conn, &event).await?;
 
             sqlx::query(
                 "
                 INSERT INTO events (
                     event_id, aggregate_id, sequence_number, 
-                    event_type, event_data, metadata, created_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?)
+                    event_type, event_data, metadata, created_at, schema_version, tenant_id
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
                 ",
             )
             .bind(event_id.to_string())
@@ -106,9 +481,23 @@ impl SqliteEventStore {
             .bind(&event_data)
             .bind(&metadata_json)
             .bind(now.to_rfc3339())
-            .execute(&mut *tx)
+            .bind(CURRENT_SCHEMA_VERSION)
+            .bind(metadata.tenant_id.as_str())
+            .execute(&mut *conn)
             .await
-            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+            .map_err(|e| {
+                // Another writer appended at this sequence number first
+                if e.as_database_error()
+                    .is_some_and(sqlx::error::DatabaseError::is_unique_violation)
+                {
+                    DomainError::ConcurrencyConflict {
+                        expected: last_sequence,
+                        actual: sequence_number,
+                    }
+                } else {
+                    DomainError::EventStoreError(e.to_string())
+                }
+            })?;
 
             envelopes.push(EventEnvelope {
                 event_id,
@@ -119,15 +508,383 @@ impl SqliteEventStore {
             });
         }
 
-        tx.commit()
+        Ok(envelopes)
+    }
+
+    /// Events appended for an earlier request with the same idempotency key,
+    /// if it was made within the idempotency window.
+    ///
+    /// Fails with `DomainError::IdempotencyKeyReused` when the key was first
+    /// used for a different request.
+    pub async fn replay(
+        &self,
+        request: Option<&IdempotentRequest>,
+    ) -> Result<Option<Vec<EventEnvelope>>, DomainError> {
+        Ok(self
+            .replay_appends(request)
+            .await?
+            .map(|appends| appends.into_iter().flatten().collect()))
+    }
+
+    /// Like `replay`, with the events of each append of a batch kept apart
+    pub async fn replay_appends(
+        &self,
+        request: Option<&IdempotentRequest>,
+    ) -> Result<Option<Vec<Vec<EventEnvelope>>>, DomainError> {
+        let Some(request) = request else {
+            return Ok(None);
+        };
+
+        let row = sqlx::query(
+            "
+            SELECT request_digest, aggregate_id, first_sequence, last_sequence
+            FROM idempotency_keys
+            WHERE tenant_id = ? AND key = ? AND created_at > ?
+            ",
+        )
+        .bind(request.tenant_id.as_str())
+        .bind(&request.key)
+        .bind((Utc::now() - self.idempotency_window).to_rfc3339())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let digest: String = row.get("request_digest");
+        if digest != request.digest {
+            return Err(DomainError::IdempotencyKeyReused(request.key.clone()));
+        }
+
+        let later = sqlx::query(
+            "
+            SELECT aggregate_id, first_sequence, last_sequence
+            FROM idempotency_key_appends
+            WHERE tenant_id = ? AND key = ?
+            ORDER BY position
+            ",
+        )
+        .bind(request.tenant_id.as_str())
+        .bind(&request.key)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        let mut appends = Vec::with_capacity(later.len() + 1);
+        for row in std::iter::once(&row).chain(&later) {
+            let aggregate_id: String = row.get("aggregate_id");
+            let aggregate_id = Uuid::parse_str(&aggregate_id)
+                .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+            let first_sequence: i64 = row.get("first_sequence");
+            let last_sequence: i64 = row.get("last_sequence");
+
+            appends.push(
+                self.get_events(&request.tenant_id, aggregate_id, Some(first_sequence - 1))
+                    .await?
+                    .into_iter()
+                    .take_while(|envelope| envelope.sequence_number <= last_sequence)
+                    .collect(),
+            );
+        }
+
+        Ok(Some(appends))
+    }
+
+    /// Record that a request's `events_len` events go at the end of a stream,
+    /// returning false when the key is still held by another request
+    async fn claim_idempotency_key(
+        &self,
+        conn: &mut SqliteConnection,
+        request: &IdempotentRequest,
+        aggregate_id: Uuid,
+        events_len: usize,
+        now: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        let last_sequence = last_sequence(conn, aggregate_id).await?;
+        let events_len = i64::try_from(events_len).unwrap_or(i64::MAX);
+
+        self.record_idempotency_key(
+            conn,
+            request,
+            aggregate_id,
+            last_sequence + 1..=last_sequence + events_len,
+            now,
+        )
+        .await
+    }
+
+    /// Events of the request that claimed the key first
+    async fn replay_claimed(
+        &self,
+        request: &IdempotentRequest,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        self.replay(Some(request)).await?.ok_or_else(|| {
+            DomainError::EventStoreError(format!(
+                "Idempotency key {:?} is held by another request",
+                request.key
+            ))
+        })
+    }
+
+    /// Record the sequence numbers a request's events are appended at,
+    /// returning false when the key is still held by another request
+    async fn record_idempotency_key(
+        &self,
+        conn: &mut SqliteConnection,
+        request: &IdempotentRequest,
+        aggregate_id: Uuid,
+        sequences: RangeInclusive<i64>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        // Keys outside the window can be reused
+        sqlx::query("DELETE FROM idempotency_keys WHERE created_at <= ?")
+            .bind((now - self.idempotency_window).to_rfc3339())
+            .execute(&mut *conn)
             .await
             .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+        sqlx::query(
+            "
+            DELETE FROM idempotency_key_appends
+            WHERE NOT EXISTS (
+                SELECT 1 FROM idempotency_keys k
+                WHERE k.tenant_id = idempotency_key_appends.tenant_id
+                  AND k.key = idempotency_key_appends.key
+            )
+            ",
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
 
-        Ok(envelopes)
+        if sequences.is_empty() {
+            return Ok(true);
+        }
+
+        let result = sqlx::query(
+            "
+            INSERT INTO idempotency_keys (
+                tenant_id, key, request_digest, aggregate_id, first_sequence, last_sequence,
+                created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(tenant_id, key) DO NOTHING
+            ",
+        )
+        .bind(request.tenant_id.as_str())
+        .bind(&request.key)
+        .bind(&request.digest)
+        .bind(aggregate_id.to_string())
+        .bind(sequences.start())
+        .bind(sequences.end())
+        .bind(now.to_rfc3339())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_name_index(
+        &self,
+        conn: &mut SqliteConnection,
+        tenant: &TenantId,
+        event: &SpecEvent,
+        now: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        match event {
+            SpecEvent::Created(e) => {
+                name_registry::reserve(conn, tenant, &e.name, e.spec_id, now).await
+            }
+            SpecEvent::Restored(e) => {
+                name_registry::reserve(conn, tenant, &e.name, e.spec_id, now).await
+            }
+            SpecEvent::Renamed(e) => {
+                name_registry::rename(
+                    conn,
+                    tenant,
+                    e.spec_id,
+                    &e.old_name,
+                    &e.new_name,
+                    self.rename_alias_period,
+                    now,
+                )
+                .await
+            }
+            SpecEvent::StateChanged(e) if e.to_state == SpecState::Deleted => {
+                name_registry::release(conn, e.spec_id, self.name_release_policy, now).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Start a streaming upload bounded by the configured size limit
+    pub fn start_upload(&self) -> Result<ContentUpload, DomainError> {
+        ContentUpload::new(self.max_upload_bytes)
+    }
+
+    /// Store uploaded content, returning the hash it can be referenced by
+    pub async fn put_content(&self, upload: ContentUpload) -> Result<ContentRef, DomainError> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        blob_store::store_upload(&mut conn, upload).await
+    }
+
+    /// Load stored content by hash
+    pub async fn get_content(&self, hash: &str) -> Result<Option<Vec<u8>>, DomainError> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        blob_store::load(&mut conn, hash).await
+    }
+
+    /// Load uploaded content by hash as spec text, for commands that reference it
+    pub async fn load_content(&self, hash: &str) -> Result<String, DomainError> {
+        let content = self
+            .get_content(hash)
+            .await?
+            .ok_or_else(|| DomainError::ContentNotFound(hash.to_string()))?;
+
+        String::from_utf8(content).map_err(|_| ValidationError::InvalidEncoding.into())
+    }
+
+    /// Specs that have declared a dependency on `spec_id` at some point;
+    /// callers check whether each still does
+    pub async fn find_dependents(
+        &self,
+        tenant: &TenantId,
+        spec_id: Uuid,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let ids = sqlx::query_scalar::<_, String>(
+            "
+            SELECT DISTINCT aggregate_id
+            FROM events
+            WHERE tenant_id = ? AND event_type = 'dependencies_set'
+              AND EXISTS (
+                  SELECT 1 FROM json_each(event_data, '$.dependencies')
+                  WHERE json_extract(value, '$.spec_id') = ?
+              )
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(spec_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        ids.iter()
+            .map(|id| Uuid::parse_str(id).map_err(|e| DomainError::EventStoreError(e.to_string())))
+            .collect()
+    }
+
+    /// Release created under `name`, if any
+    pub async fn find_release(
+        &self,
+        tenant: &TenantId,
+        name: &str,
+    ) -> Result<Option<Uuid>, DomainError> {
+        let id = sqlx::query_scalar::<_, String>(
+            "
+            SELECT aggregate_id
+            FROM events
+            WHERE tenant_id = ? AND event_type = 'release_created'
+              AND json_extract(event_data, '$.name') = ?
+            LIMIT 1
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        id.map(|id| Uuid::parse_str(&id).map_err(|e| DomainError::EventStoreError(e.to_string())))
+            .transpose()
+    }
+
+    /// Releases that have been published, most recently published first;
+    /// callers check whether each is still published
+    pub async fn published_releases(&self, tenant: &TenantId) -> Result<Vec<Uuid>, DomainError> {
+        let ids = sqlx::query_scalar::<_, String>(
+            "
+            SELECT aggregate_id
+            FROM events
+            WHERE tenant_id = ? AND event_type = 'release_published'
+            GROUP BY aggregate_id
+            ORDER BY MAX(rowid) DESC
+            ",
+        )
+        .bind(tenant.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        ids.iter()
+            .map(|id| Uuid::parse_str(id).map_err(|e| DomainError::EventStoreError(e.to_string())))
+            .collect()
+    }
+
+    /// Stream holding the events of `tenant` itself, if it was created
+    pub async fn find_tenant(&self, tenant: &TenantId) -> Result<Option<Uuid>, DomainError> {
+        let id = sqlx::query_scalar::<_, String>(
+            "
+            SELECT aggregate_id
+            FROM events
+            WHERE tenant_id = ? AND event_type = 'tenant_created'
+            LIMIT 1
+            ",
+        )
+        .bind(tenant.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        id.map(|id| Uuid::parse_str(&id).map_err(|e| DomainError::EventStoreError(e.to_string())))
+            .transpose()
+    }
+
+    /// Resolve a spec name, or a recent alias of a renamed spec, to its id
+    pub async fn resolve_name(
+        &self,
+        tenant: &TenantId,
+        name: &str,
+    ) -> Result<Option<Uuid>, DomainError> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        name_registry::resolve(&mut conn, tenant, name, Utc::now()).await
+    }
+
+    /// Current names at or in the folder `path`, with the specs holding them
+    pub async fn resolve_folder(
+        &self,
+        tenant: &TenantId,
+        path: &str,
+    ) -> Result<Vec<(String, Uuid)>, DomainError> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+        name_registry::subtree(&mut conn, tenant, path).await
     }
 
+    /// Events of an aggregate after `from_sequence`; streams of other
+    /// tenants read as empty
     pub async fn get_events(
         &self,
+        tenant: &TenantId,
         aggregate_id: Uuid,
         from_sequence: Option<i64>,
     ) -> Result<Vec<EventEnvelope>, DomainError> {
@@ -135,12 +892,15 @@ impl SqliteEventStore {
 
         let rows = sqlx::query(
             "
-            SELECT event_id, sequence_number, event_data, metadata
-            FROM events
-            WHERE aggregate_id = ? AND sequence_number > ?
-            ORDER BY sequence_number
+            SELECT e.event_id, e.sequence_number, e.event_data, e.schema_version,
+                   e.metadata, b.data AS content_data
+            FROM events e
+            LEFT JOIN content_blobs b ON b.hash = json_extract(e.event_data, '$.content_hash')
+            WHERE e.tenant_id = ? AND e.aggregate_id = ? AND e.sequence_number > ?
+            ORDER BY e.sequence_number
             ",
         )
+        .bind(tenant.as_str())
         .bind(aggregate_id.to_string())
         .bind(from_sequence)
         .fetch_all(&self.pool)
@@ -153,10 +913,11 @@ impl SqliteEventStore {
             let event_id: String = row.get("event_id");
             let sequence_number: i64 = row.get("sequence_number");
             let event_data: String = row.get("event_data");
+            let schema_version: i64 = row.get("schema_version");
             let metadata_json: String = row.get("metadata");
+            let content_data: Option<Vec<u8>> = row.get("content_data");
 
-            let event: SpecEvent = serde_json::from_str(&event_data)
-                .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+            let event = decode_event(&event_data, schema_version, content_data.as_deref())?;
 
             let metadata: EventMetadata = serde_json::from_str(&metadata_json)
                 .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
@@ -181,10 +942,12 @@ impl SqliteEventStore {
     ) -> Result<Vec<(Uuid, EventEnvelope)>, DomainError> {
         let rows = sqlx::query(
             "
-            SELECT rowid, event_id, aggregate_id, sequence_number, event_data, metadata
-            FROM events
-            WHERE rowid > ?
-            ORDER BY rowid
+            SELECT e.rowid, e.event_id, e.aggregate_id, e.sequence_number, e.event_data,
+                   e.schema_version, e.metadata, b.data AS content_data
+            FROM events e
+            LEFT JOIN content_blobs b ON b.hash = json_extract(e.event_data, '$.content_hash')
+            WHERE e.rowid > ?
+            ORDER BY e.rowid
             LIMIT ?
             ",
         )
@@ -201,10 +964,11 @@ impl SqliteEventStore {
             let aggregate_id: String = row.get("aggregate_id");
             let sequence_number: i64 = row.get("sequence_number");
             let event_data: String = row.get("event_data");
+            let schema_version: i64 = row.get("schema_version");
             let metadata_json: String = row.get("metadata");
+            let content_data: Option<Vec<u8>> = row.get("content_data");
 
-            let event: SpecEvent = serde_json::from_str(&event_data)
-                .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+            let event = decode_event(&event_data, schema_version, content_data.as_deref())?;
 
             let metadata: EventMetadata = serde_json::from_str(&metadata_json)
                 .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
@@ -227,3 +991,136 @@ impl SqliteEventStore {
         Ok(results)
     }
 }
+
+/// Sequence number of the last event in an aggregate's stream, 0 when empty
+async fn last_sequence(
+    conn: &mut SqliteConnection,
+    aggregate_id: Uuid,
+) -> Result<i64, DomainError> {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(MAX(sequence_number), 0) FROM events WHERE aggregate_id = ?",
+    )
+    .bind(aggregate_id.to_string())
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| DomainError::EventStoreError(e.to_string()))
+}
+
+/// Record the sequence numbers a later append of a batch request went to
+async fn record_batch_append(
+    conn: &mut SqliteConnection,
+    request: &IdempotentRequest,
+    position: usize,
+    aggregate_id: Uuid,
+    sequences: RangeInclusive<i64>,
+) -> Result<(), DomainError> {
+    sqlx::query(
+        "
+        INSERT INTO idempotency_key_appends (
+            tenant_id, key, position, aggregate_id, first_sequence, last_sequence
+        ) VALUES (?, ?, ?, ?, ?, ?)
+        ",
+    )
+    .bind(request.tenant_id.as_str())
+    .bind(&request.key)
+    .bind(i64::try_from(position).unwrap_or(i64::MAX))
+    .bind(aggregate_id.to_string())
+    .bind(sequences.start())
+    .bind(sequences.end())
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Name an event is stored under in the `event_type` column
+const fn event_type(event: &SpecEvent) -> &'static str {
+    match event {
+        SpecEvent::Created(_) => "created",
+        SpecEvent::Updated(_) => "updated",
+        SpecEvent::StateChanged(_) => "state_changed",
+        SpecEvent::Restored(_) => "restored",
+        SpecEvent::Reverted(_) => "reverted",
+        SpecEvent::Renamed(_) => "renamed",
+        SpecEvent::TransitionScheduled(_) => "transition_scheduled",
+        SpecEvent::ScheduleCancelled(_) => "schedule_cancelled",
+        SpecEvent::ScheduleExecuted(_) => "schedule_executed",
+        SpecEvent::ReviewRequested(_) => "review_requested",
+        SpecEvent::ReviewApproved(_) => "review_approved",
+        SpecEvent::ReviewRejected(_) => "review_rejected",
+        SpecEvent::ReviewWithdrawn(_) => "review_withdrawn",
+        SpecEvent::LabelsAdded(_) => "labels_added",
+        SpecEvent::LabelsRemoved(_) => "labels_removed",
+        SpecEvent::DependenciesSet(_) => "dependencies_set",
+        SpecEvent::CommentAdded(_) => "comment_added",
+        SpecEvent::CommentEdited(_) => "comment_edited",
+        SpecEvent::CommentResolved(_) => "comment_resolved",
+        SpecEvent::Promoted(_) => "promoted",
+        SpecEvent::Demoted(_) => "demoted",
+        SpecEvent::SunsetFailed(_) => "sunset_failed",
+        SpecEvent::SchemaRegistered(_) => "schema_registered",
+        SpecEvent::SchemaUpdated(_) => "schema_updated",
+        SpecEvent::TemplateRegistered(_) => "template_registered",
+        SpecEvent::TemplateUpdated(_) => "template_updated",
+        SpecEvent::ReleaseCreated(_) => "release_created",
+        SpecEvent::ReleasePublished(_) => "release_published",
+        SpecEvent::ReleaseRolledBack(_) => "release_rolled_back",
+        SpecEvent::TenantCreated(_) => "tenant_created",
+        SpecEvent::TenantSuspended(_) => "tenant_suspended",
+    }
+}
+
+/// Serialize an event, moving any spec content into the blob table.
+///
+/// The event keeps a `content_hash` in place of its content, so repeated
+/// content across versions is only stored once.
+async fn encode_event(
+    conn: &mut SqliteConnection,
+    event: &SpecEvent,
+) -> Result<String, DomainError> {
+    let content = match event {
+        SpecEvent::Created(e) => Some(&e.content),
+        SpecEvent::Updated(e) => Some(&e.content),
+        SpecEvent::Reverted(e) => Some(&e.content),
+        _ => None,
+    };
+
+    let mut data =
+        serde_json::to_value(event).map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    if let (Some(content), Some(fields)) = (content, data.as_object_mut()) {
+        let content_ref = blob_store::store(conn, content.as_bytes()).await?;
+        fields.remove("content");
+        fields.insert("content_hash".to_string(), content_ref.hash.into());
+    }
+
+    serde_json::to_string(&data).map_err(|e| DomainError::EventStoreError(e.to_string()))
+}
+
+/// Deserialize an event, upcasting it from the schema version it was stored
+/// at and restoring content referenced by hash.
+///
+/// Events written before content moved to blobs still carry it inline.
+fn decode_event(
+    event_data: &str,
+    schema_version: i64,
+    content_data: Option<&[u8]>,
+) -> Result<SpecEvent, DomainError> {
+    let mut data: serde_json::Value = serde_json::from_str(event_data)
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    let schema_version =
+        u32::try_from(schema_version).map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+    upcasters::upcast(&mut data, schema_version)?;
+
+    if let (Some(content_data), Some(fields)) = (content_data, data.as_object_mut()) {
+        fields.remove("content_hash");
+        fields.insert(
+            "content".to_string(),
+            blob_store::decompress_text(content_data)?.into(),
+        );
+    }
+
+    serde_json::from_value(data).map_err(|e| DomainError::EventStoreError(e.to_string()))
+}
@@ -1,4 +1,13 @@
+pub mod blob_store;
 pub mod event_processor;
 pub mod event_store;
+pub mod name_registry;
 pub mod projections;
+pub mod release_registry;
 pub mod repositories;
+pub mod scheduler;
+pub mod schema_registry;
+pub mod sunset_processor;
+pub mod template_registry;
+pub mod tenant_registry;
+pub mod upcasters;
@@ -0,0 +1,227 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqliteConnection;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::domain::{
+    errors::DomainError,
+    value_objects::{TenantId, NAME_SEPARATOR},
+};
+
+/// Controls when the name of a deleted spec becomes available again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NameReleasePolicy {
+    /// Release the name as soon as the spec is deleted
+    #[default]
+    Immediate,
+    /// Keep the name reserved for a period after deletion
+    GracePeriod(Duration),
+    /// Never release the name of a deleted spec
+    Never,
+}
+
+impl FromStr for NameReleasePolicy {
+    type Err = anyhow::Error;
+
+    /// Parses `immediate`, `never` or `grace:<seconds>`, where the grace
+    /// period cannot be negative
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "immediate" => Ok(Self::Immediate),
+            "never" => Ok(Self::Never),
+            _ => {
+                let seconds = s
+                    .strip_prefix("grace:")
+                    .and_then(|secs| secs.parse::<i64>().ok())
+                    .filter(|secs| *secs >= 0)
+                    .ok_or_else(|| anyhow!("Invalid name release policy: {s}"))?;
+                Ok(Self::GracePeriod(Duration::seconds(seconds)))
+            }
+        }
+    }
+}
+
+/// Reserve `name` for `spec_id` within its tenant, failing if another spec
+/// holds it.
+///
+/// Reservations whose release time has passed are discarded first, and
+/// re-reserving a name the spec already holds cancels any pending release.
+pub(super) async fn reserve(
+    conn: &mut SqliteConnection,
+    tenant: &TenantId,
+    name: &str,
+    spec_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<(), DomainError> {
+    sqlx::query(
+        "
+        DELETE FROM spec_names
+        WHERE tenant_id = ? AND name = ? AND released_at IS NOT NULL AND released_at <= ?
+        ",
+    )
+    .bind(tenant.as_str())
+    .bind(name)
+    .bind(now.to_rfc3339())
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    let holder = sqlx::query_scalar::<_, String>(
+        "SELECT spec_id FROM spec_names WHERE tenant_id = ? AND name = ?",
+    )
+    .bind(tenant.as_str())
+    .bind(name)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    match holder {
+        Some(holder) if holder == spec_id.to_string() => {
+            sqlx::query(
+                "UPDATE spec_names SET released_at = NULL WHERE tenant_id = ? AND name = ?",
+            )
+            .bind(tenant.as_str())
+            .bind(name)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+        }
+        Some(_) => return Err(DomainError::DuplicateSpecName(name.to_string())),
+        None => {
+            sqlx::query(
+                "
+                INSERT INTO spec_names (tenant_id, name, spec_id, reserved_at, released_at)
+                VALUES (?, ?, ?, ?, NULL)
+                ",
+            )
+            .bind(tenant.as_str())
+            .bind(name)
+            .bind(spec_id.to_string())
+            .bind(now.to_rfc3339())
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                if e.as_database_error()
+                    .is_some_and(sqlx::error::DatabaseError::is_unique_violation)
+                {
+                    DomainError::DuplicateSpecName(name.to_string())
+                } else {
+                    DomainError::EventStoreError(e.to_string())
+                }
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Move a spec to `new_name`, keeping `old_name` as an alias of the spec
+/// until `alias_period` has passed
+pub(super) async fn rename(
+    conn: &mut SqliteConnection,
+    tenant: &TenantId,
+    spec_id: Uuid,
+    old_name: &str,
+    new_name: &str,
+    alias_period: Duration,
+    now: DateTime<Utc>,
+) -> Result<(), DomainError> {
+    reserve(conn, tenant, new_name, spec_id, now).await?;
+
+    sqlx::query("UPDATE spec_names SET released_at = ? WHERE name = ? AND spec_id = ?")
+        .bind((now + alias_period).to_rfc3339())
+        .bind(old_name)
+        .bind(spec_id.to_string())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Find the spec of `tenant` holding `name`, either as its current name or
+/// as an alias that has not expired yet
+pub(super) async fn resolve(
+    conn: &mut SqliteConnection,
+    tenant: &TenantId,
+    name: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<Uuid>, DomainError> {
+    let spec_id = sqlx::query_scalar::<_, String>(
+        "
+        SELECT spec_id FROM spec_names
+        WHERE tenant_id = ? AND name = ? AND (released_at IS NULL OR released_at > ?)
+        ",
+    )
+    .bind(tenant.as_str())
+    .bind(name)
+    .bind(now.to_rfc3339())
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    spec_id
+        .map(|id| Uuid::parse_str(&id).map_err(|e| DomainError::EventStoreError(e.to_string())))
+        .transpose()
+}
+
+/// Specs of `tenant` currently named `path` or holding a name in the folder
+/// `path`, ordered by name; aliases are left out
+pub(super) async fn subtree(
+    conn: &mut SqliteConnection,
+    tenant: &TenantId,
+    path: &str,
+) -> Result<Vec<(String, Uuid)>, DomainError> {
+    let folder = format!("{path}{NAME_SEPARATOR}");
+
+    let rows = sqlx::query_as::<_, (String, String)>(
+        "
+        SELECT name, spec_id FROM spec_names
+        WHERE tenant_id = ? AND released_at IS NULL AND (name = ? OR substr(name, 1, ?) = ?)
+        ORDER BY name
+        ",
+    )
+    .bind(tenant.as_str())
+    .bind(path)
+    .bind(i64::try_from(folder.chars().count()).unwrap_or(i64::MAX))
+    .bind(&folder)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    rows.into_iter()
+        .map(|(name, id)| {
+            let id =
+                Uuid::parse_str(&id).map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+            Ok((name, id))
+        })
+        .collect()
+}
+
+/// Release the names held by `spec_id` according to `policy`
+pub(super) async fn release(
+    conn: &mut SqliteConnection,
+    spec_id: Uuid,
+    policy: NameReleasePolicy,
+    now: DateTime<Utc>,
+) -> Result<(), DomainError> {
+    let query = match policy {
+        NameReleasePolicy::Immediate => {
+            sqlx::query("DELETE FROM spec_names WHERE spec_id = ?").bind(spec_id.to_string())
+        }
+        NameReleasePolicy::GracePeriod(grace) => {
+            sqlx::query("UPDATE spec_names SET released_at = ? WHERE spec_id = ?")
+                .bind((now + grace).to_rfc3339())
+                .bind(spec_id.to_string())
+        }
+        NameReleasePolicy::Never => return Ok(()),
+    };
+
+    query
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::EventStoreError(e.to_string()))?;
+
+    Ok(())
+}
@@ -1,28 +1,86 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use sqlx::{sqlite::SqlitePool, Row};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePool, QueryBuilder, Row, Sqlite, SqliteConnection};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use super::blob_store;
 use crate::domain::{
     errors::DomainError,
-    events::{SpecEvent, SpecState},
+    events::{
+        EventEnvelope, ReleaseState, ReviewStatus, ScheduledTransition, SpecEvent, SpecState,
+        TenantState,
+    },
+    value_objects::{
+        CommentAnchor, LabelRequirement, LabelSelector, LineRange, SchemaRef, SpecDependency,
+        SpecName, TemplateParameter, TemplateRef, TenantId, Version,
+    },
 };
 
 /// Read model for current spec state
 #[derive(Debug, Clone)]
 pub struct SpecProjection {
     pub id: Uuid,
+    pub tenant_id: TenantId,
     pub name: String,
     pub content: String,
+    /// Hash the content is stored under in the blob table
+    pub content_hash: String,
     pub description: Option<String>,
-    pub version: u32,
+    /// Latest revision, which may be a pending edit of a published spec
+    pub head_version: u32,
+    /// Semantic version of the head revision
+    pub semver: semver::Version,
+    /// Revision consumers should read, if any has been published
+    pub published_version: Option<u32>,
     pub state: SpecState,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: String,
     pub updated_by: String,
+    /// Sequence number of the last event applied, used for optimistic concurrency
+    pub stream_version: i64,
+    /// Migration guidance, set once the spec has been deprecated
+    pub deprecation: Option<SpecDeprecation>,
+    /// Review of the head version, if one has been requested
+    pub review: Option<SpecReview>,
+    pub labels: BTreeMap<String, String>,
+    /// Schema the content conforms to, if one was declared
+    pub schema: Option<SchemaRef>,
+    /// Template version the spec was rendered from, if any
+    pub template: Option<TemplateRef>,
+}
+
+/// Review state of a version awaiting publication
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecReview {
+    pub version: u32,
+    pub status: ReviewStatus,
+    pub required_approvals: u32,
+    pub approvals: Vec<ReviewApproval>,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    pub rejected_by: Option<String>,
+    pub rejection_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewApproval {
+    pub approved_by: String,
+    pub approved_at: DateTime<Utc>,
+    pub comment: Option<String>,
+}
+
+/// Deprecation details of a spec
+#[derive(Debug, Clone)]
+pub struct SpecDeprecation {
+    pub reason: Option<String>,
+    pub deprecated_at: DateTime<Utc>,
+    pub successor_id: Option<Uuid>,
+    pub sunset_at: Option<DateTime<Utc>>,
 }
 
 /// Read model for spec summary (list views)
@@ -32,10 +90,322 @@ pub struct SpecSummaryProjection {
     pub name: String,
     pub description: Option<String>,
     pub latest_version: u32,
+    /// Semantic version of the latest revision
+    pub semver: semver::Version,
+    pub published_version: Option<u32>,
+    pub state: SpecState,
+    pub updated_at: DateTime<Utc>,
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Read model for a folder of path-style spec names
+#[derive(Debug, Clone)]
+pub struct FolderProjection {
+    /// `""` for the root folder
+    pub path: String,
+    /// Subfolders, each with the number of specs at any depth below it
+    pub folders: Vec<(String, u64)>,
+    pub specs: Vec<SpecSummaryProjection>,
+}
+
+/// Read model for one revision of a spec
+#[derive(Debug, Clone)]
+pub struct SpecVersionProjection {
+    pub version: u32,
+    pub semver: semver::Version,
+    pub content: String,
+    pub content_hash: String,
+    pub description: Option<String>,
+    /// When the revision was first published, if it ever was
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// One edge of the dependency graph, seen from the spec at its other end
+#[derive(Debug, Clone)]
+pub struct DependencyProjection {
+    pub spec_id: Uuid,
+    pub name: String,
+    pub state: SpecState,
+    pub published_version: Option<u32>,
+    /// Versions of the depended-on spec the dependent accepts
+    pub version: semver::VersionReq,
+}
+
+/// Read model for one comment on a spec version
+#[derive(Debug, Clone)]
+pub struct CommentProjection {
+    pub id: Uuid,
+    pub version: u32,
+    pub anchor: Option<CommentAnchor>,
+    pub body: String,
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    /// Set on a thread's first comment once the thread is resolved
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// A thread's first comment followed by its replies, oldest first
+#[derive(Debug, Clone)]
+pub struct CommentThreadProjection {
+    pub comment: CommentProjection,
+    pub replies: Vec<CommentProjection>,
+}
+
+/// A spec as one channel serves it
+#[derive(Debug, Clone)]
+pub struct ChannelSpecProjection {
+    pub channel: String,
+    pub spec_id: Uuid,
+    pub name: String,
+    pub state: SpecState,
+    pub version: u32,
+    pub semver: semver::Version,
+    /// Who last pointed the channel at this version, by promoting or demoting
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Read model for a spec template at its latest version
+#[derive(Debug, Clone)]
+pub struct TemplateProjection {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub head_version: u32,
+    pub body: String,
+    pub parameters: Vec<TemplateParameter>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub created_by: String,
+    pub updated_by: String,
+    pub stream_version: i64,
+}
+
+/// Read model for one version of a template
+#[derive(Debug, Clone)]
+pub struct TemplateVersionProjection {
+    pub version: u32,
+    pub body: String,
+    pub parameters: Vec<TemplateParameter>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: String,
+}
+
+/// A spec rendered from a template, with the template version it used
+#[derive(Debug, Clone)]
+pub struct DerivedSpecProjection {
+    pub id: Uuid,
+    pub name: String,
     pub state: SpecState,
+    pub template_version: u32,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Read model for a release and the manifest of spec versions it pins
+#[derive(Debug, Clone)]
+pub struct ReleaseProjection {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub state: ReleaseState,
+    pub entries: Vec<ReleaseEntryProjection>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: String,
+    pub updated_at: DateTime<Utc>,
+    pub updated_by: String,
+    /// When the release was last published
+    pub published_at: Option<DateTime<Utc>>,
+    pub stream_version: i64,
+}
+
+/// A spec version pinned by a release, in the order it was pinned
+#[derive(Debug, Clone)]
+pub struct ReleaseEntryProjection {
+    pub spec_id: Uuid,
+    pub name: String,
+    pub version: u32,
+    pub semver: semver::Version,
+    pub content_hash: String,
+}
+
+/// Read model for a registered JSON Schema at its latest version
+#[derive(Debug, Clone)]
+pub struct SchemaProjection {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub head_version: u32,
+    pub schema: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub created_by: String,
+    pub updated_by: String,
+    pub stream_version: i64,
+}
+
+/// Read model for one version of a registered schema
+#[derive(Debug, Clone)]
+pub struct SchemaVersionProjection {
+    pub version: u32,
+    pub schema: serde_json::Value,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: String,
+}
+
+/// Read model for a tenant
+#[derive(Debug, Clone)]
+pub struct TenantProjection {
+    pub id: TenantId,
+    pub display_name: Option<String>,
+    pub state: TenantState,
+    /// Why the tenant was suspended, if it was
+    pub suspension_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub created_by: String,
     pub updated_at: DateTime<Utc>,
+    pub updated_by: String,
+}
+
+/// Lifecycle of a scheduled transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleStatus {
+    Pending,
+    Executed,
+    Failed,
+    Cancelled,
+}
+
+impl ScheduleStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Executed => "executed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "executed" => Some(Self::Executed),
+            "failed" => Some(Self::Failed),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// Read model for a scheduled transition
+#[derive(Debug, Clone)]
+pub struct ScheduleProjection {
+    pub schedule_id: Uuid,
+    pub spec_id: Uuid,
+    pub transition: ScheduledTransition,
+    pub execute_at: DateTime<Utc>,
+    pub scheduled_by: String,
+    pub scheduled_at: DateTime<Utc>,
+    pub status: ScheduleStatus,
+    /// When the schedule was executed or cancelled
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Why the transition was rejected, for failed schedules
+    pub failure: Option<String>,
 }
 
+/// Specs served by channels, with the revision each serves; deleted specs are left out
+const CHANNEL_SPECS_QUERY: &str = "
+    SELECT c.channel, c.spec_id, p.name, p.state, c.version, h.semver,
+           c.changed_by, c.changed_at
+    FROM spec_channels c
+    JOIN spec_projections p ON p.id = c.spec_id
+    JOIN spec_version_history h ON h.id = c.spec_id AND h.version = c.version
+    WHERE p.state != 'deleted' AND p.tenant_id = ?
+";
+
+/// Release columns, filtered and ordered by the callers
+const RELEASES_QUERY: &str = "
+    SELECT id, name, description, state, created_at, created_by, updated_at, updated_by,
+           published_at, stream_version
+    FROM release_projections
+";
+
+/// Every table built from events, children before the tables they describe
+const PROJECTION_TABLES: [&str; 15] = [
+    "spec_version_history",
+    "spec_schedules",
+    "spec_folders",
+    "spec_labels",
+    "spec_dependencies",
+    "spec_comments",
+    "spec_channels",
+    "spec_projections",
+    "schema_versions",
+    "schema_projections",
+    "template_versions",
+    "template_projections",
+    "release_entries",
+    "release_projections",
+    "tenant_projections",
+];
+
+/// Columns added to projection tables after they were first created, with
+/// the definition databases built before them gain
+const ADDED_COLUMNS: [(&str, &str, &str); 21] = [
+    (
+        "spec_projections",
+        "tenant_id",
+        "TEXT NOT NULL DEFAULT 'default'",
+    ),
+    (
+        "spec_projections",
+        "content_hash",
+        "TEXT NOT NULL DEFAULT ''",
+    ),
+    ("spec_projections", "semver", "TEXT"),
+    ("spec_projections", "published_version", "INTEGER"),
+    (
+        "spec_projections",
+        "stream_version",
+        "INTEGER NOT NULL DEFAULT 0",
+    ),
+    ("spec_projections", "deprecation_reason", "TEXT"),
+    ("spec_projections", "deprecated_at", "TEXT"),
+    ("spec_projections", "successor_id", "TEXT"),
+    ("spec_projections", "sunset_at", "TEXT"),
+    ("spec_projections", "review", "TEXT"),
+    ("spec_projections", "schema_id", "TEXT"),
+    ("spec_projections", "schema_version", "INTEGER"),
+    ("spec_projections", "template_id", "TEXT"),
+    ("spec_projections", "template_version", "INTEGER"),
+    ("spec_version_history", "semver", "TEXT"),
+    (
+        "spec_version_history",
+        "content_hash",
+        "TEXT NOT NULL DEFAULT ''",
+    ),
+    ("spec_version_history", "reverted_from", "INTEGER"),
+    ("spec_version_history", "published_at", "TEXT"),
+    (
+        "schema_projections",
+        "tenant_id",
+        "TEXT NOT NULL DEFAULT 'default'",
+    ),
+    (
+        "template_projections",
+        "tenant_id",
+        "TEXT NOT NULL DEFAULT 'default'",
+    ),
+    (
+        "release_projections",
+        "tenant_id",
+        "TEXT NOT NULL DEFAULT 'default'",
+    ),
+];
+
 #[derive(Clone)]
 pub struct ProjectionStore {
     pub(super) pool: SqlitePool,
@@ -55,24 +425,40 @@ impl ProjectionStore {
         Ok(Self { pool, cache })
     }
 
+    #[allow(clippy::too_many_lines)]
     pub async fn init_schema(&self) -> Result<()> {
+        let migrated = self.add_missing_columns().await?;
+
         sqlx::query(
             "
             CREATE TABLE IF NOT EXISTS spec_projections (
                 id TEXT PRIMARY KEY,
+                tenant_id TEXT NOT NULL,
                 name TEXT NOT NULL,
-                content TEXT NOT NULL,
+                content_hash TEXT NOT NULL,
                 description TEXT,
                 version INTEGER NOT NULL,
+                semver TEXT,
+                published_version INTEGER,
                 state TEXT NOT NULL,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
                 created_by TEXT NOT NULL,
-                updated_by TEXT NOT NULL
+                updated_by TEXT NOT NULL,
+                stream_version INTEGER NOT NULL,
+                deprecation_reason TEXT,
+                deprecated_at TEXT,
+                successor_id TEXT,
+                sunset_at TEXT,
+                review TEXT,
+                schema_id TEXT,
+                schema_version INTEGER,
+                template_id TEXT,
+                template_version INTEGER
             );
 
             CREATE INDEX IF NOT EXISTS idx_spec_projections_name
-            ON spec_projections(name);
+            ON spec_projections(tenant_id, name);
 
             CREATE INDEX IF NOT EXISTS idx_spec_projections_state
             ON spec_projections(state);
@@ -80,66 +466,470 @@ impl ProjectionStore {
             CREATE INDEX IF NOT EXISTS idx_spec_projections_updated
             ON spec_projections(updated_at DESC);
 
+            CREATE INDEX IF NOT EXISTS idx_spec_projections_sunset
+            ON spec_projections(sunset_at) WHERE sunset_at IS NOT NULL;
+
+            CREATE INDEX IF NOT EXISTS idx_spec_projections_template
+            ON spec_projections(template_id) WHERE template_id IS NOT NULL;
+
             -- Version history for querying specific versions
             CREATE TABLE IF NOT EXISTS spec_version_history (
                 id TEXT NOT NULL,
                 version INTEGER NOT NULL,
-                content TEXT NOT NULL,
+                semver TEXT,
+                content_hash TEXT NOT NULL,
+                description TEXT,
+                created_at TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                reverted_from INTEGER,
+                published_at TEXT,
+                PRIMARY KEY (id, version)
+            );
+
+            -- Scheduled state transitions, pending and completed
+            CREATE TABLE IF NOT EXISTS spec_schedules (
+                schedule_id TEXT PRIMARY KEY,
+                spec_id TEXT NOT NULL,
+                transition TEXT NOT NULL,
+                execute_at TEXT NOT NULL,
+                scheduled_by TEXT NOT NULL,
+                scheduled_at TEXT NOT NULL,
+                status TEXT NOT NULL,
+                completed_at TEXT,
+                failure TEXT
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_spec_schedules_spec_id
+            ON spec_schedules(spec_id);
+
+            CREATE INDEX IF NOT EXISTS idx_spec_schedules_due
+            ON spec_schedules(status, execute_at);
+
+            -- Folders of path-style spec names: a row for every folder above a
+            -- spec that is not deleted, depth 1 being the folder holding it
+            CREATE TABLE IF NOT EXISTS spec_folders (
+                tenant_id TEXT NOT NULL,
+                folder TEXT NOT NULL,
+                parent TEXT,
+                spec_id TEXT NOT NULL,
+                depth INTEGER NOT NULL,
+                PRIMARY KEY (tenant_id, folder, spec_id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_spec_folders_parent
+            ON spec_folders(tenant_id, parent);
+
+            CREATE INDEX IF NOT EXISTS idx_spec_folders_spec_id
+            ON spec_folders(spec_id);
+
+            -- Key/value labels used to select specs
+            CREATE TABLE IF NOT EXISTS spec_labels (
+                spec_id TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (spec_id, key)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_spec_labels_key_value
+            ON spec_labels(key, value);
+
+            -- Dependency graph: spec_id accepts versions of depends_on matching version_req
+            CREATE TABLE IF NOT EXISTS spec_dependencies (
+                spec_id TEXT NOT NULL,
+                depends_on TEXT NOT NULL,
+                version_req TEXT NOT NULL,
+                PRIMARY KEY (spec_id, depends_on)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_spec_dependencies_depends_on
+            ON spec_dependencies(depends_on);
+
+            -- Review comments; replies point at their thread's first comment
+            CREATE TABLE IF NOT EXISTS spec_comments (
+                comment_id TEXT PRIMARY KEY,
+                spec_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                parent_id TEXT,
+                path TEXT,
+                start_line INTEGER,
+                end_line INTEGER,
+                body TEXT NOT NULL,
+                author TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                edited_at TEXT,
+                resolved_by TEXT,
+                resolved_at TEXT
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_spec_comments_spec_id
+            ON spec_comments(spec_id, version);
+
+            -- Version of each spec every channel serves
+            CREATE TABLE IF NOT EXISTS spec_channels (
+                channel TEXT NOT NULL,
+                spec_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                changed_by TEXT NOT NULL,
+                changed_at TEXT NOT NULL,
+                PRIMARY KEY (channel, spec_id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_spec_channels_spec_id
+            ON spec_channels(spec_id);
+
+            -- Registered JSON Schemas at their latest version
+            CREATE TABLE IF NOT EXISTS schema_projections (
+                id TEXT PRIMARY KEY,
+                tenant_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                description TEXT,
+                version INTEGER NOT NULL,
+                schema TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                updated_by TEXT NOT NULL,
+                stream_version INTEGER NOT NULL
+            );
+
+            -- Every version of every schema, which specs pin by number
+            CREATE TABLE IF NOT EXISTS schema_versions (
+                id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                schema TEXT NOT NULL,
+                description TEXT,
+                created_at TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                PRIMARY KEY (id, version)
+            );
+
+            -- Spec templates at their latest version
+            CREATE TABLE IF NOT EXISTS template_projections (
+                id TEXT PRIMARY KEY,
+                tenant_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                description TEXT,
+                version INTEGER NOT NULL,
+                body TEXT NOT NULL,
+                parameters TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                updated_by TEXT NOT NULL,
+                stream_version INTEGER NOT NULL
+            );
+
+            -- Every version of every template, which derived specs record
+            CREATE TABLE IF NOT EXISTS template_versions (
+                id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                body TEXT NOT NULL,
+                parameters TEXT NOT NULL,
                 description TEXT,
                 created_at TEXT NOT NULL,
                 created_by TEXT NOT NULL,
                 PRIMARY KEY (id, version)
             );
+
+            -- Releases; the current one is the last published
+            CREATE TABLE IF NOT EXISTS release_projections (
+                id TEXT PRIMARY KEY,
+                tenant_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                description TEXT,
+                state TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                updated_by TEXT NOT NULL,
+                published_at TEXT,
+                stream_version INTEGER NOT NULL
+            );
+
+            -- Spec versions each release pins
+            CREATE TABLE IF NOT EXISTS release_entries (
+                release_id TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                spec_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                PRIMARY KEY (release_id, spec_id)
+            );
+
+            -- Tenants created by administrators; the default tenant is implicit
+            CREATE TABLE IF NOT EXISTS tenant_projections (
+                id TEXT PRIMARY KEY,
+                display_name TEXT,
+                state TEXT NOT NULL,
+                suspension_reason TEXT,
+                created_at TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                updated_by TEXT NOT NULL
+            );
             ",
         )
         .execute(&self.pool)
         .await?;
 
+        sqlx::query(blob_store::SCHEMA).execute(&self.pool).await?;
+
+        if migrated {
+            // Rows projected before the migration lack the values of the new
+            // columns; the event processor replays from the start and projects
+            // them again
+            self.clear().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Bring tables created by an earlier release up to the current layout,
+    /// returning whether any of them changed
+    async fn add_missing_columns(&self) -> Result<bool> {
+        let mut migrated = false;
+
+        for (table, column, definition) in ADDED_COLUMNS {
+            let columns = self.table_columns(table).await?;
+            if columns.is_empty() || columns.iter().any(|existing| existing == column) {
+                continue;
+            }
+
+            sqlx::query(&format!(
+                "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+            ))
+            .execute(&self.pool)
+            .await?;
+            migrated = true;
+        }
+
+        // Content now lives in the blob table, referenced by its hash
+        for table in ["spec_projections", "spec_version_history"] {
+            let columns = self.table_columns(table).await?;
+            if columns.iter().any(|column| column == "content") {
+                sqlx::query(&format!("ALTER TABLE {table} DROP COLUMN content"))
+                    .execute(&self.pool)
+                    .await?;
+                migrated = true;
+            }
+        }
+
+        if migrated {
+            // Names were unique across the whole database before tenants; the
+            // index is created again keyed by tenant
+            sqlx::query("DROP INDEX IF EXISTS idx_spec_projections_name")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(migrated)
+    }
+
+    /// Column names of `table`, empty when it does not exist
+    async fn table_columns(&self, table: &str) -> Result<Vec<String>> {
+        Ok(
+            sqlx::query_scalar::<_, String>("SELECT name FROM pragma_table_info(?)")
+                .bind(table)
+                .fetch_all(&self.pool)
+                .await?,
+        )
+    }
+
+    /// Remove everything built from events, so they can be applied again
+    /// from the start
+    pub async fn clear(&self) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        for table in PROJECTION_TABLES {
+            sqlx::query(&format!("DELETE FROM {table}"))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            cache.clear();
+        }
+
         Ok(())
     }
 
     /// Apply an event to update projections
-    pub async fn apply_event(
-        &self,
-        _aggregate_id: Uuid,
-        event: &SpecEvent,
-    ) -> Result<(), DomainError> {
-        match event {
-            SpecEvent::Created(e) => self.handle_created(e).await,
-            SpecEvent::Updated(e) => self.handle_updated(e).await,
-            SpecEvent::StateChanged(e) => self.handle_state_changed(e).await,
+    #[allow(clippy::too_many_lines)]
+    pub async fn apply_event(&self, envelope: &EventEnvelope) -> Result<(), DomainError> {
+        let sequence_number = envelope.sequence_number;
+        let tenant = &envelope.metadata.tenant_id;
+
+        match &envelope.event {
+            SpecEvent::Created(e) => self.handle_created(e, tenant, sequence_number).await,
+            SpecEvent::Updated(e) => self.handle_updated(e, sequence_number).await,
+            SpecEvent::StateChanged(e) => self.handle_state_changed(e, sequence_number).await,
+            SpecEvent::Restored(e) => self.handle_restored(e, tenant, sequence_number).await,
+            SpecEvent::Reverted(e) => self.handle_reverted(e, sequence_number).await,
+            SpecEvent::Renamed(e) => self.handle_renamed(e, tenant, sequence_number).await,
+            SpecEvent::TransitionScheduled(e) => {
+                self.handle_transition_scheduled(e, sequence_number).await
+            }
+            SpecEvent::ScheduleCancelled(e) => {
+                self.complete_schedule(
+                    e.spec_id,
+                    e.schedule_id,
+                    ScheduleStatus::Cancelled,
+                    e.cancelled_at,
+                    None,
+                    sequence_number,
+                )
+                .await
+            }
+            SpecEvent::ReviewRequested(e) => {
+                let review = SpecReview {
+                    version: e.version,
+                    status: ReviewStatus::Pending,
+                    required_approvals: e.required_approvals,
+                    approvals: Vec::new(),
+                    requested_by: e.requested_by.clone(),
+                    requested_at: e.requested_at,
+                    rejected_by: None,
+                    rejection_reason: None,
+                };
+                self.update_review(e.spec_id, sequence_number, |current| {
+                    *current = Some(review);
+                })
+                .await
+            }
+            SpecEvent::ReviewApproved(e) => {
+                self.update_review(e.spec_id, sequence_number, |current| {
+                    if let Some(review) = current.as_mut() {
+                        review.approvals.push(ReviewApproval {
+                            approved_by: e.approved_by.clone(),
+                            approved_at: e.approved_at,
+                            comment: e.comment.clone(),
+                        });
+                        if review.approvals.len() >= review.required_approvals as usize {
+                            review.status = ReviewStatus::Approved;
+                        }
+                    }
+                })
+                .await
+            }
+            SpecEvent::ReviewRejected(e) => {
+                self.update_review(e.spec_id, sequence_number, |current| {
+                    if let Some(review) = current.as_mut() {
+                        review.status = ReviewStatus::Rejected;
+                        review.rejected_by = Some(e.rejected_by.clone());
+                        review.rejection_reason = Some(e.reason.clone());
+                    }
+                })
+                .await
+            }
+            SpecEvent::ReviewWithdrawn(e) => {
+                self.update_review(e.spec_id, sequence_number, |current| *current = None)
+                    .await
+            }
+            SpecEvent::LabelsAdded(e) => self.handle_labels_added(e, sequence_number).await,
+            SpecEvent::LabelsRemoved(e) => self.handle_labels_removed(e, sequence_number).await,
+            SpecEvent::DependenciesSet(e) => self.handle_dependencies_set(e, sequence_number).await,
+            SpecEvent::CommentAdded(e) => self.handle_comment_added(e, sequence_number).await,
+            SpecEvent::CommentEdited(e) => self.handle_comment_edited(e, sequence_number).await,
+            SpecEvent::CommentResolved(e) => self.handle_comment_resolved(e, sequence_number).await,
+            SpecEvent::Promoted(e) => self.handle_promoted(e, sequence_number).await,
+            SpecEvent::Demoted(e) => self.handle_demoted(e, sequence_number).await,
+            SpecEvent::SunsetFailed(e) => self.handle_sunset_failed(e, sequence_number).await,
+            SpecEvent::SchemaRegistered(e) => {
+                self.handle_schema_registered(e, tenant, sequence_number)
+                    .await
+            }
+            SpecEvent::SchemaUpdated(e) => self.handle_schema_updated(e, sequence_number).await,
+            SpecEvent::TemplateRegistered(e) => {
+                self.handle_template_registered(e, tenant, sequence_number)
+                    .await
+            }
+            SpecEvent::TemplateUpdated(e) => self.handle_template_updated(e, sequence_number).await,
+            SpecEvent::ReleaseCreated(e) => {
+                self.handle_release_created(e, tenant, sequence_number)
+                    .await
+            }
+            SpecEvent::ReleasePublished(e) => {
+                self.handle_release_published(e, sequence_number).await
+            }
+            SpecEvent::ReleaseRolledBack(e) => {
+                self.handle_release_rolled_back(e, sequence_number).await
+            }
+            SpecEvent::TenantCreated(e) => self.handle_tenant_created(e).await,
+            SpecEvent::TenantSuspended(e) => self.handle_tenant_suspended(e).await,
+            SpecEvent::ScheduleExecuted(e) => {
+                let status = if e.failure.is_some() {
+                    ScheduleStatus::Failed
+                } else {
+                    ScheduleStatus::Executed
+                };
+                self.complete_schedule(
+                    e.spec_id,
+                    e.schedule_id,
+                    status,
+                    e.executed_at,
+                    e.failure.as_deref(),
+                    sequence_number,
+                )
+                .await
+            }
         }
     }
 
     async fn handle_created(
         &self,
         event: &crate::domain::events::SpecCreated,
+        tenant: &TenantId,
+        sequence_number: i64,
     ) -> Result<(), DomainError> {
+        let semver = Version::initial().legacy_semver();
+
         let mut tx = self
             .pool
             .begin()
             .await
             .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
+        let content_ref = blob_store::store(&mut tx, event.content.as_bytes()).await?;
+
         // Insert into main projection
         sqlx::query(
             "
             INSERT INTO spec_projections (
-                id, name, content, description, version, state,
-                created_at, updated_at, created_by, updated_by
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                id, tenant_id, name, content_hash, description, version, semver, state,
+                created_at, updated_at, created_by, updated_by, stream_version,
+                schema_id, schema_version, template_id, template_version
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ",
         )
         .bind(event.spec_id.to_string())
+        .bind(tenant.as_str())
         .bind(&event.name)
-        .bind(&event.content)
+        .bind(&content_ref.hash)
         .bind(&event.description)
         .bind(1) // Initial version
+        .bind(semver.to_string())
         .bind("draft") // Initial state
         .bind(event.created_at.to_rfc3339())
         .bind(event.created_at.to_rfc3339())
         .bind(&event.created_by)
         .bind(&event.created_by)
+        .bind(sequence_number)
+        .bind(event.schema.map(|schema| schema.schema_id.to_string()))
+        .bind(event.schema.map(|schema| i64::from(schema.version)))
+        .bind(
+            event
+                .template
+                .map(|template| template.template_id.to_string()),
+        )
+        .bind(event.template.map(|template| i64::from(template.version)))
         .execute(&mut *tx)
         .await
         .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
@@ -148,13 +938,14 @@ impl ProjectionStore {
         sqlx::query(
             "
             INSERT INTO spec_version_history (
-                id, version, content, description, created_at, created_by
-            ) VALUES (?, ?, ?, ?, ?, ?)
+                id, version, semver, content_hash, description, created_at, created_by
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
             ",
         )
         .bind(event.spec_id.to_string())
         .bind(1)
-        .bind(&event.content)
+        .bind(semver.to_string())
+        .bind(&content_ref.hash)
         .bind(&event.description)
         .bind(event.created_at.to_rfc3339())
         .bind(&event.created_by)
@@ -162,6 +953,8 @@ impl ProjectionStore {
         .await
         .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
+        index_folders(&mut tx, tenant, event.spec_id, Some(&event.name)).await?;
+
         tx.commit()
             .await
             .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
@@ -172,15 +965,25 @@ impl ProjectionStore {
                 event.spec_id,
                 SpecProjection {
                     id: event.spec_id,
+                    tenant_id: tenant.clone(),
                     name: event.name.clone(),
                     content: event.content.clone(),
+                    content_hash: content_ref.hash,
                     description: event.description.clone(),
-                    version: 1,
+                    head_version: 1,
+                    semver,
+                    published_version: None,
                     state: SpecState::Draft,
                     created_at: event.created_at,
                     updated_at: event.created_at,
                     created_by: event.created_by.clone(),
                     updated_by: event.created_by.clone(),
+                    stream_version: sequence_number,
+                    deprecation: None,
+                    review: None,
+                    labels: BTreeMap::new(),
+                    schema: event.schema,
+                    template: event.template,
                 },
             );
         }
@@ -191,27 +994,38 @@ impl ProjectionStore {
     async fn handle_updated(
         &self,
         event: &crate::domain::events::SpecUpdated,
+        sequence_number: i64,
     ) -> Result<(), DomainError> {
+        let semver = event.semver.clone();
+
         let mut tx = self
             .pool
             .begin()
             .await
             .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
+        let content_ref = blob_store::store(&mut tx, event.content.as_bytes()).await?;
+
         // Update main projection
         sqlx::query(
             "
             UPDATE spec_projections
-            SET content = ?, description = ?, version = ?,
-                updated_at = ?, updated_by = ?
+            SET content_hash = ?, description = ?, version = ?, semver = ?,
+                updated_at = ?, updated_by = ?, stream_version = ?, review = NULL,
+                schema_id = COALESCE(?, schema_id),
+                schema_version = COALESCE(?, schema_version)
             WHERE id = ?
             ",
         )
-        .bind(&event.content)
+        .bind(&content_ref.hash)
         .bind(&event.description)
         .bind(i64::from(event.version))
+        .bind(semver.to_string())
         .bind(event.updated_at.to_rfc3339())
         .bind(&event.updated_by)
+        .bind(sequence_number)
+        .bind(event.schema.map(|schema| schema.schema_id.to_string()))
+        .bind(event.schema.map(|schema| i64::from(schema.version)))
         .bind(event.spec_id.to_string())
         .execute(&mut *tx)
         .await
@@ -221,13 +1035,14 @@ impl ProjectionStore {
         sqlx::query(
             "
             INSERT INTO spec_version_history (
-                id, version, content, description, created_at, created_by
-            ) VALUES (?, ?, ?, ?, ?, ?)
+                id, version, semver, content_hash, description, created_at, created_by
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
             ",
         )
         .bind(event.spec_id.to_string())
         .bind(i64::from(event.version))
-        .bind(&event.content)
+        .bind(semver.to_string())
+        .bind(&content_ref.hash)
         .bind(&event.description)
         .bind(event.updated_at.to_rfc3339())
         .bind(&event.updated_by)
@@ -243,10 +1058,96 @@ impl ProjectionStore {
         if let Some(cache) = self.cache.write().await.as_mut() {
             if let Some(proj) = cache.get_mut(&event.spec_id) {
                 proj.content.clone_from(&event.content);
+                proj.content_hash = content_ref.hash;
                 proj.description.clone_from(&event.description);
-                proj.version = event.version;
+                proj.head_version = event.version;
+                proj.semver = semver;
                 proj.updated_at = event.updated_at;
                 proj.updated_by.clone_from(&event.updated_by);
+                proj.stream_version = sequence_number;
+                proj.review = None;
+                if event.schema.is_some() {
+                    proj.schema = event.schema;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_reverted(
+        &self,
+        event: &crate::domain::events::SpecReverted,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let semver = event.semver.clone();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let content_ref = blob_store::store(&mut tx, event.content.as_bytes()).await?;
+
+        // Update main projection
+        sqlx::query(
+            "
+            UPDATE spec_projections
+            SET content_hash = ?, description = ?, version = ?, semver = ?,
+                updated_at = ?, updated_by = ?, stream_version = ?, review = NULL
+            WHERE id = ?
+            ",
+        )
+        .bind(&content_ref.hash)
+        .bind(&event.description)
+        .bind(i64::from(event.version))
+        .bind(semver.to_string())
+        .bind(event.reverted_at.to_rfc3339())
+        .bind(&event.reverted_by)
+        .bind(sequence_number)
+        .bind(event.spec_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Insert into version history, noting the version it was restored from
+        sqlx::query(
+            "
+            INSERT INTO spec_version_history (
+                id, version, semver, content_hash, description, created_at, created_by,
+                reverted_from
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.spec_id.to_string())
+        .bind(i64::from(event.version))
+        .bind(semver.to_string())
+        .bind(&content_ref.hash)
+        .bind(&event.description)
+        .bind(event.reverted_at.to_rfc3339())
+        .bind(&event.reverted_by)
+        .bind(i64::from(event.reverted_from))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&event.spec_id) {
+                proj.content.clone_from(&event.content);
+                proj.content_hash = content_ref.hash;
+                proj.description.clone_from(&event.description);
+                proj.head_version = event.version;
+                proj.semver = semver;
+                proj.updated_at = event.reverted_at;
+                proj.updated_by.clone_from(&event.reverted_by);
+                proj.stream_version = sequence_number;
+                proj.review = None;
             }
         }
 
@@ -256,6 +1157,7 @@ impl ProjectionStore {
     async fn handle_state_changed(
         &self,
         event: &crate::domain::events::SpecStateChanged,
+        sequence_number: i64,
     ) -> Result<(), DomainError> {
         let state_str = match event.to_state {
             SpecState::Draft => "draft",
@@ -264,160 +1166,2027 @@ impl ProjectionStore {
             SpecState::Deleted => "deleted",
         };
 
-        sqlx::query(
+        // Publishing pins the published version to the revision in the event
+        let published_version = (event.to_state == SpecState::Published).then_some(event.version);
+
+        // Deprecation details stay once set, so a restored spec keeps them
+        let deprecation = (event.to_state == SpecState::Deprecated).then(|| SpecDeprecation {
+            reason: event.reason.clone(),
+            deprecated_at: event.changed_at,
+            successor_id: event.successor_id,
+            sunset_at: event.sunset_at,
+        });
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            UPDATE spec_projections
+            SET state = ?, published_version = COALESCE(?, published_version),
+                updated_at = ?, stream_version = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(state_str)
+        .bind(published_version.map(i64::from))
+        .bind(event.changed_at.to_rfc3339())
+        .bind(sequence_number)
+        .bind(event.spec_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        if let Some(version) = published_version {
+            // Range lookups only resolve to revisions that have been published
+            sqlx::query(
+                "
+                UPDATE spec_version_history
+                SET published_at = COALESCE(published_at, ?)
+                WHERE id = ? AND version = ?
+                ",
+            )
+            .bind(event.changed_at.to_rfc3339())
+            .bind(event.spec_id.to_string())
+            .bind(i64::from(version))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        }
+
+        if let Some(deprecation) = &deprecation {
+            sqlx::query(
+                "
+                UPDATE spec_projections
+                SET deprecation_reason = ?, deprecated_at = ?, successor_id = ?, sunset_at = ?
+                WHERE id = ?
+                ",
+            )
+            .bind(&deprecation.reason)
+            .bind(deprecation.deprecated_at.to_rfc3339())
+            .bind(deprecation.successor_id.map(|id| id.to_string()))
+            .bind(deprecation.sunset_at.map(|at| at.to_rfc3339()))
+            .bind(event.spec_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        }
+
+        // Deleted specs drop out of their folders until they are restored
+        if event.to_state == SpecState::Deleted {
+            unindex_folders(&mut tx, event.spec_id).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&event.spec_id) {
+                proj.state = event.to_state;
+                if published_version.is_some() {
+                    proj.published_version = published_version;
+                }
+                if deprecation.is_some() {
+                    proj.deprecation = deprecation;
+                }
+                proj.updated_at = event.changed_at;
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_renamed(
+        &self,
+        event: &crate::domain::events::SpecRenamed,
+        tenant: &TenantId,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            UPDATE spec_projections
+            SET name = ?, updated_at = ?, stream_version = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(&event.new_name)
+        .bind(event.renamed_at.to_rfc3339())
+        .bind(sequence_number)
+        .bind(event.spec_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        index_folders(&mut tx, tenant, event.spec_id, Some(&event.new_name)).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&event.spec_id) {
+                proj.name.clone_from(&event.new_name);
+                proj.updated_at = event.renamed_at;
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_labels_added(
+        &self,
+        event: &crate::domain::events::SpecLabelsAdded,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        for (key, value) in &event.labels {
+            sqlx::query(
+                "INSERT OR REPLACE INTO spec_labels (spec_id, key, value) VALUES (?, ?, ?)",
+            )
+            .bind(event.spec_id.to_string())
+            .bind(key)
+            .bind(value)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        }
+
+        sqlx::query("UPDATE spec_projections SET updated_at = ?, stream_version = ? WHERE id = ?")
+            .bind(event.labeled_at.to_rfc3339())
+            .bind(sequence_number)
+            .bind(event.spec_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&event.spec_id) {
+                proj.labels
+                    .extend(event.labels.iter().map(|(k, v)| (k.clone(), v.clone())));
+                proj.updated_at = event.labeled_at;
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_labels_removed(
+        &self,
+        event: &crate::domain::events::SpecLabelsRemoved,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        for key in &event.keys {
+            sqlx::query("DELETE FROM spec_labels WHERE spec_id = ? AND key = ?")
+                .bind(event.spec_id.to_string())
+                .bind(key)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        }
+
+        sqlx::query("UPDATE spec_projections SET updated_at = ?, stream_version = ? WHERE id = ?")
+            .bind(event.unlabeled_at.to_rfc3339())
+            .bind(sequence_number)
+            .bind(event.spec_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&event.spec_id) {
+                for key in &event.keys {
+                    proj.labels.remove(key);
+                }
+                proj.updated_at = event.unlabeled_at;
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_dependencies_set(
+        &self,
+        event: &crate::domain::events::SpecDependenciesSet,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query("DELETE FROM spec_dependencies WHERE spec_id = ?")
+            .bind(event.spec_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        for SpecDependency { spec_id, version } in &event.dependencies {
+            sqlx::query(
+                "INSERT INTO spec_dependencies (spec_id, depends_on, version_req) VALUES (?, ?, ?)",
+            )
+            .bind(event.spec_id.to_string())
+            .bind(spec_id.to_string())
+            .bind(version.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        }
+
+        sqlx::query("UPDATE spec_projections SET updated_at = ?, stream_version = ? WHERE id = ?")
+            .bind(event.set_at.to_rfc3339())
+            .bind(sequence_number)
+            .bind(event.spec_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&event.spec_id) {
+                proj.updated_at = event.set_at;
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_comment_added(
+        &self,
+        event: &crate::domain::events::SpecCommentAdded,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let anchor = event.anchor.clone().unwrap_or_default();
+        let query = sqlx::query(
+            "
+            INSERT INTO spec_comments (
+                comment_id, spec_id, version, parent_id, path, start_line, end_line,
+                body, author, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.comment_id.to_string())
+        .bind(event.spec_id.to_string())
+        .bind(i64::from(event.version))
+        .bind(event.parent_id.map(|id| id.to_string()))
+        .bind(anchor.path)
+        .bind(anchor.lines.map(|lines| i64::from(lines.start)))
+        .bind(anchor.lines.map(|lines| i64::from(lines.end)))
+        .bind(&event.body)
+        .bind(&event.author)
+        .bind(event.added_at.to_rfc3339());
+
+        self.record_side_change(event.spec_id, sequence_number, query)
+            .await
+    }
+
+    async fn handle_comment_edited(
+        &self,
+        event: &crate::domain::events::SpecCommentEdited,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let query =
+            sqlx::query("UPDATE spec_comments SET body = ?, edited_at = ? WHERE comment_id = ?")
+                .bind(&event.body)
+                .bind(event.edited_at.to_rfc3339())
+                .bind(event.comment_id.to_string());
+
+        self.record_side_change(event.spec_id, sequence_number, query)
+            .await
+    }
+
+    async fn handle_comment_resolved(
+        &self,
+        event: &crate::domain::events::SpecCommentResolved,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let query = sqlx::query(
+            "UPDATE spec_comments SET resolved_by = ?, resolved_at = ? WHERE comment_id = ?",
+        )
+        .bind(&event.resolved_by)
+        .bind(event.resolved_at.to_rfc3339())
+        .bind(event.comment_id.to_string());
+
+        self.record_side_change(event.spec_id, sequence_number, query)
+            .await
+    }
+
+    async fn handle_promoted(
+        &self,
+        event: &crate::domain::events::SpecPromoted,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let query = sqlx::query(
+            "
+            INSERT OR REPLACE INTO spec_channels (channel, spec_id, version, changed_by, changed_at)
+            VALUES (?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(&event.channel)
+        .bind(event.spec_id.to_string())
+        .bind(i64::from(event.version))
+        .bind(&event.promoted_by)
+        .bind(event.promoted_at.to_rfc3339());
+
+        self.record_side_change(event.spec_id, sequence_number, query)
+            .await
+    }
+
+    async fn handle_demoted(
+        &self,
+        event: &crate::domain::events::SpecDemoted,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let query = match event.restored_version {
+            Some(version) => sqlx::query(
+                "
+                UPDATE spec_channels SET version = ?, changed_by = ?, changed_at = ?
+                WHERE channel = ? AND spec_id = ?
+                ",
+            )
+            .bind(i64::from(version))
+            .bind(&event.demoted_by)
+            .bind(event.demoted_at.to_rfc3339()),
+            None => sqlx::query("DELETE FROM spec_channels WHERE channel = ? AND spec_id = ?"),
+        }
+        .bind(&event.channel)
+        .bind(event.spec_id.to_string());
+
+        self.record_side_change(event.spec_id, sequence_number, query)
+            .await
+    }
+
+    /// Apply a change to a table kept beside the spec projection, such as
+    /// comments or channels; these move the spec's stream forward but leave
+    /// the rest of its projection alone
+    async fn record_side_change<'q>(
+        &self,
+        spec_id: Uuid,
+        sequence_number: i64,
+        change: sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        change
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query("UPDATE spec_projections SET stream_version = ? WHERE id = ?")
+            .bind(sequence_number)
+            .bind(spec_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&spec_id) {
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_restored(
+        &self,
+        event: &crate::domain::events::SpecRestored,
+        tenant: &TenantId,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let state_str = match event.to_state {
+            SpecState::Draft => "draft",
+            SpecState::Published => "published",
+            SpecState::Deprecated => "deprecated",
+            SpecState::Deleted => "deleted",
+        };
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            UPDATE spec_projections
+            SET state = ?, updated_at = ?, stream_version = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(state_str)
+        .bind(event.restored_at.to_rfc3339())
+        .bind(sequence_number)
+        .bind(event.spec_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        index_folders(&mut tx, tenant, event.spec_id, Some(&event.name)).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&event.spec_id) {
+                proj.state = event.to_state;
+                proj.updated_at = event.restored_at;
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_schema_registered(
+        &self,
+        event: &crate::domain::events::SchemaRegistered,
+        tenant: &TenantId,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let schema = serde_json::to_string(&event.schema)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            INSERT INTO schema_projections (
+                id, tenant_id, name, description, version, schema, created_at, updated_at,
+                created_by, updated_by, stream_version
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.schema_id.to_string())
+        .bind(tenant.as_str())
+        .bind(&event.name)
+        .bind(&event.description)
+        .bind(1) // Initial version
+        .bind(&schema)
+        .bind(event.registered_at.to_rfc3339())
+        .bind(event.registered_at.to_rfc3339())
+        .bind(&event.registered_by)
+        .bind(&event.registered_by)
+        .bind(sequence_number)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            INSERT INTO schema_versions (
+                id, version, schema, description, created_at, created_by
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.schema_id.to_string())
+        .bind(1)
+        .bind(&schema)
+        .bind(&event.description)
+        .bind(event.registered_at.to_rfc3339())
+        .bind(&event.registered_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_schema_updated(
+        &self,
+        event: &crate::domain::events::SchemaUpdated,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let schema = serde_json::to_string(&event.schema)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            UPDATE schema_projections
+            SET description = COALESCE(?, description), version = ?, schema = ?,
+                updated_at = ?, updated_by = ?, stream_version = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(&event.description)
+        .bind(i64::from(event.version))
+        .bind(&schema)
+        .bind(event.updated_at.to_rfc3339())
+        .bind(&event.updated_by)
+        .bind(sequence_number)
+        .bind(event.schema_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            INSERT INTO schema_versions (
+                id, version, schema, description, created_at, created_by
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.schema_id.to_string())
+        .bind(i64::from(event.version))
+        .bind(&schema)
+        .bind(&event.description)
+        .bind(event.updated_at.to_rfc3339())
+        .bind(&event.updated_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_template_registered(
+        &self,
+        event: &crate::domain::events::TemplateRegistered,
+        tenant: &TenantId,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let parameters = serde_json::to_string(&event.parameters)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            INSERT INTO template_projections (
+                id, tenant_id, name, description, version, body, parameters, created_at,
+                updated_at, created_by, updated_by, stream_version
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.template_id.to_string())
+        .bind(tenant.as_str())
+        .bind(&event.name)
+        .bind(&event.description)
+        .bind(1) // Initial version
+        .bind(&event.body)
+        .bind(&parameters)
+        .bind(event.registered_at.to_rfc3339())
+        .bind(event.registered_at.to_rfc3339())
+        .bind(&event.registered_by)
+        .bind(&event.registered_by)
+        .bind(sequence_number)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            INSERT INTO template_versions (
+                id, version, body, parameters, description, created_at, created_by
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.template_id.to_string())
+        .bind(1)
+        .bind(&event.body)
+        .bind(&parameters)
+        .bind(&event.description)
+        .bind(event.registered_at.to_rfc3339())
+        .bind(&event.registered_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_template_updated(
+        &self,
+        event: &crate::domain::events::TemplateUpdated,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let parameters = serde_json::to_string(&event.parameters)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            UPDATE template_projections
+            SET description = COALESCE(?, description), version = ?, body = ?, parameters = ?,
+                updated_at = ?, updated_by = ?, stream_version = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(&event.description)
+        .bind(i64::from(event.version))
+        .bind(&event.body)
+        .bind(&parameters)
+        .bind(event.updated_at.to_rfc3339())
+        .bind(&event.updated_by)
+        .bind(sequence_number)
+        .bind(event.template_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            INSERT INTO template_versions (
+                id, version, body, parameters, description, created_at, created_by
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.template_id.to_string())
+        .bind(i64::from(event.version))
+        .bind(&event.body)
+        .bind(&parameters)
+        .bind(&event.description)
+        .bind(event.updated_at.to_rfc3339())
+        .bind(&event.updated_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_release_created(
+        &self,
+        event: &crate::domain::events::ReleaseCreated,
+        tenant: &TenantId,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            INSERT INTO release_projections (
+                id, tenant_id, name, description, state, created_at, created_by, updated_at,
+                updated_by, stream_version
+            ) VALUES (?, ?, ?, ?, 'draft', ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.release_id.to_string())
+        .bind(tenant.as_str())
+        .bind(&event.name)
+        .bind(&event.description)
+        .bind(event.created_at.to_rfc3339())
+        .bind(&event.created_by)
+        .bind(event.created_at.to_rfc3339())
+        .bind(&event.created_by)
+        .bind(sequence_number)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        for (position, entry) in event.entries.iter().enumerate() {
+            sqlx::query(
+                "
+                INSERT INTO release_entries (release_id, position, spec_id, version)
+                VALUES (?, ?, ?, ?)
+                ",
+            )
+            .bind(event.release_id.to_string())
+            .bind(i64::try_from(position).unwrap_or(i64::MAX))
+            .bind(entry.spec_id.to_string())
+            .bind(i64::from(entry.version))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_release_published(
+        &self,
+        event: &crate::domain::events::ReleasePublished,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            "
+            UPDATE release_projections
+            SET state = 'published', published_at = ?, updated_at = ?, updated_by = ?,
+                stream_version = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(event.published_at.to_rfc3339())
+        .bind(event.published_at.to_rfc3339())
+        .bind(&event.published_by)
+        .bind(sequence_number)
+        .bind(event.release_id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_release_rolled_back(
+        &self,
+        event: &crate::domain::events::ReleaseRolledBack,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            "
+            UPDATE release_projections
+            SET state = 'rolled_back', updated_at = ?, updated_by = ?, stream_version = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(event.rolled_back_at.to_rfc3339())
+        .bind(&event.rolled_back_by)
+        .bind(sequence_number)
+        .bind(event.release_id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_tenant_created(
+        &self,
+        event: &crate::domain::events::TenantCreated,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            "
+            INSERT INTO tenant_projections (
+                id, display_name, state, created_at, created_by, updated_at, updated_by
+            ) VALUES (?, ?, 'active', ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.tenant_id.as_str())
+        .bind(&event.display_name)
+        .bind(event.created_at.to_rfc3339())
+        .bind(&event.created_by)
+        .bind(event.created_at.to_rfc3339())
+        .bind(&event.created_by)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_tenant_suspended(
+        &self,
+        event: &crate::domain::events::TenantSuspended,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            "
+            UPDATE tenant_projections
+            SET state = 'suspended', suspension_reason = ?, updated_at = ?, updated_by = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(&event.reason)
+        .bind(event.suspended_at.to_rfc3339())
+        .bind(&event.suspended_by)
+        .bind(event.tenant_id.as_str())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_transition_scheduled(
+        &self,
+        event: &crate::domain::events::SpecTransitionScheduled,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let transition = serde_json::to_string(&event.transition)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            INSERT OR REPLACE INTO spec_schedules (
+                schedule_id, spec_id, transition, execute_at,
+                scheduled_by, scheduled_at, status
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(event.schedule_id.to_string())
+        .bind(event.spec_id.to_string())
+        .bind(&transition)
+        .bind(event.execute_at.to_rfc3339())
+        .bind(&event.scheduled_by)
+        .bind(event.scheduled_at.to_rfc3339())
+        .bind(ScheduleStatus::Pending.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Self::bump_stream_version(&mut tx, event.spec_id, sequence_number).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        self.bump_cached_stream_version(event.spec_id, sequence_number)
+            .await;
+
+        Ok(())
+    }
+
+    async fn complete_schedule(
+        &self,
+        spec_id: Uuid,
+        schedule_id: Uuid,
+        status: ScheduleStatus,
+        completed_at: DateTime<Utc>,
+        failure: Option<&str>,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query(
+            "
+            UPDATE spec_schedules
+            SET status = ?, completed_at = ?, failure = ?
+            WHERE schedule_id = ?
+            ",
+        )
+        .bind(status.as_str())
+        .bind(completed_at.to_rfc3339())
+        .bind(failure)
+        .bind(schedule_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Self::bump_stream_version(&mut tx, spec_id, sequence_number).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        self.bump_cached_stream_version(spec_id, sequence_number)
+            .await;
+
+        Ok(())
+    }
+
+    /// Change the review state of a spec, stored as JSON on its projection
+    async fn update_review(
+        &self,
+        spec_id: Uuid,
+        sequence_number: i64,
+        update: impl FnOnce(&mut Option<SpecReview>),
+    ) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let review_json: Option<String> =
+            sqlx::query_scalar("SELECT review FROM spec_projections WHERE id = ?")
+                .bind(spec_id.to_string())
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .flatten();
+
+        let mut review = review_json
+            .map(|json| serde_json::from_str::<SpecReview>(&json))
+            .transpose()
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        update(&mut review);
+
+        let review_json = review
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        sqlx::query("UPDATE spec_projections SET review = ?, stream_version = ? WHERE id = ?")
+            .bind(review_json)
+            .bind(sequence_number)
+            .bind(spec_id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        // Update cache if enabled
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&spec_id) {
+                proj.review = review;
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record an event that leaves the spec itself unchanged
+    async fn bump_stream_version(
+        conn: &mut sqlx::SqliteConnection,
+        spec_id: Uuid,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        sqlx::query("UPDATE spec_projections SET stream_version = ? WHERE id = ?")
+            .bind(sequence_number)
+            .bind(spec_id.to_string())
+            .execute(conn)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn handle_sunset_failed(
+        &self,
+        event: &crate::domain::events::SpecSunsetFailed,
+        sequence_number: i64,
+    ) -> Result<(), DomainError> {
+        sqlx::query(
+            "
+            UPDATE spec_projections
+            SET sunset_at = NULL, updated_at = ?, stream_version = ?
+            WHERE id = ?
+            ",
+        )
+        .bind(event.failed_at.to_rfc3339())
+        .bind(sequence_number)
+        .bind(event.spec_id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&event.spec_id) {
+                if let Some(deprecation) = proj.deprecation.as_mut() {
+                    deprecation.sunset_at = None;
+                }
+                proj.updated_at = event.failed_at;
+                proj.stream_version = sequence_number;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn bump_cached_stream_version(&self, spec_id: Uuid, sequence_number: i64) {
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if let Some(proj) = cache.get_mut(&spec_id) {
+                proj.stream_version = sequence_number;
+            }
+        }
+    }
+
+    // Query methods for read models
+
+    pub async fn get_by_id(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Option<SpecProjection>, DomainError> {
+        // Check cache first
+        if let Some(cache) = self.cache.read().await.as_ref() {
+            if let Some(proj) = cache.get(&id) {
+                return Ok((proj.tenant_id == *tenant).then(|| proj.clone()));
+            }
+        }
+
+        let row = sqlx::query(
+            "
+            SELECT p.id, p.tenant_id, p.name, p.content_hash, b.data AS content_data,
+                   p.description, p.version, p.semver, p.published_version, p.state,
+                   p.created_at, p.updated_at, p.created_by, p.updated_by, p.stream_version,
+                   p.deprecation_reason, p.deprecated_at, p.successor_id, p.sunset_at, p.review,
+                   p.schema_id, p.schema_version, p.template_id, p.template_version,
+                   (SELECT json_group_object(key, value) FROM spec_labels
+                    WHERE spec_id = p.id) AS labels
+            FROM spec_projections p
+            JOIN content_blobs b ON b.hash = p.content_hash
+            WHERE p.id = ? AND p.tenant_id = ?
+            ",
+        )
+        .bind(id.to_string())
+        .bind(tenant.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_projection(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The spec as it is served by default: with the content of its published
+    /// version once it has one, and of its head before that
+    pub async fn get_current(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Option<SpecProjection>, DomainError> {
+        let Some(mut spec) = self.get_by_id(tenant, id).await? else {
+            return Ok(None);
+        };
+
+        if let Some(version) = spec
+            .published_version
+            .filter(|version| *version != spec.head_version)
+        {
+            let revision = self
+                .get_version(tenant, id, version)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::ProjectionError(format!(
+                        "Published version {version} of spec {id} is missing"
+                    ))
+                })?;
+
+            spec.content = revision.content;
+            spec.content_hash = revision.content_hash;
+            spec.description = revision.description;
+            spec.semver = revision.semver;
+        }
+
+        Ok(Some(spec))
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_by_name(
+        &self,
+        tenant: &TenantId,
+        name: &str,
+    ) -> Result<Option<SpecProjection>, DomainError> {
+        let row = sqlx::query(
+            "
+            SELECT p.id, p.tenant_id, p.name, p.content_hash, b.data AS content_data,
+                   p.description, p.version, p.semver, p.published_version, p.state,
+                   p.created_at, p.updated_at, p.created_by, p.updated_by, p.stream_version,
+                   p.deprecation_reason, p.deprecated_at, p.successor_id, p.sunset_at, p.review,
+                   p.schema_id, p.schema_version, p.template_id, p.template_version,
+                   (SELECT json_group_object(key, value) FROM spec_labels
+                    WHERE spec_id = p.id) AS labels
+            FROM spec_projections p
+            JOIN content_blobs b ON b.hash = p.content_hash
+            WHERE p.tenant_id = ? AND p.name = ?
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_projection(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List specs, optionally restricted to one state and to specs whose
+    /// labels satisfy `selector`.
+    ///
+    /// Deleted specs are only listed when asked for by state or when
+    /// `include_deleted` is set.
+    pub async fn list_by_state(
+        &self,
+        tenant: &TenantId,
+        state: Option<SpecState>,
+        include_deleted: bool,
+        selector: &LabelSelector,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SpecSummaryProjection>, DomainError> {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "
+            SELECT id, name, description, version, semver, published_version, state,
+                   updated_at,
+                   (SELECT json_group_object(key, value) FROM spec_labels
+                    WHERE spec_id = spec_projections.id) AS labels
+            FROM spec_projections
+            WHERE tenant_id = ",
+        );
+        query.push_bind(tenant.as_str().to_string()).push(" AND ");
+
+        match state {
+            Some(s) => {
+                let state_str = match s {
+                    SpecState::Draft => "draft",
+                    SpecState::Published => "published",
+                    SpecState::Deprecated => "deprecated",
+                    SpecState::Deleted => "deleted",
+                };
+                query.push("state = ").push_bind(state_str);
+            }
+            None => {
+                query
+                    .push("(")
+                    .push_bind(include_deleted)
+                    .push(" OR state != 'deleted')");
+            }
+        }
+
+        for requirement in selector.requirements() {
+            let (negated, key, value) = match requirement {
+                LabelRequirement::Equals(key, value) => (false, key, Some(value)),
+                LabelRequirement::NotEquals(key, value) => (true, key, Some(value)),
+                LabelRequirement::Exists(key) => (false, key, None),
+                LabelRequirement::NotExists(key) => (true, key, None),
+            };
+
+            query
+                .push(if negated {
+                    " AND NOT EXISTS"
+                } else {
+                    " AND EXISTS"
+                })
+                .push(" (SELECT 1 FROM spec_labels WHERE spec_id = spec_projections.id AND key = ")
+                .push_bind(key.clone());
+            if let Some(value) = value {
+                query.push(" AND value = ").push_bind(value.clone());
+            }
+            query.push(")");
+        }
+
+        query
+            .push(" ORDER BY updated_at DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let summaries = rows
+            .into_iter()
+            .map(|row| self.row_to_summary(row))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(summaries)
+    }
+
+    /// The subfolders and specs directly in the folder `path`, or with
+    /// `recursive` everything below it; `None` when no spec is below a path
+    /// other than the root
+    pub async fn list_folder(
+        &self,
+        tenant: &TenantId,
+        path: &str,
+        recursive: bool,
+    ) -> Result<Option<FolderProjection>, DomainError> {
+        let folder_rows = if recursive {
+            sqlx::query(
+                "
+                SELECT below.folder, COUNT(*) AS spec_count
+                FROM spec_folders AS root
+                JOIN spec_folders AS below
+                    ON below.spec_id = root.spec_id AND below.depth < root.depth
+                WHERE root.tenant_id = ? AND root.folder = ?
+                GROUP BY below.folder
+                ORDER BY below.folder
+                ",
+            )
+        } else {
+            sqlx::query(
+                "
+                SELECT folder, COUNT(*) AS spec_count FROM spec_folders
+                WHERE tenant_id = ? AND parent = ?
+                GROUP BY folder
+                ORDER BY folder
+                ",
+            )
+        }
+        .bind(tenant.as_str())
+        .bind(path)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let spec_rows = sqlx::query(
+            "
+            SELECT id, name, description, version, semver, published_version, state,
+                   updated_at,
+                   (SELECT json_group_object(key, value) FROM spec_labels
+                    WHERE spec_id = spec_projections.id) AS labels
+            FROM spec_projections
+            WHERE id IN (
+                SELECT spec_id FROM spec_folders
+                WHERE tenant_id = ? AND folder = ? AND (? OR depth = 1)
+            )
+            ORDER BY name
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(path)
+        .bind(recursive)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        if !path.is_empty() && folder_rows.is_empty() && spec_rows.is_empty() {
+            return Ok(None);
+        }
+
+        let folders = folder_rows
+            .iter()
+            .map(|row| {
+                let spec_count: i64 = row.get("spec_count");
+                (
+                    row.get("folder"),
+                    u64::try_from(spec_count).unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        let specs = spec_rows
+            .into_iter()
+            .map(|row| self.row_to_summary(row))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(FolderProjection {
+            path: path.to_string(),
+            folders,
+            specs,
+        }))
+    }
+
+    /// Deprecated specs whose sunset has passed, with their tenants
+    pub async fn list_sunset_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(TenantId, Uuid)>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT tenant_id, id FROM spec_projections
+            WHERE state = 'deprecated' AND sunset_at IS NOT NULL AND sunset_at <= ?
+            ",
+        )
+        .bind(now.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.get("id");
+                Ok((
+                    parse_tenant_id(row.get::<&str, _>("tenant_id"))?,
+                    Uuid::parse_str(&id)
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+                ))
+            })
+            .collect()
+    }
+
+    /// List scheduled transitions, optionally for one spec or in one status,
+    /// soonest first
+    pub async fn list_schedules(
+        &self,
+        tenant: &TenantId,
+        spec_id: Option<Uuid>,
+        status: Option<ScheduleStatus>,
+    ) -> Result<Vec<ScheduleProjection>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT s.schedule_id, s.spec_id, s.transition, s.execute_at, s.scheduled_by,
+                   s.scheduled_at, s.status, s.completed_at, s.failure
+            FROM spec_schedules s
+            JOIN spec_projections p ON p.id = s.spec_id
+            WHERE p.tenant_id = ? AND (? IS NULL OR s.spec_id = ?)
+              AND (? IS NULL OR s.status = ?)
+            ORDER BY s.execute_at ASC
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(spec_id.map(|id| id.to_string()))
+        .bind(spec_id.map(|id| id.to_string()))
+        .bind(status.map(ScheduleStatus::as_str))
+        .bind(status.map(ScheduleStatus::as_str))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_schedule(row))
+            .collect()
+    }
+
+    /// Pending schedules whose execution time has passed, as the spec's
+    /// tenant with the spec and schedule ids
+    pub async fn list_schedules_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(TenantId, Uuid, Uuid)>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT p.tenant_id, s.spec_id, s.schedule_id
+            FROM spec_schedules s
+            JOIN spec_projections p ON p.id = s.spec_id
+            WHERE s.status = 'pending' AND s.execute_at <= ?
+            ORDER BY s.execute_at ASC
+            ",
+        )
+        .bind(now.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let spec_id: String = row.get("spec_id");
+                let schedule_id: String = row.get("schedule_id");
+                Ok((
+                    parse_tenant_id(row.get::<&str, _>("tenant_id"))?,
+                    Uuid::parse_str(&spec_id)
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+                    Uuid::parse_str(&schedule_id)
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Specs `id` depends on
+    pub async fn get_dependencies(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Vec<DependencyProjection>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT p.id, p.name, p.state, p.published_version, d.version_req
+            FROM spec_dependencies d
+            JOIN spec_projections p ON p.id = d.depends_on
+            WHERE d.spec_id = ? AND p.tenant_id = ?
+            ORDER BY p.name ASC
+            ",
+        )
+        .bind(id.to_string())
+        .bind(tenant.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter().map(row_to_dependency).collect()
+    }
+
+    /// Specs that depend on `id`
+    pub async fn get_dependents(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Vec<DependencyProjection>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT p.id, p.name, p.state, p.published_version, d.version_req
+            FROM spec_dependencies d
+            JOIN spec_projections p ON p.id = d.spec_id
+            WHERE d.depends_on = ? AND p.tenant_id = ?
+            ORDER BY p.name ASC
+            ",
+        )
+        .bind(id.to_string())
+        .bind(tenant.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter().map(row_to_dependency).collect()
+    }
+
+    /// Specs `channel` serves, by name
+    pub async fn list_channel_specs(
+        &self,
+        tenant: &TenantId,
+        channel: &str,
+    ) -> Result<Vec<ChannelSpecProjection>, DomainError> {
+        let rows = sqlx::query(&format!(
+            "{CHANNEL_SPECS_QUERY} AND c.channel = ? ORDER BY p.name ASC"
+        ))
+        .bind(tenant.as_str())
+        .bind(channel)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter().map(row_to_channel_spec).collect()
+    }
+
+    /// Channels serving `id`, by channel name
+    pub async fn list_spec_channels(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Vec<ChannelSpecProjection>, DomainError> {
+        let rows = sqlx::query(&format!(
+            "{CHANNEL_SPECS_QUERY} AND c.spec_id = ? ORDER BY c.channel ASC"
+        ))
+        .bind(tenant.as_str())
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter().map(row_to_channel_spec).collect()
+    }
+
+    pub async fn get_channel_spec(
+        &self,
+        tenant: &TenantId,
+        channel: &str,
+        id: Uuid,
+    ) -> Result<Option<ChannelSpecProjection>, DomainError> {
+        let row = sqlx::query(&format!(
+            "{CHANNEL_SPECS_QUERY} AND c.channel = ? AND c.spec_id = ?"
+        ))
+        .bind(tenant.as_str())
+        .bind(channel)
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        row.as_ref().map(row_to_channel_spec).transpose()
+    }
+
+    pub async fn get_version(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+        version: u32,
+    ) -> Result<Option<SpecVersionProjection>, DomainError> {
+        let row = sqlx::query(
+            "
+            SELECT h.version, h.semver, h.content_hash, b.data AS content_data, h.description,
+                   h.published_at
+            FROM spec_version_history h
+            JOIN spec_projections p ON p.id = h.id
+            JOIN content_blobs b ON b.hash = h.content_hash
+            WHERE p.tenant_id = ? AND h.id = ? AND h.version = ?
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(id.to_string())
+        //.bind(version as i64)
+        .bind(i64::from(version))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        row.map(|row| self.row_to_version(row)).transpose()
+    }
+
+    /// Find the highest published revision whose semantic version matches `req`
+    pub async fn resolve_version(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+        req: &semver::VersionReq,
+    ) -> Result<Option<SpecVersionProjection>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT h.version, h.semver, h.content_hash, b.data AS content_data, h.description,
+                   h.published_at
+            FROM spec_version_history h
+            JOIN spec_projections p ON p.id = h.id
+            JOIN content_blobs b ON b.hash = h.content_hash
+            WHERE p.tenant_id = ? AND h.id = ? AND h.published_at IS NOT NULL
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        let versions = rows
+            .into_iter()
+            .map(|row| self.row_to_version(row))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(versions
+            .into_iter()
+            .filter(|v| req.matches(&v.semver))
+            .max_by(|a, b| a.semver.cmp(&b.semver)))
+    }
+
+    pub async fn get_schema(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Option<SchemaProjection>, DomainError> {
+        let row = sqlx::query(
+            "
+            SELECT id, name, description, version, schema, created_at, updated_at,
+                   created_by, updated_by, stream_version
+            FROM schema_projections
+            WHERE id = ? AND tenant_id = ?
+            ",
+        )
+        .bind(id.to_string())
+        .bind(tenant.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        row.map(|row| self.row_to_schema(row)).transpose()
+    }
+
+    /// List registered schemas by name
+    pub async fn list_schemas(
+        &self,
+        tenant: &TenantId,
+    ) -> Result<Vec<SchemaProjection>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT id, name, description, version, schema, created_at, updated_at,
+                   created_by, updated_by, stream_version
+            FROM schema_projections
+            WHERE tenant_id = ?
+            ORDER BY name ASC
+            ",
+        )
+        .bind(tenant.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_schema(row))
+            .collect()
+    }
+
+    pub async fn get_schema_version(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+        version: u32,
+    ) -> Result<Option<SchemaVersionProjection>, DomainError> {
+        let row = sqlx::query(
+            "
+            SELECT v.version, v.schema, v.description, v.created_at, v.created_by
+            FROM schema_versions v
+            JOIN schema_projections p ON p.id = v.id
+            WHERE p.tenant_id = ? AND v.id = ? AND v.version = ?
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(id.to_string())
+        .bind(i64::from(version))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        row.map(|row| self.row_to_schema_version(row)).transpose()
+    }
+
+    /// Comment threads on a spec, optionally only those on one version or
+    /// with one resolution state, oldest first
+    pub async fn list_comment_threads(
+        &self,
+        tenant: &TenantId,
+        spec_id: Uuid,
+        version: Option<u32>,
+        resolved: Option<bool>,
+    ) -> Result<Vec<CommentThreadProjection>, DomainError> {
+        let rows = sqlx::query(
             "
-            UPDATE spec_projections
-            SET state = ?, updated_at = ?
-            WHERE id = ?
+            SELECT c.comment_id, c.version, c.parent_id, c.path, c.start_line, c.end_line,
+                   c.body, c.author, c.created_at, c.edited_at, c.resolved_by, c.resolved_at
+            FROM spec_comments c
+            JOIN spec_projections p ON p.id = c.spec_id
+            WHERE p.tenant_id = ? AND c.spec_id = ? AND (? IS NULL OR c.version = ?)
+            ORDER BY c.created_at ASC, c.rowid ASC
             ",
         )
-        .bind(state_str)
-        .bind(event.changed_at.to_rfc3339())
-        .bind(event.spec_id.to_string())
-        .execute(&self.pool)
+        .bind(tenant.as_str())
+        .bind(spec_id.to_string())
+        .bind(version.map(i64::from))
+        .bind(version.map(i64::from))
+        .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
-        // Update cache if enabled
-        if let Some(cache) = self.cache.write().await.as_mut() {
-            if let Some(proj) = cache.get_mut(&event.spec_id) {
-                proj.state = event.to_state;
-                proj.updated_at = event.changed_at;
+        let mut threads: Vec<CommentThreadProjection> = Vec::new();
+        for row in &rows {
+            let comment = row_to_comment(row)?;
+            match row.get::<Option<String>, _>("parent_id") {
+                Some(parent_id) => {
+                    let parent_id = Uuid::parse_str(&parent_id)
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+                    if let Some(thread) = threads.iter_mut().find(|t| t.comment.id == parent_id) {
+                        thread.replies.push(comment);
+                    }
+                }
+                None => threads.push(CommentThreadProjection {
+                    comment,
+                    replies: Vec::new(),
+                }),
             }
         }
 
-        Ok(())
+        if let Some(resolved) = resolved {
+            threads.retain(|thread| thread.comment.resolved_at.is_some() == resolved);
+        }
+
+        Ok(threads)
     }
 
-    // Query methods for read models
+    pub async fn get_template(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Option<TemplateProjection>, DomainError> {
+        let row = sqlx::query(
+            "
+            SELECT id, name, description, version, body, parameters, created_at, updated_at,
+                   created_by, updated_by, stream_version
+            FROM template_projections
+            WHERE id = ? AND tenant_id = ?
+            ",
+        )
+        .bind(id.to_string())
+        .bind(tenant.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
-    pub async fn get_by_id(&self, id: Uuid) -> Result<Option<SpecProjection>, DomainError> {
-        // Check cache first
-        if let Some(cache) = self.cache.read().await.as_ref() {
-            if let Some(proj) = cache.get(&id) {
-                return Ok(Some(proj.clone()));
-            }
-        }
+        row.map(|row| row_to_template(&row)).transpose()
+    }
+
+    /// List templates by name
+    pub async fn list_templates(
+        &self,
+        tenant: &TenantId,
+    ) -> Result<Vec<TemplateProjection>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT id, name, description, version, body, parameters, created_at, updated_at,
+                   created_by, updated_by, stream_version
+            FROM template_projections
+            WHERE tenant_id = ?
+            ORDER BY name ASC
+            ",
+        )
+        .bind(tenant.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter().map(row_to_template).collect()
+    }
 
+    pub async fn get_template_version(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+        version: u32,
+    ) -> Result<Option<TemplateVersionProjection>, DomainError> {
         let row = sqlx::query(
             "
-            SELECT id, name, content, description, version, state,
-                   created_at, updated_at, created_by, updated_by
-            FROM spec_projections
-            WHERE id = ?
+            SELECT v.version, v.body, v.parameters, v.description, v.created_at, v.created_by
+            FROM template_versions v
+            JOIN template_projections p ON p.id = v.id
+            WHERE p.tenant_id = ? AND v.id = ? AND v.version = ?
             ",
         )
+        .bind(tenant.as_str())
         .bind(id.to_string())
+        .bind(i64::from(version))
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
-        match row {
-            Some(row) => Ok(Some(self.row_to_projection(row)?)),
-            None => Ok(None),
-        }
+        row.map(|row| -> Result<_, DomainError> {
+            let parameters: String = row.get("parameters");
+            let created_at: String = row.get("created_at");
+
+            Ok(TemplateVersionProjection {
+                version: u32::try_from(row.get::<i64, _>("version")).unwrap_or(0),
+                body: row.get("body"),
+                parameters: serde_json::from_str(&parameters)
+                    .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+                description: row.get("description"),
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                    .with_timezone(&Utc),
+                created_by: row.get("created_by"),
+            })
+        })
+        .transpose()
     }
 
-    #[allow(dead_code)]
-    pub async fn get_by_name(&self, name: &str) -> Result<Option<SpecProjection>, DomainError> {
-        let row = sqlx::query(
+    /// Specs rendered from any version of a template, deleted ones excluded
+    pub async fn list_template_specs(
+        &self,
+        tenant: &TenantId,
+        template_id: Uuid,
+    ) -> Result<Vec<DerivedSpecProjection>, DomainError> {
+        let rows = sqlx::query(
             "
-            SELECT id, name, content, description, version, state,
-                   created_at, updated_at, created_by, updated_by
+            SELECT id, name, state, template_version, updated_at
             FROM spec_projections
-            WHERE name = ?
+            WHERE tenant_id = ? AND template_id = ? AND state != 'deleted'
+            ORDER BY name ASC
             ",
         )
-        .bind(name)
+        .bind(tenant.as_str())
+        .bind(template_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.get("id");
+                let state: String = row.get("state");
+                let updated_at: String = row.get("updated_at");
+
+                Ok(DerivedSpecProjection {
+                    id: Uuid::parse_str(&id)
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+                    name: row.get("name"),
+                    state: parse_state(&state)?,
+                    template_version: u32::try_from(row.get::<i64, _>("template_version"))
+                        .unwrap_or(0),
+                    updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                        .with_timezone(&Utc),
+                })
+            })
+            .collect()
+    }
+
+    pub async fn get_release(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Option<ReleaseProjection>, DomainError> {
+        let row = sqlx::query(&format!("{RELEASES_QUERY} WHERE tenant_id = ? AND id = ?"))
+            .bind(tenant.as_str())
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_release(&row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The release consumers see: the most recently published one that has
+    /// not been rolled back
+    pub async fn get_current_release(
+        &self,
+        tenant: &TenantId,
+    ) -> Result<Option<ReleaseProjection>, DomainError> {
+        let row = sqlx::query(&format!(
+            "{RELEASES_QUERY} WHERE tenant_id = ? AND state = 'published'
+             ORDER BY published_at DESC LIMIT 1"
+        ))
+        .bind(tenant.as_str())
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
         match row {
-            Some(row) => Ok(Some(self.row_to_projection(row)?)),
+            Some(row) => Ok(Some(self.row_to_release(&row).await?)),
             None => Ok(None),
         }
     }
 
-    pub async fn list_by_state(
+    /// List releases, newest first
+    pub async fn list_releases(
         &self,
-        state: Option<SpecState>,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<SpecSummaryProjection>, DomainError> {
-        let query = state.map_or_else(
-            || {
-                sqlx::query(
-                    "
-                    SELECT id, name, description, version, state, updated_at
-                    FROM spec_projections
-                    WHERE state != 'deleted'
-                    ORDER BY updated_at DESC
-                    LIMIT ? OFFSET ?
-                    ",
-                )
-                .bind(limit)
-                .bind(offset)
-            },
-            |s| {
-                let state_str = match s {
-                    SpecState::Draft => "draft",
-                    SpecState::Published => "published",
-                    SpecState::Deprecated => "deprecated",
-                    SpecState::Deleted => "deleted",
-                };
-                sqlx::query(
-                    "
-                    SELECT id, name, description, version, state, updated_at
-                    FROM spec_projections
-                    WHERE state = ?
-                    ORDER BY updated_at DESC
-                    LIMIT ? OFFSET ?
-                    ",
-                )
-                .bind(state_str)
-                .bind(limit)
-                .bind(offset)
-            },
-        );
+        tenant: &TenantId,
+    ) -> Result<Vec<ReleaseProjection>, DomainError> {
+        let rows = sqlx::query(&format!(
+            "{RELEASES_QUERY} WHERE tenant_id = ? ORDER BY created_at DESC"
+        ))
+        .bind(tenant.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
-        let rows = query
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        let mut releases = Vec::with_capacity(rows.len());
+        for row in &rows {
+            releases.push(self.row_to_release(row).await?);
+        }
+        Ok(releases)
+    }
 
-        let summaries = rows
-            .into_iter()
-            .map(|row| self.row_to_summary(row))
-            .collect::<Result<Vec<_>, _>>()?;
+    /// Every spec version a release pins, with its content, in pinned order
+    pub async fn list_release_specs(
+        &self,
+        tenant: &TenantId,
+        id: Uuid,
+    ) -> Result<Vec<(ReleaseEntryProjection, SpecVersionProjection)>, DomainError> {
+        let mut specs = Vec::new();
 
-        Ok(summaries)
+        for entry in self.get_release_entries(id).await? {
+            let revision = self
+                .get_version(tenant, entry.spec_id, entry.version)
+                .await?
+                .ok_or(DomainError::VersionNotFound(entry.version))?;
+            specs.push((entry, revision));
+        }
+
+        Ok(specs)
     }
 
-    pub async fn get_version(
+    async fn get_release_entries(
         &self,
         id: Uuid,
-        version: u32,
-    ) -> Result<Option<(String, Option<String>)>, DomainError> {
-        let row = sqlx::query(
+    ) -> Result<Vec<ReleaseEntryProjection>, DomainError> {
+        let rows = sqlx::query(
             "
-            SELECT content, description
-            FROM spec_version_history
-            WHERE id = ? AND version = ?
+            SELECT e.spec_id, p.name, e.version, h.semver, h.content_hash
+            FROM release_entries e
+            JOIN spec_projections p ON p.id = e.spec_id
+            JOIN spec_version_history h ON h.id = e.spec_id AND h.version = e.version
+            WHERE e.release_id = ?
+            ORDER BY e.position ASC
             ",
         )
         .bind(id.to_string())
-        //.bind(version as i64)
-        .bind(i64::from(version))
-        .fetch_optional(&self.pool)
+        .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
 
-        Ok(row.map(|row| {
-            let content: String = row.get("content");
-            let description: Option<String> = row.get("description");
-            (content, description)
-        }))
+        rows.iter()
+            .map(|row| {
+                let spec_id: String = row.get("spec_id");
+                let version = u32::try_from(row.get::<i64, _>("version")).unwrap_or(0);
+                Ok(ReleaseEntryProjection {
+                    spec_id: Uuid::parse_str(&spec_id)
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+                    name: row.get("name"),
+                    version,
+                    semver: parse_semver(row.get("semver"), version),
+                    content_hash: row.get("content_hash"),
+                })
+            })
+            .collect()
+    }
+
+    /// List tenants by id
+    pub async fn list_tenants(&self) -> Result<Vec<TenantProjection>, DomainError> {
+        let rows = sqlx::query(
+            "
+            SELECT id, display_name, state, suspension_reason, created_at, created_by,
+                   updated_at, updated_by
+            FROM tenant_projections
+            ORDER BY id ASC
+            ",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+        rows.iter().map(row_to_tenant).collect()
+    }
+
+    async fn row_to_release(
+        &self,
+        row: &sqlx::sqlite::SqliteRow,
+    ) -> Result<ReleaseProjection, DomainError> {
+        let id: String = row.get("id");
+        let id = Uuid::parse_str(&id).map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+        let state: String = row.get("state");
+        let created_at: String = row.get("created_at");
+        let updated_at: String = row.get("updated_at");
+        let published_at: Option<String> = row.get("published_at");
+
+        Ok(ReleaseProjection {
+            id,
+            name: row.get("name"),
+            description: row.get("description"),
+            state: parse_release_state(&state)?,
+            entries: self.get_release_entries(id).await?,
+            created_at: DateTime::parse_from_rfc3339(&created_at)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .with_timezone(&Utc),
+            created_by: row.get("created_by"),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .with_timezone(&Utc),
+            updated_by: row.get("updated_by"),
+            published_at: published_at
+                .map(|at| DateTime::parse_from_rfc3339(&at))
+                .transpose()
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .map(|at| at.with_timezone(&Utc)),
+            stream_version: row.get("stream_version"),
+        })
     }
 
     #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
@@ -429,22 +3198,49 @@ impl ProjectionStore {
         let state_str: String = row.get("state");
         let created_at_str: String = row.get("created_at");
         let updated_at_str: String = row.get("updated_at");
+        let deprecated_at_str: Option<String> = row.get("deprecated_at");
+        let review_json: Option<String> = row.get("review");
+        let head_version = u32::try_from(row.get::<i64, _>("version")).unwrap_or(0);
+
+        let deprecation = match deprecated_at_str {
+            Some(deprecated_at_str) => {
+                let successor_id: Option<String> = row.get("successor_id");
+                let sunset_at: Option<String> = row.get("sunset_at");
 
-        let state = match state_str.as_str() {
-            "draft" => SpecState::Draft,
-            "published" => SpecState::Published,
-            "deprecated" => SpecState::Deprecated,
-            "deleted" => SpecState::Deleted,
-            _ => return Err(DomainError::ProjectionError("Invalid state".to_string())),
+                Some(SpecDeprecation {
+                    reason: row.get("deprecation_reason"),
+                    deprecated_at: DateTime::parse_from_rfc3339(&deprecated_at_str)
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                        .with_timezone(&Utc),
+                    successor_id: successor_id
+                        .map(|id| Uuid::parse_str(&id))
+                        .transpose()
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+                    sunset_at: sunset_at
+                        .map(|at| DateTime::parse_from_rfc3339(&at))
+                        .transpose()
+                        .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                        .map(|at| at.with_timezone(&Utc)),
+                })
+            }
+            None => None,
         };
 
+        let state = parse_state(&state_str)?;
+
         Ok(SpecProjection {
             id: Uuid::parse_str(&id_str)
                 .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+            tenant_id: parse_tenant_id(row.get::<&str, _>("tenant_id"))?,
             name: row.get("name"),
-            content: row.get("content"),
+            content: blob_store::decompress_text(&row.get::<Vec<u8>, _>("content_data"))?,
+            content_hash: row.get("content_hash"),
             description: row.get("description"),
-            version: u32::try_from(row.get::<i64, _>("version")).unwrap_or(0),
+            head_version,
+            semver: parse_semver(row.get("semver"), head_version),
+            published_version: row
+                .get::<Option<i64>, _>("published_version")
+                .and_then(|v| u32::try_from(v).ok()),
             state,
             created_at: DateTime::parse_from_rfc3339(&created_at_str)
                 .map_err(|e| DomainError::ProjectionError(e.to_string()))?
@@ -454,6 +3250,55 @@ impl ProjectionStore {
                 .with_timezone(&Utc),
             created_by: row.get("created_by"),
             updated_by: row.get("updated_by"),
+            stream_version: row.get("stream_version"),
+            deprecation,
+            review: review_json
+                .map(|json| serde_json::from_str(&json))
+                .transpose()
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+            labels: parse_labels(row.get("labels"))?,
+            schema: parse_schema_ref(row.get("schema_id"), row.get("schema_version"))?,
+            template: parse_template_ref(row.get("template_id"), row.get("template_version"))?,
+        })
+    }
+
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    fn row_to_schedule(
+        &self,
+        row: sqlx::sqlite::SqliteRow,
+    ) -> Result<ScheduleProjection, DomainError> {
+        let schedule_id_str: String = row.get("schedule_id");
+        let spec_id_str: String = row.get("spec_id");
+        let transition_str: String = row.get("transition");
+        let execute_at_str: String = row.get("execute_at");
+        let scheduled_at_str: String = row.get("scheduled_at");
+        let status_str: String = row.get("status");
+        let completed_at_str: Option<String> = row.get("completed_at");
+
+        let status = ScheduleStatus::parse(&status_str)
+            .ok_or_else(|| DomainError::ProjectionError("Invalid schedule status".to_string()))?;
+
+        Ok(ScheduleProjection {
+            schedule_id: Uuid::parse_str(&schedule_id_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+            spec_id: Uuid::parse_str(&spec_id_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+            transition: serde_json::from_str(&transition_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+            execute_at: DateTime::parse_from_rfc3339(&execute_at_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .with_timezone(&Utc),
+            scheduled_by: row.get("scheduled_by"),
+            scheduled_at: DateTime::parse_from_rfc3339(&scheduled_at_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .with_timezone(&Utc),
+            status,
+            completed_at: completed_at_str
+                .map(|at| DateTime::parse_from_rfc3339(&at))
+                .transpose()
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .map(|at| at.with_timezone(&Utc)),
+            failure: row.get("failure"),
         })
     }
 
@@ -465,25 +3310,345 @@ impl ProjectionStore {
         let id_str: String = row.get("id");
         let state_str: String = row.get("state");
         let updated_at_str: String = row.get("updated_at");
+        let latest_version = u32::try_from(row.get::<i64, _>("version")).unwrap_or(0);
 
-        let state = match state_str.as_str() {
-            "draft" => SpecState::Draft,
-            "published" => SpecState::Published,
-            "deprecated" => SpecState::Deprecated,
-            "deleted" => SpecState::Deleted,
-            _ => return Err(DomainError::ProjectionError("Invalid state".to_string())),
-        };
+        let state = parse_state(&state_str)?;
 
         Ok(SpecSummaryProjection {
             id: Uuid::parse_str(&id_str)
                 .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
             name: row.get("name"),
             description: row.get("description"),
-            latest_version: u32::try_from(row.get::<i64, _>("version")).unwrap_or(0),
+            latest_version,
+            semver: parse_semver(row.get("semver"), latest_version),
+            published_version: row
+                .get::<Option<i64>, _>("published_version")
+                .and_then(|v| u32::try_from(v).ok()),
             state,
             updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
                 .map_err(|e| DomainError::ProjectionError(e.to_string()))?
                 .with_timezone(&Utc),
+            labels: parse_labels(row.get("labels"))?,
+        })
+    }
+
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    fn row_to_version(
+        &self,
+        row: sqlx::sqlite::SqliteRow,
+    ) -> Result<SpecVersionProjection, DomainError> {
+        let version = u32::try_from(row.get::<i64, _>("version")).unwrap_or(0);
+        let published_at_str: Option<String> = row.get("published_at");
+        let content_data: Vec<u8> = row.get("content_data");
+
+        Ok(SpecVersionProjection {
+            version,
+            semver: parse_semver(row.get("semver"), version),
+            content: blob_store::decompress_text(&content_data)?,
+            content_hash: row.get("content_hash"),
+            description: row.get("description"),
+            published_at: published_at_str
+                .map(|at| DateTime::parse_from_rfc3339(&at))
+                .transpose()
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .map(|at| at.with_timezone(&Utc)),
+        })
+    }
+
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    fn row_to_schema(&self, row: sqlx::sqlite::SqliteRow) -> Result<SchemaProjection, DomainError> {
+        let id_str: String = row.get("id");
+        let schema_str: String = row.get("schema");
+        let created_at_str: String = row.get("created_at");
+        let updated_at_str: String = row.get("updated_at");
+
+        Ok(SchemaProjection {
+            id: Uuid::parse_str(&id_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+            name: row.get("name"),
+            description: row.get("description"),
+            head_version: u32::try_from(row.get::<i64, _>("version")).unwrap_or(0),
+            schema: serde_json::from_str(&schema_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .with_timezone(&Utc),
+            created_by: row.get("created_by"),
+            updated_by: row.get("updated_by"),
+            stream_version: row.get("stream_version"),
+        })
+    }
+
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    fn row_to_schema_version(
+        &self,
+        row: sqlx::sqlite::SqliteRow,
+    ) -> Result<SchemaVersionProjection, DomainError> {
+        let schema_str: String = row.get("schema");
+        let created_at_str: String = row.get("created_at");
+
+        Ok(SchemaVersionProjection {
+            version: u32::try_from(row.get::<i64, _>("version")).unwrap_or(0),
+            schema: serde_json::from_str(&schema_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+            description: row.get("description"),
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+                .with_timezone(&Utc),
+            created_by: row.get("created_by"),
         })
     }
 }
+
+fn row_to_dependency(row: &sqlx::sqlite::SqliteRow) -> Result<DependencyProjection, DomainError> {
+    let id: String = row.get("id");
+    let state: String = row.get("state");
+    let version: String = row.get("version_req");
+
+    Ok(DependencyProjection {
+        spec_id: Uuid::parse_str(&id).map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+        name: row.get("name"),
+        state: parse_state(&state)?,
+        published_version: row
+            .get::<Option<i64>, _>("published_version")
+            .and_then(|v| u32::try_from(v).ok()),
+        version: semver::VersionReq::parse(&version)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+    })
+}
+
+fn row_to_comment(row: &sqlx::sqlite::SqliteRow) -> Result<CommentProjection, DomainError> {
+    let id: String = row.get("comment_id");
+    let path: Option<String> = row.get("path");
+    let start_line: Option<i64> = row.get("start_line");
+    let end_line: Option<i64> = row.get("end_line");
+    let created_at: String = row.get("created_at");
+    let edited_at: Option<String> = row.get("edited_at");
+    let resolved_at: Option<String> = row.get("resolved_at");
+
+    let lines = match (start_line, end_line) {
+        (Some(start), Some(end)) => Some(LineRange {
+            start: u32::try_from(start).unwrap_or(0),
+            end: u32::try_from(end).unwrap_or(0),
+        }),
+        _ => None,
+    };
+    let parse_time = |s: &str| {
+        DateTime::parse_from_rfc3339(s)
+            .map(|at| at.with_timezone(&Utc))
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))
+    };
+
+    Ok(CommentProjection {
+        id: Uuid::parse_str(&id).map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+        version: u32::try_from(row.get::<i64, _>("version")).unwrap_or(0),
+        anchor: (path.is_some() || lines.is_some()).then_some(CommentAnchor { path, lines }),
+        body: row.get("body"),
+        author: row.get("author"),
+        created_at: parse_time(&created_at)?,
+        edited_at: edited_at.as_deref().map(parse_time).transpose()?,
+        resolved_by: row.get("resolved_by"),
+        resolved_at: resolved_at.as_deref().map(parse_time).transpose()?,
+    })
+}
+
+fn row_to_channel_spec(
+    row: &sqlx::sqlite::SqliteRow,
+) -> Result<ChannelSpecProjection, DomainError> {
+    let spec_id: String = row.get("spec_id");
+    let state: String = row.get("state");
+    let version = u32::try_from(row.get::<i64, _>("version")).unwrap_or(0);
+    let changed_at: String = row.get("changed_at");
+
+    Ok(ChannelSpecProjection {
+        channel: row.get("channel"),
+        spec_id: Uuid::parse_str(&spec_id)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+        name: row.get("name"),
+        state: parse_state(&state)?,
+        version,
+        semver: parse_semver(row.get("semver"), version),
+        changed_by: row.get("changed_by"),
+        changed_at: DateTime::parse_from_rfc3339(&changed_at)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+            .with_timezone(&Utc),
+    })
+}
+
+fn row_to_template(row: &sqlx::sqlite::SqliteRow) -> Result<TemplateProjection, DomainError> {
+    let id: String = row.get("id");
+    let parameters: String = row.get("parameters");
+    let created_at: String = row.get("created_at");
+    let updated_at: String = row.get("updated_at");
+
+    Ok(TemplateProjection {
+        id: Uuid::parse_str(&id).map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+        name: row.get("name"),
+        description: row.get("description"),
+        head_version: u32::try_from(row.get::<i64, _>("version")).unwrap_or(0),
+        body: row.get("body"),
+        parameters: serde_json::from_str(&parameters)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+            .with_timezone(&Utc),
+        updated_at: DateTime::parse_from_rfc3339(&updated_at)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+            .with_timezone(&Utc),
+        created_by: row.get("created_by"),
+        updated_by: row.get("updated_by"),
+        stream_version: row.get("stream_version"),
+    })
+}
+
+fn row_to_tenant(row: &sqlx::sqlite::SqliteRow) -> Result<TenantProjection, DomainError> {
+    let state: String = row.get("state");
+    let created_at: String = row.get("created_at");
+    let updated_at: String = row.get("updated_at");
+
+    Ok(TenantProjection {
+        id: parse_tenant_id(row.get::<&str, _>("id"))?,
+        display_name: row.get("display_name"),
+        state: match state.as_str() {
+            "active" => TenantState::Active,
+            "suspended" => TenantState::Suspended,
+            _ => {
+                return Err(DomainError::ProjectionError(
+                    "Invalid tenant state".to_string(),
+                ))
+            }
+        },
+        suspension_reason: row.get("suspension_reason"),
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+            .with_timezone(&Utc),
+        created_by: row.get("created_by"),
+        updated_at: DateTime::parse_from_rfc3339(&updated_at)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?
+            .with_timezone(&Utc),
+        updated_by: row.get("updated_by"),
+    })
+}
+
+/// Record `spec_id` in the folders above `name`, replacing the folders it
+/// was in before
+async fn index_folders(
+    conn: &mut SqliteConnection,
+    tenant: &TenantId,
+    spec_id: Uuid,
+    name: Option<&str>,
+) -> Result<(), DomainError> {
+    unindex_folders(conn, spec_id).await?;
+
+    let Some(name) = name else {
+        return Ok(());
+    };
+
+    let name = SpecName::recorded(name.to_string());
+    let folders = name.folders();
+    for (index, folder) in folders.iter().enumerate() {
+        sqlx::query(
+            "
+            INSERT INTO spec_folders (tenant_id, folder, parent, spec_id, depth)
+            VALUES (?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(tenant.as_str())
+        .bind(*folder)
+        .bind(index.checked_sub(1).map(|parent| folders[parent]))
+        .bind(spec_id.to_string())
+        .bind(i64::try_from(folders.len() - index).unwrap_or(i64::MAX))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+async fn unindex_folders(conn: &mut SqliteConnection, spec_id: Uuid) -> Result<(), DomainError> {
+    sqlx::query("DELETE FROM spec_folders WHERE spec_id = ?")
+        .bind(spec_id.to_string())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::ProjectionError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Tenant id stored alongside a row
+fn parse_tenant_id(tenant_id: &str) -> Result<TenantId, DomainError> {
+    TenantId::new(tenant_id).map_err(|e| DomainError::ProjectionError(e.to_string()))
+}
+
+fn parse_state(state: &str) -> Result<SpecState, DomainError> {
+    match state {
+        "draft" => Ok(SpecState::Draft),
+        "published" => Ok(SpecState::Published),
+        "deprecated" => Ok(SpecState::Deprecated),
+        "deleted" => Ok(SpecState::Deleted),
+        _ => Err(DomainError::ProjectionError("Invalid state".to_string())),
+    }
+}
+
+fn parse_release_state(state: &str) -> Result<ReleaseState, DomainError> {
+    match state {
+        "draft" => Ok(ReleaseState::Draft),
+        "published" => Ok(ReleaseState::Published),
+        "rolled_back" => Ok(ReleaseState::RolledBack),
+        _ => Err(DomainError::ProjectionError(
+            "Invalid release state".to_string(),
+        )),
+    }
+}
+
+/// Semantic version stored for a revision, falling back to the one derived
+/// from its number for rows written before semantic versions were tracked
+fn parse_semver(stored: Option<String>, version: u32) -> semver::Version {
+    stored
+        .and_then(|s| semver::Version::parse(&s).ok())
+        .unwrap_or_else(|| Version::new(version).legacy_semver())
+}
+
+/// Labels aggregated into a JSON object by the projection queries
+fn parse_labels(json: Option<String>) -> Result<BTreeMap<String, String>, DomainError> {
+    json.map_or_else(
+        || Ok(BTreeMap::new()),
+        |json| serde_json::from_str(&json).map_err(|e| DomainError::ProjectionError(e.to_string())),
+    )
+}
+
+/// Schema reference stored as separate id and version columns
+fn parse_schema_ref(
+    schema_id: Option<String>,
+    version: Option<i64>,
+) -> Result<Option<SchemaRef>, DomainError> {
+    let (Some(schema_id), Some(version)) = (schema_id, version) else {
+        return Ok(None);
+    };
+
+    Ok(Some(SchemaRef {
+        schema_id: Uuid::parse_str(&schema_id)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+        version: u32::try_from(version).unwrap_or(0),
+    }))
+}
+
+/// Template reference stored as separate id and version columns
+fn parse_template_ref(
+    template_id: Option<String>,
+    version: Option<i64>,
+) -> Result<Option<TemplateRef>, DomainError> {
+    let (Some(template_id), Some(version)) = (template_id, version) else {
+        return Ok(None);
+    };
+
+    Ok(Some(TemplateRef {
+        template_id: Uuid::parse_str(&template_id)
+            .map_err(|e| DomainError::ProjectionError(e.to_string()))?,
+        version: u32::try_from(version).unwrap_or(0),
+    }))
+}
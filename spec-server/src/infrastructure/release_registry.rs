@@ -0,0 +1,271 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::{
+    event_store::{IdempotentRequest, SqliteEventStore},
+    repositories::SpecRepository,
+};
+use crate::domain::{
+    aggregates::Release,
+    commands::{CreateRelease, PublishRelease, RollbackRelease},
+    errors::DomainError,
+    events::{EventEnvelope, EventMetadata, ReleaseState, SpecEvent, SpecState},
+    value_objects::{TenantId, Version},
+};
+
+/// Loads `Release` aggregates from the event store and publishes them,
+/// storing a release's events together with those of the specs it publishes.
+#[derive(Clone)]
+pub struct ReleaseRegistry {
+    event_store: Arc<SqliteEventStore>,
+    specs: SpecRepository,
+}
+
+impl ReleaseRegistry {
+    pub const fn new(event_store: Arc<SqliteEventStore>, specs: SpecRepository) -> Self {
+        Self { event_store, specs }
+    }
+
+    /// Rebuild a release of `tenant` together with its current stream version
+    pub async fn load(
+        &self,
+        tenant: &TenantId,
+        release_id: Uuid,
+    ) -> Result<Option<(Release, i64)>, DomainError> {
+        let envelopes = self
+            .event_store
+            .get_events(tenant, release_id, None)
+            .await?;
+
+        // Specs share the event store, so their streams are not releases
+        if !matches!(
+            envelopes.first().map(|e| &e.event),
+            Some(SpecEvent::ReleaseCreated(_))
+        ) {
+            return Ok(None);
+        }
+
+        let stream_version = envelopes.last().map_or(0, |e| e.sequence_number);
+        let release = Release::from_events(envelopes.into_iter().map(|e| e.event).collect())?;
+
+        Ok(Some((release, stream_version)))
+    }
+
+    /// Create a draft release, checking that every pinned version exists
+    pub async fn create(
+        &self,
+        command: CreateRelease,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        if self
+            .event_store
+            .find_release(&metadata.tenant_id, &command.name)
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::DuplicateReleaseName(command.name));
+        }
+
+        for entry in &command.entries {
+            let (spec, _) = self
+                .specs
+                .load(&metadata.tenant_id, entry.spec_id)
+                .await?
+                .ok_or(DomainError::SpecNotFound(entry.spec_id))?;
+
+            if spec.state == SpecState::Deleted {
+                return Err(DomainError::InvalidRelease(format!(
+                    "Spec {} is deleted",
+                    spec.name
+                )));
+            }
+            if !spec.revisions.contains_key(&Version::new(entry.version)) {
+                return Err(DomainError::InvalidRelease(format!(
+                    "Spec {} has no version {}",
+                    spec.name, entry.version
+                )));
+            }
+        }
+
+        let events = Release::create(command)?;
+
+        let release_id = match &events[0] {
+            SpecEvent::ReleaseCreated(e) => e.release_id,
+            _ => unreachable!(),
+        };
+
+        self.event_store
+            .append_events(release_id, events, Some(0), metadata, request.as_ref())
+            .await
+    }
+
+    /// Make a release current, publishing the pinned versions that are not
+    /// yet published in the same transaction, so either all of them go live
+    /// or none do.
+    ///
+    /// Returns the release's own events.
+    pub async fn publish(
+        &self,
+        command: PublishRelease,
+        expected_version: Option<i64>,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let release_id = command.release_id;
+        let request = IdempotentRequest::new(&metadata, &command);
+        let envelopes = match self.event_store.replay(request.as_ref()).await? {
+            Some(envelopes) => envelopes,
+            None => {
+                self.publish_once(command, expected_version, metadata, request)
+                    .await?
+            }
+        };
+
+        Ok(envelopes
+            .into_iter()
+            .filter(|envelope| envelope.aggregate_id == release_id)
+            .collect())
+    }
+
+    async fn publish_once(
+        &self,
+        command: PublishRelease,
+        expected_version: Option<i64>,
+        metadata: EventMetadata,
+        request: Option<IdempotentRequest>,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let release_id = command.release_id;
+        let (release, stream_version) = self
+            .load_expected(&metadata.tenant_id, release_id, expected_version)
+            .await?;
+
+        let published_by = command.published_by.clone();
+        let events = release.handle_publish(command)?;
+
+        let mut appends = vec![(release_id, events, Some(stream_version))];
+        appends.extend(
+            self.specs
+                .stage_release(&metadata.tenant_id, &release.entries, &published_by)
+                .await?,
+        );
+
+        self.event_store
+            .append_batch(appends, metadata, request.as_ref())
+            .await
+    }
+
+    /// Roll back the current release, making the release published before it
+    /// current again and publishing the versions that release pins in the
+    /// same transaction.
+    ///
+    /// Returns the release's own events.
+    pub async fn rollback(
+        &self,
+        command: RollbackRelease,
+        expected_version: Option<i64>,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let release_id = command.release_id;
+        let request = IdempotentRequest::new(&metadata, &command);
+        let envelopes = match self.event_store.replay(request.as_ref()).await? {
+            Some(envelopes) => envelopes,
+            None => {
+                self.rollback_once(command, expected_version, metadata, request)
+                    .await?
+            }
+        };
+
+        Ok(envelopes
+            .into_iter()
+            .filter(|envelope| envelope.aggregate_id == release_id)
+            .collect())
+    }
+
+    async fn rollback_once(
+        &self,
+        command: RollbackRelease,
+        expected_version: Option<i64>,
+        metadata: EventMetadata,
+        request: Option<IdempotentRequest>,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let release_id = command.release_id;
+        let (release, stream_version) = self
+            .load_expected(&metadata.tenant_id, release_id, expected_version)
+            .await?;
+
+        let mut published = self.published(&metadata.tenant_id).await?.into_iter();
+        let restored_release_id = match published.next() {
+            Some(current) if current == release_id => published.next(),
+            _ if release.state == ReleaseState::Published => {
+                return Err(DomainError::InvalidRelease(format!(
+                    "Release {} is not the current release",
+                    release.name
+                )));
+            }
+            _ => None,
+        };
+
+        let rolled_back_by = command.rolled_back_by.clone();
+        let events = release.handle_rollback(command, restored_release_id)?;
+
+        let mut appends = vec![(release_id, events, Some(stream_version))];
+        if let Some(restored_release_id) = restored_release_id {
+            let (restored, _) = self
+                .load(&metadata.tenant_id, restored_release_id)
+                .await?
+                .ok_or(DomainError::ReleaseNotFound(restored_release_id))?;
+            appends.extend(
+                self.specs
+                    .stage_rollback(&metadata.tenant_id, &restored.entries, &rolled_back_by)
+                    .await?,
+            );
+        }
+
+        self.event_store
+            .append_batch(appends, metadata, request.as_ref())
+            .await
+    }
+
+    /// Published releases of `tenant`, the current one first
+    async fn published(&self, tenant: &TenantId) -> Result<Vec<Uuid>, DomainError> {
+        let mut published = Vec::new();
+
+        for release_id in self.event_store.published_releases(tenant).await? {
+            if let Some((release, _)) = self.load(tenant, release_id).await? {
+                if release.state == ReleaseState::Published {
+                    published.push(release_id);
+                }
+            }
+        }
+
+        Ok(published)
+    }
+
+    /// Load a release, rejecting it unless its stream is at `expected_version`
+    async fn load_expected(
+        &self,
+        tenant: &TenantId,
+        release_id: Uuid,
+        expected_version: Option<i64>,
+    ) -> Result<(Release, i64), DomainError> {
+        let (release, stream_version) = self
+            .load(tenant, release_id)
+            .await?
+            .ok_or(DomainError::ReleaseNotFound(release_id))?;
+
+        if let Some(expected) = expected_version {
+            if expected != stream_version {
+                return Err(DomainError::ConcurrencyConflict {
+                    expected,
+                    actual: stream_version,
+                });
+            }
+        }
+
+        Ok((release, stream_version))
+    }
+}
@@ -1 +1,845 @@
-// Repository implementations will go here
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::{
+    event_store::{IdempotentRequest, SqliteEventStore},
+    schema_registry::SchemaRegistry,
+    template_registry::TemplateRegistry,
+};
+use crate::domain::{
+    aggregates::{PromotionPolicy, ReviewPolicy, Spec},
+    commands::{
+        BatchCommand, BatchTarget, CreateFromTemplate, CreateSpec, MoveFolder, PublishSpec,
+        RenameSpec, RepublishSpec, SpecCommand, SpecRef,
+    },
+    errors::DomainError,
+    events::{EventEnvelope, EventMetadata, SpecEvent, SpecState},
+    validation::{ValidationPolicies, ValidatorRegistry},
+    value_objects::{ReleaseEntry, SpecContent, SpecName, TenantId, Version},
+};
+
+/// Largest number of commands accepted in one batch
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// What a batch command left its spec at
+#[derive(Debug, Clone)]
+pub struct BatchItemResult {
+    pub spec_id: Uuid,
+    pub version: u32,
+    pub semver: semver::Version,
+    pub state: SpecState,
+    /// Stream version after the command's events
+    pub stream_version: i64,
+}
+
+/// Result of each command of a batch, in order; when any command failed,
+/// nothing was stored and the others report `DomainError::BatchAborted`
+#[derive(Debug)]
+pub struct BatchOutcome {
+    pub committed: bool,
+    pub items: Vec<Result<BatchItemResult, DomainError>>,
+}
+
+/// Specs of a tenant as a batch leaves them, before anything is stored
+#[derive(Default)]
+struct StagedBatch {
+    tenant: TenantId,
+    specs: BTreeMap<Uuid, (Spec, i64)>,
+    created: BTreeMap<String, Uuid>,
+    appends: Vec<(Uuid, Vec<SpecEvent>, Option<i64>)>,
+}
+
+/// Loads `Spec` aggregates from the event store and persists the events
+/// produced by commands, guarding every write with the stream version the
+/// aggregate was loaded at.
+#[derive(Clone)]
+pub struct SpecRepository {
+    event_store: Arc<SqliteEventStore>,
+    schemas: SchemaRegistry,
+    templates: TemplateRegistry,
+    review_policy: ReviewPolicy,
+    promotion_policy: PromotionPolicy,
+    validators: ValidatorRegistry,
+    validation_policies: ValidationPolicies,
+}
+
+impl SpecRepository {
+    pub fn new(event_store: Arc<SqliteEventStore>) -> Self {
+        Self {
+            schemas: SchemaRegistry::new(event_store.clone()),
+            templates: TemplateRegistry::new(event_store.clone()),
+            event_store,
+            review_policy: ReviewPolicy::default(),
+            promotion_policy: PromotionPolicy::default(),
+            validators: ValidatorRegistry::default(),
+            validation_policies: ValidationPolicies::default(),
+        }
+    }
+
+    /// Set the approval rules applied to every loaded spec
+    #[must_use]
+    pub const fn with_review_policy(mut self, policy: ReviewPolicy) -> Self {
+        self.review_policy = policy;
+        self
+    }
+
+    /// Set the channels every loaded spec can be promoted through
+    #[must_use]
+    pub fn with_promotion_policy(mut self, policy: PromotionPolicy) -> Self {
+        self.promotion_policy = policy;
+        self
+    }
+
+    /// Choose the validators new content runs through by each spec's labels
+    #[must_use]
+    pub fn with_validators(mut self, validators: ValidatorRegistry) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    /// Choose the size and naming limits for each spec by its labels
+    #[must_use]
+    pub fn with_validation_policies(mut self, policies: ValidationPolicies) -> Self {
+        self.validation_policies = policies;
+        self
+    }
+
+    /// Registry the repository validates spec content against
+    pub const fn schemas(&self) -> &SchemaRegistry {
+        &self.schemas
+    }
+
+    /// Registry specs can be rendered from
+    pub const fn templates(&self) -> &TemplateRegistry {
+        &self.templates
+    }
+
+    /// Rebuild a spec of `tenant` together with its current stream version
+    pub async fn load(
+        &self,
+        tenant: &TenantId,
+        spec_id: Uuid,
+    ) -> Result<Option<(Spec, i64)>, DomainError> {
+        let envelopes = self.event_store.get_events(tenant, spec_id, None).await?;
+
+        let Some(stream_version) = envelopes.last().map(|e| e.sequence_number) else {
+            return Ok(None);
+        };
+
+        let spec = Spec::from_events(envelopes.into_iter().map(|e| e.event).collect())?;
+
+        Ok(Some((self.configure(spec), stream_version)))
+    }
+
+    /// Apply the review and promotion policies, validators and limits a
+    /// rebuilt spec runs with
+    fn configure(&self, spec: Spec) -> Spec {
+        let validators = self.validators.pipeline_for(&spec.labels);
+        let policy = self.validation_policies.policy_for(&spec.labels);
+
+        spec.with_review_policy(self.review_policy)
+            .with_promotion_policy(self.promotion_policy.clone())
+            .with_validators(validators)
+            .with_validation_policy(policy)
+    }
+
+    /// Create a new spec, running its content through the validators its
+    /// labels select and checking it against any declared schema
+    pub async fn create(
+        &self,
+        command: CreateSpec,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        self.create_once(command, metadata, request.as_ref()).await
+    }
+
+    /// Create a spec, recording the request's idempotency key with its events
+    async fn create_once(
+        &self,
+        command: CreateSpec,
+        metadata: EventMetadata,
+        request: Option<&IdempotentRequest>,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let validators = self.validators.pipeline_for(&command.labels);
+        let policy = self.validation_policies.policy_for(&command.labels);
+        let events = Spec::create(command, &policy, &validators)?;
+        self.check_schemas(&metadata.tenant_id, None, &events)
+            .await?;
+
+        let spec_id = match &events[0] {
+            SpecEvent::Created(e) => e.spec_id,
+            _ => unreachable!(),
+        };
+
+        self.event_store
+            .append_events(spec_id, events, Some(0), metadata, request)
+            .await
+    }
+
+    /// Create a spec from a rendered template, recording the template version
+    /// so the spec can be found when the template changes
+    pub async fn create_from_template(
+        &self,
+        command: CreateFromTemplate,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        let (template, content) = self
+            .templates
+            .render(
+                &metadata.tenant_id,
+                command.template_id,
+                command.version,
+                &command.parameters,
+            )
+            .await?;
+
+        self.create_once(
+            CreateSpec {
+                name: command.name,
+                content,
+                description: command.description,
+                schema: command.schema,
+                labels: command.labels,
+                template: Some(template),
+                created_by: command.created_by,
+            },
+            metadata,
+            request.as_ref(),
+        )
+        .await
+    }
+
+    /// Handle a command against the current state of a spec.
+    ///
+    /// When `expected_version` is given the command is rejected unless the
+    /// stream is still at that version; otherwise the version observed while
+    /// loading is used, so concurrent writers never silently overwrite each
+    /// other.
+    pub async fn execute(
+        &self,
+        spec_id: Uuid,
+        command: SpecCommand,
+        expected_version: Option<i64>,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &(spec_id, &command));
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        let tenant = &metadata.tenant_id;
+        let (spec, stream_version) = self
+            .load(tenant, spec_id)
+            .await?
+            .ok_or(DomainError::SpecNotFound(spec_id))?;
+
+        if let Some(expected) = expected_version {
+            if expected != stream_version {
+                return Err(DomainError::ConcurrencyConflict {
+                    expected,
+                    actual: stream_version,
+                });
+            }
+        }
+
+        let events = spec.handle_command(command)?;
+        self.check_schemas(tenant, Some(&spec), &events).await?;
+        self.check_dependencies(tenant, &spec, &events, None)
+            .await?;
+
+        self.event_store
+            .append_events(
+                spec_id,
+                events,
+                Some(stream_version),
+                metadata,
+                request.as_ref(),
+            )
+            .await
+    }
+
+    /// Apply a batch of commands in order, storing all of their events in one
+    /// transaction or none of them.
+    ///
+    /// Later commands see the effects of earlier ones, so a spec created in
+    /// the batch can be updated or published by name further on. Every
+    /// command is tried, so each failure is reported. A retry of a committed
+    /// batch with the same idempotency key reports the original results.
+    pub async fn execute_batch(
+        &self,
+        commands: Vec<BatchCommand>,
+        metadata: EventMetadata,
+    ) -> Result<BatchOutcome, DomainError> {
+        if commands.is_empty() || commands.len() > MAX_BATCH_SIZE {
+            return Err(DomainError::InvalidBatch(format!(
+                "A batch must have 1 to {MAX_BATCH_SIZE} commands"
+            )));
+        }
+
+        let request = IdempotentRequest::new(&metadata, &commands);
+        if let Some(appends) = self.event_store.replay_appends(request.as_ref()).await? {
+            let mut items = Vec::with_capacity(appends.len());
+            for envelopes in &appends {
+                items.push(Ok(self
+                    .replayed_item(&metadata.tenant_id, envelopes)
+                    .await?));
+            }
+            return Ok(BatchOutcome {
+                committed: true,
+                items,
+            });
+        }
+
+        let mut batch = StagedBatch {
+            tenant: metadata.tenant_id.clone(),
+            ..StagedBatch::default()
+        };
+        let mut items = Vec::with_capacity(commands.len());
+        for command in commands {
+            items.push(self.stage(&mut batch, command).await);
+        }
+
+        let committed = items.iter().all(Result::is_ok);
+        if committed {
+            self.event_store
+                .append_batch(batch.appends, metadata, request.as_ref())
+                .await?;
+        } else {
+            for item in &mut items {
+                if item.is_ok() {
+                    *item = Err(DomainError::BatchAborted);
+                }
+            }
+        }
+
+        Ok(BatchOutcome { committed, items })
+    }
+
+    /// What the command behind one append of a committed batch left its spec
+    /// at, each command having made exactly one append
+    async fn replayed_item(
+        &self,
+        tenant: &TenantId,
+        envelopes: &[EventEnvelope],
+    ) -> Result<BatchItemResult, DomainError> {
+        let last = envelopes.last().ok_or_else(|| {
+            DomainError::EventStoreError("Replayed batch command has no events".to_string())
+        })?;
+
+        let events = self
+            .event_store
+            .get_events(tenant, last.aggregate_id, None)
+            .await?
+            .into_iter()
+            .take_while(|envelope| envelope.sequence_number <= last.sequence_number)
+            .map(|envelope| envelope.event)
+            .collect();
+        let spec = Spec::from_events(events)?;
+
+        Ok(BatchItemResult {
+            spec_id: spec.id,
+            version: spec.head_version.as_u32(),
+            semver: spec.semver,
+            state: spec.state,
+            stream_version: last.sequence_number,
+        })
+    }
+
+    /// Run one batch command against the specs as earlier commands left them
+    async fn stage(
+        &self,
+        batch: &mut StagedBatch,
+        command: BatchCommand,
+    ) -> Result<BatchItemResult, DomainError> {
+        let (target, spec_id, command) = match command {
+            BatchCommand::Create(command) => return self.stage_create(batch, command).await,
+            BatchCommand::Update(target, mut command) => {
+                command.spec_id = self.resolve_target(batch, &target.spec).await?;
+                (target, command.spec_id, SpecCommand::Update(command))
+            }
+            BatchCommand::Publish(target, mut command) => {
+                command.spec_id = self.resolve_target(batch, &target.spec).await?;
+                (target, command.spec_id, SpecCommand::Publish(command))
+            }
+            BatchCommand::Deprecate(target, mut command) => {
+                command.spec_id = self.resolve_target(batch, &target.spec).await?;
+                (target, command.spec_id, SpecCommand::Deprecate(command))
+            }
+        };
+
+        let (spec, stream_version) = match batch.specs.get(&spec_id) {
+            Some(staged) => staged.clone(),
+            None => self
+                .load(&batch.tenant, spec_id)
+                .await?
+                .ok_or(DomainError::SpecNotFound(spec_id))?,
+        };
+
+        if let Some(expected) = target.expected_version {
+            if expected != stream_version {
+                return Err(DomainError::ConcurrencyConflict {
+                    expected,
+                    actual: stream_version,
+                });
+            }
+        }
+
+        let events = spec.handle_command(command)?;
+        self.check_schemas(&batch.tenant, Some(&spec), &events)
+            .await?;
+        self.check_dependencies(&batch.tenant, &spec, &events, Some(batch))
+            .await?;
+
+        let spec = events.iter().fold(spec, Spec::apply_event);
+        Ok(stage_events(batch, spec, stream_version, events))
+    }
+
+    /// Events that publish the versions a release pins, for storing with the
+    /// release's own events.
+    ///
+    /// Each pinned version must be its spec's published version or its head,
+    /// which is published like any other version. Specs are published in the
+    /// order they are pinned, so a spec can depend on one pinned before it.
+    pub(super) async fn stage_release(
+        &self,
+        tenant: &TenantId,
+        entries: &[ReleaseEntry],
+        published_by: &str,
+    ) -> Result<Vec<(Uuid, Vec<SpecEvent>, Option<i64>)>, DomainError> {
+        let mut batch = StagedBatch {
+            tenant: tenant.clone(),
+            ..StagedBatch::default()
+        };
+
+        for entry in entries {
+            let (spec, _) = self
+                .load(tenant, entry.spec_id)
+                .await?
+                .ok_or(DomainError::SpecNotFound(entry.spec_id))?;
+
+            let version = Version::new(entry.version);
+            if spec.state == SpecState::Published && spec.published_version == Some(version) {
+                continue;
+            }
+            if version != spec.head_version {
+                return Err(DomainError::InvalidRelease(format!(
+                    "Version {version} of spec {} is neither its published nor its head version",
+                    spec.name
+                )));
+            }
+
+            let command = BatchCommand::Publish(
+                BatchTarget {
+                    spec: SpecRef::Id(entry.spec_id),
+                    expected_version: None,
+                },
+                PublishSpec {
+                    spec_id: entry.spec_id,
+                    version: Some(entry.version),
+                    published_by: published_by.to_string(),
+                },
+            );
+            self.stage(&mut batch, command).await?;
+        }
+
+        Ok(batch.appends)
+    }
+
+    /// Events that publish again the versions an earlier release pins, for
+    /// storing with the rollback of the release that replaced it.
+    ///
+    /// Specs already at their pinned version are left alone, as are those
+    /// no longer published, which were deprecated or deleted since. Each
+    /// restored revision must pass the checks of a publish, and the published
+    /// specs depending on it must accept its version, as the rollback leaves
+    /// them all.
+    pub(super) async fn stage_rollback(
+        &self,
+        tenant: &TenantId,
+        entries: &[ReleaseEntry],
+        published_by: &str,
+    ) -> Result<Vec<(Uuid, Vec<SpecEvent>, Option<i64>)>, DomainError> {
+        let mut batch = StagedBatch {
+            tenant: tenant.clone(),
+            ..StagedBatch::default()
+        };
+        let mut restored = Vec::new();
+
+        for entry in entries {
+            let Some((spec, stream_version)) = self.load(tenant, entry.spec_id).await? else {
+                continue;
+            };
+            if spec.state != SpecState::Published
+                || spec.published_version == Some(Version::new(entry.version))
+            {
+                continue;
+            }
+
+            let events = spec.handle_command(SpecCommand::Republish(RepublishSpec {
+                spec_id: entry.spec_id,
+                version: entry.version,
+                published_by: published_by.to_string(),
+            }))?;
+            self.check_schemas(tenant, Some(&spec), &events).await?;
+
+            let staged = events.iter().fold(spec.clone(), Spec::apply_event);
+            stage_events(&mut batch, staged, stream_version, events.clone());
+            restored.push((spec, events));
+        }
+
+        for (spec, events) in &restored {
+            self.check_dependencies(tenant, spec, events, Some(&batch))
+                .await?;
+            self.check_dependents_accept(tenant, spec.id, &batch)
+                .await?;
+        }
+
+        Ok(batch.appends)
+    }
+
+    /// Rename the spec named `from` and every spec in the folder `from` so
+    /// they sit under `to`, storing all of the renames in one transaction or
+    /// none of them.
+    ///
+    /// Deleted specs keep their names. Returns the renames' events.
+    pub async fn move_folder(
+        &self,
+        command: MoveFolder,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        if command.from.is_empty() || command.to.is_empty() {
+            return Err(DomainError::InvalidMove(
+                "Both paths are required".to_string(),
+            ));
+        }
+        if SpecName::recorded(command.to.clone()).is_within(&command.from)
+            || SpecName::recorded(command.from.clone()).is_within(&command.to)
+        {
+            return Err(DomainError::InvalidMove(format!(
+                "Cannot move {} to {}: one path contains the other",
+                command.from, command.to
+            )));
+        }
+
+        let tenant = &metadata.tenant_id;
+        let names = self
+            .event_store
+            .resolve_folder(tenant, &command.from)
+            .await?;
+
+        let mut appends = Vec::with_capacity(names.len());
+        for (name, spec_id) in names {
+            let (spec, stream_version) = self
+                .load(tenant, spec_id)
+                .await?
+                .ok_or(DomainError::SpecNotFound(spec_id))?;
+
+            if spec.state == SpecState::Deleted {
+                continue;
+            }
+
+            let events = spec.handle_command(SpecCommand::Rename(RenameSpec {
+                spec_id,
+                new_name: format!("{}{}", command.to, &name[command.from.len()..]),
+                renamed_by: command.moved_by.clone(),
+            }))?;
+            appends.push((spec_id, events, Some(stream_version)));
+        }
+
+        if appends.is_empty() {
+            return Err(DomainError::SpecNameNotFound(command.from));
+        }
+
+        self.event_store
+            .append_batch(appends, metadata, request.as_ref())
+            .await
+    }
+
+    async fn stage_create(
+        &self,
+        batch: &mut StagedBatch,
+        command: CreateSpec,
+    ) -> Result<BatchItemResult, DomainError> {
+        if batch.created.contains_key(&command.name)
+            || self
+                .event_store
+                .resolve_name(&batch.tenant, &command.name)
+                .await?
+                .is_some()
+        {
+            return Err(DomainError::DuplicateSpecName(command.name));
+        }
+
+        let name = command.name.clone();
+        let validators = self.validators.pipeline_for(&command.labels);
+        let policy = self.validation_policies.policy_for(&command.labels);
+        let events = Spec::create(command, &policy, &validators)?;
+        self.check_schemas(&batch.tenant, None, &events).await?;
+
+        let spec = self.configure(Spec::from_events(events.clone())?);
+        batch.created.insert(name, spec.id);
+        Ok(stage_events(batch, spec, 0, events))
+    }
+
+    /// Id of the spec a batch command targets, looking names up among specs
+    /// created earlier in the batch first
+    async fn resolve_target(
+        &self,
+        batch: &StagedBatch,
+        spec: &SpecRef,
+    ) -> Result<Uuid, DomainError> {
+        match spec {
+            SpecRef::Id(id) => Ok(*id),
+            SpecRef::Name(name) => match batch.created.get(name) {
+                Some(id) => Ok(*id),
+                None => self
+                    .event_store
+                    .resolve_name(&batch.tenant, name)
+                    .await?
+                    .ok_or_else(|| DomainError::SpecNameNotFound(name.clone())),
+            },
+        }
+    }
+
+    /// Validate content that events would store or publish against the
+    /// schema the spec declares
+    async fn check_schemas(
+        &self,
+        tenant: &TenantId,
+        spec: Option<&Spec>,
+        events: &[SpecEvent],
+    ) -> Result<(), DomainError> {
+        let current_schema = spec.and_then(|spec| spec.schema);
+
+        for event in events {
+            let (schema, content) = match event {
+                SpecEvent::Created(e) => (e.schema, SpecContent::recorded(e.content.clone())),
+                SpecEvent::Updated(e) => (e.schema, SpecContent::recorded(e.content.clone())),
+                SpecEvent::Reverted(e) => {
+                    (current_schema, SpecContent::recorded(e.content.clone()))
+                }
+                // The published revision may predate the head when republished
+                SpecEvent::StateChanged(e) if e.to_state == SpecState::Published => {
+                    match spec.and_then(|spec| spec.revisions.get(&Version::new(e.version))) {
+                        Some(revision) => (revision.schema, revision.content.clone()),
+                        None => continue,
+                    }
+                }
+                // A channel serves the promoted revision, which may predate the head
+                SpecEvent::Promoted(e) => {
+                    match spec.and_then(|spec| spec.revisions.get(&Version::new(e.version))) {
+                        Some(revision) => (revision.schema, revision.content.clone()),
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+
+            if let Some(schema) = schema {
+                self.schemas.validate(tenant, schema, &content).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Keep the dependency graph consistent: declared dependencies must exist
+    /// without forming a cycle, a spec can only be published or promoted once
+    /// its dependencies are published, as they are stored or as a batch leaves
+    /// them, and a spec published specs depend on cannot be deprecated or
+    /// deleted
+    async fn check_dependencies(
+        &self,
+        tenant: &TenantId,
+        spec: &Spec,
+        events: &[SpecEvent],
+        batch: Option<&StagedBatch>,
+    ) -> Result<(), DomainError> {
+        for event in events {
+            match event {
+                SpecEvent::DependenciesSet(e) => {
+                    for dependency in &e.dependencies {
+                        self.check_dependency_target(tenant, spec.id, dependency.spec_id)
+                            .await?;
+                    }
+                }
+                SpecEvent::StateChanged(e) if e.to_state == SpecState::Published => {
+                    self.check_dependencies_published(tenant, spec, batch)
+                        .await?;
+                }
+                SpecEvent::Promoted(_) => {
+                    self.check_dependencies_published(tenant, spec, batch)
+                        .await?;
+                }
+                SpecEvent::StateChanged(e)
+                    if matches!(e.to_state, SpecState::Deprecated | SpecState::Deleted) =>
+                {
+                    let mut dependents = Vec::new();
+                    for dependent_id in self.event_store.find_dependents(tenant, spec.id).await? {
+                        if let Some((dependent, _)) = self.load(tenant, dependent_id).await? {
+                            let depends =
+                                dependent.dependencies.iter().any(|d| d.spec_id == spec.id);
+                            if depends && dependent.state == SpecState::Published {
+                                dependents.push(dependent_id);
+                            }
+                        }
+                    }
+
+                    if !dependents.is_empty() {
+                        return Err(DomainError::HasDependents(dependents));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that every dependency of `spec` is published at a version it accepts
+    async fn check_dependencies_published(
+        &self,
+        tenant: &TenantId,
+        spec: &Spec,
+        batch: Option<&StagedBatch>,
+    ) -> Result<(), DomainError> {
+        for dependency in &spec.dependencies {
+            let staged = batch.and_then(|batch| batch.specs.get(&dependency.spec_id));
+            let target = match staged {
+                Some(staged) => Some(staged.clone()),
+                None => self.load(tenant, dependency.spec_id).await?,
+            };
+            let published = target
+                .filter(|(target, _)| target.state == SpecState::Published)
+                .and_then(|(target, _)| target.published_semver().cloned());
+
+            if !published.is_some_and(|semver| dependency.version.matches(&semver)) {
+                return Err(DomainError::UnpublishedDependency {
+                    spec_id: dependency.spec_id,
+                    version: dependency.version.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the published specs depending on the staged spec `spec_id`
+    /// accept the version it publishes, as they are stored or as the batch
+    /// leaves them
+    async fn check_dependents_accept(
+        &self,
+        tenant: &TenantId,
+        spec_id: Uuid,
+        batch: &StagedBatch,
+    ) -> Result<(), DomainError> {
+        let Some(semver) = batch
+            .specs
+            .get(&spec_id)
+            .and_then(|(spec, _)| spec.published_semver())
+        else {
+            return Ok(());
+        };
+
+        let mut dependents = Vec::new();
+        for dependent_id in self.event_store.find_dependents(tenant, spec_id).await? {
+            let dependent = match batch.specs.get(&dependent_id) {
+                Some(staged) => Some(staged.clone()),
+                None => self.load(tenant, dependent_id).await?,
+            };
+            let Some((dependent, _)) = dependent else {
+                continue;
+            };
+
+            let rejects = dependent
+                .dependencies
+                .iter()
+                .any(|d| d.spec_id == spec_id && !d.version.matches(semver));
+            if rejects && dependent.state == SpecState::Published {
+                dependents.push(dependent_id);
+            }
+        }
+
+        if !dependents.is_empty() {
+            return Err(DomainError::HasDependents(dependents));
+        }
+
+        Ok(())
+    }
+
+    /// Check that `spec_id` can depend on `target_id`: the target must exist,
+    /// must not be deleted, and must not already depend on `spec_id`
+    async fn check_dependency_target(
+        &self,
+        tenant: &TenantId,
+        spec_id: Uuid,
+        target_id: Uuid,
+    ) -> Result<(), DomainError> {
+        let (target, _) = self
+            .load(tenant, target_id)
+            .await?
+            .ok_or(DomainError::SpecNotFound(target_id))?;
+
+        if target.state == SpecState::Deleted {
+            return Err(DomainError::InvalidDependencies(format!(
+                "Spec {target_id} is deleted"
+            )));
+        }
+
+        let mut visited = BTreeSet::from([target_id]);
+        let mut pending: Vec<Uuid> = target.dependencies.iter().map(|d| d.spec_id).collect();
+
+        while let Some(id) = pending.pop() {
+            if id == spec_id {
+                return Err(DomainError::InvalidDependencies(format!(
+                    "Depending on spec {target_id} would create a cycle"
+                )));
+            }
+            if visited.insert(id) {
+                if let Some((next, _)) = self.load(tenant, id).await? {
+                    pending.extend(next.dependencies.iter().map(|d| d.spec_id));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Record a command's events in the batch and what they left the spec at
+fn stage_events(
+    batch: &mut StagedBatch,
+    spec: Spec,
+    stream_version: i64,
+    events: Vec<SpecEvent>,
+) -> BatchItemResult {
+    let stream_version_after = stream_version + i64::try_from(events.len()).unwrap_or(0);
+    let result = BatchItemResult {
+        spec_id: spec.id,
+        version: spec.head_version.as_u32(),
+        semver: spec.semver.clone(),
+        state: spec.state,
+        stream_version: stream_version_after,
+    };
+
+    batch.appends.push((spec.id, events, Some(stream_version)));
+    batch.specs.insert(spec.id, (spec, stream_version_after));
+
+    result
+}
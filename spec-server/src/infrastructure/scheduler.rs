@@ -0,0 +1,148 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration};
+use tracing::{error, info, warn};
+
+use super::projections::ProjectionStore;
+use super::repositories::SpecRepository;
+use crate::domain::{
+    commands::{ExecuteSchedule, FailSchedule, SpecCommand},
+    errors::DomainError,
+    events::EventMetadata,
+    value_objects::TenantId,
+};
+use uuid::Uuid;
+
+/// Runs scheduled publish and deprecate transitions once they are due
+pub struct TransitionScheduler {
+    repository: SpecRepository,
+    projection_store: Arc<ProjectionStore>,
+    shutdown_rx: mpsc::Receiver<()>,
+}
+
+impl TransitionScheduler {
+    pub fn new(
+        repository: SpecRepository,
+        projection_store: Arc<ProjectionStore>,
+        shutdown_rx: mpsc::Receiver<()>,
+    ) -> Self {
+        Self {
+            repository,
+            projection_store,
+            shutdown_rx,
+        }
+    }
+
+    /// Start the scheduler in a background task
+    pub fn start_background(
+        repository: SpecRepository,
+        projection_store: Arc<ProjectionStore>,
+    ) -> (tokio::task::JoinHandle<Result<()>>, mpsc::Sender<()>) {
+        let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+
+        let scheduler = Self::new(repository, projection_store, shutdown_rx);
+        let handle = tokio::spawn(async move { scheduler.start().await });
+
+        (handle, shutdown_tx)
+    }
+
+    pub async fn start(mut self) -> Result<()> {
+        info!("Starting transition scheduler");
+
+        let poll_interval = Duration::from_secs(5);
+
+        loop {
+            // Check for shutdown signal
+            if self.shutdown_rx.try_recv().is_ok() {
+                info!("Transition scheduler received shutdown signal");
+                break;
+            }
+
+            match self.execute_due_schedules().await {
+                Ok(executed) if executed > 0 => {
+                    info!("Executed {} scheduled transitions", executed);
+                }
+                Ok(_) => {}
+                Err(e) => error!("Error processing schedules: {}", e),
+            }
+
+            sleep(poll_interval).await;
+        }
+
+        info!("Transition scheduler stopped");
+        Ok(())
+    }
+
+    async fn execute_due_schedules(&self) -> Result<usize, DomainError> {
+        let due = self.projection_store.list_schedules_due(Utc::now()).await?;
+
+        let mut executed = 0;
+
+        for (tenant_id, spec_id, schedule_id) in due {
+            let command = SpecCommand::ExecuteSchedule(ExecuteSchedule {
+                spec_id,
+                schedule_id,
+            });
+
+            let metadata = EventMetadata {
+                causation_id: Some(schedule_id),
+                ..EventMetadata::default()
+            }
+            .with_tenant(tenant_id.clone());
+
+            match self
+                .repository
+                .execute(spec_id, command, None, metadata)
+                .await
+            {
+                Ok(_) => executed += 1,
+                // Already completed; the projection has not caught up yet
+                Err(DomainError::ScheduleNotFound(_)) => {}
+                Err(e) if e.is_transient() => {
+                    warn!(
+                        "Failed to execute schedule {}, retrying: {}",
+                        schedule_id, e
+                    );
+                }
+                Err(e) => {
+                    // Complete the schedule as failed so it is not run again
+                    warn!("Schedule {} was rejected: {}", schedule_id, e);
+                    self.fail_schedule(tenant_id, spec_id, schedule_id, &e)
+                        .await;
+                }
+            }
+        }
+
+        Ok(executed)
+    }
+
+    async fn fail_schedule(
+        &self,
+        tenant_id: TenantId,
+        spec_id: Uuid,
+        schedule_id: Uuid,
+        error: &DomainError,
+    ) {
+        let command = SpecCommand::FailSchedule(FailSchedule {
+            spec_id,
+            schedule_id,
+            failure: error.to_string(),
+        });
+
+        let metadata = EventMetadata {
+            causation_id: Some(schedule_id),
+            ..EventMetadata::default()
+        }
+        .with_tenant(tenant_id);
+
+        if let Err(e) = self
+            .repository
+            .execute(spec_id, command, None, metadata)
+            .await
+        {
+            warn!("Failed to record rejected schedule {}: {}", schedule_id, e);
+        }
+    }
+}
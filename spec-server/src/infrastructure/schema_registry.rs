@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::event_store::{IdempotentRequest, SqliteEventStore};
+use crate::domain::{
+    aggregates::Schema,
+    commands::{RegisterSchema, UpdateSchema},
+    errors::DomainError,
+    events::{EventEnvelope, EventMetadata, SpecEvent},
+    value_objects::{SchemaRef, SpecContent, TenantId},
+};
+
+/// Loads `Schema` aggregates from the event store, persists new schema
+/// versions and checks spec content against them.
+#[derive(Clone)]
+pub struct SchemaRegistry {
+    event_store: Arc<SqliteEventStore>,
+}
+
+impl SchemaRegistry {
+    pub const fn new(event_store: Arc<SqliteEventStore>) -> Self {
+        Self { event_store }
+    }
+
+    /// Rebuild a schema of `tenant` together with its current stream version
+    pub async fn load(
+        &self,
+        tenant: &TenantId,
+        schema_id: Uuid,
+    ) -> Result<Option<(Schema, i64)>, DomainError> {
+        let envelopes = self.event_store.get_events(tenant, schema_id, None).await?;
+
+        // Specs share the event store, so their streams are not schemas
+        if !matches!(
+            envelopes.first().map(|e| &e.event),
+            Some(SpecEvent::SchemaRegistered(_))
+        ) {
+            return Ok(None);
+        }
+
+        let stream_version = envelopes.last().map_or(0, |e| e.sequence_number);
+        let schema = Schema::from_events(envelopes.into_iter().map(|e| e.event).collect())?;
+
+        Ok(Some((schema, stream_version)))
+    }
+
+    pub async fn register(
+        &self,
+        command: RegisterSchema,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        let events = Schema::register(command)?;
+
+        let schema_id = match &events[0] {
+            SpecEvent::SchemaRegistered(e) => e.schema_id,
+            _ => unreachable!(),
+        };
+
+        self.event_store
+            .append_events(schema_id, events, Some(0), metadata, request.as_ref())
+            .await
+    }
+
+    /// Add a new version of a schema, guarded like spec commands by the
+    /// stream version it was loaded at
+    pub async fn update(
+        &self,
+        command: UpdateSchema,
+        expected_version: Option<i64>,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        let schema_id = command.schema_id;
+        let (schema, stream_version) = self
+            .load(&metadata.tenant_id, schema_id)
+            .await?
+            .ok_or(DomainError::SchemaNotFound(schema_id))?;
+
+        if let Some(expected) = expected_version {
+            if expected != stream_version {
+                return Err(DomainError::ConcurrencyConflict {
+                    expected,
+                    actual: stream_version,
+                });
+            }
+        }
+
+        let events = schema.handle_update(command)?;
+
+        self.event_store
+            .append_events(
+                schema_id,
+                events,
+                Some(stream_version),
+                metadata,
+                request.as_ref(),
+            )
+            .await
+    }
+
+    /// Check `content` against a schema version of `tenant`, reporting every
+    /// violation
+    pub async fn validate(
+        &self,
+        tenant: &TenantId,
+        schema_ref: SchemaRef,
+        content: &SpecContent,
+    ) -> Result<(), DomainError> {
+        let (schema, _) = self
+            .load(tenant, schema_ref.schema_id)
+            .await?
+            .ok_or(DomainError::SchemaNotFound(schema_ref.schema_id))?;
+
+        schema
+            .document(schema_ref.version)?
+            .validate(content)
+            .map_err(DomainError::SchemaViolation)
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration};
+use tracing::{error, info, warn};
+
+use super::projections::ProjectionStore;
+use super::repositories::SpecRepository;
+use crate::domain::{
+    commands::{DeleteSpec, FailSunset, SpecCommand},
+    errors::DomainError,
+    events::{EventMetadata, SpecState},
+    value_objects::TenantId,
+};
+use uuid::Uuid;
+
+/// User recorded on deletions performed by the sunset processor
+const SUNSET_USER: &str = "system:sunset";
+
+/// Deletes deprecated specs once their sunset date has passed
+pub struct SunsetProcessor {
+    repository: SpecRepository,
+    projection_store: Arc<ProjectionStore>,
+    shutdown_rx: mpsc::Receiver<()>,
+}
+
+impl SunsetProcessor {
+    pub fn new(
+        repository: SpecRepository,
+        projection_store: Arc<ProjectionStore>,
+        shutdown_rx: mpsc::Receiver<()>,
+    ) -> Self {
+        Self {
+            repository,
+            projection_store,
+            shutdown_rx,
+        }
+    }
+
+    /// Start the processor in a background task
+    pub fn start_background(
+        repository: SpecRepository,
+        projection_store: Arc<ProjectionStore>,
+    ) -> (tokio::task::JoinHandle<Result<()>>, mpsc::Sender<()>) {
+        let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+
+        let processor = Self::new(repository, projection_store, shutdown_rx);
+        let handle = tokio::spawn(async move { processor.start().await });
+
+        (handle, shutdown_tx)
+    }
+
+    pub async fn start(mut self) -> Result<()> {
+        info!("Starting sunset processor");
+
+        let poll_interval = Duration::from_mins(1);
+
+        loop {
+            // Check for shutdown signal
+            if self.shutdown_rx.try_recv().is_ok() {
+                info!("Sunset processor received shutdown signal");
+                break;
+            }
+
+            match self.delete_due_specs().await {
+                Ok(deleted) if deleted > 0 => info!("Deleted {} sunset specs", deleted),
+                Ok(_) => {}
+                Err(e) => error!("Error processing sunsets: {}", e),
+            }
+
+            sleep(poll_interval).await;
+        }
+
+        info!("Sunset processor stopped");
+        Ok(())
+    }
+
+    async fn delete_due_specs(&self) -> Result<usize, DomainError> {
+        let due = self.projection_store.list_sunset_due(Utc::now()).await?;
+
+        let mut deleted = 0;
+
+        for (tenant_id, spec_id) in due {
+            let command = SpecCommand::Delete(DeleteSpec {
+                spec_id,
+                deleted_by: SUNSET_USER.to_string(),
+            });
+
+            match self
+                .repository
+                .execute(
+                    spec_id,
+                    command,
+                    None,
+                    EventMetadata::default().with_tenant(tenant_id.clone()),
+                )
+                .await
+            {
+                Ok(_) => deleted += 1,
+                // Already deleted; the projection has not caught up yet
+                Err(DomainError::InvalidStateForOperation(SpecState::Deleted)) => {}
+                Err(e) if e.is_transient() => {
+                    warn!("Failed to delete sunset spec {}, retrying: {}", spec_id, e);
+                }
+                Err(e) => {
+                    // Drop the sunset so the deletion is not tried again
+                    warn!("Deleting sunset spec {} was rejected: {}", spec_id, e);
+                    self.fail_sunset(tenant_id, spec_id, &e).await;
+                }
+            }
+        }
+
+        Ok(deleted)
+    }
+
+    async fn fail_sunset(&self, tenant_id: TenantId, spec_id: Uuid, error: &DomainError) {
+        let command = SpecCommand::FailSunset(FailSunset {
+            spec_id,
+            failure: error.to_string(),
+        });
+
+        if let Err(e) = self
+            .repository
+            .execute(
+                spec_id,
+                command,
+                None,
+                EventMetadata::default().with_tenant(tenant_id),
+            )
+            .await
+        {
+            warn!(
+                "Failed to record rejected sunset of spec {}: {}",
+                spec_id, e
+            );
+        }
+    }
+}
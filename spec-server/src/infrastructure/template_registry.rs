@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::event_store::{IdempotentRequest, SqliteEventStore};
+use crate::domain::{
+    aggregates::Template,
+    commands::{RegisterTemplate, UpdateTemplate},
+    errors::DomainError,
+    events::{EventEnvelope, EventMetadata, SpecEvent},
+    value_objects::{TemplateRef, TenantId},
+};
+
+/// Loads `Template` aggregates from the event store, persists new template
+/// versions and renders them into spec content.
+#[derive(Clone)]
+pub struct TemplateRegistry {
+    event_store: Arc<SqliteEventStore>,
+}
+
+impl TemplateRegistry {
+    pub const fn new(event_store: Arc<SqliteEventStore>) -> Self {
+        Self { event_store }
+    }
+
+    /// Rebuild a template of `tenant` together with its current stream version
+    pub async fn load(
+        &self,
+        tenant: &TenantId,
+        template_id: Uuid,
+    ) -> Result<Option<(Template, i64)>, DomainError> {
+        let envelopes = self
+            .event_store
+            .get_events(tenant, template_id, None)
+            .await?;
+
+        // Specs share the event store, so their streams are not templates
+        if !matches!(
+            envelopes.first().map(|e| &e.event),
+            Some(SpecEvent::TemplateRegistered(_))
+        ) {
+            return Ok(None);
+        }
+
+        let stream_version = envelopes.last().map_or(0, |e| e.sequence_number);
+        let template = Template::from_events(envelopes.into_iter().map(|e| e.event).collect())?;
+
+        Ok(Some((template, stream_version)))
+    }
+
+    pub async fn register(
+        &self,
+        command: RegisterTemplate,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        let events = Template::register(command)?;
+
+        let template_id = match &events[0] {
+            SpecEvent::TemplateRegistered(e) => e.template_id,
+            _ => unreachable!(),
+        };
+
+        self.event_store
+            .append_events(template_id, events, Some(0), metadata, request.as_ref())
+            .await
+    }
+
+    /// Add a new version of a template, guarded like spec commands by the
+    /// stream version it was loaded at
+    pub async fn update(
+        &self,
+        command: UpdateTemplate,
+        expected_version: Option<i64>,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        let template_id = command.template_id;
+        let (template, stream_version) = self
+            .load(&metadata.tenant_id, template_id)
+            .await?
+            .ok_or(DomainError::TemplateNotFound(template_id))?;
+
+        if let Some(expected) = expected_version {
+            if expected != stream_version {
+                return Err(DomainError::ConcurrencyConflict {
+                    expected,
+                    actual: stream_version,
+                });
+            }
+        }
+
+        let events = template.handle_update(command)?;
+
+        self.event_store
+            .append_events(
+                template_id,
+                events,
+                Some(stream_version),
+                metadata,
+                request.as_ref(),
+            )
+            .await
+    }
+
+    /// Render a template version, the latest when `version` is absent,
+    /// returning the version used along with the content
+    pub async fn render(
+        &self,
+        tenant: &TenantId,
+        template_id: Uuid,
+        version: Option<u32>,
+        parameters: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<(TemplateRef, String), DomainError> {
+        let (template, _) = self
+            .load(tenant, template_id)
+            .await?
+            .ok_or(DomainError::TemplateNotFound(template_id))?;
+
+        let version = version.unwrap_or_else(|| template.head_version.as_u32());
+        let content = template.document(version)?.render(parameters)?;
+
+        Ok((
+            TemplateRef {
+                template_id,
+                version,
+            },
+            content,
+        ))
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::event_store::{IdempotentRequest, SqliteEventStore};
+use crate::domain::{
+    aggregates::Tenant,
+    commands::{CreateTenant, SuspendTenant},
+    errors::DomainError,
+    events::{EventEnvelope, EventMetadata, TenantState},
+    value_objects::TenantId,
+};
+
+/// Loads `Tenant` aggregates from the event store and decides whether
+/// requests may act for a tenant.
+///
+/// Each tenant's events are stored in a stream of its own, recorded for the
+/// tenant they describe.
+#[derive(Clone)]
+pub struct TenantRegistry {
+    event_store: Arc<SqliteEventStore>,
+}
+
+impl TenantRegistry {
+    pub const fn new(event_store: Arc<SqliteEventStore>) -> Self {
+        Self { event_store }
+    }
+
+    /// Rebuild a tenant together with its stream id and version
+    pub async fn load(
+        &self,
+        tenant_id: &TenantId,
+    ) -> Result<Option<(Tenant, Uuid, i64)>, DomainError> {
+        let Some(stream_id) = self.event_store.find_tenant(tenant_id).await? else {
+            return Ok(None);
+        };
+
+        let envelopes = self
+            .event_store
+            .get_events(tenant_id, stream_id, None)
+            .await?;
+
+        let stream_version = envelopes.last().map_or(0, |e| e.sequence_number);
+        let tenant = Tenant::from_events(envelopes.into_iter().map(|e| e.event).collect())?;
+
+        Ok(Some((tenant, stream_id, stream_version)))
+    }
+
+    pub async fn create(
+        &self,
+        command: CreateTenant,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        let metadata = metadata.with_tenant(command.tenant_id.clone());
+
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        if self.load(&command.tenant_id).await?.is_some() {
+            return Err(DomainError::DuplicateTenant(command.tenant_id.to_string()));
+        }
+
+        let events = Tenant::create(command)?;
+
+        self.event_store
+            .append_events(Uuid::new_v4(), events, Some(0), metadata, request.as_ref())
+            .await
+    }
+
+    /// Suspend a tenant, rejecting every later request made for it; the
+    /// default tenant cannot be suspended
+    pub async fn suspend(
+        &self,
+        command: SuspendTenant,
+        metadata: EventMetadata,
+    ) -> Result<Vec<EventEnvelope>, DomainError> {
+        if command.tenant_id.is_default() {
+            return Err(DomainError::InvalidTenant(
+                "The default tenant cannot be suspended".to_string(),
+            ));
+        }
+
+        let metadata = metadata.with_tenant(command.tenant_id.clone());
+
+        let request = IdempotentRequest::new(&metadata, &command);
+        if let Some(envelopes) = self.event_store.replay(request.as_ref()).await? {
+            return Ok(envelopes);
+        }
+
+        let (tenant, stream_id, stream_version) = self
+            .load(&command.tenant_id)
+            .await?
+            .ok_or_else(|| DomainError::TenantNotFound(command.tenant_id.to_string()))?;
+
+        let events = tenant.handle_suspend(command)?;
+
+        self.event_store
+            .append_events(
+                stream_id,
+                events,
+                Some(stream_version),
+                metadata,
+                request.as_ref(),
+            )
+            .await
+    }
+
+    /// Check that requests may act for `tenant_id`: the default tenant always
+    /// exists, others must have been created and not suspended
+    pub async fn authorize(&self, tenant_id: &TenantId) -> Result<(), DomainError> {
+        if tenant_id.is_default() {
+            return Ok(());
+        }
+
+        let (tenant, _, _) = self
+            .load(tenant_id)
+            .await?
+            .ok_or_else(|| DomainError::TenantNotFound(tenant_id.to_string()))?;
+
+        if tenant.state == TenantState::Suspended {
+            return Err(DomainError::TenantSuspended(tenant_id.to_string()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use serde_json::{Map, Value};
+
+use crate::domain::{errors::DomainError, value_objects::Version};
+
+/// Schema version new events are stored at
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Migrates a stored payload from one schema version to the next
+type Upcaster = fn(&mut Map<String, Value>) -> Result<(), DomainError>;
+
+/// The upcaster at index `n` migrates payloads from version `n + 1` to `n + 2`
+const UPCASTERS: &[Upcaster] = &[record_legacy_semver];
+
+const _: () = assert!(UPCASTERS.len() + 1 == CURRENT_SCHEMA_VERSION as usize);
+
+/// Bring a payload stored at `schema_version` up to the current event shape.
+///
+/// Events stored before schema versions were recorded are version 1.
+pub fn upcast(data: &mut Value, schema_version: u32) -> Result<(), DomainError> {
+    if schema_version == 0 || schema_version > CURRENT_SCHEMA_VERSION {
+        return Err(DomainError::EventStoreError(format!(
+            "Unsupported event schema version {schema_version} (current is {CURRENT_SCHEMA_VERSION})"
+        )));
+    }
+
+    let fields = data.as_object_mut().ok_or_else(|| {
+        DomainError::EventStoreError("Event payload is not an object".to_string())
+    })?;
+
+    for upcaster in &UPCASTERS[schema_version as usize - 1..] {
+        upcaster(fields)?;
+    }
+
+    Ok(())
+}
+
+/// Version 2: events that change content always carry their semantic version.
+///
+/// Version 1 events recorded before semantic versions were tracked get the
+/// `N.0.0` version they have always been reported as; without it they do not
+/// deserialize.
+pub fn record_legacy_semver(fields: &mut Map<String, Value>) -> Result<(), DomainError> {
+    let changes_content = matches!(
+        fields.get("type").and_then(Value::as_str),
+        Some("updated" | "reverted")
+    );
+    if !changes_content || fields.contains_key("semver") {
+        return Ok(());
+    }
+
+    let version = fields
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| {
+            DomainError::EventStoreError("Version 1 event has no valid version".to_string())
+        })?;
+
+    fields.insert(
+        "semver".to_string(),
+        Version::new(version).legacy_semver().to_string().into(),
+    );
+
+    Ok(())
+}
@@ -5,12 +5,37 @@ mod infrastructure;
 use std::sync::Arc;
 use tower_http::trace::TraceLayer;
 
-use crate::api::rest::{create_router, AppState};
+use crate::api::{
+    auth::Authenticator,
+    rest::{create_router, AppState},
+};
+use crate::domain::{
+    aggregates::{PromotionPolicy, ReviewPolicy},
+    validation::{ValidationPolicies, ValidatorRegistry},
+};
 use crate::infrastructure::{
     event_processor::EventProcessorManager, event_store::SqliteEventStore,
-    projections::ProjectionStore,
+    name_registry::NameReleasePolicy, projections::ProjectionStore,
+    release_registry::ReleaseRegistry, repositories::SpecRepository,
+    scheduler::TransitionScheduler, sunset_processor::SunsetProcessor,
+    tenant_registry::TenantRegistry,
 };
 
+/// Bearer tokens and who each authenticates as; authentication is disabled if unset
+fn load_authenticator() -> anyhow::Result<Authenticator> {
+    let authenticator: Authenticator = std::env::var("API_TOKENS").unwrap_or_default().parse()?;
+
+    if authenticator.is_empty() {
+        tracing::warn!(
+            "API_TOKENS is not set: every request acts anonymously for the default tenant"
+        );
+    } else {
+        tracing::info!("Accepting {} API tokens", authenticator.len());
+    }
+
+    Ok(authenticator)
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Initialize tracing
@@ -24,8 +49,34 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Using database: {}", database_url);
 
+    // Name release policy for deleted specs: immediate, never or grace:<seconds>
+    let name_release_policy = std::env::var("NAME_RELEASE_POLICY")
+        .map_or_else(|_| Ok(NameReleasePolicy::default()), |p| p.parse())?;
+
+    tracing::info!("Name release policy: {:?}", name_release_policy);
+
     // Initialize stores
-    let event_store = Arc::new(SqliteEventStore::new(&database_url).await?);
+    let mut event_store = SqliteEventStore::new(&database_url)
+        .await?
+        .with_name_release_policy(name_release_policy);
+
+    // How long old names of renamed specs keep resolving, in seconds
+    if let Ok(secs) = std::env::var("RENAME_ALIAS_SECS") {
+        event_store =
+            event_store.with_rename_alias_period(chrono::Duration::seconds(secs.parse()?));
+    }
+
+    // Largest content accepted by streaming uploads, in bytes
+    if let Ok(max) = std::env::var("MAX_UPLOAD_BYTES") {
+        event_store = event_store.with_max_upload_bytes(max.parse()?);
+    }
+
+    // How long retries with the same idempotency key replay the original events, in seconds
+    if let Ok(secs) = std::env::var("IDEMPOTENCY_WINDOW_SECS") {
+        event_store = event_store.with_idempotency_window(chrono::Duration::seconds(secs.parse()?));
+    }
+
+    let event_store = Arc::new(event_store);
     let projection_store = Arc::new(ProjectionStore::new(&database_url, true).await?);
 
     // Initialize schemas
@@ -33,15 +84,65 @@ async fn main() -> anyhow::Result<()> {
     event_store.init_schema().await?;
     projection_store.init_schema().await?;
 
+    let authenticator = load_authenticator()?;
+
     // Start event processor
     tracing::info!("Starting event processor...");
     let manager = EventProcessorManager::new(event_store.clone(), projection_store.clone());
     let (_processor_handle, _shutdown_tx) = manager.start_background();
 
+    // Approvals a version needs before it can be published; 0 makes reviews optional
+    let required_approvals = std::env::var("REQUIRED_APPROVALS").map_or(Ok(0), |n| n.parse())?;
+
+    tracing::info!("Required approvals: {}", required_approvals);
+
+    // Validators content runs through, with extra ones for specs matching label selectors
+    let validators: ValidatorRegistry = std::env::var("VALIDATORS")
+        .unwrap_or_else(|_| "duplicate_keys,tab_indentation,max_nesting_depth=32".to_string())
+        .parse()?;
+
+    tracing::info!("Validators: {:?}", validators);
+
+    // Content size and name limits, with overrides for specs matching label selectors
+    let validation_policies: ValidationPolicies = std::env::var("VALIDATION_POLICY")
+        .unwrap_or_default()
+        .parse()?;
+
+    tracing::info!("Validation policies: {:?}", validation_policies);
+
+    // Channels versions are promoted through, in order; any channel is allowed if unset
+    let promotion_policy: PromotionPolicy =
+        std::env::var("CHANNELS").unwrap_or_default().parse()?;
+
+    tracing::info!("Promotion policy: {:?}", promotion_policy);
+
+    let repository = SpecRepository::new(event_store.clone())
+        .with_review_policy(ReviewPolicy { required_approvals })
+        .with_promotion_policy(promotion_policy)
+        .with_validators(validators)
+        .with_validation_policies(validation_policies);
+
+    let releases = ReleaseRegistry::new(event_store.clone(), repository.clone());
+    let tenants = TenantRegistry::new(event_store.clone());
+
+    // Start sunset processor for deprecated specs
+    tracing::info!("Starting sunset processor...");
+    let (_sunset_handle, _sunset_shutdown_tx) =
+        SunsetProcessor::start_background(repository.clone(), projection_store.clone());
+
+    // Start scheduler for queued publish/deprecate transitions
+    tracing::info!("Starting transition scheduler...");
+    let (_scheduler_handle, _scheduler_shutdown_tx) =
+        TransitionScheduler::start_background(repository.clone(), projection_store.clone());
+
     // Create app state
     let app_state = AppState {
         event_store: event_store.clone(),
         projection_store: projection_store.clone(),
+        repository: repository.clone(),
+        releases: releases.clone(),
+        tenants: tenants.clone(),
+        authenticator: authenticator.clone(),
     };
 
     // Create REST router
@@ -62,8 +163,14 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("gRPC API listening on {}", grpc_addr);
 
-    let grpc_service =
-        api::grpc::SpecServiceImpl::new(event_store.clone(), projection_store.clone());
+    let grpc_service = api::grpc::SpecServiceImpl::new(
+        event_store.clone(),
+        projection_store.clone(),
+        repository,
+        releases,
+        tenants,
+        authenticator,
+    );
 
     let grpc_server = tonic::transport::Server::builder()
         .add_service(grpc_service.into_service())
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        grpc::{
+            spec_proto::{
+                batch_item::Command, spec_service_server::SpecService, BatchExecuteRequest,
+                BatchItem, CreateSpecRequest, PublishSpecRequest, SpecState,
+            },
+            SpecServiceImpl,
+        },
+        rest::{create_router, AppState},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tonic::Code;
+use tower::ServiceExt;
+
+async fn state(dir: &TempDir) -> AppState {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(event_store.clone());
+    AppState {
+        event_store: event_store.clone(),
+        projection_store: Arc::new(projection_store),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store),
+        authenticator: Authenticator::default(),
+    }
+}
+
+async fn batch(router: &Router, items: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/specs/batch")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "items": items }).to_string()))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn event_count(state: &AppState) -> usize {
+    state
+        .event_store
+        .get_all_events(0, 100)
+        .await
+        .unwrap()
+        .len()
+}
+
+#[tokio::test]
+async fn batches_commit_every_command_or_none() {
+    let dir = TempDir::new().unwrap();
+    let state = state(&dir).await;
+    let router = create_router(state.clone());
+
+    let (status, failed) = batch(
+        &router,
+        json!([
+            { "op": "create", "name": "orders-api", "content": "openapi: 3.0.0\n" },
+            { "op": "publish", "spec": "orders-api" },
+            { "op": "publish", "spec": "payments-api" },
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(failed["committed"], false);
+    let statuses: Vec<&Value> = failed["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| &item["status"])
+        .collect();
+    assert_eq!(statuses, [424, 424, 404]);
+    assert_eq!(event_count(&state).await, 0);
+
+    // The rolled back create left its name free
+    let (status, committed) = batch(
+        &router,
+        json!([
+            { "op": "create", "name": "orders-api", "content": "openapi: 3.0.0\n" },
+            { "op": "create", "name": "payments-api", "content": "openapi: 3.0.0\n" },
+            { "op": "update", "spec": "orders-api", "content": "openapi: 3.1.0\n" },
+            { "op": "publish", "spec": "orders-api" },
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(committed["committed"], true);
+    let items = committed["items"].as_array().unwrap();
+    assert_eq!(items[0]["status"], 201);
+    assert_eq!(items[2]["status"], 200);
+    assert_eq!(items[2]["id"], items[0]["id"]);
+    assert_eq!(items[2]["version"], 2);
+    assert_eq!(items[3]["state"], "published");
+    assert_eq!(items[3]["stream_version"], 3);
+    assert_eq!(event_count(&state).await, 4);
+
+    // A stale expected version fails the batch like any other error
+    let (status, conflict) = batch(
+        &router,
+        json!([
+            { "op": "create", "name": "billing-api", "content": "openapi: 3.0.0\n" },
+            { "op": "deprecate", "spec": "orders-api", "reason": "Superseded", "expected_version": 1 },
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(conflict["items"][0]["status"], 424);
+    assert_eq!(event_count(&state).await, 4);
+}
+
+#[tokio::test]
+async fn batch_execute_reports_each_item() {
+    let dir = TempDir::new().unwrap();
+    let state = state(&dir).await;
+    let grpc = SpecServiceImpl::new(
+        state.event_store.clone(),
+        state.projection_store.clone(),
+        state.repository.clone(),
+        state.releases.clone(),
+        state.tenants.clone(),
+        state.authenticator.clone(),
+    );
+
+    let create = |name: &str| BatchItem {
+        command: Some(Command::Create(CreateSpecRequest {
+            name: name.to_string(),
+            content: "openapi: 3.0.0\n".to_string(),
+            ..CreateSpecRequest::default()
+        })),
+    };
+    let publish = |name: &str| BatchItem {
+        command: Some(Command::Publish(PublishSpecRequest {
+            id: name.to_string(),
+            ..PublishSpecRequest::default()
+        })),
+    };
+
+    let failed = grpc
+        .batch_execute(tonic::Request::new(BatchExecuteRequest {
+            items: vec![create("orders-api"), publish("payments-api")],
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(!failed.committed);
+    let codes: Vec<i32> = failed.results.iter().map(|result| result.code).collect();
+    assert_eq!(codes, [Code::Aborted as i32, Code::NotFound as i32]);
+    assert_eq!(event_count(&state).await, 0);
+
+    let committed = grpc
+        .batch_execute(tonic::Request::new(BatchExecuteRequest {
+            items: vec![create("orders-api"), publish("orders-api")],
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(committed.committed);
+    assert!(committed
+        .results
+        .iter()
+        .all(|result| result.code == Code::Ok as i32));
+    assert_eq!(committed.results[1].id, committed.results[0].id);
+    assert_eq!(committed.results[1].state, SpecState::Published as i32);
+    assert_eq!(event_count(&state).await, 2);
+}
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use spec_server::{
+    domain::{
+        commands::CreateSpec,
+        events::{EventMetadata, SpecEvent},
+        value_objects::TenantId,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore, repositories::SpecRepository,
+    },
+};
+use sqlx::sqlite::SqlitePool;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const CONTENT: &str = "openapi: 3.0.0\ninfo:\n  title: Shared\n";
+
+async fn create(repository: &SpecRepository, name: &str) -> Uuid {
+    let envelopes = repository
+        .create(
+            CreateSpec {
+                name: name.to_string(),
+                content: CONTENT.to_string(),
+                description: None,
+                schema: None,
+                labels: BTreeMap::new(),
+                template: None,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+    match &envelopes[0].event {
+        SpecEvent::Created(e) => e.spec_id,
+        event => panic!("expected created, got {event:?}"),
+    }
+}
+
+#[tokio::test]
+async fn specs_with_identical_content_share_one_blob() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(event_store.clone());
+    let orders = create(&repository, "orders-api").await;
+    let billing = create(&repository, "billing-api").await;
+
+    for (_, envelope) in event_store.get_all_events(0, 100).await.unwrap() {
+        projection_store.apply_event(&envelope).await.unwrap();
+    }
+
+    let pool = SqlitePool::connect(&url).await.unwrap();
+    let blobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM content_blobs")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(blobs, 1);
+
+    // Projections keep only the hash of their content
+    let columns: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM pragma_table_info('spec_projections')")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert!(columns.iter().any(|column| column == "content_hash"));
+    assert!(!columns.iter().any(|column| column == "content"));
+
+    let tenant = TenantId::default();
+    for id in [orders, billing] {
+        let spec = projection_store
+            .get_by_id(&tenant, id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(spec.content, CONTENT);
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        aggregates::Spec,
+        commands::{CreateSpec, DeleteSpec, PublishSpec, RestoreSpec, SpecCommand},
+        errors::DomainError,
+        events::{EventMetadata, SpecState},
+        validation::{ValidationPolicy, ValidatorPipeline},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+fn create_spec(name: &str) -> CreateSpec {
+    CreateSpec {
+        name: name.to_string(),
+        content: "openapi: 3.0.0\n".to_string(),
+        description: None,
+        schema: None,
+        labels: BTreeMap::new(),
+        template: None,
+        created_by: "alice@example.com".to_string(),
+    }
+}
+
+/// Handle `command` and apply the events it produced
+fn run(spec: Spec, command: SpecCommand) -> Result<Spec, DomainError> {
+    let events = spec.handle_command(command)?;
+    Ok(events.iter().fold(spec, Spec::apply_event))
+}
+
+fn delete(spec: &Spec) -> SpecCommand {
+    SpecCommand::Delete(DeleteSpec {
+        spec_id: spec.id,
+        deleted_by: "alice@example.com".to_string(),
+    })
+}
+
+fn restore(spec: &Spec) -> SpecCommand {
+    SpecCommand::Restore(RestoreSpec {
+        spec_id: spec.id,
+        restored_by: "alice@example.com".to_string(),
+    })
+}
+
+#[test]
+fn restoring_returns_a_deleted_spec_to_its_prior_state() {
+    let events = Spec::create(
+        create_spec("orders-api"),
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+    let spec = Spec::from_events(events).unwrap();
+
+    assert!(matches!(
+        run(spec.clone(), restore(&spec)),
+        Err(DomainError::InvalidStateForOperation(SpecState::Draft))
+    ));
+
+    let command = SpecCommand::Publish(PublishSpec {
+        spec_id: spec.id,
+        version: Some(1),
+        published_by: "alice@example.com".to_string(),
+    });
+    let spec = run(spec, command).unwrap();
+
+    let spec = run(spec.clone(), delete(&spec)).unwrap();
+    assert_eq!(spec.state, SpecState::Deleted);
+    assert!(run(spec.clone(), delete(&spec)).is_err());
+
+    let spec = run(spec.clone(), restore(&spec)).unwrap();
+    assert_eq!(spec.state, SpecState::Published);
+}
+
+struct Server {
+    router: Router,
+    event_store: Arc<SqliteEventStore>,
+    projection_store: Arc<ProjectionStore>,
+    /// Events applied to the projections so far
+    applied: i64,
+}
+
+impl Server {
+    async fn new(dir: &TempDir) -> Self {
+        let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+        let event_store = SqliteEventStore::new(&url).await.unwrap();
+        event_store.init_schema().await.unwrap();
+        let event_store = Arc::new(event_store);
+        let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+        projection_store.init_schema().await.unwrap();
+        let projection_store = Arc::new(projection_store);
+
+        let repository = SpecRepository::new(event_store.clone());
+        let router = create_router(AppState {
+            event_store: event_store.clone(),
+            projection_store: projection_store.clone(),
+            releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+            repository,
+            tenants: TenantRegistry::new(event_store.clone()),
+            authenticator: Authenticator::default(),
+        });
+
+        Self {
+            router,
+            event_store,
+            projection_store,
+            applied: 0,
+        }
+    }
+
+    /// Send a request, then bring the projections up to date
+    async fn send(&mut self, method: Method, uri: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        for (_, envelope) in self
+            .event_store
+            .get_all_events(self.applied, 100)
+            .await
+            .unwrap()
+        {
+            self.projection_store.apply_event(&envelope).await.unwrap();
+            self.applied += 1;
+        }
+
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+}
+
+#[tokio::test]
+async fn deleted_specs_are_hidden_until_asked_for_or_restored() {
+    let dir = TempDir::new().unwrap();
+    let mut server = Server::new(&dir).await;
+
+    let repository = SpecRepository::new(server.event_store.clone());
+    let envelopes = repository
+        .create(create_spec("orders-api"), EventMetadata::default())
+        .await
+        .unwrap();
+    let spec = format!("/specs/{}", envelopes[0].aggregate_id);
+
+    let (status, _) = server.send(Method::DELETE, &spec).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = server.send(Method::GET, &spec).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) = server
+        .send(Method::GET, &format!("{spec}?include_deleted=true"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["state"], "deleted");
+
+    let (_, body) = server.send(Method::GET, "/specs").await;
+    assert_eq!(body["total"], 0);
+    let (_, body) = server
+        .send(Method::GET, "/specs?include_deleted=true")
+        .await;
+    assert_eq!(body["total"], 1);
+
+    let (status, _) = server.send(Method::POST, &format!("{spec}/restore")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = server.send(Method::GET, &spec).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["state"], "draft");
+
+    let (status, _) = server.send(Method::POST, &format!("{spec}/restore")).await;
+    assert_ne!(status, StatusCode::OK);
+}
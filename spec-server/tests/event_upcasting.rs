@@ -0,0 +1,240 @@
+use serde_json::{json, Map, Value};
+use spec_server::{
+    domain::{
+        aggregates::Spec,
+        events::{EventMetadata, SpecEvent, SpecState},
+        value_objects::TenantId,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore,
+        upcasters::{record_legacy_semver, upcast, CURRENT_SCHEMA_VERSION},
+    },
+};
+use sqlx::sqlite::SqlitePool;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const FIXTURE_SPEC_ID: &str = "7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11";
+const V2_FIXTURE_SPEC_ID: &str = "3f8b2d61-6c4a-4e9b-b1d7-5a2c8e0f9b22";
+
+/// Open a store over a database seeded with the frozen version 1 rows
+async fn store_with_v1_rows(dir: &TempDir) -> (SqliteEventStore, SqlitePool) {
+    store_with_rows(dir, include_str!("fixtures/events_v1.sql")).await
+}
+
+/// Open a store over a database seeded with `fixture`
+async fn store_with_rows(dir: &TempDir, fixture: &str) -> (SqliteEventStore, SqlitePool) {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("events.db").display());
+
+    let pool = SqlitePool::connect(&url).await.unwrap();
+    sqlx::raw_sql(fixture).execute(&pool).await.unwrap();
+
+    let store = SqliteEventStore::new(&url).await.unwrap();
+    store.init_schema().await.unwrap();
+
+    (store, pool)
+}
+
+#[tokio::test]
+async fn frozen_v1_rows_load_as_current_events() {
+    let dir = TempDir::new().unwrap();
+    let (store, _pool) = store_with_v1_rows(&dir).await;
+    let spec_id = Uuid::parse_str(FIXTURE_SPEC_ID).unwrap();
+
+    let events: Vec<SpecEvent> = store
+        .get_events(&TenantId::default(), spec_id, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|envelope| envelope.event)
+        .collect();
+    assert_eq!(events.len(), 4);
+
+    let SpecEvent::Created(created) = &events[0] else {
+        panic!("expected created, got {:?}", events[0]);
+    };
+    assert_eq!(created.name, "orders-api");
+    assert_eq!(created.content, "openapi: 3.0.0\ninfo:\n  title: Orders\n");
+    assert!(created.schema.is_none());
+    assert!(created.warnings.is_empty());
+
+    let SpecEvent::Updated(updated) = &events[1] else {
+        panic!("expected updated, got {:?}", events[1]);
+    };
+    assert_eq!(updated.semver, semver::Version::new(2, 0, 0));
+
+    let SpecEvent::StateChanged(changed) = &events[2] else {
+        panic!("expected state_changed, got {:?}", events[2]);
+    };
+    assert_eq!(changed.to_state, SpecState::Published);
+    assert!(changed.sunset_at.is_none());
+
+    let SpecEvent::Updated(updated) = &events[3] else {
+        panic!("expected updated, got {:?}", events[3]);
+    };
+    assert_eq!(updated.semver, semver::Version::new(2, 1, 0));
+
+    let spec = Spec::from_events(events).unwrap();
+    assert_eq!(spec.semver, semver::Version::new(2, 1, 0));
+    assert_eq!(spec.state, SpecState::Published);
+}
+
+#[tokio::test]
+async fn frozen_v2_rows_without_a_tenant_load_into_the_default_tenant() {
+    let dir = TempDir::new().unwrap();
+    let (store, _pool) = store_with_rows(&dir, include_str!("fixtures/events_v2.sql")).await;
+    let spec_id = Uuid::parse_str(V2_FIXTURE_SPEC_ID).unwrap();
+
+    let envelopes = store
+        .get_events(&TenantId::default(), spec_id, None)
+        .await
+        .unwrap();
+    assert_eq!(envelopes.len(), 2);
+    assert!(envelopes
+        .iter()
+        .all(|envelope| envelope.metadata.tenant_id == TenantId::default()));
+    assert_eq!(
+        envelopes[0].metadata.idempotency_key.as_deref(),
+        Some("create-billing")
+    );
+
+    let SpecEvent::Updated(updated) = &envelopes[1].event else {
+        panic!("expected updated, got {:?}", envelopes[1].event);
+    };
+    assert_eq!(updated.semver, semver::Version::new(1, 1, 0));
+
+    let other = TenantId::new("acme").unwrap();
+    assert!(store
+        .get_events(&other, spec_id, None)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn new_events_are_stored_at_current_version() {
+    let dir = TempDir::new().unwrap();
+    let (store, pool) = store_with_v1_rows(&dir).await;
+    let spec_id = Uuid::parse_str(FIXTURE_SPEC_ID).unwrap();
+
+    let mut events = store
+        .get_events(&TenantId::default(), spec_id, None)
+        .await
+        .unwrap();
+    let SpecEvent::Updated(mut updated) = events.pop().unwrap().event else {
+        panic!("expected the last fixture row to be an update");
+    };
+    updated.version = 4;
+    updated.semver = semver::Version::new(2, 2, 0);
+
+    store
+        .append_events(
+            spec_id,
+            vec![SpecEvent::Updated(updated)],
+            Some(4),
+            EventMetadata::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let versions: Vec<i64> =
+        sqlx::query_scalar("SELECT schema_version FROM events ORDER BY sequence_number")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        versions,
+        vec![1, 1, 1, 1, i64::from(CURRENT_SCHEMA_VERSION)]
+    );
+
+    let all = store.get_all_events(0, 100).await.unwrap();
+    assert_eq!(all.len(), 5);
+}
+
+#[tokio::test]
+async fn rows_from_newer_versions_are_rejected() {
+    let dir = TempDir::new().unwrap();
+    let (store, pool) = store_with_v1_rows(&dir).await;
+    let spec_id = Uuid::parse_str(FIXTURE_SPEC_ID).unwrap();
+
+    sqlx::query("UPDATE events SET schema_version = ? WHERE sequence_number = 4")
+        .bind(CURRENT_SCHEMA_VERSION + 1)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert!(store
+        .get_events(&TenantId::default(), spec_id, None)
+        .await
+        .is_err());
+}
+
+/// Payloads of the frozen version 1 rows, exactly as stored
+async fn stored_v1_payloads(dir: &TempDir) -> Vec<Map<String, Value>> {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("events.db").display());
+    let pool = SqlitePool::connect(&url).await.unwrap();
+    sqlx::raw_sql(include_str!("fixtures/events_v1.sql"))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query_scalar::<_, String>("SELECT event_data FROM events ORDER BY sequence_number")
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+        .iter()
+        .map(|data| serde_json::from_str(data).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn record_legacy_semver_is_what_lets_stored_v1_payloads_deserialize() {
+    let dir = TempDir::new().unwrap();
+    let mut semvers = Vec::new();
+
+    for mut fields in stored_v1_payloads(&dir).await {
+        let as_stored = serde_json::from_value::<SpecEvent>(Value::Object(fields.clone()));
+        let missing_semver = fields["type"] == "updated" && !fields.contains_key("semver");
+        assert_eq!(as_stored.is_err(), missing_semver, "{fields:?}");
+
+        record_legacy_semver(&mut fields).unwrap();
+        if let SpecEvent::Updated(updated) = serde_json::from_value(Value::Object(fields)).unwrap()
+        {
+            semvers.push(updated.semver);
+        }
+    }
+
+    // The unversioned update gets the version it was reported as; the other
+    // keeps the one it recorded
+    assert_eq!(
+        semvers,
+        vec![semver::Version::new(2, 0, 0), semver::Version::new(2, 1, 0)]
+    );
+}
+
+#[test]
+fn upcast_fills_legacy_semver_only_where_missing() {
+    let mut reverted = json!({"type": "reverted", "version": 5, "reverted_from": 2});
+    upcast(&mut reverted, 1).unwrap();
+    assert_eq!(reverted["semver"], "5.0.0");
+
+    let mut updated = json!({"type": "updated", "version": 3, "semver": "2.1.0"});
+    upcast(&mut updated, 1).unwrap();
+    assert_eq!(updated["semver"], "2.1.0");
+
+    let mut renamed = json!({"type": "renamed", "version": 3});
+    upcast(&mut renamed, 1).unwrap();
+    assert!(renamed.get("semver").is_none());
+}
+
+#[test]
+fn upcast_leaves_current_payloads_alone() {
+    let original = json!({"type": "updated", "version": 3});
+    let mut current = original.clone();
+    upcast(&mut current, CURRENT_SCHEMA_VERSION).unwrap();
+    assert_eq!(current, original);
+
+    assert!(upcast(&mut current, 0).is_err());
+    assert!(upcast(&mut current, CURRENT_SCHEMA_VERSION + 1).is_err());
+}
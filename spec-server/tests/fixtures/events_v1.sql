@@ -0,0 +1,52 @@
+-- Rows as stored before events recorded a schema version (schema version 1).
+-- Frozen: never edit these to match newer event shapes.
+
+CREATE TABLE events (
+    event_id TEXT PRIMARY KEY,
+    aggregate_id TEXT NOT NULL,
+    sequence_number INTEGER NOT NULL,
+    event_type TEXT NOT NULL,
+    event_data TEXT NOT NULL,
+    metadata TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+INSERT INTO events VALUES (
+    '0b6f1b8e-5a43-4d7e-9a38-3c1f0f6d2a01',
+    '7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11',
+    1,
+    'created',
+    '{"type":"created","spec_id":"7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11","name":"orders-api","content":"openapi: 3.0.0\ninfo:\n  title: Orders\n","description":"Order service","created_by":"alice@example.com","created_at":"2024-05-01T10:00:00Z"}',
+    '{"correlation_id":null,"causation_id":null,"user_agent":null,"ip_address":null}',
+    '2024-05-01T10:00:00+00:00'
+);
+
+INSERT INTO events VALUES (
+    '0b6f1b8e-5a43-4d7e-9a38-3c1f0f6d2a02',
+    '7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11',
+    2,
+    'updated',
+    '{"type":"updated","spec_id":"7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11","version":2,"content":"openapi: 3.0.0\ninfo:\n  title: Orders API\n","description":"Order service","updated_by":"bob@example.com","updated_at":"2024-05-02T09:30:00Z"}',
+    '{"correlation_id":null,"causation_id":null,"user_agent":null,"ip_address":null}',
+    '2024-05-02T09:30:00+00:00'
+);
+
+INSERT INTO events VALUES (
+    '0b6f1b8e-5a43-4d7e-9a38-3c1f0f6d2a03',
+    '7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11',
+    3,
+    'state_changed',
+    '{"type":"state_changed","spec_id":"7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11","version":2,"from_state":"draft","to_state":"published","reason":"Ready","changed_by":"alice@example.com","changed_at":"2024-05-03T12:00:00Z"}',
+    '{"correlation_id":null,"causation_id":null,"user_agent":null,"ip_address":null}',
+    '2024-05-03T12:00:00+00:00'
+);
+
+INSERT INTO events VALUES (
+    '0b6f1b8e-5a43-4d7e-9a38-3c1f0f6d2a04',
+    '7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11',
+    4,
+    'updated',
+    '{"type":"updated","spec_id":"7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11","version":3,"semver":"2.1.0","content":"openapi: 3.0.0\ninfo:\n  title: Orders API\n  version: 2.1.0\n","description":"Order service","updated_by":"bob@example.com","updated_at":"2024-06-10T08:15:00Z"}',
+    '{"correlation_id":null,"causation_id":null,"user_agent":null,"ip_address":null}',
+    '2024-06-10T08:15:00+00:00'
+);
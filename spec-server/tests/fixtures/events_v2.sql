@@ -0,0 +1,36 @@
+-- Rows as stored at schema version 2, before events belonged to a tenant:
+-- the table has no tenant_id column and the metadata carries none.
+-- Frozen: never edit these to match newer event shapes.
+
+CREATE TABLE events (
+    event_id TEXT PRIMARY KEY,
+    aggregate_id TEXT NOT NULL,
+    sequence_number INTEGER NOT NULL,
+    event_type TEXT NOT NULL,
+    event_data TEXT NOT NULL,
+    metadata TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    schema_version INTEGER NOT NULL DEFAULT 1
+);
+
+INSERT INTO events VALUES (
+    '5e2a7c41-9b3d-4f80-a6c2-1d8e3b7f4a01',
+    '3f8b2d61-6c4a-4e9b-b1d7-5a2c8e0f9b22',
+    1,
+    'created',
+    '{"type":"created","spec_id":"3f8b2d61-6c4a-4e9b-b1d7-5a2c8e0f9b22","name":"billing-api","content":"openapi: 3.0.0\ninfo:\n  title: Billing\n","description":null,"created_by":"carol@example.com","created_at":"2024-09-02T14:00:00Z"}',
+    '{"correlation_id":null,"causation_id":null,"user_agent":"curl/8.4.0","ip_address":null,"idempotency_key":"create-billing"}',
+    '2024-09-02T14:00:00+00:00',
+    2
+);
+
+INSERT INTO events VALUES (
+    '5e2a7c41-9b3d-4f80-a6c2-1d8e3b7f4a02',
+    '3f8b2d61-6c4a-4e9b-b1d7-5a2c8e0f9b22',
+    2,
+    'updated',
+    '{"type":"updated","spec_id":"3f8b2d61-6c4a-4e9b-b1d7-5a2c8e0f9b22","version":2,"semver":"1.1.0","content":"openapi: 3.0.0\ninfo:\n  title: Billing API\n","description":null,"updated_by":"carol@example.com","updated_at":"2024-09-03T08:45:00Z"}',
+    '{"correlation_id":null,"causation_id":null,"user_agent":null,"ip_address":null}',
+    '2024-09-03T08:45:00+00:00',
+    2
+);
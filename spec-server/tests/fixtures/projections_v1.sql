@@ -0,0 +1,89 @@
+-- Projection tables as created before tenants, blobs and the later spec
+-- columns existed, holding the spec from events_v1.sql.
+-- Frozen: never edit these to match newer table layouts.
+
+CREATE TABLE spec_projections (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    content TEXT NOT NULL,
+    description TEXT,
+    version INTEGER NOT NULL,
+    state TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    updated_by TEXT NOT NULL
+);
+
+CREATE INDEX idx_spec_projections_name
+ON spec_projections(name);
+
+CREATE INDEX idx_spec_projections_state
+ON spec_projections(state);
+
+CREATE INDEX idx_spec_projections_updated
+ON spec_projections(updated_at DESC);
+
+CREATE TABLE spec_version_history (
+    id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    PRIMARY KEY (id, version)
+);
+
+INSERT INTO spec_projections VALUES (
+    '7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11',
+    'orders-api',
+    'openapi: 3.0.0
+info:
+  title: Orders API
+  version: 2.1.0
+',
+    'Order service',
+    3,
+    'published',
+    '2024-05-01T10:00:00+00:00',
+    '2024-06-10T08:15:00+00:00',
+    'alice@example.com',
+    'bob@example.com'
+);
+
+INSERT INTO spec_version_history VALUES (
+    '7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11',
+    1,
+    'openapi: 3.0.0
+info:
+  title: Orders
+',
+    'Order service',
+    '2024-05-01T10:00:00+00:00',
+    'alice@example.com'
+);
+
+INSERT INTO spec_version_history VALUES (
+    '7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11',
+    2,
+    'openapi: 3.0.0
+info:
+  title: Orders API
+',
+    'Order service',
+    '2024-05-02T09:30:00+00:00',
+    'bob@example.com'
+);
+
+INSERT INTO spec_version_history VALUES (
+    '7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11',
+    3,
+    'openapi: 3.0.0
+info:
+  title: Orders API
+  version: 2.1.0
+',
+    'Order service',
+    '2024-06-10T08:15:00+00:00',
+    'bob@example.com'
+);
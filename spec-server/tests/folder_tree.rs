@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        validation::ValidationPolicy,
+        value_objects::{SpecName, ValidationError},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+#[test]
+fn names_are_paths_of_valid_segments() {
+    let policy = ValidationPolicy::default();
+
+    let name = SpecName::new("payments/auth/regex-rules".to_string(), &policy).unwrap();
+    assert_eq!(name.folders(), ["", "payments", "payments/auth"]);
+    assert!(name.is_within("payments"));
+    assert!(name.is_within("payments/auth/regex-rules"));
+    assert!(!name.is_within("pay"));
+
+    for invalid in [
+        "payments//auth",
+        "/payments",
+        "payments/",
+        "payments/../orders",
+    ] {
+        assert!(
+            matches!(
+                SpecName::new(invalid.to_string(), &policy),
+                Err(ValidationError::InvalidNameSegment(_))
+            ),
+            "{invalid}"
+        );
+    }
+    assert!(SpecName::new("payments/auth rules".to_string(), &policy).is_err());
+    assert!(SpecName::single_segment("payments/auth".to_string(), &policy).is_err());
+}
+
+struct Server {
+    router: Router,
+    event_store: Arc<SqliteEventStore>,
+    projection_store: Arc<ProjectionStore>,
+    /// Events applied to the projections so far
+    applied: i64,
+}
+
+impl Server {
+    async fn new(dir: &TempDir) -> Self {
+        let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+        let event_store = SqliteEventStore::new(&url).await.unwrap();
+        event_store.init_schema().await.unwrap();
+        let event_store = Arc::new(event_store);
+        let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+        projection_store.init_schema().await.unwrap();
+        let projection_store = Arc::new(projection_store);
+
+        let repository = SpecRepository::new(event_store.clone());
+        let router = create_router(AppState {
+            event_store: event_store.clone(),
+            projection_store: projection_store.clone(),
+            releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+            repository,
+            tenants: TenantRegistry::new(event_store.clone()),
+            authenticator: Authenticator::default(),
+        });
+
+        Self {
+            router,
+            event_store,
+            projection_store,
+            applied: 0,
+        }
+    }
+
+    /// Send a request, then bring the projections up to date
+    async fn send(&mut self, method: Method, uri: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        for (_, envelope) in self
+            .event_store
+            .get_all_events(self.applied, 100)
+            .await
+            .unwrap()
+        {
+            self.projection_store.apply_event(&envelope).await.unwrap();
+            self.applied += 1;
+        }
+
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    async fn tree(&mut self, uri: &str) -> (StatusCode, Value) {
+        self.send(Method::GET, uri, Value::Null).await
+    }
+}
+
+fn names(specs: &Value) -> Vec<&str> {
+    let mut names: Vec<&str> = specs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|spec| spec["name"].as_str().unwrap())
+        .collect();
+    names.sort_unstable();
+    names
+}
+
+#[tokio::test]
+async fn folders_list_their_children_or_whole_subtree() {
+    let dir = TempDir::new().unwrap();
+    let mut server = Server::new(&dir).await;
+
+    for name in [
+        "orders",
+        "payments/billing",
+        "payments/auth/regex-rules",
+        "payments/auth/tokens",
+    ] {
+        let (status, _) = server
+            .send(
+                Method::POST,
+                "/specs",
+                json!({ "name": name, "content": "openapi: 3.0.0\n" }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{name}");
+    }
+
+    let (_, root) = server.tree("/tree").await;
+    assert_eq!(
+        root["folders"],
+        json!([{ "path": "payments", "spec_count": 3 }])
+    );
+    assert_eq!(names(&root["specs"]), ["orders"]);
+
+    let (status, payments) = server.tree("/tree/payments").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(payments["path"], "payments");
+    assert_eq!(
+        payments["folders"],
+        json!([{ "path": "payments/auth", "spec_count": 2 }])
+    );
+    assert_eq!(names(&payments["specs"]), ["payments/billing"]);
+
+    let (_, subtree) = server.tree("/tree/payments?recursive=true").await;
+    assert_eq!(
+        names(&subtree["specs"]),
+        [
+            "payments/auth/regex-rules",
+            "payments/auth/tokens",
+            "payments/billing"
+        ]
+    );
+
+    let (status, _) = server.tree("/tree/pay").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn moving_a_folder_renames_everything_below_it() {
+    let dir = TempDir::new().unwrap();
+    let mut server = Server::new(&dir).await;
+
+    for name in [
+        "payments/billing",
+        "payments/auth/regex-rules",
+        "payments/auth/tokens",
+    ] {
+        server
+            .send(
+                Method::POST,
+                "/specs",
+                json!({ "name": name, "content": "openapi: 3.0.0\n" }),
+            )
+            .await;
+    }
+
+    let (status, moved) = server
+        .send(
+            Method::POST,
+            "/specs/move",
+            json!({ "from": "payments/auth", "to": "identity/" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let mut renames: Vec<(&str, &str)> = moved["moved"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| {
+            (
+                m["old_name"].as_str().unwrap(),
+                m["new_name"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    renames.sort_unstable();
+    assert_eq!(
+        renames,
+        [
+            ("payments/auth/regex-rules", "identity/regex-rules"),
+            ("payments/auth/tokens", "identity/tokens"),
+        ]
+    );
+
+    let (_, identity) = server.tree("/tree/identity").await;
+    assert_eq!(
+        names(&identity["specs"]),
+        ["identity/regex-rules", "identity/tokens"]
+    );
+    let (status, _) = server.tree("/tree/payments/auth").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // A move that would collide with an existing name changes nothing
+    let (status, _) = server
+        .send(
+            Method::POST,
+            "/specs/move",
+            json!({ "from": "identity/tokens", "to": "payments/billing" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (_, identity) = server.tree("/tree/identity").await;
+    assert_eq!(names(&identity["specs"]).len(), 2);
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        aggregates::{ReviewPolicy, Spec},
+        commands::{ApproveSpec, CreateSpec, PublishSpec, RequestReview, SpecCommand},
+        errors::DomainError,
+        events::SpecEvent,
+        validation::{ValidationPolicy, ValidatorPipeline},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+/// A spec by alice at version 1 whose review needs `required_approvals`
+fn under_review(required_approvals: u32) -> Spec {
+    let events = Spec::create(
+        CreateSpec {
+            name: "orders-api".to_string(),
+            content: "openapi: 3.0.0\n".to_string(),
+            description: None,
+            schema: None,
+            labels: BTreeMap::new(),
+            template: None,
+            created_by: "alice@example.com".to_string(),
+        },
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+
+    let spec = Spec::from_events(events)
+        .unwrap()
+        .with_review_policy(ReviewPolicy { required_approvals });
+    let spec_id = spec.id;
+    run(
+        spec,
+        SpecCommand::RequestReview(RequestReview {
+            spec_id,
+            requested_by: "alice@example.com".to_string(),
+        }),
+    )
+}
+
+/// Handle `command` and apply the events it produced
+fn run(spec: Spec, command: SpecCommand) -> Spec {
+    let events = spec.handle_command(command).unwrap();
+    events.iter().fold(spec, Spec::apply_event)
+}
+
+fn approve(spec: &Spec, user: &str) -> SpecCommand {
+    SpecCommand::Approve(ApproveSpec {
+        spec_id: spec.id,
+        comment: None,
+        approved_by: user.to_string(),
+    })
+}
+
+fn publish(spec: &Spec) -> Result<Vec<SpecEvent>, DomainError> {
+    spec.handle_command(SpecCommand::Publish(PublishSpec {
+        spec_id: spec.id,
+        version: Some(1),
+        published_by: "alice@example.com".to_string(),
+    }))
+}
+
+#[test]
+fn authors_cannot_approve_their_own_changes() {
+    let spec = under_review(1);
+
+    assert!(matches!(
+        spec.handle_command(approve(&spec, "alice@example.com")),
+        Err(DomainError::SelfApproval(_))
+    ));
+}
+
+#[test]
+fn publishing_waits_for_the_required_number_of_distinct_approvers() {
+    let spec = under_review(2);
+
+    let spec = run(spec.clone(), approve(&spec, "bob@example.com"));
+    assert!(publish(&spec).is_err());
+
+    assert!(matches!(
+        spec.handle_command(approve(&spec, "bob@example.com")),
+        Err(DomainError::InvalidReview(_))
+    ));
+
+    let spec = run(spec.clone(), approve(&spec, "carol@example.com"));
+    assert!(publish(&spec).is_ok());
+}
+
+/// Send a REST request as the holder of `token`
+async fn send(router: &Router, method: Method, uri: &str, token: &str, body: Value) -> StatusCode {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {token}"))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    router.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn rest_approvals_are_recorded_for_the_authenticated_user() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(event_store.clone()).with_review_policy(ReviewPolicy {
+        required_approvals: 2,
+    });
+    let router = create_router(AppState {
+        event_store: event_store.clone(),
+        projection_store: Arc::new(projection_store),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store.clone()),
+        authenticator: "alice-token:alice@example.com:default,\
+                        bob-token:bob@example.com:default,\
+                        carol-token:carol@example.com:default"
+            .parse::<Authenticator>()
+            .unwrap(),
+    });
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/specs")
+        .header(header::AUTHORIZATION, "Bearer alice-token")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            json!({ "name": "orders-api", "content": "openapi: 3.0.0\n" }).to_string(),
+        ))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let created: Value = serde_json::from_slice(&body).unwrap();
+    let spec = format!("/specs/{}", created["id"].as_str().unwrap());
+
+    let review = format!("{spec}/review");
+    let approve = format!("{spec}/review/approve");
+    let publish = format!("{spec}/publish");
+
+    assert_eq!(
+        send(&router, Method::POST, &review, "alice-token", json!({})).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&router, Method::POST, &approve, "alice-token", json!({})).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        send(&router, Method::POST, &approve, "bob-token", json!({})).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&router, Method::POST, &approve, "bob-token", json!({})).await,
+        StatusCode::CONFLICT
+    );
+    assert_ne!(
+        send(&router, Method::POST, &publish, "alice-token", json!({})).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&router, Method::POST, &approve, "carol-token", json!({})).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(&router, Method::POST, &publish, "alice-token", json!({})).await,
+        StatusCode::OK
+    );
+}
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use chrono::Duration;
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        grpc::{
+            spec_proto::{spec_service_server::SpecService, CreateSpecRequest},
+            SpecServiceImpl,
+        },
+        rest::{create_router, AppState},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tonic::Code;
+use tower::ServiceExt;
+
+async fn state(dir: &TempDir, window: Duration) -> AppState {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store.with_idempotency_window(window));
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(event_store.clone());
+    AppState {
+        event_store: event_store.clone(),
+        projection_store: Arc::new(projection_store),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store),
+        authenticator: Authenticator::default(),
+    }
+}
+
+async fn send(
+    router: &Router,
+    method: Method,
+    uri: &str,
+    key: &str,
+    body: Value,
+) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("Idempotency-Key", key)
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn event_count(state: &AppState) -> usize {
+    state
+        .event_store
+        .get_all_events(0, 100)
+        .await
+        .unwrap()
+        .len()
+}
+
+#[tokio::test]
+async fn retried_requests_return_the_original_response() {
+    let dir = TempDir::new().unwrap();
+    let state = state(&dir, Duration::hours(24)).await;
+    let router = create_router(state.clone());
+
+    let create = json!({ "name": "orders-api", "content": "openapi: 3.0.0\n" });
+    let (status, created) = send(&router, Method::POST, "/specs", "ci-1", create.clone()).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, retried) = send(&router, Method::POST, "/specs", "ci-1", create).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(retried["id"], created["id"]);
+    assert_eq!(retried["version"], 1);
+
+    let spec = format!("/specs/{}", created["id"].as_str().unwrap());
+    let update = json!({ "content": "openapi: 3.1.0\n" });
+    for _ in 0..2 {
+        let (status, updated) = send(&router, Method::PUT, &spec, "ci-2", update.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["version"], 2);
+    }
+    assert_eq!(event_count(&state).await, 2);
+
+    // A key names one request; sending another with it is a client bug
+    let (status, _) = send(
+        &router,
+        Method::PUT,
+        &spec,
+        "ci-2",
+        json!({ "content": "openapi: 3.2.0\n" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(event_count(&state).await, 2);
+}
+
+#[tokio::test]
+async fn keys_are_forgotten_after_the_window() {
+    let dir = TempDir::new().unwrap();
+    let state = state(&dir, Duration::zero()).await;
+    let router = create_router(state.clone());
+
+    let (_, created) = send(
+        &router,
+        Method::POST,
+        "/specs",
+        "ci-1",
+        json!({ "name": "orders-api", "content": "openapi: 3.0.0\n" }),
+    )
+    .await;
+    let spec = format!("/specs/{}", created["id"].as_str().unwrap());
+
+    let update = json!({ "content": "openapi: 3.1.0\n" });
+    let (_, first) = send(&router, Method::PUT, &spec, "ci-2", update.clone()).await;
+    let (status, second) = send(&router, Method::PUT, &spec, "ci-2", update).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["version"], 2);
+    assert_eq!(second["version"], 3);
+}
+
+#[tokio::test]
+async fn grpc_requests_take_the_key_from_metadata() {
+    let dir = TempDir::new().unwrap();
+    let state = state(&dir, Duration::hours(24)).await;
+    let grpc = SpecServiceImpl::new(
+        state.event_store.clone(),
+        state.projection_store.clone(),
+        state.repository.clone(),
+        state.releases.clone(),
+        state.tenants.clone(),
+        state.authenticator.clone(),
+    );
+
+    let request = |key: &str, name: &str| {
+        let mut request = tonic::Request::new(CreateSpecRequest {
+            name: name.to_string(),
+            content: "openapi: 3.0.0\n".to_string(),
+            ..CreateSpecRequest::default()
+        });
+        request
+            .metadata_mut()
+            .insert("idempotency-key", key.parse().unwrap());
+        request
+    };
+
+    let created = grpc
+        .create_spec(request("ci-1", "orders-api"))
+        .await
+        .unwrap()
+        .into_inner();
+    let retried = grpc
+        .create_spec(request("ci-1", "orders-api"))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(retried.id, created.id);
+    assert_eq!(retried.stream_version, created.stream_version);
+    assert_eq!(event_count(&state).await, 1);
+
+    let status = grpc
+        .create_spec(request("ci-1", "payments-api"))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use spec_server::{
+    domain::{
+        commands::{BatchCommand, BatchTarget, CreateSpec, MoveFolder, PublishSpec, SpecRef},
+        events::{EventEnvelope, EventMetadata, SpecEvent, SpecState},
+        value_objects::TenantId,
+    },
+    infrastructure::{event_store::SqliteEventStore, repositories::SpecRepository},
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Open a repository over a fresh event store
+async fn repository(dir: &TempDir) -> (Arc<SqliteEventStore>, SpecRepository) {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("events.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+
+    (event_store.clone(), SpecRepository::new(event_store))
+}
+
+fn create(name: &str) -> CreateSpec {
+    CreateSpec {
+        name: name.to_string(),
+        content: "openapi: 3.0.0\n".to_string(),
+        description: None,
+        schema: None,
+        labels: BTreeMap::new(),
+        template: None,
+        created_by: "alice@example.com".to_string(),
+    }
+}
+
+fn keyed(key: &str) -> EventMetadata {
+    EventMetadata::default().with_idempotency_key(key).unwrap()
+}
+
+fn event_ids(envelopes: &[EventEnvelope]) -> Vec<Uuid> {
+    envelopes.iter().map(|envelope| envelope.event_id).collect()
+}
+
+#[tokio::test]
+async fn retried_move_replays_every_rename() {
+    let dir = TempDir::new().unwrap();
+    let (event_store, repository) = repository(&dir).await;
+
+    for name in ["team/orders", "team/payments", "team/billing/invoices"] {
+        repository
+            .create(create(name), EventMetadata::default())
+            .await
+            .unwrap();
+    }
+
+    let command = MoveFolder {
+        from: "team".to_string(),
+        to: "platform".to_string(),
+        moved_by: "alice@example.com".to_string(),
+    };
+    let moved = repository
+        .move_folder(command.clone(), keyed("move-1"))
+        .await
+        .unwrap();
+    assert_eq!(moved.len(), 3);
+
+    let replayed = repository
+        .move_folder(command, keyed("move-1"))
+        .await
+        .unwrap();
+    assert_eq!(event_ids(&replayed), event_ids(&moved));
+    assert!(replayed
+        .iter()
+        .all(|envelope| matches!(envelope.event, SpecEvent::Renamed(_))));
+
+    // Nothing was renamed twice
+    let all = event_store.get_all_events(0, 100).await.unwrap();
+    assert_eq!(all.len(), 6);
+    assert!(event_store
+        .resolve_name(&TenantId::default(), "platform/billing/invoices")
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn retried_batch_reports_the_original_results() {
+    let dir = TempDir::new().unwrap();
+    let (event_store, repository) = repository(&dir).await;
+
+    let commands = || {
+        vec![
+            BatchCommand::Create(create("orders")),
+            BatchCommand::Create(create("payments")),
+            BatchCommand::Publish(
+                BatchTarget {
+                    spec: SpecRef::Name("orders".to_string()),
+                    expected_version: None,
+                },
+                PublishSpec {
+                    spec_id: Uuid::nil(),
+                    version: None,
+                    published_by: "alice@example.com".to_string(),
+                },
+            ),
+        ]
+    };
+
+    let outcome = repository
+        .execute_batch(commands(), keyed("batch-1"))
+        .await
+        .unwrap();
+    assert!(outcome.committed);
+
+    let replayed = repository
+        .execute_batch(commands(), keyed("batch-1"))
+        .await
+        .unwrap();
+    assert!(replayed.committed);
+    assert_eq!(replayed.items.len(), 3);
+
+    for (original, replayed) in outcome.items.iter().zip(&replayed.items) {
+        let (original, replayed) = (original.as_ref().unwrap(), replayed.as_ref().unwrap());
+        assert_eq!(replayed.spec_id, original.spec_id);
+        assert_eq!(replayed.version, original.version);
+        assert_eq!(replayed.state, original.state);
+        assert_eq!(replayed.stream_version, original.stream_version);
+    }
+    assert_eq!(
+        replayed.items[2].as_ref().unwrap().state,
+        SpecState::Published
+    );
+
+    // The retry stored nothing
+    let all = event_store.get_all_events(0, 100).await.unwrap();
+    assert_eq!(all.len(), 3);
+}
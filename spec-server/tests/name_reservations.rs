@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::Duration;
+use spec_server::{
+    domain::{
+        commands::{CreateSpec, DeleteSpec, SpecCommand},
+        errors::DomainError,
+        events::{EventMetadata, SpecEvent},
+        value_objects::TenantId,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, name_registry::NameReleasePolicy,
+        repositories::SpecRepository,
+    },
+};
+use sqlx::sqlite::SqlitePool;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+fn database_url(dir: &TempDir) -> String {
+    format!("sqlite:{}?mode=rwc", dir.path().join("events.db").display())
+}
+
+async fn repository(dir: &TempDir, policy: NameReleasePolicy) -> SpecRepository {
+    let event_store = SqliteEventStore::new(&database_url(dir))
+        .await
+        .unwrap()
+        .with_name_release_policy(policy);
+    event_store.init_schema().await.unwrap();
+
+    SpecRepository::new(Arc::new(event_store))
+}
+
+async fn create(repository: &SpecRepository, name: &str) -> Result<Uuid, DomainError> {
+    let envelopes = repository
+        .create(
+            CreateSpec {
+                name: name.to_string(),
+                content: "openapi: 3.0.0\n".to_string(),
+                description: None,
+                schema: None,
+                labels: BTreeMap::new(),
+                template: None,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await?;
+
+    match &envelopes[0].event {
+        SpecEvent::Created(e) => Ok(e.spec_id),
+        event => panic!("expected created, got {event:?}"),
+    }
+}
+
+async fn delete(repository: &SpecRepository, spec_id: Uuid) {
+    repository
+        .execute(
+            spec_id,
+            SpecCommand::Delete(DeleteSpec {
+                spec_id,
+                deleted_by: "alice@example.com".to_string(),
+            }),
+            None,
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+}
+
+#[test]
+fn release_policies_parse_and_reject_negative_grace_periods() {
+    assert_eq!(
+        "immediate".parse::<NameReleasePolicy>().unwrap(),
+        NameReleasePolicy::Immediate
+    );
+    assert_eq!(
+        "never".parse::<NameReleasePolicy>().unwrap(),
+        NameReleasePolicy::Never
+    );
+    assert_eq!(
+        "grace:3600".parse::<NameReleasePolicy>().unwrap(),
+        NameReleasePolicy::GracePeriod(Duration::hours(1))
+    );
+
+    assert!("grace:-60".parse::<NameReleasePolicy>().is_err());
+    assert!("grace:soon".parse::<NameReleasePolicy>().is_err());
+    assert!("sometimes".parse::<NameReleasePolicy>().is_err());
+}
+
+#[tokio::test]
+async fn names_are_unique_within_a_tenant() {
+    let dir = TempDir::new().unwrap();
+    let repository = repository(&dir, NameReleasePolicy::Immediate).await;
+
+    create(&repository, "orders-api").await.unwrap();
+
+    assert!(matches!(
+        create(&repository, "orders-api").await,
+        Err(DomainError::DuplicateSpecName(_))
+    ));
+    assert!(create(&repository, "billing-api").await.is_ok());
+}
+
+#[tokio::test]
+async fn deleted_names_are_reused_according_to_the_release_policy() {
+    let cases = [
+        (NameReleasePolicy::Immediate, true),
+        (NameReleasePolicy::GracePeriod(Duration::zero()), true),
+        (NameReleasePolicy::GracePeriod(Duration::days(7)), false),
+        (NameReleasePolicy::Never, false),
+    ];
+
+    for (policy, reusable) in cases {
+        let dir = TempDir::new().unwrap();
+        let repository = repository(&dir, policy).await;
+
+        let spec_id = create(&repository, "orders-api").await.unwrap();
+        delete(&repository, spec_id).await;
+
+        let recreated = create(&repository, "orders-api").await;
+        assert_eq!(recreated.is_ok(), reusable, "{policy:?}: {recreated:?}");
+        if !reusable {
+            assert!(matches!(recreated, Err(DomainError::DuplicateSpecName(_))));
+        }
+    }
+}
+
+#[tokio::test]
+async fn names_of_specs_stored_before_reservations_are_backfilled() {
+    let dir = TempDir::new().unwrap();
+    let pool = SqlitePool::connect(&database_url(&dir)).await.unwrap();
+    sqlx::raw_sql(include_str!("fixtures/events_v1.sql"))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let repository = repository(&dir, NameReleasePolicy::Immediate).await;
+
+    assert!(matches!(
+        create(&repository, "orders-api").await,
+        Err(DomainError::DuplicateSpecName(_))
+    ));
+}
+
+#[tokio::test]
+async fn backfilled_reservations_replay_deletions_under_the_release_policy() {
+    let dir = TempDir::new().unwrap();
+    let kept = {
+        let repository = repository(&dir, NameReleasePolicy::Never).await;
+        let deleted = create(&repository, "orders-api").await.unwrap();
+        delete(&repository, deleted).await;
+        create(&repository, "billing-api").await.unwrap()
+    };
+
+    // As in a database from before names were reserved
+    let pool = SqlitePool::connect(&database_url(&dir)).await.unwrap();
+    sqlx::query("DROP TABLE spec_names")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let repository = repository(&dir, NameReleasePolicy::Never).await;
+
+    assert!(matches!(
+        create(&repository, "orders-api").await,
+        Err(DomainError::DuplicateSpecName(_))
+    ));
+    assert!(matches!(
+        create(&repository, "billing-api").await,
+        Err(DomainError::DuplicateSpecName(_))
+    ));
+    let event_store = SqliteEventStore::new(&database_url(&dir)).await.unwrap();
+    assert_eq!(
+        event_store
+            .resolve_name(&TenantId::default(), "billing-api")
+            .await
+            .unwrap(),
+        Some(kept)
+    );
+}
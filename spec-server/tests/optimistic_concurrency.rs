@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        commands::{CreateSpec, SpecCommand, UpdateSpec},
+        errors::DomainError,
+        events::{EventMetadata, SpecEvent},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+async fn event_store(dir: &TempDir) -> Arc<SqliteEventStore> {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    Arc::new(event_store)
+}
+
+fn update(spec_id: Uuid, content: &str) -> SpecCommand {
+    SpecCommand::Update(UpdateSpec {
+        spec_id,
+        content: content.to_string(),
+        description: None,
+        bump: None,
+        schema: None,
+        updated_by: "alice@example.com".to_string(),
+    })
+}
+
+#[tokio::test]
+async fn stale_expected_versions_are_rejected() {
+    let dir = TempDir::new().unwrap();
+    let repository = SpecRepository::new(event_store(&dir).await);
+
+    let envelopes = repository
+        .create(
+            CreateSpec {
+                name: "orders-api".to_string(),
+                content: "openapi: 3.0.0\n".to_string(),
+                description: None,
+                schema: None,
+                labels: BTreeMap::new(),
+                template: None,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    let spec_id = match &envelopes[0].event {
+        SpecEvent::Created(e) => e.spec_id,
+        event => panic!("expected created, got {event:?}"),
+    };
+
+    // Both editors read version 1; the first to write wins
+    repository
+        .execute(
+            spec_id,
+            update(spec_id, "openapi: 3.0.1\n"),
+            Some(1),
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+    let stale = repository
+        .execute(
+            spec_id,
+            update(spec_id, "openapi: 3.0.2\n"),
+            Some(1),
+            EventMetadata::default(),
+        )
+        .await;
+    assert!(matches!(
+        stale,
+        Err(DomainError::ConcurrencyConflict {
+            expected: 1,
+            actual: 2
+        })
+    ));
+
+    assert!(repository
+        .execute(
+            spec_id,
+            update(spec_id, "openapi: 3.0.2\n"),
+            Some(2),
+            EventMetadata::default(),
+        )
+        .await
+        .is_ok());
+}
+
+async fn router(dir: &TempDir) -> Router {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+    let event_store = event_store(dir).await;
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(event_store.clone());
+    create_router(AppState {
+        event_store: event_store.clone(),
+        projection_store: Arc::new(projection_store),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store),
+        authenticator: Authenticator::default(),
+    })
+}
+
+/// Send `body` to `uri`, with an `If-Match` header when `if_match` is set
+async fn send(
+    router: &Router,
+    method: Method,
+    uri: &str,
+    if_match: Option<&str>,
+    body: Value,
+) -> (StatusCode, Option<String>, Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(if_match) = if_match {
+        request = request.header(header::IF_MATCH, if_match);
+    }
+
+    let response = router
+        .clone()
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let etag = response
+        .headers()
+        .get(header::ETAG)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (
+        status,
+        etag,
+        serde_json::from_slice(&body).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn rest_updates_honour_if_match_and_return_the_new_etag() {
+    let dir = TempDir::new().unwrap();
+    let router = router(&dir).await;
+
+    let (status, etag, created) = send(
+        &router,
+        Method::POST,
+        "/specs",
+        None,
+        json!({ "name": "orders-api", "content": "openapi: 3.0.0\n" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(etag.as_deref(), Some("\"1\""));
+    let spec = format!("/specs/{}", created["id"].as_str().unwrap());
+
+    let (status, etag, _) = send(
+        &router,
+        Method::PUT,
+        &spec,
+        Some("\"1\""),
+        json!({ "content": "openapi: 3.0.1\n" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"2\""));
+
+    let (status, _, _) = send(
+        &router,
+        Method::PUT,
+        &spec,
+        Some("\"1\""),
+        json!({ "content": "openapi: 3.0.2\n" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    // Weak tags and `*` are accepted too
+    let (status, _, _) = send(
+        &router,
+        Method::PUT,
+        &spec,
+        Some("W/\"2\""),
+        json!({ "content": "openapi: 3.0.2\n" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _, _) = send(
+        &router,
+        Method::PUT,
+        &spec,
+        Some("*"),
+        json!({ "content": "openapi: 3.0.3\n" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,72 @@
+use spec_server::{
+    domain::value_objects::TenantId,
+    infrastructure::{event_store::SqliteEventStore, projections::ProjectionStore},
+};
+use sqlx::sqlite::SqlitePool;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const FIXTURE_SPEC_ID: &str = "7d3c9a52-1f0e-4c1b-8f59-2a6b4e9c0d11";
+
+/// Open both stores over a database seeded with the frozen version 1 events
+/// and the projections built from them before any column was added
+async fn stores_with_v1_tables(dir: &TempDir) -> (SqliteEventStore, ProjectionStore) {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let pool = SqlitePool::connect(&url).await.unwrap();
+    sqlx::raw_sql(include_str!("fixtures/events_v1.sql"))
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::raw_sql(include_str!("fixtures/projections_v1.sql"))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    (event_store, projection_store)
+}
+
+#[tokio::test]
+async fn v1_projection_tables_are_migrated_and_projected_again() {
+    let dir = TempDir::new().unwrap();
+    let (event_store, projection_store) = stores_with_v1_tables(&dir).await;
+    let tenant = TenantId::default();
+    let spec_id = Uuid::parse_str(FIXTURE_SPEC_ID).unwrap();
+
+    // A second start finds nothing left to migrate
+    projection_store.init_schema().await.unwrap();
+
+    // The startup replay projects every event from the start
+    for (_, envelope) in event_store.get_all_events(0, 100).await.unwrap() {
+        projection_store.apply_event(&envelope).await.unwrap();
+    }
+
+    let spec = projection_store
+        .get_by_id(&tenant, spec_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(spec.tenant_id, tenant);
+    assert_eq!(spec.head_version, 3);
+    assert_eq!(spec.published_version, Some(2));
+    assert_eq!(spec.stream_version, 4);
+    assert!(!spec.content_hash.is_empty());
+
+    let first = projection_store
+        .get_version(&tenant, spec_id, 1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.content, "openapi: 3.0.0\ninfo:\n  title: Orders\n");
+
+    let found = projection_store
+        .get_by_name(&tenant, "orders-api")
+        .await
+        .unwrap();
+    assert_eq!(found.map(|spec| spec.id), Some(spec_id));
+}
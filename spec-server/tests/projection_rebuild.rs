@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use spec_server::{
+    domain::{
+        commands::{CreateRelease, CreateSpec},
+        events::EventMetadata,
+        value_objects::{ReleaseEntry, TenantId},
+    },
+    infrastructure::{
+        event_processor::EventProcessorManager, event_store::SqliteEventStore,
+        projections::ProjectionStore, release_registry::ReleaseRegistry,
+        repositories::SpecRepository,
+    },
+};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn rebuilding_twice_yields_the_same_projections() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, true).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+    let projection_store = Arc::new(projection_store);
+
+    let specs = SpecRepository::new(event_store.clone());
+    let releases = ReleaseRegistry::new(event_store.clone(), specs.clone());
+    let tenant = TenantId::default();
+
+    let created = specs
+        .create(
+            CreateSpec {
+                name: "payments/orders-api".to_string(),
+                content: "openapi: 3.0.0\n".to_string(),
+                description: None,
+                schema: None,
+                labels: BTreeMap::from([("team".to_string(), "payments".to_string())]),
+                template: None,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    let spec_id = created[0].aggregate_id;
+
+    releases
+        .create(
+            CreateRelease {
+                name: "2024.1".to_string(),
+                description: None,
+                entries: vec![ReleaseEntry {
+                    spec_id,
+                    version: 1,
+                }],
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+    let manager = EventProcessorManager::new(event_store, projection_store.clone());
+    manager.rebuild_projections().await.unwrap();
+    manager.rebuild_projections().await.unwrap();
+
+    let spec = projection_store
+        .get_by_id(&tenant, spec_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        spec.labels.get("team").map(String::as_str),
+        Some("payments")
+    );
+
+    let folder = projection_store
+        .list_folder(&tenant, "payments", false)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(folder.specs.len(), 1);
+
+    let releases = projection_store.list_releases(&tenant).await.unwrap();
+    assert_eq!(releases.len(), 1);
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use spec_server::{
+    domain::{
+        commands::{CreateSpec, PublishSpec, SpecCommand, UpdateSpec},
+        events::{EventMetadata, SpecEvent},
+        value_objects::TenantId,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore, repositories::SpecRepository,
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+const PUBLISHED: &str = "openapi: 3.0.0\ninfo:\n  title: Orders\n";
+const DRAFT: &str = "openapi: 3.0.0\ninfo:\n  title: Orders API\n";
+
+/// Open an event store and projections over one fresh database
+async fn stores(dir: &TempDir) -> (Arc<SqliteEventStore>, ProjectionStore) {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    (Arc::new(event_store), projection_store)
+}
+
+/// Apply the events stored after `position` to the projections, returning
+/// the new position
+async fn project(
+    event_store: &SqliteEventStore,
+    projection_store: &ProjectionStore,
+    position: i64,
+) -> i64 {
+    let events = event_store.get_all_events(position, 1000).await.unwrap();
+    for (_, envelope) in &events {
+        projection_store.apply_event(envelope).await.unwrap();
+    }
+    position + i64::try_from(events.len()).unwrap()
+}
+
+#[tokio::test]
+async fn reads_serve_the_published_version_until_the_next_publish() {
+    let dir = TempDir::new().unwrap();
+    let (event_store, projection_store) = stores(&dir).await;
+    let repository = SpecRepository::new(event_store.clone());
+    let tenant = TenantId::default();
+
+    let created = repository
+        .create(
+            CreateSpec {
+                name: "orders-api".to_string(),
+                content: PUBLISHED.to_string(),
+                description: None,
+                schema: None,
+                labels: BTreeMap::new(),
+                template: None,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    let SpecEvent::Created(created) = &created[0].event else {
+        panic!("expected created, got {:?}", created[0].event);
+    };
+    let spec_id: Uuid = created.spec_id;
+
+    let publish = |version| {
+        SpecCommand::Publish(PublishSpec {
+            spec_id,
+            version: Some(version),
+            published_by: "alice@example.com".to_string(),
+        })
+    };
+    repository
+        .execute(spec_id, publish(1), None, EventMetadata::default())
+        .await
+        .unwrap();
+    repository
+        .execute(
+            spec_id,
+            SpecCommand::Update(UpdateSpec {
+                spec_id,
+                content: DRAFT.to_string(),
+                description: None,
+                bump: None,
+                schema: None,
+                updated_by: "bob@example.com".to_string(),
+            }),
+            None,
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    let position = project(&event_store, &projection_store, 0).await;
+
+    let current = projection_store
+        .get_current(&tenant, spec_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(current.content, PUBLISHED);
+    assert_eq!(current.published_version, Some(1));
+    assert_eq!(current.head_version, 2);
+
+    let head = projection_store
+        .get_by_id(&tenant, spec_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(head.content, DRAFT);
+
+    repository
+        .execute(spec_id, publish(2), None, EventMetadata::default())
+        .await
+        .unwrap();
+    project(&event_store, &projection_store, position).await;
+
+    let current = projection_store
+        .get_current(&tenant, spec_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(current.content, DRAFT);
+    assert_eq!(current.published_version, Some(2));
+}
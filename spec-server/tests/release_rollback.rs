@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use spec_server::{
+    domain::{
+        commands::{
+            CreateRelease, CreateSpec, PublishRelease, PublishSpec, RollbackRelease,
+            SetDependencies, SpecCommand, UpdateSpec,
+        },
+        errors::DomainError,
+        events::{EventMetadata, SpecEvent, SpecState},
+        value_objects::{BumpLevel, ReleaseEntry, SpecDependency, TenantId, Version},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, release_registry::ReleaseRegistry,
+        repositories::SpecRepository,
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Open a spec repository and a release registry over a fresh event store
+async fn registries(dir: &TempDir) -> (SpecRepository, ReleaseRegistry) {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("events.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+
+    let specs = SpecRepository::new(event_store.clone());
+    (specs.clone(), ReleaseRegistry::new(event_store, specs))
+}
+
+/// Create and publish a release pinning `entries`
+async fn release(releases: &ReleaseRegistry, name: &str, entries: Vec<ReleaseEntry>) -> Uuid {
+    let created = releases
+        .create(
+            CreateRelease {
+                name: name.to_string(),
+                description: None,
+                entries,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    let SpecEvent::ReleaseCreated(created) = &created[0].event else {
+        panic!("expected release_created, got {:?}", created[0].event);
+    };
+
+    releases
+        .publish(
+            PublishRelease {
+                release_id: created.release_id,
+                published_by: "alice@example.com".to_string(),
+            },
+            None,
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+    created.release_id
+}
+
+/// Create a draft spec named `name`
+async fn create(specs: &SpecRepository, name: &str) -> Uuid {
+    let created = specs
+        .create(
+            CreateSpec {
+                name: name.to_string(),
+                content: format!("openapi: 3.0.0\ninfo:\n  title: {name}\n"),
+                description: None,
+                schema: None,
+                labels: BTreeMap::new(),
+                template: None,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+    created[0].aggregate_id
+}
+
+async fn execute(specs: &SpecRepository, spec_id: Uuid, command: SpecCommand) {
+    specs
+        .execute(spec_id, command, None, EventMetadata::default())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rollback_publishes_the_previous_release_again() {
+    let dir = TempDir::new().unwrap();
+    let (specs, releases) = registries(&dir).await;
+    let tenant = TenantId::default();
+
+    let created = specs
+        .create(
+            CreateSpec {
+                name: "orders-api".to_string(),
+                content: "openapi: 3.0.0\ninfo:\n  title: Orders\n".to_string(),
+                description: None,
+                schema: None,
+                labels: BTreeMap::new(),
+                template: None,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    let spec_id = created[0].aggregate_id;
+
+    release(
+        &releases,
+        "2024.1",
+        vec![ReleaseEntry {
+            spec_id,
+            version: 1,
+        }],
+    )
+    .await;
+
+    specs
+        .execute(
+            spec_id,
+            SpecCommand::Update(UpdateSpec {
+                spec_id,
+                content: "openapi: 3.0.0\ninfo:\n  title: Orders API\n".to_string(),
+                description: None,
+                bump: None,
+                schema: None,
+                updated_by: "bob@example.com".to_string(),
+            }),
+            None,
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    let current = release(
+        &releases,
+        "2024.2",
+        vec![ReleaseEntry {
+            spec_id,
+            version: 2,
+        }],
+    )
+    .await;
+
+    let (spec, _) = specs.load(&tenant, spec_id).await.unwrap().unwrap();
+    assert_eq!(spec.published_version, Some(Version::new(2)));
+
+    let rolled_back = releases
+        .rollback(
+            RollbackRelease {
+                release_id: current,
+                rolled_back_by: "alice@example.com".to_string(),
+            },
+            None,
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    assert!(rolled_back
+        .iter()
+        .all(|envelope| envelope.aggregate_id == current));
+
+    let (spec, _) = specs.load(&tenant, spec_id).await.unwrap().unwrap();
+    assert_eq!(spec.state, SpecState::Published);
+    assert_eq!(spec.published_version, Some(Version::new(1)));
+    assert_eq!(spec.head_version, Version::new(2));
+}
+
+#[tokio::test]
+async fn rollback_is_refused_when_published_dependents_reject_the_restored_version() {
+    let dir = TempDir::new().unwrap();
+    let (specs, releases) = registries(&dir).await;
+    let tenant = TenantId::default();
+
+    let orders = create(&specs, "orders-api").await;
+    release(
+        &releases,
+        "2024.1",
+        vec![ReleaseEntry {
+            spec_id: orders,
+            version: 1,
+        }],
+    )
+    .await;
+
+    execute(
+        &specs,
+        orders,
+        SpecCommand::Update(UpdateSpec {
+            spec_id: orders,
+            content: "openapi: 3.1.0\ninfo:\n  title: Orders v2\n".to_string(),
+            description: None,
+            bump: Some(BumpLevel::Major),
+            schema: None,
+            updated_by: "bob@example.com".to_string(),
+        }),
+    )
+    .await;
+    let current = release(
+        &releases,
+        "2024.2",
+        vec![ReleaseEntry {
+            spec_id: orders,
+            version: 2,
+        }],
+    )
+    .await;
+
+    // Billing needs the breaking change only the current release publishes
+    let (spec, _) = specs.load(&tenant, orders).await.unwrap().unwrap();
+    let billing = create(&specs, "billing-api").await;
+    execute(
+        &specs,
+        billing,
+        SpecCommand::SetDependencies(SetDependencies {
+            spec_id: billing,
+            dependencies: vec![SpecDependency {
+                spec_id: orders,
+                version: semver::VersionReq::parse(&format!(">={}", spec.semver)).unwrap(),
+            }],
+            set_by: "carol@example.com".to_string(),
+        }),
+    )
+    .await;
+    execute(
+        &specs,
+        billing,
+        SpecCommand::Publish(PublishSpec {
+            spec_id: billing,
+            version: None,
+            published_by: "carol@example.com".to_string(),
+        }),
+    )
+    .await;
+
+    let rolled_back = releases
+        .rollback(
+            RollbackRelease {
+                release_id: current,
+                rolled_back_by: "alice@example.com".to_string(),
+            },
+            None,
+            EventMetadata::default(),
+        )
+        .await;
+    assert!(
+        matches!(&rolled_back, Err(DomainError::HasDependents(ids)) if *ids == vec![billing]),
+        "{rolled_back:?}"
+    );
+
+    let (spec, _) = specs.load(&tenant, orders).await.unwrap().unwrap();
+    assert_eq!(spec.published_version, Some(Version::new(2)));
+}
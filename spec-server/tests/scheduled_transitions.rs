@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use spec_server::{
+    domain::{
+        aggregates::Spec,
+        commands::{CancelSchedule, CreateSpec, ExecuteSchedule, ScheduleTransition, SpecCommand},
+        errors::DomainError,
+        events::{
+            EventMetadata, ScheduledTransition, SpecEvent, SpecState, SpecTransitionScheduled,
+        },
+        validation::{ValidationPolicy, ValidatorPipeline},
+        value_objects::TenantId,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore,
+        projections::{ProjectionStore, ScheduleStatus},
+        repositories::SpecRepository,
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+fn create_spec() -> CreateSpec {
+    CreateSpec {
+        name: "orders-api".to_string(),
+        content: "openapi: 3.0.0\n".to_string(),
+        description: None,
+        schema: None,
+        labels: BTreeMap::new(),
+        template: None,
+        created_by: "alice@example.com".to_string(),
+    }
+}
+
+fn draft() -> Spec {
+    let events = Spec::create(
+        create_spec(),
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+    Spec::from_events(events).unwrap()
+}
+
+fn schedule(spec_id: Uuid, transition: ScheduledTransition, hours: i64) -> SpecCommand {
+    SpecCommand::ScheduleTransition(ScheduleTransition {
+        spec_id,
+        transition,
+        execute_at: Utc::now() + Duration::hours(hours),
+        scheduled_by: "alice@example.com".to_string(),
+    })
+}
+
+/// A spec with `transition` scheduled for an hour ago, as when the
+/// scheduler finds it due
+fn due(spec: Spec, transition: ScheduledTransition) -> (Spec, Uuid) {
+    let schedule_id = Uuid::new_v4();
+    let event = SpecEvent::TransitionScheduled(SpecTransitionScheduled {
+        spec_id: spec.id,
+        schedule_id,
+        transition,
+        execute_at: Utc::now() - Duration::hours(1),
+        scheduled_by: "alice@example.com".to_string(),
+        scheduled_at: Utc::now() - Duration::days(1),
+    });
+    (spec.apply_event(&event), schedule_id)
+}
+
+fn execute(spec: &Spec, schedule_id: Uuid) -> Result<Vec<SpecEvent>, DomainError> {
+    spec.handle_command(SpecCommand::ExecuteSchedule(ExecuteSchedule {
+        spec_id: spec.id,
+        schedule_id,
+    }))
+}
+
+#[test]
+fn transitions_are_scheduled_for_the_future_and_run_once_due() {
+    let spec = draft();
+    let publish = ScheduledTransition::Publish { version: Some(1) };
+
+    assert!(matches!(
+        spec.handle_command(schedule(spec.id, publish.clone(), -1)),
+        Err(DomainError::InvalidSchedule(_))
+    ));
+
+    let events = spec
+        .handle_command(schedule(spec.id, publish.clone(), 1))
+        .unwrap();
+    let scheduled = events.iter().fold(spec.clone(), Spec::apply_event);
+    let (&schedule_id, _) = scheduled.pending_schedules.iter().next().unwrap();
+    assert!(matches!(
+        execute(&scheduled, schedule_id),
+        Err(DomainError::InvalidSchedule(_))
+    ));
+
+    let (spec, schedule_id) = due(spec, publish);
+    let events = execute(&spec, schedule_id).unwrap();
+    let spec = events.iter().fold(spec, Spec::apply_event);
+    assert_eq!(spec.state, SpecState::Published);
+    assert!(spec.pending_schedules.is_empty());
+}
+
+#[test]
+fn rejected_transitions_complete_the_schedule_with_the_failure() {
+    let (spec, schedule_id) = due(
+        draft(),
+        ScheduledTransition::Deprecate {
+            reason: "Replaced".to_string(),
+            successor_id: None,
+            sunset_at: None,
+        },
+    );
+
+    let events = execute(&spec, schedule_id).unwrap();
+    match events.as_slice() {
+        [SpecEvent::ScheduleExecuted(e)] => assert!(e.failure.is_some()),
+        events => panic!("expected a failed schedule, got {events:?}"),
+    }
+
+    let spec = events.iter().fold(spec, Spec::apply_event);
+    assert_eq!(spec.state, SpecState::Draft);
+    assert!(matches!(
+        spec.handle_command(SpecCommand::CancelSchedule(CancelSchedule {
+            spec_id: spec.id,
+            schedule_id,
+            cancelled_by: "alice@example.com".to_string(),
+        })),
+        Err(DomainError::ScheduleNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn pending_schedules_are_projected_until_cancelled() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(event_store.clone());
+    let envelopes = repository
+        .create(create_spec(), EventMetadata::default())
+        .await
+        .unwrap();
+    let spec_id = envelopes[0].aggregate_id;
+
+    let mut schedule_ids = Vec::new();
+    for hours in [2, 4] {
+        let transition = ScheduledTransition::Publish { version: Some(1) };
+        let envelopes = repository
+            .execute(
+                spec_id,
+                schedule(spec_id, transition, hours),
+                None,
+                EventMetadata::default(),
+            )
+            .await
+            .unwrap();
+        match &envelopes[0].event {
+            SpecEvent::TransitionScheduled(e) => schedule_ids.push(e.schedule_id),
+            event => panic!("expected scheduled, got {event:?}"),
+        }
+    }
+
+    repository
+        .execute(
+            spec_id,
+            SpecCommand::CancelSchedule(CancelSchedule {
+                spec_id,
+                schedule_id: schedule_ids[1],
+                cancelled_by: "alice@example.com".to_string(),
+            }),
+            None,
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+    for (_, envelope) in event_store.get_all_events(0, 100).await.unwrap() {
+        projection_store.apply_event(&envelope).await.unwrap();
+    }
+
+    let tenant = TenantId::default();
+    let pending = projection_store
+        .list_schedules(&tenant, Some(spec_id), Some(ScheduleStatus::Pending))
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].schedule_id, schedule_ids[0]);
+
+    let cancelled = projection_store
+        .list_schedules(&tenant, Some(spec_id), Some(ScheduleStatus::Cancelled))
+        .await
+        .unwrap();
+    assert_eq!(cancelled.len(), 1);
+    assert!(cancelled[0].completed_at.is_some());
+
+    assert!(projection_store
+        .list_schedules_due(Utc::now())
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        projection_store
+            .list_schedules_due(Utc::now() + Duration::days(1))
+            .await
+            .unwrap(),
+        vec![(tenant, spec_id, schedule_ids[0])]
+    );
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+async fn router(dir: &TempDir) -> Router {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(event_store.clone());
+    create_router(AppState {
+        event_store: event_store.clone(),
+        projection_store: Arc::new(projection_store),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store),
+        authenticator: Authenticator::default(),
+    })
+}
+
+async fn send(router: &Router, method: Method, uri: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+/// Rules must have an integer `limit`
+fn rules_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["rules"],
+        "properties": {
+            "rules": {
+                "type": "object",
+                "required": ["limit"],
+                "properties": { "limit": { "type": "integer" } }
+            }
+        }
+    })
+}
+
+#[tokio::test]
+async fn spec_content_is_checked_against_its_declared_schema_version() {
+    let dir = TempDir::new().unwrap();
+    let router = router(&dir).await;
+
+    let (status, schema) = send(
+        &router,
+        Method::POST,
+        "/schemas",
+        json!({ "name": "rate-limits", "schema": rules_schema() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(schema["version"], 1);
+    let schema_id = schema["id"].clone();
+
+    let (status, error) = send(
+        &router,
+        Method::POST,
+        "/specs",
+        json!({
+            "name": "checkout-limits",
+            "content": "rules:\n  limt: 10\n  limit: ten\n",
+            "schema": { "schema_id": schema_id, "version": 1 },
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let paths: Vec<&str> = error["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|violation| violation["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, ["/rules/limit"]);
+
+    let (status, spec) = send(
+        &router,
+        Method::POST,
+        "/specs",
+        json!({
+            "name": "checkout-limits",
+            "content": "rules:\n  limit: 10\n",
+            "schema": { "schema_id": schema_id, "version": 1 },
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let spec = format!("/specs/{}", spec["id"].as_str().unwrap());
+
+    // Updates keep the declared schema unless they name another version
+    let (status, _) = send(
+        &router,
+        Method::PUT,
+        &spec,
+        json!({ "content": "rules:\n  burst: 5\n" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Version 2 of the schema also requires `burst`
+    let mut stricter = rules_schema();
+    stricter["properties"]["rules"]["required"] = json!(["limit", "burst"]);
+    let (status, schema) = send(
+        &router,
+        Method::PUT,
+        &format!("/schemas/{}", schema_id.as_str().unwrap()),
+        json!({ "schema": stricter }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(schema["version"], 2);
+
+    let (status, error) = send(
+        &router,
+        Method::PUT,
+        &spec,
+        json!({
+            "content": "rules:\n  limit: 20\n",
+            "schema": { "schema_id": schema_id, "version": 2 },
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["violations"][0]["path"], "/rules");
+
+    let (status, _) = send(
+        &router,
+        Method::PUT,
+        &spec,
+        json!({
+            "content": "rules:\n  limit: 20\n  burst: 5\n",
+            "schema": { "schema_id": schema_id, "version": 2 },
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn unknown_schemas_and_versions_are_refused() {
+    let dir = TempDir::new().unwrap();
+    let router = router(&dir).await;
+
+    let (_, schema) = send(
+        &router,
+        Method::POST,
+        "/schemas",
+        json!({ "name": "rate-limits", "schema": rules_schema() }),
+    )
+    .await;
+
+    for schema_ref in [
+        json!({ "schema_id": schema["id"], "version": 2 }),
+        json!({ "schema_id": uuid::Uuid::new_v4(), "version": 1 }),
+    ] {
+        let (status, _) = send(
+            &router,
+            Method::POST,
+            "/specs",
+            json!({
+                "name": "checkout-limits",
+                "content": "rules:\n  limit: 10\n",
+                "schema": schema_ref,
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{schema_ref}");
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        aggregates::Spec,
+        commands::{CreateSpec, PublishSpec, SpecCommand, UpdateSpec},
+        events::{EventMetadata, SpecEvent},
+        validation::{ValidationPolicy, ValidatorPipeline},
+        value_objects::BumpLevel,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const V1: &str = "rules:\n  limit: 10\n";
+
+fn create_spec() -> CreateSpec {
+    CreateSpec {
+        name: "orders-api".to_string(),
+        content: V1.to_string(),
+        description: None,
+        schema: None,
+        labels: BTreeMap::new(),
+        template: None,
+        created_by: "alice@example.com".to_string(),
+    }
+}
+
+fn update(spec_id: Uuid, content: &str, bump: Option<BumpLevel>) -> SpecCommand {
+    SpecCommand::Update(UpdateSpec {
+        spec_id,
+        content: content.to_string(),
+        description: None,
+        bump,
+        schema: None,
+        updated_by: "alice@example.com".to_string(),
+    })
+}
+
+/// Update `spec` and return it with the semantic version it reached
+fn bump(spec: Spec, content: &str, level: Option<BumpLevel>) -> (Spec, String) {
+    let events = spec
+        .handle_command(update(spec.id, content, level))
+        .unwrap();
+    let semver = match &events[0] {
+        SpecEvent::Updated(e) => e.semver.to_string(),
+        event => panic!("expected updated, got {event:?}"),
+    };
+    (events.iter().fold(spec, Spec::apply_event), semver)
+}
+
+#[test]
+fn bump_levels_are_inferred_from_the_yaml_change_unless_named() {
+    let events = Spec::create(
+        create_spec(),
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+    let spec = Spec::from_events(events).unwrap();
+    assert_eq!(spec.semver.to_string(), "1.0.0");
+
+    // Changed values are patches, added keys minor and removed keys major
+    let (spec, semver) = bump(spec, "rules:\n  limit: 20\n", None);
+    assert_eq!(semver, "1.0.1");
+    let (spec, semver) = bump(spec, "rules:\n  limit: 20\n  burst: 5\n", None);
+    assert_eq!(semver, "1.1.0");
+    let (spec, semver) = bump(spec, "rules:\n  burst: 5\n", None);
+    assert_eq!(semver, "2.0.0");
+
+    let (spec, semver) = bump(spec, "rules:\n  burst: 6\n", Some(BumpLevel::Major));
+    assert_eq!(semver, "3.0.0");
+    let (_, semver) = bump(spec, "rules: []\n", Some(BumpLevel::Patch));
+    assert_eq!(semver, "3.0.1");
+}
+
+async fn get(router: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn version_ranges_resolve_to_the_latest_compatible_published_version() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+    let projection_store = Arc::new(projection_store);
+
+    let repository = SpecRepository::new(event_store.clone());
+    let envelopes = repository
+        .create(create_spec(), EventMetadata::default())
+        .await
+        .unwrap();
+    let spec_id = envelopes[0].aggregate_id;
+
+    let publish = |version| {
+        SpecCommand::Publish(PublishSpec {
+            spec_id,
+            version: Some(version),
+            published_by: "alice@example.com".to_string(),
+        })
+    };
+
+    // 1.0.0 and 1.1.0 are published, 1.1.1 is not, and 2.0.0 is
+    for command in [
+        publish(1),
+        update(spec_id, "rules:\n  limit: 10\n  burst: 5\n", None),
+        publish(2),
+        update(spec_id, "rules:\n  limit: 10\n  burst: 6\n", None),
+        update(spec_id, "rules:\n  burst: 6\n", None),
+        publish(4),
+    ] {
+        repository
+            .execute(spec_id, command, None, EventMetadata::default())
+            .await
+            .unwrap();
+    }
+
+    for (_, envelope) in event_store.get_all_events(0, 100).await.unwrap() {
+        projection_store.apply_event(&envelope).await.unwrap();
+    }
+
+    let router = create_router(AppState {
+        event_store: event_store.clone(),
+        projection_store,
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store),
+        authenticator: Authenticator::default(),
+    });
+
+    let (status, body) = get(&router, &format!("/specs/{spec_id}/versions/^1")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["version"], 2);
+    assert_eq!(body["semver"], "1.1.0");
+
+    let (_, body) = get(&router, &format!("/specs/{spec_id}/versions/~1.0")).await;
+    assert_eq!(body["semver"], "1.0.0");
+
+    let (_, body) = get(&router, &format!("/specs/{spec_id}/versions/^2.0")).await;
+    assert_eq!(body["version"], 4);
+    assert_eq!(body["semver"], "2.0.0");
+
+    let (status, _) = get(&router, &format!("/specs/{spec_id}/versions/^3")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Integer versions still address a single revision
+    let (_, body) = get(&router, &format!("/specs/{spec_id}/versions/3")).await;
+    assert_eq!(body["semver"], "1.1.1");
+}
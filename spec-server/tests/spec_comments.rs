@@ -0,0 +1,336 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        aggregates::Spec,
+        commands::{AddComment, CreateSpec, EditComment, ResolveComment, SpecCommand},
+        errors::DomainError,
+        events::SpecEvent,
+        validation::{ValidationPolicy, ValidatorPipeline},
+        value_objects::{CommentAnchor, LineRange, ValidationError},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const CONTENT: &str = "rules:\n  limit: 10\n  burst: 5\n";
+
+fn create_spec() -> Spec {
+    let events = Spec::create(
+        CreateSpec {
+            name: "checkout-limits".to_string(),
+            content: CONTENT.to_string(),
+            description: None,
+            schema: None,
+            labels: BTreeMap::new(),
+            template: None,
+            created_by: "alice@example.com".to_string(),
+        },
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+    Spec::from_events(events).unwrap()
+}
+
+fn comment(spec: &Spec, parent_id: Option<Uuid>, anchor: Option<CommentAnchor>) -> SpecCommand {
+    SpecCommand::AddComment(AddComment {
+        spec_id: spec.id,
+        version: None,
+        anchor,
+        parent_id,
+        body: "Is 10 enough for the sale?".to_string(),
+        author: "bob@example.com".to_string(),
+    })
+}
+
+/// Apply `command`, returning the spec and the id of any comment it added
+fn handle(spec: Spec, command: SpecCommand) -> (Spec, Option<Uuid>) {
+    let events = spec.handle_command(command).unwrap();
+    let comment_id = match &events[0] {
+        SpecEvent::CommentAdded(e) => Some(e.comment_id),
+        _ => None,
+    };
+    (events.iter().fold(spec, Spec::apply_event), comment_id)
+}
+
+#[test]
+fn comments_thread_without_changing_the_content() {
+    let spec = create_spec();
+
+    let anchor = CommentAnchor {
+        path: Some("/rules/limit".to_string()),
+        lines: Some(LineRange { start: 2, end: 2 }),
+    };
+    let (spec, thread) = handle(spec.clone(), comment(&spec, None, Some(anchor)));
+    let thread = thread.unwrap();
+    assert_eq!(spec.head_version.as_u32(), 1);
+
+    // Replies are attached to the thread, even when answering a reply
+    let (spec, reply) = handle(spec.clone(), comment(&spec, Some(thread), None));
+    let events = spec.handle_command(comment(&spec, reply, None)).unwrap();
+    assert!(matches!(
+        &events[0],
+        SpecEvent::CommentAdded(e) if e.parent_id == Some(thread) && e.version == 1
+    ));
+
+    assert!(matches!(
+        spec.handle_command(SpecCommand::EditComment(EditComment {
+            spec_id: spec.id,
+            comment_id: thread,
+            body: "Never mind".to_string(),
+            edited_by: "alice@example.com".to_string(),
+        })),
+        Err(DomainError::NotCommentAuthor { author, .. }) if author == "bob@example.com"
+    ));
+
+    let resolve = |comment_id| {
+        SpecCommand::ResolveComment(ResolveComment {
+            spec_id: spec.id,
+            comment_id,
+            resolved_by: "alice@example.com".to_string(),
+        })
+    };
+    assert!(matches!(
+        spec.handle_command(resolve(reply.unwrap())),
+        Err(DomainError::InvalidComment(_))
+    ));
+    let (spec, _) = handle(spec.clone(), resolve(thread));
+    assert!(matches!(
+        spec.handle_command(resolve(thread)),
+        Err(DomainError::InvalidComment(_))
+    ));
+    assert!(matches!(
+        spec.handle_command(comment(&spec, Some(thread), None)),
+        Err(DomainError::InvalidComment(_))
+    ));
+    assert!(matches!(
+        spec.handle_command(resolve(Uuid::new_v4())),
+        Err(DomainError::CommentNotFound(_))
+    ));
+}
+
+#[test]
+fn comments_must_say_something_about_what_exists() {
+    let spec = create_spec();
+
+    let mut empty = comment(&spec, None, None);
+    if let SpecCommand::AddComment(add) = &mut empty {
+        add.body = "  ".to_string();
+    }
+    assert!(matches!(
+        spec.handle_command(empty),
+        Err(DomainError::InvalidComment(_))
+    ));
+
+    for anchor in [
+        CommentAnchor {
+            path: None,
+            lines: Some(LineRange { start: 2, end: 9 }),
+        },
+        CommentAnchor {
+            path: Some("/rules/window".to_string()),
+            lines: None,
+        },
+        CommentAnchor {
+            path: Some("rules".to_string()),
+            lines: None,
+        },
+    ] {
+        assert!(matches!(
+            spec.handle_command(comment(&spec, None, Some(anchor))),
+            Err(DomainError::ValidationError(
+                ValidationError::InvalidAnchor(_)
+            ))
+        ));
+    }
+
+    let (spec, thread) = handle(spec.clone(), comment(&spec, None, None));
+    let anchored_reply = comment(
+        &spec,
+        thread,
+        Some(CommentAnchor {
+            path: Some("/rules".to_string()),
+            lines: None,
+        }),
+    );
+    assert!(matches!(
+        spec.handle_command(anchored_reply),
+        Err(DomainError::InvalidComment(_))
+    ));
+}
+
+struct Server {
+    router: Router,
+    event_store: Arc<SqliteEventStore>,
+    projection_store: Arc<ProjectionStore>,
+    /// Events applied to the projections so far
+    applied: i64,
+}
+
+impl Server {
+    async fn new(dir: &TempDir) -> Self {
+        let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+        let event_store = SqliteEventStore::new(&url).await.unwrap();
+        event_store.init_schema().await.unwrap();
+        let event_store = Arc::new(event_store);
+        let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+        projection_store.init_schema().await.unwrap();
+        let projection_store = Arc::new(projection_store);
+
+        let repository = SpecRepository::new(event_store.clone());
+        let router = create_router(AppState {
+            event_store: event_store.clone(),
+            projection_store: projection_store.clone(),
+            releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+            repository,
+            tenants: TenantRegistry::new(event_store.clone()),
+            authenticator: Authenticator::default(),
+        });
+
+        Self {
+            router,
+            event_store,
+            projection_store,
+            applied: 0,
+        }
+    }
+
+    /// Send a request, then bring the projections up to date
+    async fn send(&mut self, method: Method, uri: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        for (_, envelope) in self
+            .event_store
+            .get_all_events(self.applied, 100)
+            .await
+            .unwrap()
+        {
+            self.projection_store.apply_event(&envelope).await.unwrap();
+            self.applied += 1;
+        }
+
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+}
+
+#[tokio::test]
+async fn threads_are_listed_with_their_replies() {
+    let dir = TempDir::new().unwrap();
+    let mut server = Server::new(&dir).await;
+
+    let (_, created) = server
+        .send(
+            Method::POST,
+            "/specs",
+            json!({ "name": "checkout-limits", "content": CONTENT }),
+        )
+        .await;
+    let comments = format!("/specs/{}/comments", created["id"].as_str().unwrap());
+
+    let (status, thread) = server
+        .send(
+            Method::POST,
+            &comments,
+            json!({ "body": "Is 10 enough?", "anchor": { "path": "/rules/limit" } }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(thread["version"], 1);
+    let thread_id = thread["id"].as_str().unwrap().to_string();
+
+    let (status, _) = server
+        .send(
+            Method::POST,
+            &comments,
+            json!({ "body": "Line 7?", "anchor": { "lines": { "start": 7, "end": 7 } } }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = server
+        .send(
+            Method::POST,
+            &comments,
+            json!({ "body": "Raised to 20", "parent_id": thread_id }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = server
+        .send(
+            Method::PUT,
+            &format!("{comments}/{thread_id}"),
+            json!({ "body": "Is 10 enough for the sale?" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    server
+        .send(Method::POST, &comments, json!({ "body": "Add a window" }))
+        .await;
+
+    let (status, _) = server
+        .send(
+            Method::POST,
+            &format!("{comments}/{thread_id}/resolve"),
+            Value::Null,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, listed) = server.send(Method::GET, &comments, Value::Null).await;
+    assert_eq!(listed["threads"].as_array().unwrap().len(), 2);
+
+    let (_, resolved) = server
+        .send(
+            Method::GET,
+            &format!("{comments}?resolved=true"),
+            Value::Null,
+        )
+        .await;
+    let threads = resolved["threads"].as_array().unwrap();
+    assert_eq!(threads.len(), 1);
+    assert_eq!(threads[0]["id"], thread_id);
+    assert_eq!(threads[0]["body"], "Is 10 enough for the sale?");
+    assert!(threads[0]["edited_at"].is_string());
+    assert_eq!(threads[0]["anchor"]["path"], "/rules/limit");
+    assert_eq!(threads[0]["resolved_by"], "anonymous");
+    assert_eq!(threads[0]["replies"][0]["body"], "Raised to 20");
+
+    let (_, open) = server
+        .send(
+            Method::GET,
+            &format!("{comments}?resolved=false"),
+            Value::Null,
+        )
+        .await;
+    assert_eq!(open["threads"][0]["body"], "Add a window");
+    assert!(open["threads"][0]["replies"].as_array().unwrap().is_empty());
+}
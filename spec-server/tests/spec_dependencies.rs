@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        commands::{
+            CreateSpec, DeleteSpec, DeprecateSpec, PublishSpec, SetDependencies, SpecCommand,
+        },
+        errors::DomainError,
+        events::EventMetadata,
+        value_objects::SpecDependency,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct Fixture {
+    event_store: Arc<SqliteEventStore>,
+    projection_store: Arc<ProjectionStore>,
+    repository: SpecRepository,
+}
+
+impl Fixture {
+    async fn new(dir: &TempDir) -> Self {
+        let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+        let event_store = SqliteEventStore::new(&url).await.unwrap();
+        event_store.init_schema().await.unwrap();
+        let event_store = Arc::new(event_store);
+        let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+        projection_store.init_schema().await.unwrap();
+
+        Self {
+            repository: SpecRepository::new(event_store.clone()),
+            event_store,
+            projection_store: Arc::new(projection_store),
+        }
+    }
+
+    async fn create(&self, name: &str) -> Uuid {
+        let envelopes = self
+            .repository
+            .create(
+                CreateSpec {
+                    name: name.to_string(),
+                    content: "openapi: 3.0.0\n".to_string(),
+                    description: None,
+                    schema: None,
+                    labels: BTreeMap::new(),
+                    template: None,
+                    created_by: "alice@example.com".to_string(),
+                },
+                EventMetadata::default(),
+            )
+            .await
+            .unwrap();
+        envelopes[0].aggregate_id
+    }
+
+    async fn execute(&self, spec_id: Uuid, command: SpecCommand) -> Result<(), DomainError> {
+        self.repository
+            .execute(spec_id, command, None, EventMetadata::default())
+            .await
+            .map(|_| ())
+    }
+
+    async fn depend(&self, spec_id: Uuid, on: &[(Uuid, &str)]) -> Result<(), DomainError> {
+        let dependencies = on
+            .iter()
+            .map(|(target, version)| SpecDependency {
+                spec_id: *target,
+                version: version.parse().unwrap(),
+            })
+            .collect();
+        let command = SpecCommand::SetDependencies(SetDependencies {
+            spec_id,
+            dependencies,
+            set_by: "alice@example.com".to_string(),
+        });
+        self.execute(spec_id, command).await
+    }
+
+    async fn publish(&self, spec_id: Uuid) -> Result<(), DomainError> {
+        let command = SpecCommand::Publish(PublishSpec {
+            spec_id,
+            version: Some(1),
+            published_by: "alice@example.com".to_string(),
+        });
+        self.execute(spec_id, command).await
+    }
+}
+
+#[tokio::test]
+async fn dependencies_must_exist_without_cycles() {
+    let dir = TempDir::new().unwrap();
+    let fixture = Fixture::new(&dir).await;
+
+    let workflow = fixture.create("approval-workflow").await;
+    let rules = fixture.create("regex-rules").await;
+
+    assert!(matches!(
+        fixture.depend(workflow, &[(workflow, "^1")]).await,
+        Err(DomainError::InvalidDependencies(_))
+    ));
+    assert!(fixture
+        .depend(workflow, &[(Uuid::new_v4(), "^1")])
+        .await
+        .is_err());
+
+    fixture.depend(workflow, &[(rules, "^1")]).await.unwrap();
+    assert!(matches!(
+        fixture.depend(rules, &[(workflow, "^1")]).await,
+        Err(DomainError::InvalidDependencies(_))
+    ));
+}
+
+#[tokio::test]
+async fn publishing_and_retiring_respect_the_dependency_graph() {
+    let dir = TempDir::new().unwrap();
+    let fixture = Fixture::new(&dir).await;
+
+    let workflow = fixture.create("approval-workflow").await;
+    let rules = fixture.create("regex-rules").await;
+    fixture.depend(workflow, &[(rules, "^1")]).await.unwrap();
+
+    assert!(matches!(
+        fixture.publish(workflow).await,
+        Err(DomainError::UnpublishedDependency { spec_id, .. }) if spec_id == rules
+    ));
+
+    fixture.publish(rules).await.unwrap();
+    fixture.publish(workflow).await.unwrap();
+
+    let deprecate = SpecCommand::Deprecate(DeprecateSpec {
+        spec_id: rules,
+        reason: "Superseded".to_string(),
+        successor_id: None,
+        sunset_at: None,
+        deprecated_by: "alice@example.com".to_string(),
+    });
+    let delete = SpecCommand::Delete(DeleteSpec {
+        spec_id: rules,
+        deleted_by: "alice@example.com".to_string(),
+    });
+    for command in [deprecate, delete.clone()] {
+        assert!(matches!(
+            fixture.execute(rules, command).await,
+            Err(DomainError::HasDependents(dependents)) if dependents == [workflow]
+        ));
+    }
+
+    // Once nothing published depends on it, the spec can go
+    fixture.depend(workflow, &[]).await.unwrap();
+    fixture.execute(rules, delete).await.unwrap();
+}
+
+async fn get(router: &Router, uri: &str) -> Value {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn dependencies_and_dependents_are_listed() {
+    let dir = TempDir::new().unwrap();
+    let fixture = Fixture::new(&dir).await;
+
+    let workflow = fixture.create("approval-workflow").await;
+    let rules = fixture.create("regex-rules").await;
+    let limits = fixture.create("rate-limits").await;
+    fixture
+        .depend(workflow, &[(rules, "^1.2"), (limits, "=1.0.0")])
+        .await
+        .unwrap();
+
+    for (_, envelope) in fixture.event_store.get_all_events(0, 100).await.unwrap() {
+        fixture
+            .projection_store
+            .apply_event(&envelope)
+            .await
+            .unwrap();
+    }
+
+    let router = create_router(AppState {
+        event_store: fixture.event_store.clone(),
+        projection_store: fixture.projection_store.clone(),
+        releases: ReleaseRegistry::new(fixture.event_store.clone(), fixture.repository.clone()),
+        repository: fixture.repository.clone(),
+        tenants: TenantRegistry::new(fixture.event_store.clone()),
+        authenticator: Authenticator::default(),
+    });
+
+    let body = get(&router, &format!("/specs/{workflow}/dependencies")).await;
+    let mut dependencies: Vec<(String, String)> = body["dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| {
+            (
+                d["name"].as_str().unwrap().to_string(),
+                d["version"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    dependencies.sort();
+    assert_eq!(
+        dependencies,
+        [
+            ("rate-limits".to_string(), "=1.0.0".to_string()),
+            ("regex-rules".to_string(), "^1.2".to_string()),
+        ]
+    );
+
+    let body = get(&router, &format!("/specs/{rules}/dependents")).await;
+    assert_eq!(body["dependents"].as_array().unwrap().len(), 1);
+    assert_eq!(body["dependents"][0]["spec_id"], workflow.to_string());
+    assert_eq!(body["dependents"][0]["name"], "approval-workflow");
+
+    let body = get(&router, &format!("/specs/{workflow}/dependents")).await;
+    assert!(body["dependents"].as_array().unwrap().is_empty());
+}
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        aggregates::Spec,
+        commands::{AddLabels, CreateSpec, RemoveLabels, SpecCommand},
+        errors::DomainError,
+        events::EventMetadata,
+        validation::{ValidationPolicy, ValidatorPipeline},
+        value_objects::{LabelSelector, TenantId},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+        .collect()
+}
+
+fn create_spec(name: &str, labels: BTreeMap<String, String>) -> CreateSpec {
+    CreateSpec {
+        name: name.to_string(),
+        content: "openapi: 3.0.0\n".to_string(),
+        description: None,
+        schema: None,
+        labels,
+        template: None,
+        created_by: "alice@example.com".to_string(),
+    }
+}
+
+fn add_labels(spec_id: Uuid, pairs: &[(&str, &str)]) -> SpecCommand {
+    SpecCommand::AddLabels(AddLabels {
+        spec_id,
+        labels: labels(pairs),
+        labeled_by: "alice@example.com".to_string(),
+    })
+}
+
+fn remove_labels(spec_id: Uuid, keys: &[&str]) -> SpecCommand {
+    SpecCommand::RemoveLabels(RemoveLabels {
+        spec_id,
+        keys: keys.iter().map(ToString::to_string).collect(),
+        unlabeled_by: "alice@example.com".to_string(),
+    })
+}
+
+#[test]
+fn selectors_match_equality_inequality_and_existence() {
+    let selector: LabelSelector = "team=payments,env!=test,owner,!legacy".parse().unwrap();
+
+    assert!(selector.matches(&labels(&[("team", "payments"), ("owner", "bob")])));
+    assert!(selector.matches(&labels(&[
+        ("team", "payments"),
+        ("env", "prod"),
+        ("owner", "bob")
+    ])));
+    assert!(!selector.matches(&labels(&[
+        ("team", "payments"),
+        ("env", "test"),
+        ("owner", "bob")
+    ])));
+    assert!(!selector.matches(&labels(&[("team", "payments")])));
+    assert!(!selector.matches(&labels(&[
+        ("team", "payments"),
+        ("owner", "bob"),
+        ("legacy", "yes")
+    ])));
+
+    assert!("=payments".parse::<LabelSelector>().is_err());
+}
+
+#[test]
+fn labels_do_not_bump_the_version() {
+    let events = Spec::create(
+        create_spec("orders-api", BTreeMap::new()),
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+    let spec = Spec::from_events(events).unwrap();
+
+    let events = spec
+        .handle_command(add_labels(spec.id, &[("team", "payments")]))
+        .unwrap();
+    let spec = events.iter().fold(spec, Spec::apply_event);
+    assert_eq!(spec.head_version.as_u32(), 1);
+    assert_eq!(spec.semver.to_string(), "1.0.0");
+    assert_eq!(spec.labels, labels(&[("team", "payments")]));
+
+    assert!(matches!(
+        spec.handle_command(add_labels(spec.id, &[("team", "payments")])),
+        Err(DomainError::InvalidLabels(_))
+    ));
+    assert!(matches!(
+        spec.handle_command(remove_labels(spec.id, &["env"])),
+        Err(DomainError::InvalidLabels(_))
+    ));
+
+    let events = spec
+        .handle_command(remove_labels(spec.id, &["team"]))
+        .unwrap();
+    let spec = events.iter().fold(spec, Spec::apply_event);
+    assert!(spec.labels.is_empty());
+    assert_eq!(spec.head_version.as_u32(), 1);
+}
+
+#[tokio::test]
+async fn listings_are_filtered_by_label_selectors() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+    let projection_store = Arc::new(projection_store);
+
+    let repository = SpecRepository::new(event_store.clone());
+    for (name, pairs) in [
+        ("orders-api", &[("team", "payments"), ("env", "prod")][..]),
+        ("refunds-api", &[("team", "payments"), ("env", "test")][..]),
+        ("search-api", &[("team", "discovery")][..]),
+    ] {
+        repository
+            .create(create_spec(name, labels(pairs)), EventMetadata::default())
+            .await
+            .unwrap();
+    }
+
+    // Labels added later are filtered on too
+    let envelopes = repository
+        .create(
+            create_spec("billing-api", BTreeMap::new()),
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+    let billing = envelopes[0].aggregate_id;
+    repository
+        .execute(
+            billing,
+            add_labels(billing, &[("team", "payments")]),
+            None,
+            EventMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+    for (_, envelope) in event_store.get_all_events(0, 100).await.unwrap() {
+        projection_store.apply_event(&envelope).await.unwrap();
+    }
+
+    let names = |selector: &str| {
+        let projection_store = projection_store.clone();
+        let selector: LabelSelector = selector.parse().unwrap();
+        async move {
+            let mut names: Vec<String> = projection_store
+                .list_by_state(&TenantId::default(), None, false, &selector, 100, 0)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|spec| spec.name)
+                .collect();
+            names.sort();
+            names
+        }
+    };
+
+    assert_eq!(
+        names("team=payments,env!=test").await,
+        ["billing-api", "orders-api"]
+    );
+    assert_eq!(
+        names("team=payments,env").await,
+        ["orders-api", "refunds-api"]
+    );
+    assert_eq!(names("!env").await, ["billing-api", "search-api"]);
+    assert_eq!(names("").await.len(), 4);
+
+    let router = create_router(AppState {
+        event_store: event_store.clone(),
+        projection_store: projection_store.clone(),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store),
+        authenticator: Authenticator::default(),
+    });
+    let request = Request::builder()
+        .uri("/specs?selector=team%3Ddiscovery")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let listed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(listed["total"], 1);
+    assert_eq!(listed["specs"][0]["name"], "search-api");
+
+    let request = Request::builder()
+        .uri("/specs?selector=%3Dpayments")
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
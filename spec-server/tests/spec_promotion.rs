@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use spec_server::domain::{
+    aggregates::{ReviewPolicy, Spec},
+    commands::{
+        ApproveSpec, CreateSpec, DeprecateSpec, PromoteSpec, PublishSpec, RejectSpec,
+        RequestReview, SpecCommand,
+    },
+    errors::DomainError,
+    events::SpecEvent,
+    validation::{ValidationPolicy, ValidatorPipeline},
+};
+
+/// A draft spec at version 1 whose reviews need one approval
+fn draft() -> Spec {
+    let events = Spec::create(
+        CreateSpec {
+            name: "orders-api".to_string(),
+            content: "openapi: 3.0.0\n".to_string(),
+            description: None,
+            schema: None,
+            labels: BTreeMap::new(),
+            template: None,
+            created_by: "alice@example.com".to_string(),
+        },
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+
+    Spec::from_events(events)
+        .unwrap()
+        .with_review_policy(ReviewPolicy {
+            required_approvals: 1,
+        })
+}
+
+/// Handle `command` and apply the events it produced
+fn run(spec: Spec, command: SpecCommand) -> Spec {
+    let events = spec.handle_command(command).unwrap();
+    events.iter().fold(spec, Spec::apply_event)
+}
+
+fn promote(spec: &Spec, channel: &str) -> Result<Vec<SpecEvent>, DomainError> {
+    spec.handle_command(SpecCommand::Promote(PromoteSpec {
+        spec_id: spec.id,
+        version: 1,
+        channel: channel.to_string(),
+        promoted_by: "alice@example.com".to_string(),
+    }))
+}
+
+fn request_review(spec: Spec) -> Spec {
+    let spec_id = spec.id;
+    run(
+        spec,
+        SpecCommand::RequestReview(RequestReview {
+            spec_id,
+            requested_by: "alice@example.com".to_string(),
+        }),
+    )
+}
+
+#[test]
+fn draft_versions_cannot_be_promoted() {
+    let spec = draft();
+
+    assert!(matches!(
+        promote(&spec, "dev"),
+        Err(DomainError::InvalidPromotion(_))
+    ));
+}
+
+#[test]
+fn versions_under_review_or_rejected_cannot_be_promoted() {
+    let spec = request_review(draft());
+    assert!(matches!(
+        promote(&spec, "dev"),
+        Err(DomainError::InvalidPromotion(_))
+    ));
+
+    let spec_id = spec.id;
+    let spec = run(
+        spec,
+        SpecCommand::Reject(RejectSpec {
+            spec_id,
+            reason: "Missing examples".to_string(),
+            rejected_by: "bob@example.com".to_string(),
+        }),
+    );
+    assert!(matches!(
+        promote(&spec, "dev"),
+        Err(DomainError::InvalidPromotion(_))
+    ));
+}
+
+#[test]
+fn approved_versions_can_be_promoted_before_they_are_published() {
+    let spec = request_review(draft());
+    let spec_id = spec.id;
+    let spec = run(
+        spec,
+        SpecCommand::Approve(ApproveSpec {
+            spec_id,
+            comment: None,
+            approved_by: "bob@example.com".to_string(),
+        }),
+    );
+
+    assert!(promote(&spec, "dev").is_ok());
+}
+
+#[test]
+fn published_versions_can_be_promoted_until_deprecated() {
+    let spec = request_review(draft());
+    let spec_id = spec.id;
+    let spec = run(
+        spec,
+        SpecCommand::Approve(ApproveSpec {
+            spec_id,
+            comment: None,
+            approved_by: "bob@example.com".to_string(),
+        }),
+    );
+    let spec = run(
+        spec,
+        SpecCommand::Publish(PublishSpec {
+            spec_id,
+            version: Some(1),
+            published_by: "alice@example.com".to_string(),
+        }),
+    );
+    assert!(promote(&spec, "prod").is_ok());
+
+    let spec = run(
+        spec,
+        SpecCommand::Deprecate(DeprecateSpec {
+            spec_id,
+            reason: "Replaced".to_string(),
+            successor_id: None,
+            sunset_at: None,
+            deprecated_by: "alice@example.com".to_string(),
+        }),
+    );
+    assert!(matches!(
+        promote(&spec, "prod"),
+        Err(DomainError::InvalidStateForOperation(_))
+    ));
+}
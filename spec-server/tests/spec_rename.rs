@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::Duration;
+use spec_server::{
+    domain::{
+        commands::{CreateSpec, RenameSpec, SpecCommand},
+        errors::DomainError,
+        events::{EventMetadata, SpecEvent},
+        value_objects::TenantId,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore, repositories::SpecRepository,
+    },
+};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn event_store(dir: &TempDir, alias_period: Duration) -> Arc<SqliteEventStore> {
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+    let event_store = SqliteEventStore::new(&url)
+        .await
+        .unwrap()
+        .with_rename_alias_period(alias_period);
+    event_store.init_schema().await.unwrap();
+    Arc::new(event_store)
+}
+
+async fn create(repository: &SpecRepository, name: &str) -> Result<Uuid, DomainError> {
+    let envelopes = repository
+        .create(
+            CreateSpec {
+                name: name.to_string(),
+                content: "openapi: 3.0.0\n".to_string(),
+                description: None,
+                schema: None,
+                labels: BTreeMap::new(),
+                template: None,
+                created_by: "alice@example.com".to_string(),
+            },
+            EventMetadata::default(),
+        )
+        .await?;
+
+    match &envelopes[0].event {
+        SpecEvent::Created(e) => Ok(e.spec_id),
+        event => panic!("expected created, got {event:?}"),
+    }
+}
+
+async fn rename(
+    repository: &SpecRepository,
+    spec_id: Uuid,
+    new_name: &str,
+) -> Result<(), DomainError> {
+    repository
+        .execute(
+            spec_id,
+            SpecCommand::Rename(RenameSpec {
+                spec_id,
+                new_name: new_name.to_string(),
+                renamed_by: "alice@example.com".to_string(),
+            }),
+            None,
+            EventMetadata::default(),
+        )
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn old_names_resolve_to_the_renamed_spec_during_the_alias_period() {
+    let dir = TempDir::new().unwrap();
+    let event_store = event_store(&dir, Duration::days(30)).await;
+    let repository = SpecRepository::new(event_store.clone());
+    let tenant = TenantId::default();
+
+    let spec_id = create(&repository, "orders").await.unwrap();
+    rename(&repository, spec_id, "commerce/orders-api")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        event_store
+            .resolve_name(&tenant, "commerce/orders-api")
+            .await
+            .unwrap(),
+        Some(spec_id)
+    );
+    assert_eq!(
+        event_store.resolve_name(&tenant, "orders").await.unwrap(),
+        Some(spec_id)
+    );
+
+    // The alias keeps the old name from being taken
+    assert!(matches!(
+        create(&repository, "orders").await,
+        Err(DomainError::DuplicateSpecName(_))
+    ));
+
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+    for (_, envelope) in event_store.get_all_events(0, 100).await.unwrap() {
+        projection_store.apply_event(&envelope).await.unwrap();
+    }
+    let spec = projection_store
+        .get_by_id(&tenant, spec_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(spec.name, "commerce/orders-api");
+}
+
+#[tokio::test]
+async fn old_names_are_released_once_the_alias_period_is_over() {
+    let dir = TempDir::new().unwrap();
+    let event_store = event_store(&dir, Duration::zero()).await;
+    let repository = SpecRepository::new(event_store.clone());
+
+    let spec_id = create(&repository, "orders").await.unwrap();
+    rename(&repository, spec_id, "orders-api").await.unwrap();
+
+    assert_eq!(
+        event_store
+            .resolve_name(&TenantId::default(), "orders")
+            .await
+            .unwrap(),
+        None
+    );
+    assert!(create(&repository, "orders").await.is_ok());
+}
+
+#[tokio::test]
+async fn renames_to_taken_or_invalid_names_are_refused() {
+    let dir = TempDir::new().unwrap();
+    let repository = SpecRepository::new(event_store(&dir, Duration::days(30)).await);
+
+    let orders = create(&repository, "orders-api").await.unwrap();
+    create(&repository, "billing-api").await.unwrap();
+
+    assert!(matches!(
+        rename(&repository, orders, "billing-api").await,
+        Err(DomainError::DuplicateSpecName(_))
+    ));
+    assert!(matches!(
+        rename(&repository, orders, "orders-api").await,
+        Err(DomainError::DuplicateSpecName(_))
+    ));
+    assert!(rename(&repository, orders, "").await.is_err());
+}
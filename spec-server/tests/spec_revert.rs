@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        aggregates::Spec,
+        commands::{CreateSpec, RevertSpec, SpecCommand, UpdateSpec},
+        errors::DomainError,
+        events::{EventMetadata, SpecEvent},
+        validation::{ValidationPolicy, ValidatorPipeline},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const ORIGINAL: &str = "openapi: 3.0.0\ninfo:\n  title: Orders\n";
+const BROKEN: &str = "openapi: 3.0.0\ninfo:\n  title: Orders\npaths: {}\n";
+
+fn create_spec() -> CreateSpec {
+    CreateSpec {
+        name: "orders-api".to_string(),
+        content: ORIGINAL.to_string(),
+        description: None,
+        schema: None,
+        labels: BTreeMap::new(),
+        template: None,
+        created_by: "alice@example.com".to_string(),
+    }
+}
+
+fn update(spec_id: Uuid) -> SpecCommand {
+    SpecCommand::Update(UpdateSpec {
+        spec_id,
+        content: BROKEN.to_string(),
+        description: None,
+        bump: None,
+        schema: None,
+        updated_by: "alice@example.com".to_string(),
+    })
+}
+
+fn revert(spec: &Spec, to_version: u32) -> SpecCommand {
+    SpecCommand::Revert(RevertSpec {
+        spec_id: spec.id,
+        to_version,
+        reason: Some("Broke the checkout".to_string()),
+        reverted_by: "alice@example.com".to_string(),
+    })
+}
+
+#[test]
+fn reverting_restores_old_content_as_a_new_version() {
+    let events = Spec::create(
+        create_spec(),
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+    let spec = Spec::from_events(events).unwrap();
+    let events = spec.handle_command(update(spec.id)).unwrap();
+    let spec = events.iter().fold(spec, Spec::apply_event);
+
+    let events = spec.handle_command(revert(&spec, 1)).unwrap();
+    match &events[0] {
+        SpecEvent::Reverted(e) => {
+            assert_eq!(e.version, 3);
+            assert_eq!(e.reverted_from, 1);
+            assert_eq!(e.content, ORIGINAL);
+            assert_eq!(e.reason.as_deref(), Some("Broke the checkout"));
+        }
+        event => panic!("expected reverted, got {event:?}"),
+    }
+
+    let spec = events.iter().fold(spec, Spec::apply_event);
+    assert_eq!(spec.head_version.as_u32(), 3);
+    assert_eq!(spec.content.as_str(), ORIGINAL);
+    assert_eq!(spec.revisions.len(), 3);
+}
+
+#[test]
+fn reverting_to_the_head_or_an_unknown_version_is_refused() {
+    let events = Spec::create(
+        create_spec(),
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+    let spec = Spec::from_events(events).unwrap();
+
+    assert!(matches!(
+        spec.handle_command(revert(&spec, 1)),
+        Err(DomainError::InvalidRevertTarget(1))
+    ));
+    assert!(matches!(
+        spec.handle_command(revert(&spec, 7)),
+        Err(DomainError::VersionNotFound(7))
+    ));
+}
+
+async fn post(router: &Router, uri: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn rest_reverts_are_recorded_in_the_version_history() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+    let projection_store = Arc::new(projection_store);
+
+    let repository = SpecRepository::new(event_store.clone());
+    let envelopes = repository
+        .create(create_spec(), EventMetadata::default())
+        .await
+        .unwrap();
+    let spec_id = envelopes[0].aggregate_id;
+    repository
+        .execute(spec_id, update(spec_id), None, EventMetadata::default())
+        .await
+        .unwrap();
+
+    let router = create_router(AppState {
+        event_store: event_store.clone(),
+        projection_store: projection_store.clone(),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store.clone()),
+        authenticator: Authenticator::default(),
+    });
+
+    let (status, body) = post(
+        &router,
+        &format!("/specs/{spec_id}/revert"),
+        json!({ "to_version": 1, "reason": "Broke the checkout" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["version"], 3);
+    assert_eq!(body["reverted_from"], 1);
+
+    let (status, _) = post(
+        &router,
+        &format!("/specs/{spec_id}/revert"),
+        json!({ "to_version": 9 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    for (_, envelope) in event_store.get_all_events(0, 100).await.unwrap() {
+        projection_store.apply_event(&envelope).await.unwrap();
+    }
+
+    let request = Request::builder()
+        .uri(format!("/specs/{spec_id}/versions/3"))
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let revision: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(revision["content"], ORIGINAL);
+}
@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::value_objects::{ParameterType, TemplateDocument, TemplateParameter},
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+const BODY: &str = "service: {{ service }}\nlimit: {{ limit }}\n";
+
+fn parameter(name: &str, kind: ParameterType, default: Option<Value>) -> TemplateParameter {
+    TemplateParameter {
+        name: name.to_string(),
+        kind,
+        description: None,
+        default,
+    }
+}
+
+fn values(value: Value) -> BTreeMap<String, Value> {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn templates_render_typed_parameters_with_defaults() {
+    let template = TemplateDocument::new(
+        BODY.to_string(),
+        vec![
+            parameter("service", ParameterType::String, None),
+            parameter("limit", ParameterType::Integer, Some(json!(100))),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        template
+            .render(&values(json!({ "service": "checkout" })))
+            .unwrap(),
+        "service: checkout\nlimit: 100\n"
+    );
+    assert_eq!(
+        template
+            .render(&values(json!({ "service": "checkout", "limit": 5 })))
+            .unwrap(),
+        "service: checkout\nlimit: 5\n"
+    );
+
+    for invalid in [
+        json!({}),
+        json!({ "service": "checkout", "limit": "many" }),
+        json!({ "service": "checkout", "burst": 5 }),
+    ] {
+        assert!(
+            template.render(&values(invalid.clone())).is_err(),
+            "{invalid}"
+        );
+    }
+}
+
+#[test]
+fn templates_must_declare_their_placeholders() {
+    assert!(TemplateDocument::new(
+        BODY.to_string(),
+        vec![parameter("service", ParameterType::String, None)],
+    )
+    .is_err());
+    assert!(TemplateDocument::new(
+        "limit: {{ limit }}\n".to_string(),
+        vec![parameter(
+            "limit",
+            ParameterType::Integer,
+            Some(json!("ten"))
+        )],
+    )
+    .is_err());
+    assert!(TemplateDocument::new(
+        "limit: {{ limit\n".to_string(),
+        vec![parameter("limit", ParameterType::Integer, None)],
+    )
+    .is_err());
+}
+
+struct Server {
+    router: Router,
+    event_store: Arc<SqliteEventStore>,
+    projection_store: Arc<ProjectionStore>,
+    /// Events applied to the projections so far
+    applied: i64,
+}
+
+impl Server {
+    async fn new(dir: &TempDir) -> Self {
+        let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+        let event_store = SqliteEventStore::new(&url).await.unwrap();
+        event_store.init_schema().await.unwrap();
+        let event_store = Arc::new(event_store);
+        let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+        projection_store.init_schema().await.unwrap();
+        let projection_store = Arc::new(projection_store);
+
+        let repository = SpecRepository::new(event_store.clone());
+        let router = create_router(AppState {
+            event_store: event_store.clone(),
+            projection_store: projection_store.clone(),
+            releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+            repository,
+            tenants: TenantRegistry::new(event_store.clone()),
+            authenticator: Authenticator::default(),
+        });
+
+        Self {
+            router,
+            event_store,
+            projection_store,
+            applied: 0,
+        }
+    }
+
+    /// Send a request, then bring the projections up to date
+    async fn send(&mut self, method: Method, uri: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        for (_, envelope) in self
+            .event_store
+            .get_all_events(self.applied, 100)
+            .await
+            .unwrap()
+        {
+            self.projection_store.apply_event(&envelope).await.unwrap();
+            self.applied += 1;
+        }
+
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+}
+
+#[tokio::test]
+async fn specs_rendered_from_a_template_are_tracked_across_its_versions() {
+    let dir = TempDir::new().unwrap();
+    let mut server = Server::new(&dir).await;
+
+    let parameters = json!([
+        { "name": "service", "type": "string" },
+        { "name": "limit", "type": "integer", "default": 100 },
+    ]);
+    let (status, template) = server
+        .send(
+            Method::POST,
+            "/templates",
+            json!({ "name": "rate-limit", "body": BODY, "parameters": parameters }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let template_id = template["id"].as_str().unwrap().to_string();
+
+    let (status, preview) = server
+        .send(
+            Method::POST,
+            &format!("/templates/{template_id}/preview"),
+            json!({ "parameters": { "service": "checkout" } }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(preview["content"], "service: checkout\nlimit: 100\n");
+
+    let (status, _) = server
+        .send(
+            Method::POST,
+            "/specs/from-template",
+            json!({ "name": "checkout-limits", "template_id": template_id }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, created) = server
+        .send(
+            Method::POST,
+            "/specs/from-template",
+            json!({
+                "name": "checkout-limits",
+                "template_id": template_id,
+                "parameters": { "service": "checkout", "limit": 5 },
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let spec_id = created["id"].as_str().unwrap().to_string();
+
+    let (_, spec) = server
+        .send(Method::GET, &format!("/specs/{spec_id}"), Value::Null)
+        .await;
+    assert_eq!(spec["content"], "service: checkout\nlimit: 5\n");
+
+    let specs = format!("/templates/{template_id}/specs");
+    let (_, derived) = server.send(Method::GET, &specs, Value::Null).await;
+    assert_eq!(derived["specs"][0]["id"], spec_id);
+    assert_eq!(derived["specs"][0]["template_version"], 1);
+    assert_eq!(derived["specs"][0]["outdated"], false);
+
+    let (status, _) = server
+        .send(
+            Method::PUT,
+            &format!("/templates/{template_id}"),
+            json!({ "body": format!("{BODY}burst: 10\n"), "parameters": parameters }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, derived) = server.send(Method::GET, &specs, Value::Null).await;
+    assert_eq!(derived["head_version"], 2);
+    assert_eq!(derived["specs"][0]["outdated"], true);
+}
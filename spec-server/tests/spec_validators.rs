@@ -0,0 +1,151 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::validation::{
+        DuplicateKeys, ForbiddenKeys, MaxNestingDepth, Severity, SpecValidator, TabIndentation,
+        ValidatorRegistry,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+/// A literal block whose second line starts with a tab after its indentation
+const TABBED: &str = "script: |\n  run:\n  \techo done\n";
+
+fn run(validator: &dyn SpecValidator, content: &str) -> Vec<(Severity, Option<String>)> {
+    let document = serde_yaml::from_str::<serde_yaml::Value>(content).ok();
+    validator
+        .validate(content, document.as_ref())
+        .into_iter()
+        .map(|issue| {
+            (
+                issue.severity,
+                issue.path.or(issue.line.map(|l| l.to_string())),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn built_in_validators_report_where_the_problem_is() {
+    assert_eq!(
+        run(&DuplicateKeys, "rules:\n  limit: 1\n  limit: 2\n"),
+        [(Severity::Error, Some("/rules/limit".to_string()))]
+    );
+    assert!(run(&DuplicateKeys, "rules:\n  limit: 1\n").is_empty());
+
+    assert_eq!(
+        run(&TabIndentation, TABBED),
+        [(Severity::Warning, Some("3".to_string()))]
+    );
+
+    assert_eq!(
+        run(&MaxNestingDepth(2), "a:\n  b:\n    c: 1\n"),
+        [(Severity::Error, Some("/a/b".to_string()))]
+    );
+    assert!(run(&MaxNestingDepth(2), "a:\n  b: 1\n").is_empty());
+
+    let forbidden = ForbiddenKeys(BTreeSet::from(["password".to_string()]));
+    assert_eq!(
+        run(&forbidden, "db:\n  - password: hunter2\n"),
+        [(Severity::Error, Some("/db/0/password".to_string()))]
+    );
+}
+
+#[test]
+fn registries_add_validators_for_specs_with_matching_labels() {
+    let registry: ValidatorRegistry = "duplicate_keys;kind=policy:forbidden_keys=password|token"
+        .parse()
+        .unwrap();
+    let policy = BTreeMap::from([("kind".to_string(), "policy".to_string())]);
+
+    let content = "auth:\n  token: abc\n";
+    assert!(registry
+        .pipeline_for(&BTreeMap::new())
+        .run(content)
+        .is_empty());
+    assert_eq!(registry.pipeline_for(&policy).run(content).len(), 1);
+
+    // Every pipeline checks the YAML syntax
+    assert!(registry
+        .pipeline_for(&BTreeMap::new())
+        .run("rules: [")
+        .iter()
+        .any(|issue| issue.validator == "yaml_syntax"));
+
+    assert!("max_nesting_depth=deep"
+        .parse::<ValidatorRegistry>()
+        .is_err());
+    assert!("spell_check".parse::<ValidatorRegistry>().is_err());
+    assert!("duplicate_keys;forbidden_keys=token"
+        .parse::<ValidatorRegistry>()
+        .is_err());
+}
+
+async fn create(router: &Router, name: &str, content: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/specs")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            json!({ "name": name, "content": content }).to_string(),
+        ))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn warnings_are_returned_and_errors_reject_the_content() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(event_store.clone())
+        .with_validators("duplicate_keys,tab_indentation".parse().unwrap());
+    let router = create_router(AppState {
+        event_store: event_store.clone(),
+        projection_store: Arc::new(projection_store),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store),
+        authenticator: Authenticator::default(),
+    });
+
+    let (status, created) = create(&router, "deploy-script", TABBED).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["warnings"][0]["validator"], "tab_indentation");
+    assert_eq!(created["warnings"][0]["severity"], "warning");
+    assert_eq!(created["warnings"][0]["line"], 3);
+
+    let (status, error) = create(&router, "limits", "limit: 1\nlimit: 2\n").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["issues"][0]["validator"], "duplicate_keys");
+    assert_eq!(error["issues"][0]["severity"], "error");
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, TimeZone, Utc};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        rest::{create_router, AppState},
+    },
+    domain::{
+        aggregates::Spec,
+        commands::{CreateSpec, DeprecateSpec, PublishSpec, SpecCommand},
+        errors::DomainError,
+        events::{EventMetadata, SpecState},
+        validation::{ValidationPolicy, ValidatorPipeline},
+        value_objects::TenantId,
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn create_spec(name: &str) -> CreateSpec {
+    CreateSpec {
+        name: name.to_string(),
+        content: "openapi: 3.0.0\n".to_string(),
+        description: None,
+        schema: None,
+        labels: BTreeMap::new(),
+        template: None,
+        created_by: "alice@example.com".to_string(),
+    }
+}
+
+fn publish(spec_id: Uuid) -> SpecCommand {
+    SpecCommand::Publish(PublishSpec {
+        spec_id,
+        version: Some(1),
+        published_by: "alice@example.com".to_string(),
+    })
+}
+
+fn deprecate(
+    spec_id: Uuid,
+    successor_id: Option<Uuid>,
+    sunset_at: Option<chrono::DateTime<Utc>>,
+) -> SpecCommand {
+    SpecCommand::Deprecate(DeprecateSpec {
+        spec_id,
+        reason: "Replaced by orders-api v2".to_string(),
+        successor_id,
+        sunset_at,
+        deprecated_by: "alice@example.com".to_string(),
+    })
+}
+
+#[test]
+fn deprecations_need_a_published_spec_another_successor_and_a_future_sunset() {
+    let events = Spec::create(
+        create_spec("orders-api"),
+        &ValidationPolicy::default(),
+        &ValidatorPipeline::default(),
+    )
+    .unwrap();
+    let spec = Spec::from_events(events).unwrap();
+    let tomorrow = Utc::now() + Duration::days(1);
+
+    assert!(matches!(
+        spec.handle_command(deprecate(spec.id, None, None)),
+        Err(DomainError::InvalidStateTransition {
+            from: SpecState::Draft,
+            to: SpecState::Deprecated
+        })
+    ));
+
+    let events = spec.handle_command(publish(spec.id)).unwrap();
+    let spec = events.iter().fold(spec, Spec::apply_event);
+
+    assert!(matches!(
+        spec.handle_command(deprecate(spec.id, Some(spec.id), Some(tomorrow))),
+        Err(DomainError::InvalidDeprecation(_))
+    ));
+    assert!(matches!(
+        spec.handle_command(deprecate(
+            spec.id,
+            None,
+            Some(Utc::now() - Duration::hours(1))
+        )),
+        Err(DomainError::InvalidDeprecation(_))
+    ));
+    assert!(spec
+        .handle_command(deprecate(spec.id, Some(Uuid::new_v4()), Some(tomorrow)))
+        .is_ok());
+}
+
+#[tokio::test]
+async fn deprecated_specs_carry_migration_guidance_until_their_sunset() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+    let event_store = Arc::new(event_store);
+    let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+    projection_store.init_schema().await.unwrap();
+    let projection_store = Arc::new(projection_store);
+
+    let repository = SpecRepository::new(event_store.clone());
+    let mut ids = Vec::new();
+    for name in ["orders-api", "orders-api-v2"] {
+        let envelopes = repository
+            .create(create_spec(name), EventMetadata::default())
+            .await
+            .unwrap();
+        ids.push(envelopes[0].aggregate_id);
+    }
+    let (spec_id, successor_id) = (ids[0], ids[1]);
+
+    let sunset_at = Utc.with_ymd_and_hms(2099, 1, 1, 0, 0, 0).unwrap();
+    for command in [
+        publish(spec_id),
+        deprecate(spec_id, Some(successor_id), Some(sunset_at)),
+    ] {
+        repository
+            .execute(spec_id, command, None, EventMetadata::default())
+            .await
+            .unwrap();
+    }
+
+    for (_, envelope) in event_store.get_all_events(0, 100).await.unwrap() {
+        projection_store.apply_event(&envelope).await.unwrap();
+    }
+
+    let router = create_router(AppState {
+        event_store: event_store.clone(),
+        projection_store: projection_store.clone(),
+        releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+        repository,
+        tenants: TenantRegistry::new(event_store.clone()),
+        authenticator: Authenticator::default(),
+    });
+    let request = Request::builder()
+        .uri(format!("/specs/{spec_id}"))
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let headers = response.headers();
+    assert!(headers
+        .get("deprecation")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .starts_with('@'));
+    assert_eq!(
+        headers.get("sunset").unwrap(),
+        "Thu, 01 Jan 2099 00:00:00 GMT"
+    );
+    assert_eq!(
+        headers.get(header::LINK).unwrap().to_str().unwrap(),
+        format!("</specs/{successor_id}>; rel=\"successor-version\"")
+    );
+
+    // The sunset processor deletes the spec only once its sunset has passed
+    assert!(projection_store
+        .list_sunset_due(Utc::now())
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        projection_store.list_sunset_due(sunset_at).await.unwrap(),
+        vec![(TenantId::default(), spec_id)]
+    );
+}
@@ -0,0 +1,367 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use spec_server::{
+    api::{
+        auth::Authenticator,
+        grpc::{
+            spec_proto::{
+                spec_service_server::SpecService, CreateSpecRequest, CreateTenantRequest,
+                GetSpecRequest, ListSpecsRequest, SuspendTenantRequest,
+            },
+            SpecServiceImpl,
+        },
+        rest::{create_router, AppState},
+    },
+    infrastructure::{
+        event_store::SqliteEventStore, projections::ProjectionStore,
+        release_registry::ReleaseRegistry, repositories::SpecRepository,
+        tenant_registry::TenantRegistry,
+    },
+};
+use tempfile::TempDir;
+use tonic::Code;
+use tower::ServiceExt;
+
+/// Alice works for payments, Bob for billing and Olivia administers tenants
+const TOKENS: &str = "alice-token:alice@example.com:payments,\
+                      bob-token:bob@example.com:billing,\
+                      olivia-token:olivia@example.com:default:admin";
+
+struct Server {
+    event_store: Arc<SqliteEventStore>,
+    projection_store: Arc<ProjectionStore>,
+    state: AppState,
+}
+
+impl Server {
+    async fn start(dir: &TempDir) -> Self {
+        let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+
+        let event_store = SqliteEventStore::new(&url).await.unwrap();
+        event_store.init_schema().await.unwrap();
+        let event_store = Arc::new(event_store);
+        let projection_store = ProjectionStore::new(&url, false).await.unwrap();
+        projection_store.init_schema().await.unwrap();
+        let projection_store = Arc::new(projection_store);
+
+        let repository = SpecRepository::new(event_store.clone());
+        let state = AppState {
+            event_store: event_store.clone(),
+            projection_store: projection_store.clone(),
+            releases: ReleaseRegistry::new(event_store.clone(), repository.clone()),
+            repository,
+            tenants: TenantRegistry::new(event_store.clone()),
+            authenticator: TOKENS.parse::<Authenticator>().unwrap(),
+        };
+
+        Self {
+            event_store,
+            projection_store,
+            state,
+        }
+    }
+
+    fn router(&self) -> Router {
+        create_router(self.state.clone())
+    }
+
+    fn grpc(&self) -> SpecServiceImpl {
+        SpecServiceImpl::new(
+            self.state.event_store.clone(),
+            self.state.projection_store.clone(),
+            self.state.repository.clone(),
+            self.state.releases.clone(),
+            self.state.tenants.clone(),
+            self.state.authenticator.clone(),
+        )
+    }
+
+    /// Apply every stored event to freshly cleared projections
+    async fn project(&self) {
+        self.projection_store.clear().await.unwrap();
+        for (_, envelope) in self.event_store.get_all_events(0, 1000).await.unwrap() {
+            self.projection_store.apply_event(&envelope).await.unwrap();
+        }
+    }
+
+    /// Send a REST request as the holder of `token`, if any
+    async fn send(
+        &self,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let body = match body {
+            Some(body) => {
+                request = request.header(header::CONTENT_TYPE, "application/json");
+                Body::from(body.to_string())
+            }
+            None => Body::empty(),
+        };
+
+        let response = self
+            .router()
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+}
+
+/// A gRPC request carrying `token`
+fn grpc_request<T>(message: T, token: &str) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    request
+        .metadata_mut()
+        .insert("authorization", format!("Bearer {token}").parse().unwrap());
+    request
+}
+
+/// Register the payments and billing tenants as the administrator
+async fn create_tenants(server: &Server) {
+    for tenant in ["payments", "billing"] {
+        let (status, _) = server
+            .send(
+                Method::POST,
+                "/tenants",
+                Some("olivia-token"),
+                Some(json!({ "id": tenant })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+}
+
+#[tokio::test]
+async fn rest_requests_only_reach_their_principals_tenant() {
+    let dir = TempDir::new().unwrap();
+    let server = Server::start(&dir).await;
+    create_tenants(&server).await;
+
+    let (status, created) = server
+        .send(
+            Method::POST,
+            "/specs",
+            Some("alice-token"),
+            Some(json!({ "name": "orders-api", "content": "openapi: 3.0.0\n" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let uri = format!("/specs/{}", created["id"].as_str().unwrap());
+    server.project().await;
+
+    let (status, _) = server
+        .send(Method::GET, &uri, Some("alice-token"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = server
+        .send(Method::GET, &uri, Some("bob-token"), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, listed) = server
+        .send(Method::GET, "/specs", Some("bob-token"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed["specs"].as_array().unwrap().len(), 0);
+
+    // Naming another tenant in a header changes nothing
+    let response = server
+        .router()
+        .oneshot(
+            Request::builder()
+                .uri(&uri)
+                .header(header::AUTHORIZATION, "Bearer bob-token")
+                .header("x-tenant-id", "payments")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let (status, _) = server.send(Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = server
+        .send(Method::GET, &uri, Some("mallory-token"), None)
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rest_tenant_administration_needs_an_administrator() {
+    let dir = TempDir::new().unwrap();
+    let server = Server::start(&dir).await;
+    create_tenants(&server).await;
+
+    let (status, _) = server
+        .send(
+            Method::POST,
+            "/tenants",
+            Some("alice-token"),
+            Some(json!({ "id": "shipping" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = server
+        .send(
+            Method::POST,
+            "/tenants/billing/suspend",
+            Some("alice-token"),
+            Some(json!({ "reason": "Unpaid" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = server
+        .send(Method::GET, "/tenants", Some("alice-token"), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = server
+        .send(
+            Method::POST,
+            "/tenants/billing/suspend",
+            Some("olivia-token"),
+            Some(json!({ "reason": "Unpaid" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // The suspended tenant's principals are refused
+    let (status, _) = server
+        .send(Method::GET, "/specs", Some("bob-token"), None)
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn grpc_requests_only_reach_their_principals_tenant() {
+    let dir = TempDir::new().unwrap();
+    let server = Server::start(&dir).await;
+    let grpc = server.grpc();
+
+    for tenant in ["payments", "billing"] {
+        grpc.create_tenant(grpc_request(
+            CreateTenantRequest {
+                id: tenant.to_string(),
+                display_name: None,
+            },
+            "olivia-token",
+        ))
+        .await
+        .unwrap();
+    }
+
+    let created = grpc
+        .create_spec(grpc_request(
+            CreateSpecRequest {
+                name: "orders-api".to_string(),
+                content: "openapi: 3.0.0\n".to_string(),
+                ..CreateSpecRequest::default()
+            },
+            "alice-token",
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    server.project().await;
+
+    let get = |token| {
+        grpc_request(
+            GetSpecRequest {
+                id: created.id.clone(),
+                ..GetSpecRequest::default()
+            },
+            token,
+        )
+    };
+    assert!(grpc.get_spec(get("alice-token")).await.is_ok());
+    assert_eq!(
+        grpc.get_spec(get("bob-token")).await.unwrap_err().code(),
+        Code::NotFound
+    );
+
+    let listed = grpc
+        .list_specs(grpc_request(ListSpecsRequest::default(), "bob-token"))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(listed.specs.is_empty());
+
+    let mut spoofed = get("bob-token");
+    spoofed
+        .metadata_mut()
+        .insert("tenant-id", "payments".parse().unwrap());
+    assert_eq!(
+        grpc.get_spec(spoofed).await.unwrap_err().code(),
+        Code::NotFound
+    );
+
+    let anonymous = tonic::Request::new(GetSpecRequest {
+        id: created.id.clone(),
+        ..GetSpecRequest::default()
+    });
+    assert_eq!(
+        grpc.get_spec(anonymous).await.unwrap_err().code(),
+        Code::Unauthenticated
+    );
+}
+
+#[tokio::test]
+async fn grpc_tenant_administration_needs_an_administrator() {
+    let dir = TempDir::new().unwrap();
+    let server = Server::start(&dir).await;
+    let grpc = server.grpc();
+
+    grpc.create_tenant(grpc_request(
+        CreateTenantRequest {
+            id: "payments".to_string(),
+            display_name: None,
+        },
+        "olivia-token",
+    ))
+    .await
+    .unwrap();
+
+    let created = grpc
+        .create_tenant(grpc_request(
+            CreateTenantRequest {
+                id: "shipping".to_string(),
+                display_name: None,
+            },
+            "alice-token",
+        ))
+        .await;
+    assert_eq!(created.unwrap_err().code(), Code::PermissionDenied);
+
+    let suspended = grpc
+        .suspend_tenant(grpc_request(
+            SuspendTenantRequest {
+                id: "payments".to_string(),
+                reason: None,
+            },
+            "alice-token",
+        ))
+        .await;
+    assert_eq!(suspended.unwrap_err().code(), Code::PermissionDenied);
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use spec_server::{
+    domain::{
+        commands::CreateSpec,
+        errors::DomainError,
+        events::EventMetadata,
+        validation::{ValidationPolicies, ValidationPolicy, ValidatorPipeline},
+        value_objects::{SpecContent, SpecName, ValidationError},
+    },
+    infrastructure::{event_store::SqliteEventStore, repositories::SpecRepository},
+};
+use tempfile::TempDir;
+
+fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+        .collect()
+}
+
+#[test]
+fn overrides_apply_to_specs_whose_labels_match() {
+    let policies: ValidationPolicies = "max_content_bytes=4096,name_punctuation=-;\
+                                        kind=approval-workflow:max_content_bytes=16384;\
+                                        namespace=legacy:max_name_length=32,name_punctuation=-_."
+        .parse()
+        .unwrap();
+
+    let base = policies.policy_for(&BTreeMap::new());
+    assert_eq!(
+        base,
+        ValidationPolicy {
+            max_content_bytes: 4096,
+            max_name_length: 255,
+            name_punctuation: "-".to_string(),
+        }
+    );
+
+    let workflow = policies.policy_for(&labels(&[("kind", "approval-workflow")]));
+    assert_eq!(workflow.max_content_bytes, 16384);
+    assert_eq!(workflow.name_punctuation, "-");
+
+    let both = policies.policy_for(&labels(&[
+        ("kind", "approval-workflow"),
+        ("namespace", "legacy"),
+    ]));
+    assert_eq!(both.max_content_bytes, 16384);
+    assert_eq!(both.max_name_length, 32);
+    assert_eq!(both.name_punctuation, "-_.");
+
+    assert!("max_content_bytes=big"
+        .parse::<ValidationPolicies>()
+        .is_err());
+    assert!("max_spec_count=3".parse::<ValidationPolicies>().is_err());
+    assert!(";max_content_bytes=10"
+        .parse::<ValidationPolicies>()
+        .is_err());
+}
+
+#[test]
+fn errors_name_the_configured_limits() {
+    let policy = ValidationPolicy {
+        max_content_bytes: 16,
+        max_name_length: 8,
+        name_punctuation: "-".to_string(),
+    };
+
+    let error = SpecContent::new(
+        "rules:\n  limit: 10\n".to_string(),
+        &policy,
+        &ValidatorPipeline::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        ValidationError::ContentTooLarge { max: 16 }
+    ));
+    assert_eq!(error.to_string(), "Content too large (max 16 bytes)");
+
+    let error = SpecName::new("orders-api".to_string(), &policy).unwrap_err();
+    assert_eq!(error.to_string(), "Name too long (max 8 characters)");
+
+    let error = SpecName::new("orders_1".to_string(), &policy).unwrap_err();
+    assert!(error.to_string().contains("\"-\""));
+    assert!(SpecName::new("orders-1".to_string(), &policy).is_ok());
+}
+
+#[tokio::test]
+async fn large_specs_are_stored_when_their_kind_allows_it() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("sqlite:{}?mode=rwc", dir.path().join("specs.db").display());
+    let event_store = SqliteEventStore::new(&url).await.unwrap();
+    event_store.init_schema().await.unwrap();
+
+    let repository = SpecRepository::new(Arc::new(event_store)).with_validation_policies(
+        ";kind=approval-workflow:max_content_bytes=16384"
+            .parse()
+            .unwrap(),
+    );
+
+    // About 8 KB of steps, past the default limit
+    let content: String = (0..400).map(|i| format!("step{i:03}: approve\n")).collect();
+    assert!(content.len() > ValidationPolicy::default().max_content_bytes);
+
+    let create = |name: &str, labels| CreateSpec {
+        name: name.to_string(),
+        content: content.clone(),
+        description: None,
+        schema: None,
+        labels,
+        template: None,
+        created_by: "alice@example.com".to_string(),
+    };
+
+    assert!(matches!(
+        repository
+            .create(
+                create("expenses", BTreeMap::new()),
+                EventMetadata::default()
+            )
+            .await,
+        Err(DomainError::ValidationError(
+            ValidationError::ContentTooLarge { max: 2048 }
+        ))
+    ));
+    assert!(repository
+        .create(
+            create("expenses", labels(&[("kind", "approval-workflow")])),
+            EventMetadata::default(),
+        )
+        .await
+        .is_ok());
+}